@@ -174,7 +174,7 @@ def example4_filter_logs():
     print("Filter 1: Only ERROR level logs")
     errors_only = rust_processor.filter_logs(
         log_lines,
-        min_level="ERROR",
+        min_level=rust_processor.LogLevel.Error,
         min_duration_ms=None,
         status_codes=None
     )
@@ -204,7 +204,7 @@ def example4_filter_logs():
     print("Filter 4: Slow ERROR level logs (duration > 100ms)")
     slow_errors = rust_processor.filter_logs(
         log_lines,
-        min_level="ERROR",
+        min_level=rust_processor.LogLevel.Error,
         min_duration_ms=100.0,
         status_codes=None
     )
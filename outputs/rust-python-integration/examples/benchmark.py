@@ -212,7 +212,7 @@ def benchmark_filter_logs(dataset_sizes: List[int]):
             "Rust filter",
             rust_processor.filter_logs,
             test_data,
-            min_level="WARN",
+            min_level=rust_processor.LogLevel.Warn,
             min_duration_ms=100.0,
             status_codes=None
         )
@@ -158,7 +158,7 @@ class LogPipeline:
 
         filtered = rust_processor.filter_logs(
             log_lines,
-            min_level=min_level,
+            min_level=rust_processor.LogLevel.from_str(min_level),
             min_duration_ms=min_duration_ms,
             status_codes=None
         )
@@ -44,6 +44,19 @@ class PythonLogStats:
         )
 
 
+def _percentile(sorted_values: List[float], p: float) -> float:
+    """
+    Linearly interpolated (R-7) percentile of a sorted, non-empty list.
+
+    Matches rust_core::compute_percentiles, so benchmarks compare like with like.
+    """
+    rank = (len(sorted_values) - 1) * p
+    lower = int(rank)
+    upper = min(lower + 1, len(sorted_values) - 1)
+    fraction = rank - lower
+    return sorted_values[lower] + (sorted_values[upper] - sorted_values[lower]) * fraction
+
+
 class PurePythonProcessor:
     """
     Pure Python implementation of log processing.
@@ -175,14 +188,10 @@ class PurePythonProcessor:
             min_duration = durations[0]
             max_duration = durations[-1]
 
-            # Calculate percentiles
-            p50_idx = int(len(durations) * 0.50)
-            p95_idx = int(len(durations) * 0.95)
-            p99_idx = int(len(durations) * 0.99)
-
-            p50 = durations[min(p50_idx, len(durations) - 1)]
-            p95 = durations[min(p95_idx, len(durations) - 1)]
-            p99 = durations[min(p99_idx, len(durations) - 1)]
+            # Calculate percentiles (R-7, same as Rust)
+            p50 = _percentile(durations, 0.50)
+            p95 = _percentile(durations, 0.95)
+            p99 = _percentile(durations, 0.99)
         else:
             avg_duration = min_duration = max_duration = 0.0
             p50 = p95 = p99 = 0.0
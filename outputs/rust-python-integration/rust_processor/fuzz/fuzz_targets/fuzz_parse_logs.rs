@@ -0,0 +1,10 @@
+//! `parse_logs` must return, Ok or Err, for any input line
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let line = String::from_utf8_lossy(data).into_owned();
+    let _ = rust_processor::parse_logs(vec![line]);
+});
@@ -0,0 +1,11 @@
+//! `validate_logs` must return, Ok or Err, for any input line in either mode
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let line = String::from_utf8_lossy(data).into_owned();
+    let _ = rust_processor::validate_logs(vec![line.clone()], false);
+    let _ = rust_processor::validate_logs(vec![line], true);
+});
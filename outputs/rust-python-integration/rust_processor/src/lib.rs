@@ -1,20 +1,197 @@
-use pyo3::prelude::*;
+// pyo3 0.20's `#[new]` expansion trips rustc's `non_local_definitions` lint
+#![allow(non_local_definitions)]
+
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
 use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
 use rayon::prelude::*;
+use regex::Regex;
+use rust_core::{prometheus, DataRecord, ProcessResult, ProcessingError};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::OnceLock;
 
 /// Represents a single log entry after parsing
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct LogEntry {
+    /// RFC 3339 timestamp of the entry
     pub timestamp: String,
+    /// Severity such as INFO, WARN or ERROR
     pub level: String,
     pub message: String,
+    /// Request duration in milliseconds
     pub duration_ms: Option<f64>,
+    /// HTTP status code of the request
     pub status_code: Option<i32>,
     pub user_id: Option<String>,
 }
 
+impl LogEntry {
+    /// Start building an entry field by field
+    pub fn builder() -> LogEntryBuilder {
+        LogEntryBuilder::default()
+    }
+}
+
+/// Fluent builder for `LogEntry`
+///
+/// The default builder has an empty timestamp and level, so `build` fails
+/// until both are set.
+#[derive(Debug, Clone, Default)]
+pub struct LogEntryBuilder {
+    entry: LogEntry,
+}
+
+impl LogEntryBuilder {
+    pub fn timestamp(mut self, timestamp: &str) -> Self {
+        self.entry.timestamp = timestamp.to_string();
+        self
+    }
+
+    pub fn level(mut self, level: &str) -> Self {
+        self.entry.level = level.to_string();
+        self
+    }
+
+    pub fn message(mut self, message: &str) -> Self {
+        self.entry.message = message.to_string();
+        self
+    }
+
+    pub fn duration_ms(mut self, duration_ms: f64) -> Self {
+        self.entry.duration_ms = Some(duration_ms);
+        self
+    }
+
+    pub fn status_code(mut self, status_code: i32) -> Self {
+        self.entry.status_code = Some(status_code);
+        self
+    }
+
+    pub fn user_id(mut self, user_id: &str) -> Self {
+        self.entry.user_id = Some(user_id.to_string());
+        self
+    }
+
+    /// Finish the entry, applying the same checks as `validate_logs`
+    pub fn build(self) -> Result<LogEntry, String> {
+        validate_log_entry(&self.entry)?;
+        Ok(self.entry)
+    }
+}
+
+pyo3::create_exception!(
+    rust_processor,
+    RustValidationError,
+    PyValueError,
+    "Raised when DataRecords fail validation; `.errors` lists each failure."
+);
+
+/// Convert a core `ProcessingError` into the matching Python exception
+///
+/// Validation failures raise `RustValidationError` (a `ValueError` subclass)
+/// with an `errors` attribute holding `{"record_id", "message"}` dicts;
+/// everything else raises `ValueError`.
+fn processing_error_to_py(e: ProcessingError) -> PyErr {
+    match &e {
+        ProcessingError::ValidationFailed { errors } => Python::with_gil(|py| {
+            let details: Vec<HashMap<&str, String>> = errors
+                .iter()
+                .map(|err| {
+                    HashMap::from([
+                        ("record_id", err.record_id.clone()),
+                        ("message", err.message.clone()),
+                    ])
+                })
+                .collect();
+            let py_err = RustValidationError::new_err(e.to_string());
+            match py_err.value(py).setattr("errors", details) {
+                Ok(()) => py_err,
+                Err(setattr_err) => setattr_err,
+            }
+        }),
+        _ => PyValueError::new_err(e.to_string()),
+    }
+}
+
+/// Log severity, ordered from least to most severe
+///
+/// Exposed to Python as `LogLevel.Debug` … `LogLevel.Error`; use
+/// `LogLevel.from_str` to parse user input case-insensitively.
+#[pyclass]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Debug = 0,
+    Info = 1,
+    Warn = 2,
+    Error = 3,
+}
+
+impl LogLevel {
+    /// Upper-case name as it appears in log entries' `level` field
+    pub fn display_name(&self) -> &str {
+        match self {
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+
+    /// Level of an entry's `level` field, matched exactly; anything
+    /// unrecognised ranks as `Debug`
+    fn of_entry(level: &str) -> LogLevel {
+        match level {
+            "ERROR" => LogLevel::Error,
+            "WARN" => LogLevel::Warn,
+            "INFO" => LogLevel::Info,
+            _ => LogLevel::Debug,
+        }
+    }
+}
+
+impl std::str::FromStr for LogLevel {
+    type Err = String;
+
+    /// Case-insensitive; also accepts `WARNING`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "DEBUG" => Ok(LogLevel::Debug),
+            "INFO" => Ok(LogLevel::Info),
+            "WARN" | "WARNING" => Ok(LogLevel::Warn),
+            "ERROR" => Ok(LogLevel::Error),
+            _ => Err(format!(
+                "Unknown log level '{}': expected DEBUG, INFO, WARN or ERROR",
+                s
+            )),
+        }
+    }
+}
+
+#[pymethods]
+impl LogLevel {
+    /// Parse a level name case-insensitively, raising `ValueError` if unknown
+    #[classmethod]
+    #[pyo3(name = "from_str")]
+    fn from_str_py(_cls: &pyo3::types::PyType, s: &str) -> PyResult<LogLevel> {
+        s.parse().map_err(PyValueError::new_err)
+    }
+
+    #[pyo3(name = "display_name")]
+    fn display_name_py(&self) -> &str {
+        self.display_name()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("LogLevel.{:?}", self)
+    }
+
+    fn __str__(&self) -> &str {
+        self.display_name()
+    }
+}
+
 /// Validation error details
 #[derive(Debug, Clone)]
 pub struct ValidationError {
@@ -25,7 +202,8 @@ pub struct ValidationError {
 /// Statistics computed from log entries
 /// This is exposed to Python as a class with accessible properties
 #[pyclass]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct LogStats {
     #[pyo3(get)]
     pub total_count: usize,
@@ -42,6 +220,14 @@ pub struct LogStats {
     #[pyo3(get)]
     pub avg_duration_ms: f64,
 
+    /// Sum of all durations, kept so merged averages stay exact
+    #[pyo3(get)]
+    pub total_duration_ms: f64,
+
+    /// Number of entries that had a duration (the denominator of the average)
+    #[pyo3(get)]
+    pub duration_count: usize,
+
     #[pyo3(get)]
     pub min_duration_ms: f64,
 
@@ -57,6 +243,12 @@ pub struct LogStats {
     #[pyo3(get)]
     pub p99_duration_ms: f64,
 
+    #[pyo3(get)]
+    pub variance_duration_ms: f64,
+
+    #[pyo3(get)]
+    pub std_dev_duration_ms: f64,
+
     #[pyo3(get)]
     pub status_code_distribution: HashMap<i32, usize>,
 
@@ -64,16 +256,553 @@ pub struct LogStats {
     pub error_count_by_code: HashMap<i32, usize>,
 }
 
+/// One Prometheus sample: an optional extra label and the value
+type PrometheusSample<'a> = (Option<(&'a str, String)>, f64);
+
+/// Render `{k="v",...}` from sorted base labels plus an optional extra label
+///
+/// Returns an empty string when there are no labels at all.
+fn prometheus_label_set(base_labels: &[(&str, String)], extra: Option<(&str, String)>) -> String {
+    let rendered: Vec<String> = base_labels
+        .iter()
+        .cloned()
+        .chain(extra)
+        .map(|(k, v)| format!("{}=\"{}\"", k, prometheus::escape_label_value(&v)))
+        .collect();
+    if rendered.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", rendered.join(","))
+    }
+}
+
+/// Check a metric name and user labels the way `records_to_prometheus_text` does
+///
+/// `reserved` are the labels the exporter adds itself, which a user label
+/// must not repeat.
+fn validate_prometheus_names(
+    metric_name: &str,
+    labels: &HashMap<String, String>,
+    reserved: &[&str],
+) -> PyResult<()> {
+    if !prometheus::is_valid_metric_name(metric_name) {
+        return Err(PyValueError::new_err(format!(
+            "Invalid Prometheus metric name '{}'",
+            metric_name
+        )));
+    }
+    for key in labels.keys() {
+        if !prometheus::is_valid_label_name(key) {
+            return Err(PyValueError::new_err(format!(
+                "Invalid Prometheus label name '{}'",
+                key
+            )));
+        }
+        if reserved.contains(&key.as_str()) {
+            return Err(PyValueError::new_err(format!(
+                "Duplicate Prometheus label '{}'",
+                key
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Upper bounds (ms) of the finite `to_prometheus_histogram` buckets
+const PROMETHEUS_HISTOGRAM_BUCKETS: [f64; 11] = [
+    1.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0,
+];
+
+/// Escape an InfluxDB measurement name (commas and spaces)
+fn escape_influx_measurement(value: &str) -> String {
+    value.replace(',', "\\,").replace(' ', "\\ ")
+}
+
+/// Escape an InfluxDB tag key or value (commas, equals signs and spaces)
+fn escape_influx_tag(value: &str) -> String {
+    value
+        .replace(',', "\\,")
+        .replace('=', "\\=")
+        .replace(' ', "\\ ")
+}
+
+/// Build `measurement,k1=v1,k2=v2` with tags sorted by key
+///
+/// Tags with empty values are dropped since line protocol rejects them.
+fn influx_series_key(
+    measurement: &str,
+    tags: &HashMap<String, String>,
+    extra: Option<(&str, String)>,
+) -> String {
+    let mut tag_pairs: Vec<(&str, String)> = tags
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.clone()))
+        .chain(extra)
+        .filter(|(_, v)| !v.is_empty())
+        .collect();
+    tag_pairs.sort();
+
+    let mut key = escape_influx_measurement(measurement);
+    for (k, v) in tag_pairs {
+        key.push_str(&format!(
+            ",{}={}",
+            escape_influx_tag(k),
+            escape_influx_tag(&v)
+        ));
+    }
+    key
+}
+
+impl LogStats {
+    /// Estimated fraction of timed entries with a duration of at most `bound`
+    ///
+    /// Interpolates linearly between (min, 0), the known percentiles and
+    /// (max, 1); 0 when no entry had a duration.
+    fn duration_fraction_at_most(&self, bound: f64) -> f64 {
+        if self.duration_count == 0 {
+            return 0.0;
+        }
+        let mut knots = vec![(self.min_duration_ms, 0.0)];
+        for (value, fraction) in [
+            (self.p50_duration_ms, 0.5),
+            (self.p95_duration_ms, 0.95),
+            (self.p99_duration_ms, 0.99),
+        ] {
+            if value >= 0.0 {
+                // Keep durations non-decreasing even if the inputs disagree
+                let floor = knots.last().map_or(value, |&(prev, _)| prev);
+                knots.push((value.max(floor), fraction));
+            }
+        }
+        let floor = knots.last().map_or(self.max_duration_ms, |&(prev, _)| prev);
+        knots.push((self.max_duration_ms.max(floor), 1.0));
+
+        if bound < knots[0].0 {
+            return 0.0;
+        }
+        for pair in knots.windows(2) {
+            let ((lo, lo_frac), (hi, hi_frac)) = (pair[0], pair[1]);
+            if bound < hi {
+                return lo_frac + (hi_frac - lo_frac) * (bound - lo) / (hi - lo);
+            }
+        }
+        1.0
+    }
+}
+
 #[pymethods]
 impl LogStats {
     /// String representation for Python
     fn __repr__(&self) -> String {
         format!(
-            "LogStats(total={}, errors={}, avg_duration={:.2}ms, p95={:.2}ms)",
-            self.total_count, self.error_count, self.avg_duration_ms, self.p95_duration_ms
+            "LogStats(total={}, errors={}, avg_duration={:.2}ms, std_dev={:.2}ms, p95={:.2}ms)",
+            self.total_count,
+            self.error_count,
+            self.avg_duration_ms,
+            self.std_dev_duration_ms,
+            self.p95_duration_ms
+        )
+    }
+
+    /// Serialize the statistics as a JSON object
+    ///
+    /// Keys are the attribute names. The status code maps become objects
+    /// keyed by the code as a string (`{"200": 5}`), since JSON keys must be
+    /// strings; `from_json` parses them back to integers.
+    ///
+    /// Raises `ValueError` if any duration field is NaN or infinite (for
+    /// example a total that overflowed), since JSON has no representation for
+    /// them and `from_json` could not read the result back.
+    fn to_json(&self) -> PyResult<String> {
+        // The total goes first: when it overflows, the average follows it
+        let durations = [
+            ("total_duration_ms", self.total_duration_ms),
+            ("avg_duration_ms", self.avg_duration_ms),
+            ("min_duration_ms", self.min_duration_ms),
+            ("max_duration_ms", self.max_duration_ms),
+            ("p50_duration_ms", self.p50_duration_ms),
+            ("p95_duration_ms", self.p95_duration_ms),
+            ("p99_duration_ms", self.p99_duration_ms),
+            ("variance_duration_ms", self.variance_duration_ms),
+            ("std_dev_duration_ms", self.std_dev_duration_ms),
+        ];
+        if let Some((name, value)) = durations.iter().find(|(_, value)| !value.is_finite()) {
+            return Err(PyValueError::new_err(format!(
+                "{} is {}, which JSON cannot represent",
+                name, value
+            )));
+        }
+        serde_json::to_string(self).map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Restore statistics from `to_json` output
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<LogStats> {
+        serde_json::from_str(json)
+            .map_err(|e| PyValueError::new_err(format!("JSON parse error: {}", e)))
+    }
+
+    /// Convert to a dict keyed by attribute name, without going through JSON
+    ///
+    /// The status code maps stay keyed by `int`, so the result can be passed
+    /// straight to `pandas.DataFrame([stats.to_dict()])`.
+    fn to_dict(&self, py: Python) -> PyResult<PyObject> {
+        let dict = pyo3::types::PyDict::new(py);
+        dict.set_item("total_count", self.total_count)?;
+        dict.set_item("error_count", self.error_count)?;
+        dict.set_item("warn_count", self.warn_count)?;
+        dict.set_item("info_count", self.info_count)?;
+        dict.set_item("avg_duration_ms", self.avg_duration_ms)?;
+        dict.set_item("total_duration_ms", self.total_duration_ms)?;
+        dict.set_item("duration_count", self.duration_count)?;
+        dict.set_item("min_duration_ms", self.min_duration_ms)?;
+        dict.set_item("max_duration_ms", self.max_duration_ms)?;
+        dict.set_item("p50_duration_ms", self.p50_duration_ms)?;
+        dict.set_item("p95_duration_ms", self.p95_duration_ms)?;
+        dict.set_item("p99_duration_ms", self.p99_duration_ms)?;
+        dict.set_item("variance_duration_ms", self.variance_duration_ms)?;
+        dict.set_item("std_dev_duration_ms", self.std_dev_duration_ms)?;
+        dict.set_item("status_code_distribution", &self.status_code_distribution)?;
+        dict.set_item("error_count_by_code", &self.error_count_by_code)?;
+        Ok(dict.into())
+    }
+
+    /// Build statistics from a `to_dict` dict
+    ///
+    /// Every key `to_dict` produces is required; raises `KeyError` if one is
+    /// missing and `TypeError` if a value has the wrong type.
+    #[staticmethod]
+    fn from_dict(dict: &pyo3::types::PyDict) -> PyResult<LogStats> {
+        fn field<'py, T: FromPyObject<'py>>(
+            dict: &'py pyo3::types::PyDict,
+            key: &str,
+        ) -> PyResult<T> {
+            dict.get_item(key)?
+                .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err(key.to_string()))?
+                .extract()
+        }
+
+        Ok(LogStats {
+            total_count: field(dict, "total_count")?,
+            error_count: field(dict, "error_count")?,
+            warn_count: field(dict, "warn_count")?,
+            info_count: field(dict, "info_count")?,
+            avg_duration_ms: field(dict, "avg_duration_ms")?,
+            total_duration_ms: field(dict, "total_duration_ms")?,
+            duration_count: field(dict, "duration_count")?,
+            min_duration_ms: field(dict, "min_duration_ms")?,
+            max_duration_ms: field(dict, "max_duration_ms")?,
+            p50_duration_ms: field(dict, "p50_duration_ms")?,
+            p95_duration_ms: field(dict, "p95_duration_ms")?,
+            p99_duration_ms: field(dict, "p99_duration_ms")?,
+            variance_duration_ms: field(dict, "variance_duration_ms")?,
+            std_dev_duration_ms: field(dict, "std_dev_duration_ms")?,
+            status_code_distribution: field(dict, "status_code_distribution")?,
+            error_count_by_code: field(dict, "error_count_by_code")?,
+        })
+    }
+
+    /// Combine statistics from another shard into a new `LogStats`
+    ///
+    /// Counts and status code maps are summed, min/max widened, and the
+    /// average recomputed from `total_duration_ms / duration_count`, so it is
+    /// exact. Variance is combined exactly with Chan's parallel formula.
+    /// Percentiles cannot be merged from summaries, so p50/p95/p99 are set to
+    /// -1.0; recompute from the full data when accurate percentiles are needed.
+    fn merge(&self, other: &LogStats) -> LogStats {
+        let mut status_code_distribution = self.status_code_distribution.clone();
+        for (code, count) in &other.status_code_distribution {
+            *status_code_distribution.entry(*code).or_insert(0) += count;
+        }
+        let mut error_count_by_code = self.error_count_by_code.clone();
+        for (code, count) in &other.error_count_by_code {
+            *error_count_by_code.entry(*code).or_insert(0) += count;
+        }
+
+        let duration_count = self.duration_count + other.duration_count;
+        let total_duration_ms = self.total_duration_ms + other.total_duration_ms;
+        let (avg, min, max, variance) = match (self.duration_count, other.duration_count) {
+            (0, _) => (
+                other.avg_duration_ms,
+                other.min_duration_ms,
+                other.max_duration_ms,
+                other.variance_duration_ms,
+            ),
+            (_, 0) => (
+                self.avg_duration_ms,
+                self.min_duration_ms,
+                self.max_duration_ms,
+                self.variance_duration_ms,
+            ),
+            (n_a, n_b) => {
+                let (n_a, n_b, n) = (n_a as f64, n_b as f64, duration_count as f64);
+                let delta = other.avg_duration_ms - self.avg_duration_ms;
+                let m2 = self.variance_duration_ms * (n_a - 1.0)
+                    + other.variance_duration_ms * (n_b - 1.0)
+                    + delta * delta * n_a * n_b / n;
+                (
+                    total_duration_ms / n,
+                    self.min_duration_ms.min(other.min_duration_ms),
+                    self.max_duration_ms.max(other.max_duration_ms),
+                    m2 / (n - 1.0),
+                )
+            }
+        };
+
+        LogStats {
+            total_count: self.total_count + other.total_count,
+            error_count: self.error_count + other.error_count,
+            warn_count: self.warn_count + other.warn_count,
+            info_count: self.info_count + other.info_count,
+            avg_duration_ms: avg,
+            total_duration_ms,
+            duration_count,
+            min_duration_ms: min,
+            max_duration_ms: max,
+            p50_duration_ms: -1.0,
+            p95_duration_ms: -1.0,
+            p99_duration_ms: -1.0,
+            variance_duration_ms: variance,
+            std_dev_duration_ms: variance.sqrt(),
+            status_code_distribution,
+            error_count_by_code,
+        }
+    }
+
+    /// Render the statistics in the Prometheus text exposition format
+    ///
+    /// Emits `{prefix}_log_total` and `{prefix}_log_errors_total` counters,
+    /// `{prefix}_log_duration_milliseconds_avg` and
+    /// `{prefix}_log_duration_milliseconds{quantile="..."}` gauges, and a
+    /// `{prefix}_log_responses_total{status_code="..."}` counter per status
+    /// code. `labels` are attached to every sample, sorted by key. Negative
+    /// percentiles (unknown, e.g. after `merge`) are exported as `NaN`.
+    /// Raises `ValueError` if the prefix does not form valid metric names, or
+    /// if a label key is not a valid Prometheus label name or is `quantile` or
+    /// `status_code`, which the exporter adds itself.
+    #[pyo3(signature = (metric_prefix, labels=HashMap::new()))]
+    fn to_prometheus(
+        &self,
+        metric_prefix: &str,
+        labels: HashMap<String, String>,
+    ) -> PyResult<String> {
+        validate_prometheus_names(
+            &format!("{}_log_total", metric_prefix),
+            &labels,
+            &["quantile", "status_code"],
+        )?;
+
+        let mut base_labels: Vec<(&str, String)> = labels
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.clone()))
+            .collect();
+        base_labels.sort();
+
+        let mut output = String::new();
+        let mut family = |name: &str, kind: &str, help: &str, samples: Vec<PrometheusSample>| {
+            let name = format!("{}_{}", metric_prefix, name);
+            output.push_str(&format!(
+                "# HELP {} {}\n# TYPE {} {}\n",
+                name, help, name, kind
+            ));
+            for (extra, value) in samples {
+                output.push_str(&format!(
+                    "{}{} {}\n",
+                    name,
+                    prometheus_label_set(&base_labels, extra),
+                    prometheus::format_value(value)
+                ));
+            }
+        };
+
+        family(
+            "log_total",
+            "counter",
+            "Total number of log entries.",
+            vec![(None, self.total_count as f64)],
+        );
+        family(
+            "log_errors_total",
+            "counter",
+            "Number of ERROR log entries.",
+            vec![(None, self.error_count as f64)],
+        );
+        family(
+            "log_duration_milliseconds_avg",
+            "gauge",
+            "Mean request duration in milliseconds.",
+            vec![(None, self.avg_duration_ms)],
+        );
+
+        let quantile = |q: &'static str, value: f64| {
+            let value = if value < 0.0 { f64::NAN } else { value };
+            (Some(("quantile", q.to_string())), value)
+        };
+        family(
+            "log_duration_milliseconds",
+            "gauge",
+            "Request duration percentiles in milliseconds.",
+            vec![
+                quantile("0.5", self.p50_duration_ms),
+                quantile("0.95", self.p95_duration_ms),
+                quantile("0.99", self.p99_duration_ms),
+            ],
+        );
+
+        let mut codes: Vec<(&i32, &usize)> = self.status_code_distribution.iter().collect();
+        codes.sort();
+        family(
+            "log_responses_total",
+            "counter",
+            "Number of log entries per HTTP status code.",
+            codes
+                .into_iter()
+                .map(|(code, count)| (Some(("status_code", code.to_string())), *count as f64))
+                .collect(),
+        );
+
+        Ok(output)
+    }
+
+    /// Render request durations as a Prometheus histogram
+    ///
+    /// Emits `{metric_name}_bucket{le="..."}` for 1, 5, 10, 25, 50, 100, 250,
+    /// 500, 1000, 2500 and 5000 ms and `+Inf`, then `_sum` and `_count`.
+    /// Raw durations are not kept, so each finite bucket is estimated by
+    /// linearly interpolating the duration distribution through min, p50,
+    /// p95, p99 and max; percentiles that are unknown (negative, after
+    /// `merge`) are skipped. Entries without a duration only fall in the
+    /// `+Inf` bucket, whose count is `total_count`. `labels` are attached to
+    /// every sample, sorted by key, before `le`. Raises `ValueError` for an
+    /// invalid metric or label name, or a label key of `le`.
+    #[pyo3(signature = (metric_name, labels=HashMap::new()))]
+    fn to_prometheus_histogram(
+        &self,
+        metric_name: &str,
+        labels: HashMap<String, String>,
+    ) -> PyResult<String> {
+        validate_prometheus_names(metric_name, &labels, &["le"])?;
+
+        let mut base_labels: Vec<(&str, String)> = labels
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.clone()))
+            .collect();
+        base_labels.sort();
+
+        let mut output = format!(
+            "# HELP {} Request duration in milliseconds.\n# TYPE {} histogram\n",
+            metric_name, metric_name
+        );
+        let mut cumulative = 0;
+        for bound in PROMETHEUS_HISTOGRAM_BUCKETS {
+            let estimate = (self.duration_fraction_at_most(bound) * self.duration_count as f64)
+                .round() as usize;
+            // Rounding must never make a later bucket smaller than an earlier one
+            cumulative = estimate.clamp(cumulative, self.duration_count);
+            output.push_str(&format!(
+                "{}_bucket{} {}\n",
+                metric_name,
+                prometheus_label_set(&base_labels, Some(("le", bound.to_string()))),
+                cumulative
+            ));
+        }
+        output.push_str(&format!(
+            "{}_bucket{} {}\n",
+            metric_name,
+            prometheus_label_set(&base_labels, Some(("le", "+Inf".to_string()))),
+            self.total_count
+        ));
+
+        let label_set = prometheus_label_set(&base_labels, None);
+        output.push_str(&format!(
+            "{}_sum{} {}\n",
+            metric_name,
+            label_set,
+            prometheus::format_value(self.total_duration_ms)
+        ));
+        output.push_str(&format!(
+            "{}_count{} {}\n",
+            metric_name, label_set, self.total_count
+        ));
+        Ok(output)
+    }
+
+    /// Render the statistics as one InfluxDB line protocol point
+    ///
+    /// Counts are written as integer fields (`i` suffix) and durations as
+    /// float fields; durations that are unknown (negative percentiles after
+    /// `merge`) or non-finite are left out because line protocol cannot
+    /// represent them. Tags are sorted by key and escaped per the spec.
+    #[pyo3(signature = (measurement, tags=HashMap::new(), timestamp_ns=0))]
+    fn to_influxdb_line_protocol(
+        &self,
+        measurement: &str,
+        tags: HashMap<String, String>,
+        timestamp_ns: u64,
+    ) -> String {
+        let mut fields: Vec<String> = [
+            ("total_count", self.total_count),
+            ("error_count", self.error_count),
+            ("warn_count", self.warn_count),
+            ("info_count", self.info_count),
+        ]
+        .iter()
+        .map(|(key, count)| format!("{}={}i", key, count))
+        .collect();
+        let durations = [
+            ("avg_duration_ms", self.avg_duration_ms),
+            ("min_duration_ms", self.min_duration_ms),
+            ("max_duration_ms", self.max_duration_ms),
+            ("p50_duration_ms", self.p50_duration_ms),
+            ("p95_duration_ms", self.p95_duration_ms),
+            ("p99_duration_ms", self.p99_duration_ms),
+            ("std_dev_duration_ms", self.std_dev_duration_ms),
+        ];
+        fields.extend(
+            durations
+                .iter()
+                .filter(|(_, value)| value.is_finite() && *value >= 0.0)
+                .map(|(key, value)| format!("{}={}", key, value)),
+        );
+
+        format!(
+            "{} {} {}",
+            influx_series_key(measurement, &tags, None),
+            fields.join(","),
+            timestamp_ns
         )
     }
 
+    /// Render one InfluxDB line protocol point per status code
+    ///
+    /// Each line adds a `status_code` tag and carries `count` and
+    /// `error_count` integer fields; all points share the `window_ns`
+    /// timestamp. Lines are ordered by status code.
+    #[pyo3(signature = (measurement, tags=HashMap::new(), window_ns=0))]
+    fn to_influxdb_series(
+        &self,
+        measurement: &str,
+        tags: HashMap<String, String>,
+        window_ns: u64,
+    ) -> Vec<String> {
+        let mut codes: Vec<(&i32, &usize)> = self.status_code_distribution.iter().collect();
+        codes.sort();
+
+        codes
+            .into_iter()
+            .map(|(code, count)| {
+                let errors = self.error_count_by_code.get(code).copied().unwrap_or(0);
+                format!(
+                    "{} count={}i,error_count={}i {}",
+                    influx_series_key(measurement, &tags, Some(("status_code", code.to_string()))),
+                    count,
+                    errors,
+                    window_ns
+                )
+            })
+            .collect()
+    }
+
     /// Get a summary string
     fn summary(&self) -> String {
         format!(
@@ -82,6 +811,7 @@ impl LogStats {
              Warning count: {}\n\
              Info count: {}\n\
              Average duration: {:.2}ms\n\
+             Std dev duration: {:.2}ms\n\
              P95 duration: {:.2}ms\n\
              P99 duration: {:.2}ms",
             self.total_count,
@@ -89,6 +819,7 @@ impl LogStats {
             self.warn_count,
             self.info_count,
             self.avg_duration_ms,
+            self.std_dev_duration_ms,
             self.p95_duration_ms,
             self.p99_duration_ms
         )
@@ -107,7 +838,7 @@ impl LogStats {
 /// # Returns
 /// * Result containing vector of parsed LogEntry objects or error message
 #[pyfunction]
-fn parse_logs(log_lines: Vec<String>) -> PyResult<Vec<HashMap<String, String>>> {
+pub fn parse_logs(log_lines: Vec<String>) -> PyResult<Vec<HashMap<String, String>>> {
     // Use Rayon to parse logs in parallel across available CPU cores
     // This is where we get the major performance win - Python's GIL doesn't apply here!
     let results: Result<Vec<LogEntry>, _> = log_lines
@@ -121,141 +852,474 @@ fn parse_logs(log_lines: Vec<String>) -> PyResult<Vec<HashMap<String, String>>>
     match results {
         Ok(entries) => {
             // Convert to Python-friendly format (HashMap)
-            let py_entries: Vec<HashMap<String, String>> = entries
-                .iter()
-                .map(|entry| {
-                    let mut map = HashMap::new();
-                    map.insert("timestamp".to_string(), entry.timestamp.clone());
-                    map.insert("level".to_string(), entry.level.clone());
-                    map.insert("message".to_string(), entry.message.clone());
-                    if let Some(duration) = entry.duration_ms {
-                        map.insert("duration_ms".to_string(), duration.to_string());
-                    }
-                    if let Some(status) = entry.status_code {
-                        map.insert("status_code".to_string(), status.to_string());
-                    }
-                    if let Some(ref user_id) = entry.user_id {
-                        map.insert("user_id".to_string(), user_id.clone());
-                    }
-                    map
-                })
-                .collect();
+            let py_entries: Vec<HashMap<String, String>> =
+                entries.iter().map(entry_to_dict).collect();
             Ok(py_entries)
         }
         Err(e) => Err(PyValueError::new_err(e)),
     }
 }
 
-/// Validate log entries with detailed error reporting
+/// Map a logfmt key to its canonical `LogEntry` field name, if it has one
+fn canonical_logfmt_key(key: &str) -> &str {
+    match key {
+        "ts" | "time" | "timestamp" => "timestamp",
+        "level" | "lvl" => "level",
+        "msg" | "message" => "message",
+        "duration" | "duration_ms" => "duration_ms",
+        "status" | "status_code" => "status_code",
+        other => other,
+    }
+}
+
+/// Split one logfmt line into key/value pairs
 ///
-/// This function checks log schema and returns detailed validation errors.
-/// The validation rules demonstrate how Rust's type system and error handling
-/// can catch issues that might slip through in dynamically-typed Python.
+/// Values may be bare (ending at the next whitespace, so `a=b=c` yields
+/// `b=c`) or double-quoted with `\"` and `\\` escapes. A key without `=` is
+/// kept with an empty value.
+fn parse_logfmt_line(line: &str) -> Result<HashMap<String, String>, String> {
+    let mut map = HashMap::new();
+    let mut chars = line.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            break;
+        }
+
+        let mut key = String::new();
+        while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '=') {
+            key.push(c);
+        }
+        if key.is_empty() {
+            return Err("empty key".to_string());
+        }
+
+        let mut value = String::new();
+        if chars.next_if_eq(&'=').is_some() {
+            if chars.next_if_eq(&'"').is_some() {
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(escaped) => value.push(escaped),
+                            None => return Err(format!("unterminated quoted value for '{}'", key)),
+                        },
+                        Some(c) => value.push(c),
+                        None => return Err(format!("unterminated quoted value for '{}'", key)),
+                    }
+                }
+            } else {
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                    value.push(c);
+                }
+            }
+        }
+
+        map.insert(canonical_logfmt_key(&key).to_string(), value);
+    }
+
+    Ok(map)
+}
+
+/// Parse logfmt log lines in parallel
+///
+/// Many Go services emit `key=value key="quoted value"` lines instead of JSON.
+/// Well-known keys are renamed to the fields `parse_logs` produces (`ts`/`time`
+/// become `timestamp`, `lvl` becomes `level`, `msg` becomes `message`,
+/// `duration` becomes `duration_ms`, `status` becomes `status_code`); unknown
+/// keys are preserved as-is.
 ///
 /// # Arguments
-/// * `log_lines` - Vector of JSON strings to validate
+/// * `log_lines` - Vector of logfmt strings, one per log entry
 ///
 /// # Returns
-/// * Tuple of (valid_count, error_messages)
+/// * Result containing one map per line or error message
 #[pyfunction]
-fn validate_logs(log_lines: Vec<String>) -> PyResult<(usize, Vec<String>)> {
-    let results: Vec<Result<LogEntry, String>> = log_lines
+fn parse_logs_logfmt(log_lines: Vec<String>) -> PyResult<Vec<HashMap<String, String>>> {
+    let results: Result<Vec<HashMap<String, String>>, _> = log_lines
         .par_iter()
         .enumerate()
         .map(|(idx, line)| {
-            // Try to parse
-            let entry: LogEntry = serde_json::from_str(line)
-                .map_err(|e| format!("Line {}: JSON parse error: {}", idx + 1, e))?;
-
-            // Validate required fields
-            if entry.timestamp.is_empty() {
-                return Err(format!("Line {}: Missing or empty timestamp", idx + 1));
-            }
-
-            // Validate log level
-            let valid_levels = ["ERROR", "WARN", "INFO", "DEBUG"];
-            if !valid_levels.contains(&entry.level.as_str()) {
-                return Err(format!(
-                    "Line {}: Invalid log level '{}'. Must be one of: ERROR, WARN, INFO, DEBUG",
-                    idx + 1,
-                    entry.level
-                ));
-            }
+            parse_logfmt_line(line).map_err(|e| format!("Parse error on line {}: {}", idx, e))
+        })
+        .collect();
 
-            // Validate duration if present
-            if let Some(duration) = entry.duration_ms {
-                if duration < 0.0 {
-                    return Err(format!(
-                        "Line {}: Invalid duration_ms {}. Must be >= 0",
-                        idx + 1,
-                        duration
-                    ));
-                }
-            }
+    results.map_err(PyValueError::new_err)
+}
 
-            // Validate status code if present
-            if let Some(status) = entry.status_code {
-                if !(100..=599).contains(&status) {
-                    return Err(format!(
-                        "Line {}: Invalid status_code {}. Must be 100-599",
-                        idx + 1,
-                        status
-                    ));
-                }
-            }
+/// Common/Combined Log Format line pattern, compiled once per process
+fn clf_regex() -> &'static Regex {
+    static CLF: OnceLock<Regex> = OnceLock::new();
+    CLF.get_or_init(|| {
+        Regex::new(
+            r#"^(\S+) \S+ (\S+) \[([^\]]+)\] "([^"]*)" (\d{3}) (\d+|-)(?: "([^"]*)" "([^"]*)")?\s*$"#,
+        )
+        .expect("CLF pattern is valid")
+    })
+}
 
-            Ok(entry)
-        })
-        .collect();
+/// Parse one CLF line into the same keys `parse_logs` produces plus
+/// `remote_host`, `bytes_sent`, `referer` and `user_agent`
+///
+/// Fields logged as `-` are omitted from the map.
+fn parse_clf_line(line: &str) -> Option<HashMap<String, String>> {
+    let caps = clf_regex().captures(line)?;
+    let timestamp = DateTime::parse_from_str(&caps[3], "%d/%b/%Y:%H:%M:%S %z").ok()?;
+    let status: u16 = caps[5].parse().ok()?;
+    let level = match status {
+        500.. => "ERROR",
+        400..=499 => "WARN",
+        _ => "INFO",
+    };
 
-    let mut errors = Vec::new();
-    let mut valid_count = 0;
+    let mut map = HashMap::new();
+    map.insert("remote_host".to_string(), caps[1].to_string());
+    map.insert(
+        "timestamp".to_string(),
+        timestamp.to_rfc3339_opts(SecondsFormat::Secs, true),
+    );
+    map.insert("level".to_string(), level.to_string());
+    map.insert("message".to_string(), caps[4].to_string());
+    map.insert("status_code".to_string(), status.to_string());
 
-    for result in results {
-        match result {
-            Ok(_) => valid_count += 1,
-            Err(e) => errors.push(e),
+    let optional = [
+        ("user_id", caps.get(2)),
+        ("bytes_sent", caps.get(6)),
+        ("referer", caps.get(7)),
+        ("user_agent", caps.get(8)),
+    ];
+    for (key, value) in optional {
+        if let Some(value) = value.map(|m| m.as_str()).filter(|v| *v != "-") {
+            map.insert(key.to_string(), value.to_string());
         }
     }
 
-    Ok((valid_count, errors))
+    Some(map)
 }
 
-/// Compute comprehensive statistics from log entries
+/// Parse Common Log Format (and Combined Log Format) lines in parallel
 ///
-/// This is the performance showcase function - it processes potentially millions
-/// of log entries to compute aggregations and percentiles in parallel. This would
-/// be 20-50x slower in pure Python due to GIL and interpreted nature.
+/// Produces the same keys as `parse_logs`, with `level` derived from the
+/// status code (5xx → ERROR, 4xx → WARN, otherwise INFO), `message` holding
+/// the request line and `user_id` the remote user. CLF-specific fields are
+/// kept as `remote_host`, `bytes_sent`, `referer` and `user_agent`. Fields
+/// logged as `-` are omitted.
 ///
 /// # Arguments
-/// * `log_lines` - Vector of JSON log strings
+/// * `log_lines` - Vector of CLF strings, one per request
 ///
 /// # Returns
-/// * LogStats object with all computed statistics
+/// * Tuple of (parsed entries, error messages for lines that did not match)
 #[pyfunction]
-fn compute_stats(log_lines: Vec<String>) -> PyResult<LogStats> {
-    // Parse all logs in parallel
-    let entries: Vec<LogEntry> = log_lines
+pub fn parse_logs_clf(log_lines: Vec<String>) -> (Vec<HashMap<String, String>>, Vec<String>) {
+    let results: Vec<Result<HashMap<String, String>, String>> = log_lines
         .par_iter()
-        .filter_map(|line| serde_json::from_str::<LogEntry>(line).ok())
+        .enumerate()
+        .map(|(idx, line)| {
+            parse_clf_line(line)
+                .ok_or_else(|| format!("Line {}: does not match Common Log Format", idx))
+        })
         .collect();
 
-    if entries.is_empty() {
-        return Err(PyValueError::new_err("No valid log entries found"));
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+    for result in results {
+        match result {
+            Ok(entry) => entries.push(entry),
+            Err(e) => errors.push(e),
+        }
     }
+    (entries, errors)
+}
 
-    // Count by log level
-    let error_count = entries.par_iter().filter(|e| e.level == "ERROR").count();
-    let warn_count = entries.par_iter().filter(|e| e.level == "WARN").count();
-    let info_count = entries.par_iter().filter(|e| e.level == "INFO").count();
+/// RFC 5424 header: PRI, VERSION, TIMESTAMP, HOSTNAME, APP-NAME, PROCID, MSGID,
+/// STRUCTURED-DATA and an optional MSG
+fn syslog_regex() -> &'static Regex {
+    static SYSLOG: OnceLock<Regex> = OnceLock::new();
+    SYSLOG.get_or_init(|| {
+        Regex::new(
+            r#"^<(\d{1,3})>(\d{1,2}) (\S+) (\S+) (\S+) (\S+) (\S+) (-|(?:\[(?:[^\]"]|"(?:[^"\\]|\\.)*")*\])+)(?: (.*))?$"#,
+        )
+        .expect("syslog pattern is valid")
+    })
+}
 
-    // Collect all durations for percentile calculation
-    let mut durations: Vec<f64> = entries
-        .par_iter()
-        .filter_map(|e| e.duration_ms)
-        .collect();
+/// `name="value"` pairs inside an RFC 5424 structured data element
+fn syslog_sd_param_regex() -> &'static Regex {
+    static SD_PARAM: OnceLock<Regex> = OnceLock::new();
+    SD_PARAM.get_or_init(|| {
+        Regex::new(r#"([^\s=\]"]+)="((?:[^"\\]|\\.)*)""#).expect("SD-PARAM pattern is valid")
+    })
+}
 
-    let (avg_duration, min_duration, max_duration, p50, p95, p99) = if durations.is_empty() {
+/// Undo the `\"`, `\\` and `\]` escapes allowed in SD-PARAM values
+fn unescape_sd_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some(next @ ('"' | '\\' | ']'))) => {
+                out.push(next);
+                chars.next();
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Parse one RFC 5424 line into `parse_logs`-style keys plus `hostname`,
+/// `pid`, `message_id` and every structured data parameter
+///
+/// NILVALUE (`-`) header fields are omitted from the map.
+fn parse_syslog_line(line: &str) -> Result<HashMap<String, String>, String> {
+    let caps = syslog_regex()
+        .captures(line)
+        .ok_or_else(|| "does not match RFC 5424 syslog format".to_string())?;
+    let pri: u8 = caps[1]
+        .parse()
+        .ok()
+        .filter(|pri| *pri <= 191)
+        .ok_or_else(|| format!("invalid PRI value {}", &caps[1]))?;
+    let level = match pri % 8 {
+        0..=1 => "ERROR",
+        2..=3 => "WARN",
+        4..=5 => "INFO",
+        _ => "DEBUG",
+    };
+
+    let mut map = HashMap::new();
+    // Structured data goes in first so header fields win on key collisions
+    for param in syslog_sd_param_regex().captures_iter(&caps[8]) {
+        map.insert(param[1].to_string(), unescape_sd_value(&param[2]));
+    }
+
+    map.insert("level".to_string(), level.to_string());
+    let message = caps.get(9).map_or("", |m| m.as_str());
+    map.insert(
+        "message".to_string(),
+        message.trim_start_matches('\u{feff}').to_string(),
+    );
+
+    let header = [
+        ("timestamp", &caps[3]),
+        ("hostname", &caps[4]),
+        ("service", &caps[5]),
+        ("pid", &caps[6]),
+        ("message_id", &caps[7]),
+    ];
+    for (key, value) in header {
+        if value != "-" {
+            map.insert(key.to_string(), value.to_string());
+        }
+    }
+
+    Ok(map)
+}
+
+/// Parse RFC 5424 syslog lines in parallel
+///
+/// `TIMESTAMP`, `APP-NAME`, `PROCID`, `MSGID` and `MSG` become `timestamp`,
+/// `service`, `pid`, `message_id` and `message`; the severity (PRI mod 8) is
+/// mapped to `level` (0–1 ERROR, 2–3 WARN, 4–5 INFO, 6–7 DEBUG). Structured
+/// data parameters are added as plain keys. A line that does not parse is
+/// returned in place as `{"raw": <line>, "parse_error": <reason>}`.
+///
+/// # Arguments
+/// * `log_lines` - Sequence of syslog strings, one per message
+///
+/// # Returns
+/// * One map per input line, or an error if `log_lines` is not a sequence of strings
+#[pyfunction]
+fn parse_logs_syslog(log_lines: &PyAny) -> PyResult<Vec<HashMap<String, String>>> {
+    let log_lines: Vec<String> = log_lines.extract().map_err(|e| {
+        PyValueError::new_err(format!("log_lines must be a sequence of strings: {}", e))
+    })?;
+
+    let entries = log_lines
+        .par_iter()
+        .map(|line| {
+            parse_syslog_line(line).unwrap_or_else(|reason| {
+                HashMap::from([
+                    ("raw".to_string(), line.clone()),
+                    ("parse_error".to_string(), reason),
+                ])
+            })
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+/// Parse and validate a single log line, returning the error without a
+/// line-number prefix
+fn validate_log_line(line: &str) -> Result<LogEntry, String> {
+    // Try to parse
+    let entry: LogEntry =
+        serde_json::from_str(line).map_err(|e| format!("JSON parse error: {}", e))?;
+    validate_log_entry(&entry)?;
+    Ok(entry)
+}
+
+/// Check an already-parsed entry against the log schema rules
+fn validate_log_entry(entry: &LogEntry) -> Result<(), String> {
+    // Validate required fields
+    if entry.timestamp.is_empty() {
+        return Err("Missing or empty timestamp".to_string());
+    }
+
+    // Validate log level
+    let valid_levels = ["ERROR", "WARN", "INFO", "DEBUG"];
+    if !valid_levels.contains(&entry.level.as_str()) {
+        return Err(format!(
+            "Invalid log level '{}'. Must be one of: ERROR, WARN, INFO, DEBUG",
+            entry.level
+        ));
+    }
+
+    // Validate duration if present
+    if let Some(duration) = entry.duration_ms {
+        if duration < 0.0 {
+            return Err(format!("Invalid duration_ms {}. Must be >= 0", duration));
+        }
+    }
+
+    // Validate status code if present
+    if let Some(status) = entry.status_code {
+        if !(100..=599).contains(&status) {
+            return Err(format!("Invalid status_code {}. Must be 100-599", status));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate log entries with detailed error reporting
+///
+/// This function checks log schema and returns detailed validation errors.
+/// The validation rules demonstrate how Rust's type system and error handling
+/// can catch issues that might slip through in dynamically-typed Python.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON strings to validate
+/// * `strict` - Also require timestamps to be valid RFC 3339 (default False,
+///   which only requires them to be non-empty)
+///
+/// # Returns
+/// * Tuple of (valid_count, error_messages)
+#[pyfunction]
+#[pyo3(signature = (log_lines, strict=false))]
+pub fn validate_logs(log_lines: Vec<String>, strict: bool) -> PyResult<(usize, Vec<String>)> {
+    let results: Vec<Result<LogEntry, String>> = log_lines
+        .par_iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            validate_log_line(line)
+                .and_then(|entry| {
+                    if strict {
+                        rust_core::validate_timestamp_format(&entry.timestamp)
+                            .map_err(|e| e.to_string())?;
+                    }
+                    Ok(entry)
+                })
+                .map_err(|e| format!("Line {}: {}", idx + 1, e))
+        })
+        .collect();
+
+    let mut errors = Vec::new();
+    let mut valid_count = 0;
+
+    for result in results {
+        match result {
+            Ok(_) => valid_count += 1,
+            Err(e) => errors.push(e),
+        }
+    }
+
+    Ok((valid_count, errors))
+}
+
+/// Validate log entries, returning one structured dict per invalid line
+///
+/// Applies the same rules as `validate_logs`. Each dict has `line` (1-based),
+/// `id` (the line's top-level `"id"` string, or None if absent or unparseable)
+/// and `error` (the message without a line prefix). Valid lines produce no
+/// entry, and results follow input order.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON strings to validate
+#[pyfunction]
+fn validate_logs_detailed(py: Python, log_lines: Vec<String>) -> PyResult<Vec<PyObject>> {
+    let failures: Vec<(usize, Option<String>, String)> = log_lines
+        .par_iter()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let error = validate_log_line(line).err()?;
+            let id = serde_json::from_str::<serde_json::Value>(line)
+                .ok()
+                .and_then(|v| v.get("id")?.as_str().map(str::to_string));
+            Some((idx + 1, id, error))
+        })
+        .collect();
+
+    failures
+        .into_iter()
+        .map(|(line, id, error)| {
+            let dict = pyo3::types::PyDict::new(py);
+            dict.set_item("line", line)?;
+            dict.set_item("id", id)?;
+            dict.set_item("error", error)?;
+            Ok(dict.into())
+        })
+        .collect()
+}
+
+/// Compute comprehensive statistics from log entries
+///
+/// This is the performance showcase function - it processes potentially millions
+/// of log entries to compute aggregations and percentiles in parallel. This would
+/// be 20-50x slower in pure Python due to GIL and interpreted nature.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+///
+/// # Returns
+/// * LogStats object with all computed statistics
+#[pyfunction]
+fn compute_stats(log_lines: Vec<String>) -> PyResult<LogStats> {
+    // Parse all logs in parallel
+    let entries: Vec<LogEntry> = log_lines
+        .par_iter()
+        .filter_map(|line| serde_json::from_str::<LogEntry>(line).ok())
+        .collect();
+
+    if entries.is_empty() {
+        return Err(PyValueError::new_err("No valid log entries found"));
+    }
+
+    Ok(compute_stats_from_entries(&entries))
+}
+
+/// Compute `LogStats` over already-parsed entries
+///
+/// Shared by `compute_stats`, `batch_process_partial` and the grouped
+/// variants. Empty input yields a `LogStats` with every count, duration and
+/// distribution at zero or empty; callers that treat it as an error must check
+/// before calling this.
+fn compute_stats_from_entries(entries: &[LogEntry]) -> LogStats {
+    // Count by log level
+    let error_count = entries.par_iter().filter(|e| e.level == "ERROR").count();
+    let warn_count = entries.par_iter().filter(|e| e.level == "WARN").count();
+    let info_count = entries.par_iter().filter(|e| e.level == "INFO").count();
+
+    // Collect all durations for percentile calculation
+    let mut durations: Vec<f64> = entries
+        .par_iter()
+        .filter_map(|e| e.duration_ms)
+        .collect();
+
+    let (avg_duration, min_duration, max_duration, p50, p95, p99) = if durations.is_empty() {
         (0.0, 0.0, 0.0, 0.0, 0.0, 0.0)
     } else {
         // Sort for percentile calculation
@@ -266,21 +1330,26 @@ fn compute_stats(log_lines: Vec<String>) -> PyResult<LogStats> {
         let min = durations[0];
         let max = durations[durations.len() - 1];
 
-        // Calculate percentiles
-        let p50_idx = (durations.len() as f64 * 0.50) as usize;
-        let p95_idx = (durations.len() as f64 * 0.95) as usize;
-        let p99_idx = (durations.len() as f64 * 0.99) as usize;
+        // Interpolated (R-7) percentiles; cannot fail since durations is non-empty
+        let p = rust_core::compute_percentiles(&durations, &[0.50, 0.95, 0.99])
+            .unwrap_or_else(|_| vec![0.0; 3]);
+
+        (avg, min, max, p[0], p[1], p[2])
+    };
 
-        let p50 = durations[p50_idx.min(durations.len() - 1)];
-        let p95 = durations[p95_idx.min(durations.len() - 1)];
-        let p99 = durations[p99_idx.min(durations.len() - 1)];
+    let total_duration_ms: f64 = durations.iter().sum();
 
-        (avg, min, max, p50, p95, p99)
+    // Sample variance, reusing the durations collected for percentiles
+    let variance = if durations.len() < 2 {
+        0.0
+    } else {
+        let sum_sq: f64 = durations.iter().map(|d| (d - avg_duration).powi(2)).sum();
+        sum_sq / (durations.len() - 1) as f64
     };
 
     // Status code distribution
     let mut status_code_distribution = HashMap::new();
-    for entry in &entries {
+    for entry in entries {
         if let Some(code) = entry.status_code {
             *status_code_distribution.entry(code).or_insert(0) += 1;
         }
@@ -288,7 +1357,7 @@ fn compute_stats(log_lines: Vec<String>) -> PyResult<LogStats> {
 
     // Error codes (4xx, 5xx)
     let mut error_count_by_code = HashMap::new();
-    for entry in &entries {
+    for entry in entries {
         if let Some(code) = entry.status_code {
             if code >= 400 {
                 *error_count_by_code.entry(code).or_insert(0) += 1;
@@ -296,20 +1365,24 @@ fn compute_stats(log_lines: Vec<String>) -> PyResult<LogStats> {
         }
     }
 
-    Ok(LogStats {
+    LogStats {
         total_count: entries.len(),
         error_count,
         warn_count,
         info_count,
         avg_duration_ms: avg_duration,
+        total_duration_ms,
+        duration_count: durations.len(),
         min_duration_ms: min_duration,
         max_duration_ms: max_duration,
         p50_duration_ms: p50,
         p95_duration_ms: p95,
         p99_duration_ms: p99,
+        variance_duration_ms: variance,
+        std_dev_duration_ms: variance.sqrt(),
         status_code_distribution,
         error_count_by_code,
-    })
+    }
 }
 
 /// Filter logs by various criteria
@@ -320,47 +1393,101 @@ fn compute_stats(log_lines: Vec<String>) -> PyResult<LogStats> {
 ///
 /// # Arguments
 /// * `log_lines` - Vector of JSON log strings
-/// * `min_level` - Minimum log level (ERROR=3, WARN=2, INFO=1, DEBUG=0)
+/// * `min_level` - Minimum `LogLevel`. A level name string is still accepted
+///   but deprecated: it must match exactly (`"ERROR"`, not `"error"`), an
+///   unknown name applies no filter, and it emits a `DeprecationWarning`
 /// * `min_duration_ms` - Minimum duration in milliseconds (None = no filter)
 /// * `status_codes` - List of status codes to include (empty = all)
+/// * `start_time` - Inclusive RFC 3339 lower bound on timestamp (None = open)
+/// * `end_time` - Inclusive RFC 3339 upper bound on timestamp (None = open)
+///
+/// When a time bound is given, entries whose timestamp cannot be parsed are
+/// excluded.
 ///
 /// # Returns
 /// * Filtered list of log entries as HashMaps
 #[pyfunction]
+#[pyo3(signature = (
+    log_lines,
+    min_level=None,
+    min_duration_ms=None,
+    status_codes=None,
+    start_time=None,
+    end_time=None
+))]
 fn filter_logs(
+    py: Python,
+    log_lines: Vec<String>,
+    min_level: Option<&PyAny>,
+    min_duration_ms: Option<f64>,
+    status_codes: Option<Vec<i32>>,
+    start_time: Option<String>,
+    end_time: Option<String>,
+) -> PyResult<Vec<HashMap<String, String>>> {
+    let min_level = match min_level {
+        None => LogLevel::Debug,
+        Some(level) => match level.extract::<LogLevel>() {
+            Ok(level) => level,
+            Err(_) => {
+                let name: String = level.extract().map_err(|_| {
+                    pyo3::exceptions::PyTypeError::new_err("min_level must be a LogLevel or str")
+                })?;
+                PyErr::warn(
+                    py,
+                    py.get_type::<pyo3::exceptions::PyDeprecationWarning>(),
+                    "passing min_level as a string is deprecated; use LogLevel",
+                    1,
+                )?;
+                LogLevel::of_entry(&name)
+            }
+        },
+    };
+
+    filter_log_lines(
+        log_lines,
+        min_level,
+        min_duration_ms,
+        status_codes,
+        start_time,
+        end_time,
+    )
+}
+
+/// `filter_logs` once `min_level` has been resolved to a `LogLevel`
+fn filter_log_lines(
     log_lines: Vec<String>,
-    min_level: Option<String>,
+    min_level: LogLevel,
     min_duration_ms: Option<f64>,
     status_codes: Option<Vec<i32>>,
+    start_time: Option<String>,
+    end_time: Option<String>,
 ) -> PyResult<Vec<HashMap<String, String>>> {
+    // Validate the time bounds up front so bad input fails loudly
+    let parse_bound = |bound: &Option<String>, name: &str| -> PyResult<Option<DateTime<Utc>>> {
+        bound
+            .as_ref()
+            .map(|s| {
+                parse_timestamp(s).ok_or_else(|| {
+                    PyValueError::new_err(format!("Invalid {} '{}': expected RFC 3339", name, s))
+                })
+            })
+            .transpose()
+    };
+    let start = parse_bound(&start_time, "start_time")?;
+    let end = parse_bound(&end_time, "end_time")?;
+
     // Parse all logs in parallel
     let entries: Vec<LogEntry> = log_lines
         .par_iter()
         .filter_map(|line| serde_json::from_str::<LogEntry>(line).ok())
         .collect();
 
-    // Helper to convert level to numeric value for comparison
-    let level_to_num = |level: &str| -> i32 {
-        match level {
-            "ERROR" => 3,
-            "WARN" => 2,
-            "INFO" => 1,
-            "DEBUG" => 0,
-            _ => 0,
-        }
-    };
-
-    let min_level_num = min_level
-        .as_ref()
-        .map(|l| level_to_num(l))
-        .unwrap_or(0);
-
     // Apply filters in parallel
     let filtered: Vec<LogEntry> = entries
         .into_par_iter()
         .filter(|entry| {
             // Check log level
-            if level_to_num(&entry.level) < min_level_num {
+            if LogLevel::of_entry(&entry.level) < min_level {
                 return false;
             }
 
@@ -375,6 +1502,16 @@ fn filter_logs(
                 }
             }
 
+            // Check time range
+            if start.is_some() || end.is_some() {
+                let Some(ts) = parse_timestamp(&entry.timestamp) else {
+                    return false;
+                };
+                if start.is_some_and(|s| ts < s) || end.is_some_and(|e| ts > e) {
+                    return false;
+                }
+            }
+
             // Check status codes
             if let Some(ref codes) = status_codes {
                 if !codes.is_empty() {
@@ -393,27 +1530,7 @@ fn filter_logs(
         .collect();
 
     // Convert to Python-friendly format
-    let result: Vec<HashMap<String, String>> = filtered
-        .iter()
-        .map(|entry| {
-            let mut map = HashMap::new();
-            map.insert("timestamp".to_string(), entry.timestamp.clone());
-            map.insert("level".to_string(), entry.level.clone());
-            map.insert("message".to_string(), entry.message.clone());
-            if let Some(duration) = entry.duration_ms {
-                map.insert("duration_ms".to_string(), duration.to_string());
-            }
-            if let Some(status) = entry.status_code {
-                map.insert("status_code".to_string(), status.to_string());
-            }
-            if let Some(ref user_id) = entry.user_id {
-                map.insert("user_id".to_string(), user_id.clone());
-            }
-            map
-        })
-        .collect();
-
-    Ok(result)
+    Ok(filtered.iter().map(entry_to_dict).collect())
 }
 
 /// Batch process logs with all operations
@@ -429,23 +1546,3062 @@ fn filter_logs(
 /// * Tuple of (LogStats, error_messages)
 #[pyfunction]
 fn batch_process(log_lines: Vec<String>) -> PyResult<(LogStats, Vec<String>)> {
-    let (_, errors) = validate_logs(log_lines.clone())?;
+    let (_, errors) = validate_logs(log_lines.clone(), false)?;
     let stats = compute_stats(log_lines)?;
     Ok((stats, errors))
 }
 
-/// Python module definition
+/// Parse JSON log lines in parallel, silently skipping malformed lines
+fn parse_entries(log_lines: &[String]) -> Vec<LogEntry> {
+    log_lines
+        .par_iter()
+        .filter_map(|line| serde_json::from_str::<LogEntry>(line).ok())
+        .collect()
+}
+
+/// Convert a `LogEntry` into the string map returned to Python
 ///
-/// This is where we expose our Rust functions to Python. PyO3 handles all the
-/// type conversions and memory management automatically. The module can be
-/// imported in Python as: `import rust_processor`
-#[pymodule]
-fn rust_processor(_py: Python, m: &PyModule) -> PyResult<()> {
-    m.add_function(wrap_pyfunction!(parse_logs, m)?)?;
-    m.add_function(wrap_pyfunction!(validate_logs, m)?)?;
-    m.add_function(wrap_pyfunction!(compute_stats, m)?)?;
-    m.add_function(wrap_pyfunction!(filter_logs, m)?)?;
-    m.add_function(wrap_pyfunction!(batch_process, m)?)?;
-    m.add_class::<LogStats>()?;
-    Ok(())
+/// Optional fields are omitted rather than set to an empty string.
+fn entry_to_dict(entry: &LogEntry) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    map.insert("timestamp".to_string(), entry.timestamp.clone());
+    map.insert("level".to_string(), entry.level.clone());
+    map.insert("message".to_string(), entry.message.clone());
+    if let Some(duration) = entry.duration_ms {
+        map.insert("duration_ms".to_string(), duration.to_string());
+    }
+    if let Some(status) = entry.status_code {
+        map.insert("status_code".to_string(), status.to_string());
+    }
+    if let Some(ref user_id) = entry.user_id {
+        map.insert("user_id".to_string(), user_id.clone());
+    }
+    map
+}
+
+/// Parse an RFC 3339 timestamp into UTC, returning None if it is malformed
+fn parse_timestamp(timestamp: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Format a Unix epoch second as an RFC 3339 UTC timestamp
+fn format_epoch(seconds: i64) -> String {
+    Utc.timestamp_opt(seconds, 0)
+        .single()
+        .map(|dt| dt.to_rfc3339_opts(SecondsFormat::Secs, true))
+        .unwrap_or_default()
+}
+
+/// Group log entries into per-user sessions
+///
+/// Entries for each user are sorted by timestamp and split into a new session
+/// whenever the gap between consecutive entries reaches `session_timeout_minutes`.
+/// Entries without a `user_id` or with an unparseable timestamp are skipped.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+/// * `session_timeout_minutes` - Idle gap that ends a session; ValueError if
+///   it is too large to represent as a duration
+///
+/// # Returns
+/// * List of session dicts with `user_id`, `session_start`, `session_end`,
+///   `entry_count` and `has_error`, ordered by user then start time
+#[pyfunction]
+fn compute_sessions(
+    py: Python,
+    log_lines: Vec<String>,
+    session_timeout_minutes: u64,
+) -> PyResult<Vec<HashMap<String, PyObject>>> {
+    let timeout = i64::try_from(session_timeout_minutes)
+        .ok()
+        .and_then(chrono::Duration::try_minutes)
+        .ok_or_else(|| PyValueError::new_err("session_timeout_minutes is too large"))?;
+    let entries = parse_entries(&log_lines);
+
+    // Group by user, keeping the parsed timestamp alongside each entry
+    let mut by_user: HashMap<&str, Vec<(DateTime<Utc>, &LogEntry)>> = HashMap::new();
+    for entry in &entries {
+        let Some(ref user_id) = entry.user_id else {
+            continue;
+        };
+        if let Some(ts) = parse_timestamp(&entry.timestamp) {
+            by_user
+                .entry(user_id.as_str())
+                .or_default()
+                .push((ts, entry));
+        }
+    }
+
+    let mut users: Vec<&str> = by_user.keys().copied().collect();
+    users.sort();
+
+    let mut sessions = Vec::new();
+    for user_id in users {
+        let user_entries = by_user.get_mut(user_id).unwrap();
+        user_entries.sort_by_key(|(ts, _)| *ts);
+
+        let mut start = 0;
+        for i in 1..=user_entries.len() {
+            let split =
+                i == user_entries.len() || user_entries[i].0 - user_entries[i - 1].0 >= timeout;
+            if !split {
+                continue;
+            }
+
+            let session = &user_entries[start..i];
+            let mut map = HashMap::new();
+            map.insert("user_id".to_string(), user_id.into_py(py));
+            map.insert(
+                "session_start".to_string(),
+                session[0].1.timestamp.clone().into_py(py),
+            );
+            map.insert(
+                "session_end".to_string(),
+                session[session.len() - 1].1.timestamp.clone().into_py(py),
+            );
+            map.insert("entry_count".to_string(), session.len().into_py(py));
+            map.insert(
+                "has_error".to_string(),
+                session.iter().any(|(_, e)| e.level == "ERROR").into_py(py),
+            );
+            sessions.push(map);
+            start = i;
+        }
+    }
+
+    Ok(sessions)
+}
+
+/// Parse a single JSON `DataRecord` passed in from Python
+fn parse_record_json(record_json: &str) -> PyResult<DataRecord> {
+    serde_json::from_str(record_json)
+        .map_err(|e| PyValueError::new_err(format!("JSON parse error: {}", e)))
+}
+
+/// Parse a human-readable bucket size such as "30s", "5m", "1h" or "1d"
+///
+/// A bare integer is taken as seconds. Zero-length buckets are rejected.
+fn parse_bucket_size(spec: &str) -> PyResult<u64> {
+    let spec = spec.trim();
+    let (digits, unit_seconds) = match spec.char_indices().last() {
+        Some((idx, 's')) => (&spec[..idx], 1),
+        Some((idx, 'm')) => (&spec[..idx], 60),
+        Some((idx, 'h')) => (&spec[..idx], 3600),
+        Some((idx, 'd')) => (&spec[..idx], 86400),
+        _ => (spec, 1),
+    };
+
+    match digits.parse::<u64>() {
+        Ok(n) if n > 0 => n
+            .checked_mul(unit_seconds)
+            .ok_or_else(|| PyValueError::new_err(format!("Bucket size too large: '{}'", spec))),
+        _ => Err(PyValueError::new_err(format!(
+            "Invalid bucket size '{}': expected e.g. \"30s\", \"5m\", \"1h\" or \"1d\"",
+            spec
+        ))),
+    }
+}
+
+/// Parse a JSON array of `DataRecord`s passed in from Python
+fn parse_records_json(records_json: &str) -> PyResult<Vec<DataRecord>> {
+    serde_json::from_str(records_json)
+        .map_err(|e| PyValueError::new_err(format!("JSON parse error: {}", e)))
+}
+
+/// Export records as Prometheus text-format gauges
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `metric_name` - Prometheus metric name for every sample
+/// * `label_keys` - Metadata keys to attach as labels
+///
+/// # Returns
+/// * Prometheus exposition text with one sample per record
+///
+/// # Raises
+/// * `ValueError` if `metric_name` or a label key is not a valid Prometheus
+///   name, or a key is `id` or repeats
+#[pyfunction]
+fn records_to_prometheus_text(
+    records_json: &str,
+    metric_name: &str,
+    label_keys: Vec<String>,
+) -> PyResult<String> {
+    let records = parse_records_json(records_json)?;
+    let label_keys: Vec<&str> = label_keys.iter().map(String::as_str).collect();
+
+    rust_core::records_to_prometheus_metrics(&records, metric_name, &label_keys)
+        .map_err(processing_error_to_py)
+}
+
+/// Render records as an HTML table, e.g. for display in Jupyter notebooks
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `max_rows` - Maximum rows to render (None = all)
+///
+/// # Returns
+/// * HTML `<table>` string with all values escaped
+#[pyfunction]
+#[pyo3(signature = (records_json, max_rows=None))]
+fn records_to_html_table(records_json: &str, max_rows: Option<usize>) -> PyResult<String> {
+    let records = parse_records_json(records_json)?;
+    Ok(rust_core::records_to_html_table(&records, max_rows))
+}
+
+/// Compute the running fraction of ERROR entries over time
+///
+/// Entries are sorted by timestamp (unparseable timestamps sort first), then
+/// after the k-th entry the rate is `errors_seen / k`.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+///
+/// # Returns
+/// * List of `(timestamp, error_rate)` tuples, one per parsed entry
+#[pyfunction]
+fn compute_running_error_rate(log_lines: Vec<String>) -> PyResult<Vec<(String, f64)>> {
+    let mut entries = parse_entries(&log_lines);
+    entries.sort_by_cached_key(|e| parse_timestamp(&e.timestamp));
+
+    let mut errors_seen = 0usize;
+    let rates = entries
+        .into_iter()
+        .enumerate()
+        .map(|(idx, entry)| {
+            if entry.level == "ERROR" {
+                errors_seen += 1;
+            }
+            (entry.timestamp, errors_seen as f64 / (idx + 1) as f64)
+        })
+        .collect();
+
+    Ok(rates)
+}
+
+/// Compute the mix of HTTP status classes per time bucket
+///
+/// Entries are grouped into buckets of `bucket_minutes` aligned to the Unix
+/// epoch. Entries without a `status_code` count towards `total` but no class;
+/// buckets that contain no status codes at all are omitted. Entries with an
+/// unparseable timestamp are skipped.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+/// * `bucket_minutes` - Bucket width in minutes (must be > 0)
+///
+/// # Returns
+/// * List of dicts with `bucket`, `2xx_count`, `3xx_count`, `4xx_count`,
+///   `5xx_count` and `total`, sorted by bucket
+#[pyfunction]
+fn compute_status_code_series(
+    py: Python,
+    log_lines: Vec<String>,
+    bucket_minutes: u64,
+) -> PyResult<Vec<HashMap<String, PyObject>>> {
+    if bucket_minutes == 0 {
+        return Err(PyValueError::new_err(
+            "bucket_minutes must be greater than 0",
+        ));
+    }
+    let bucket_seconds = bucket_minutes
+        .checked_mul(60)
+        .and_then(|seconds| i64::try_from(seconds).ok())
+        .ok_or_else(|| PyValueError::new_err("bucket_minutes is too large"))?;
+
+    // bucket start -> (counts for 2xx..5xx, total, has status code)
+    let mut buckets: BTreeMap<i64, ([usize; 4], usize, bool)> = BTreeMap::new();
+    for entry in parse_entries(&log_lines) {
+        let Some(ts) = parse_timestamp(&entry.timestamp) else {
+            continue;
+        };
+        let bucket = ts.timestamp().div_euclid(bucket_seconds) * bucket_seconds;
+        let (classes, total, has_status) = buckets.entry(bucket).or_default();
+        *total += 1;
+        if let Some(code) = entry.status_code {
+            *has_status = true;
+            if (200..600).contains(&code) {
+                classes[(code / 100 - 2) as usize] += 1;
+            }
+        }
+    }
+
+    let series = buckets
+        .into_iter()
+        .filter(|(_, (_, _, has_status))| *has_status)
+        .map(|(bucket, (classes, total, _))| {
+            let mut map = HashMap::new();
+            map.insert("bucket".to_string(), format_epoch(bucket).into_py(py));
+            for (i, count) in classes.iter().enumerate() {
+                map.insert(format!("{}xx_count", i + 2), count.into_py(py));
+            }
+            map.insert("total".to_string(), total.into_py(py));
+            map
+        })
+        .collect();
+
+    Ok(series)
+}
+
+/// Compute `LogStats` separately for each user
+///
+/// Entries are grouped by `user_id`; entries without one are collected under
+/// `"<unknown>"`. Each group gets a full `LogStats` computed in parallel.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+///
+/// # Returns
+/// * Dict mapping user ID to LogStats
+#[pyfunction]
+fn compute_stats_by_user(log_lines: Vec<String>) -> PyResult<HashMap<String, LogStats>> {
+    let entries = parse_entries(&log_lines);
+
+    let mut by_user: HashMap<String, Vec<LogEntry>> = HashMap::new();
+    for entry in entries {
+        let user = entry
+            .user_id
+            .clone()
+            .unwrap_or_else(|| "<unknown>".to_string());
+        by_user.entry(user).or_default().push(entry);
+    }
+
+    Ok(by_user
+        .into_par_iter()
+        .map(|(user, entries)| {
+            let stats = compute_stats_from_entries(&entries);
+            (user, stats)
+        })
+        .collect())
+}
+
+/// Convert a core `ProcessResult` into a Python dict
+fn process_result_to_dict(py: Python, result: &ProcessResult) -> PyObject {
+    let dict = pyo3::types::PyDict::new(py);
+    // Setting string keys on a fresh dict cannot fail
+    let _ = dict.set_item("total_processed", result.total_processed);
+    let _ = dict.set_item("total_value", result.total_value);
+    let _ = dict.set_item("average_value", result.average_value);
+    let _ = dict.set_item("min_value", result.min_value);
+    let _ = dict.set_item("max_value", result.max_value);
+    let _ = dict.set_item("categories", result.categories.clone());
+    let _ = dict.set_item("capped_count", result.capped_count);
+    dict.into()
+}
+
+/// Convert a `DataRecord` into a Python dict with the same keys as its JSON form
+fn record_to_dict(py: Python, record: &DataRecord) -> PyObject {
+    let dict = pyo3::types::PyDict::new(py);
+    // Setting string keys on a fresh dict cannot fail
+    let _ = dict.set_item("id", &record.id);
+    let _ = dict.set_item("value", record.value);
+    let _ = dict.set_item("category", &record.category);
+    let _ = dict.set_item("timestamp", &record.timestamp);
+    let _ = dict.set_item("metadata", record.metadata.clone());
+    dict.into()
+}
+
+/// Pretty-printed JSON Schema describing a `LogEntry`
+#[cfg(feature = "schema")]
+pub fn log_entry_schema() -> String {
+    let schema = schemars::schema_for!(LogEntry);
+    serde_json::to_string_pretty(&schema).expect("JSON Schema always serializes")
+}
+
+// OpenTelemetry mapping follows the Logs Data Model and the OTLP/JSON
+// encoding of opentelemetry-proto v1.3.2: 64-bit integers are encoded as
+// decimal strings and attribute values are typed `AnyValue` objects.
+
+/// OTel `SeverityNumber` for a log level (0 is SEVERITY_NUMBER_UNSPECIFIED)
+fn otel_severity_number(level: &str) -> u8 {
+    match level.to_ascii_uppercase().as_str() {
+        "DEBUG" => 5,
+        "INFO" => 9,
+        "WARN" | "WARNING" => 13,
+        "ERROR" => 17,
+        _ => 0,
+    }
+}
+
+/// Nanoseconds since the Unix epoch as an OTLP/JSON string, "0" if unknown
+fn otel_time_unix_nano(timestamp: Option<DateTime<Utc>>) -> String {
+    timestamp
+        .and_then(|dt| dt.timestamp_nanos_opt())
+        .unwrap_or(0)
+        .to_string()
+}
+
+/// Map a `LogEntry` to an OTel `LogRecord` in OTLP/JSON form
+///
+/// Missing optional fields are left out of `attributes`, and an unparseable
+/// timestamp becomes `timeUnixNano: "0"` (unknown).
+pub fn log_entry_to_otel_json(entry: &LogEntry) -> String {
+    let mut attributes = Vec::new();
+    if let Some(duration) = entry.duration_ms {
+        attributes.push(serde_json::json!({
+            "key": "duration_ms",
+            "value": { "doubleValue": duration },
+        }));
+    }
+    if let Some(status) = entry.status_code {
+        attributes.push(serde_json::json!({
+            "key": "http.status_code",
+            "value": { "intValue": status.to_string() },
+        }));
+    }
+    if let Some(user_id) = &entry.user_id {
+        attributes.push(serde_json::json!({
+            "key": "user.id",
+            "value": { "stringValue": user_id },
+        }));
+    }
+
+    serde_json::json!({
+        "timeUnixNano": otel_time_unix_nano(parse_timestamp(&entry.timestamp)),
+        "severityNumber": otel_severity_number(&entry.level),
+        "severityText": entry.level,
+        "body": { "stringValue": entry.message },
+        "attributes": attributes,
+    })
+    .to_string()
+}
+
+/// Map `LogStats` to an OTLP/JSON `scopeMetrics` array
+///
+/// The single scope holds gauges for the average, median, p95, p99 and
+/// maximum duration, cumulative monotonic sums for the entry and level
+/// counts, and a response sum with one data point per `http.status_code`.
+/// Every data point is stamped with the current time.
+pub fn log_stats_to_otel_metrics_json(stats: &LogStats, scope_name: &str) -> String {
+    const CUMULATIVE: u8 = 2;
+    let now = otel_time_unix_nano(Some(Utc::now()));
+
+    let gauge = |name: &str, description: &str, value: f64| {
+        serde_json::json!({
+            "name": name,
+            "description": description,
+            "unit": "ms",
+            "gauge": { "dataPoints": [{ "timeUnixNano": now, "asDouble": value }] },
+        })
+    };
+    let sum = |name: &str, description: &str, data_points: Vec<serde_json::Value>| {
+        serde_json::json!({
+            "name": name,
+            "description": description,
+            "unit": "1",
+            "sum": {
+                "dataPoints": data_points,
+                "aggregationTemporality": CUMULATIVE,
+                "isMonotonic": true,
+            },
+        })
+    };
+    let point =
+        |count: usize| serde_json::json!({ "timeUnixNano": now, "asInt": count.to_string() });
+
+    let mut status_codes: Vec<(&i32, &usize)> = stats.status_code_distribution.iter().collect();
+    status_codes.sort();
+    let response_points = status_codes
+        .into_iter()
+        .map(|(code, count)| {
+            let mut data_point = point(*count);
+            data_point["attributes"] = serde_json::json!([{
+                "key": "http.status_code",
+                "value": { "intValue": code.to_string() },
+            }]);
+            data_point
+        })
+        .collect();
+
+    let gauges = [
+        (
+            "log.duration.avg",
+            "Average request duration",
+            stats.avg_duration_ms,
+        ),
+        (
+            "log.duration.p50",
+            "Median request duration",
+            stats.p50_duration_ms,
+        ),
+        (
+            "log.duration.p95",
+            "95th percentile request duration",
+            stats.p95_duration_ms,
+        ),
+        (
+            "log.duration.p99",
+            "99th percentile request duration",
+            stats.p99_duration_ms,
+        ),
+        (
+            "log.duration.max",
+            "Maximum request duration",
+            stats.max_duration_ms,
+        ),
+    ];
+    let counters = [
+        ("log.entries", "Log entries processed", stats.total_count),
+        ("log.errors", "ERROR level entries", stats.error_count),
+        ("log.warnings", "WARN level entries", stats.warn_count),
+    ];
+
+    let mut metrics: Vec<serde_json::Value> = gauges
+        .iter()
+        .map(|(name, description, value)| gauge(name, description, *value))
+        .collect();
+    metrics.extend(
+        counters
+            .iter()
+            .map(|(name, description, count)| sum(name, description, vec![point(*count)])),
+    );
+    metrics.push(sum(
+        "log.responses",
+        "Responses by HTTP status code",
+        response_points,
+    ));
+
+    serde_json::json!([{
+        "scope": { "name": scope_name },
+        "metrics": metrics,
+    }])
+    .to_string()
+}
+
+/// Incrementally maintained record statistics
+///
+/// Records are passed as JSON strings, matching the other DataRecord-based
+/// functions. `finalize` is O(1) regardless of how many records were added.
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+pub struct StatsAccumulator {
+    inner: rust_core::StatsAccumulator,
+}
+
+#[pymethods]
+impl StatsAccumulator {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a record (JSON object) to the running statistics
+    fn add_record(&mut self, record_json: &str) -> PyResult<()> {
+        let record = parse_record_json(record_json)?;
+        self.inner.add_record(&record);
+        Ok(())
+    }
+
+    /// Remove a previously added record (JSON object)
+    fn remove_record(&mut self, record_json: &str) -> PyResult<()> {
+        let record = parse_record_json(record_json)?;
+        self.inner.remove_record(&record);
+        Ok(())
+    }
+
+    /// Compute the current statistics as a dict
+    fn finalize(&self, py: Python) -> PyObject {
+        process_result_to_dict(py, &self.inner.finalize())
+    }
+
+    /// Serialize the accumulator state for checkpointing
+    fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(&self.inner).map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Restore an accumulator from a `to_json` checkpoint
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        serde_json::from_str(json)
+            .map(|inner| StatsAccumulator { inner })
+            .map_err(|e| PyValueError::new_err(format!("JSON parse error: {}", e)))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("StatsAccumulator(count={})", self.inner.count())
+    }
+}
+
+/// Records from a trailing time span with O(1) statistics
+///
+/// Records are passed as JSON strings. The window covers the last
+/// `duration_seconds` before the newest timestamp pushed (or passed to
+/// `advance_to`); older records are evicted.
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct SlidingWindow {
+    inner: rust_core::SlidingWindow,
+}
+
+#[pymethods]
+impl SlidingWindow {
+    #[new]
+    fn new(duration_seconds: u64) -> Self {
+        SlidingWindow {
+            inner: rust_core::SlidingWindow::new(duration_seconds),
+        }
+    }
+
+    /// Add a record (JSON object) and evict expired ones
+    fn push(&mut self, record_json: &str) -> PyResult<()> {
+        let record = parse_record_json(record_json)?;
+        self.inner.push(record).map_err(processing_error_to_py)
+    }
+
+    /// Move the window's clock forward without adding a record
+    fn advance_to(&mut self, timestamp: &str) -> PyResult<()> {
+        self.inner
+            .advance_to(timestamp)
+            .map_err(processing_error_to_py)
+    }
+
+    /// Statistics over the records currently in the window, as a dict
+    fn current_stats(&self, py: Python) -> PyObject {
+        process_result_to_dict(py, &self.inner.current_stats())
+    }
+
+    fn current_count(&self) -> usize {
+        self.inner.current_count()
+    }
+
+    fn oldest_timestamp(&self) -> Option<String> {
+        self.inner.oldest_timestamp()
+    }
+
+    fn newest_timestamp(&self) -> Option<String> {
+        self.inner.newest_timestamp()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "SlidingWindow(duration_seconds={}, count={})",
+            self.inner.duration_seconds(),
+            self.inner.current_count()
+        )
+    }
+}
+
+/// Histogram of `duration_ms` across log entries
+///
+/// Returns `(bucket_lower, bucket_upper, count)` tuples for `bucket_count`
+/// equal-width buckets. Entries without a duration, or with a NaN/infinite
+/// one, are skipped.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+/// * `bucket_count` - Number of buckets (must be greater than zero)
+#[pyfunction]
+fn compute_duration_histogram(
+    log_lines: Vec<String>,
+    bucket_count: usize,
+) -> PyResult<Vec<(f64, f64, usize)>> {
+    let durations: Vec<f64> = parse_entries(&log_lines)
+        .iter()
+        .filter_map(|entry| entry.duration_ms)
+        .collect();
+
+    rust_core::compute_histogram(&durations, bucket_count).map_err(processing_error_to_py)
+}
+
+/// Aggregate DataRecords by category
+///
+/// Returns a dict mapping category name (in sorted order) to a dict of that
+/// category's stats. An empty record list yields an empty dict.
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `percentile_targets` - Quantiles in `[0.0, 1.0]` to report as a list of
+///   `(quantile, value)` tuples under `percentiles`
+#[pyfunction]
+#[pyo3(signature = (records_json, percentile_targets=Vec::new()))]
+fn aggregate_by_category(
+    py: Python,
+    records_json: &str,
+    percentile_targets: Vec<f64>,
+) -> PyResult<BTreeMap<String, PyObject>> {
+    let records = parse_records_json(records_json)?;
+
+    rust_core::aggregate_by_category(&records, &percentile_targets)
+        .map_err(processing_error_to_py)?
+        .into_iter()
+        .map(|(category, stats)| {
+            let dict = pyo3::types::PyDict::new(py);
+            dict.set_item("count", stats.count)?;
+            dict.set_item("total_value", stats.total_value)?;
+            dict.set_item("average_value", stats.average_value)?;
+            dict.set_item("min_value", stats.min_value)?;
+            dict.set_item("max_value", stats.max_value)?;
+            dict.set_item("variance", stats.variance)?;
+            dict.set_item("std_dev", stats.std_dev)?;
+            dict.set_item("percentiles", stats.percentiles)?;
+            Ok((category, dict.into()))
+        })
+        .collect()
+}
+
+/// Join two DataRecord sets on `id`
+///
+/// Matched records keep the left record's fields with metadata merged (right
+/// wins on conflicts). Outer joins keep unmatched records unchanged.
+///
+/// # Arguments
+/// * `left_json` / `right_json` - JSON arrays of DataRecord objects
+/// * `strategy` - One of "inner", "left_outer" or "right_outer"
+///
+/// # Returns
+/// * JSON array of joined records
+#[pyfunction]
+#[pyo3(signature = (left_json, right_json, strategy="inner"))]
+fn join_records(left_json: &str, right_json: &str, strategy: &str) -> PyResult<String> {
+    let strategy = match strategy {
+        "inner" => rust_core::JoinStrategy::Inner,
+        "left_outer" => rust_core::JoinStrategy::LeftOuter,
+        "right_outer" => rust_core::JoinStrategy::RightOuter,
+        other => {
+            return Err(PyValueError::new_err(format!(
+                "Unknown join strategy '{}': expected inner, left_outer or right_outer",
+                other
+            )))
+        }
+    };
+    let left = parse_records_json(left_json)?;
+    let right = parse_records_json(right_json)?;
+
+    let joined = rust_core::join_records(&left, &right, strategy);
+    serde_json::to_string(&joined).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Union two DataRecord sets, resolving records that share an ID
+///
+/// Output follows primary order, followed by the secondary-only records.
+///
+/// # Arguments
+/// * `primary_json` / `secondary_json` - JSON arrays of DataRecord objects
+/// * `conflict_strategy` - One of "keep_primary", "keep_secondary",
+///   "merge_metadata" (primary fields plus the secondary's missing metadata
+///   keys) or "use_highest_value"
+///
+/// # Returns
+/// * JSON array of merged records
+#[pyfunction]
+#[pyo3(signature = (primary_json, secondary_json, conflict_strategy="keep_primary"))]
+fn merge_datasets(
+    primary_json: &str,
+    secondary_json: &str,
+    conflict_strategy: &str,
+) -> PyResult<String> {
+    let conflict = match conflict_strategy {
+        "keep_primary" => rust_core::ConflictResolution::KeepPrimary,
+        "keep_secondary" => rust_core::ConflictResolution::KeepSecondary,
+        "merge_metadata" => rust_core::ConflictResolution::MergeMetadata,
+        "use_highest_value" => rust_core::ConflictResolution::UseHighestValue,
+        other => {
+            return Err(PyValueError::new_err(format!(
+                "Unknown conflict strategy '{}': expected keep_primary, keep_secondary, merge_metadata or use_highest_value",
+                other
+            )))
+        }
+    };
+    let primary = parse_records_json(primary_json)?;
+    let secondary = parse_records_json(secondary_json)?;
+
+    let merged = rust_core::merge_datasets(primary, secondary, conflict);
+    serde_json::to_string(&merged).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Find log entries whose `duration_ms` is an IQR outlier
+///
+/// Entries outside `[Q1 - k * IQR, Q3 + k * IQR]` of the duration
+/// distribution are returned as dicts, in input order. Entries without a
+/// duration are ignored; fewer than four durations yields an empty list.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+/// * `iqr_multiplier` - Fence multiplier `k` (1.5 is the conventional choice)
+#[pyfunction]
+#[pyo3(signature = (log_lines, iqr_multiplier=1.5))]
+fn detect_outliers(log_lines: Vec<String>, iqr_multiplier: f64) -> Vec<HashMap<String, String>> {
+    let entries = parse_entries(&log_lines);
+    let durations: Vec<f64> = entries.iter().filter_map(|e| e.duration_ms).collect();
+    let Some((lower, upper)) = rust_core::iqr_fences(&durations, iqr_multiplier) else {
+        return Vec::new();
+    };
+
+    entries
+        .iter()
+        .filter(|e| e.duration_ms.is_some_and(|d| d < lower || d > upper))
+        .map(entry_to_dict)
+        .collect()
+}
+
+/// Pearson correlation between two numeric log entry fields
+///
+/// Supported fields are `duration_ms` and `status_code`. Entries missing
+/// either field are excluded. Returns None if fewer than two entries remain or
+/// either field is constant.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+/// * `field_a` / `field_b` - Numeric field names to correlate
+#[pyfunction]
+fn compute_correlation(
+    log_lines: Vec<String>,
+    field_a: &str,
+    field_b: &str,
+) -> PyResult<Option<f64>> {
+    fn numeric_field(field: &str) -> PyResult<fn(&LogEntry) -> Option<f64>> {
+        match field {
+            "duration_ms" => Ok(|e| e.duration_ms),
+            "status_code" => Ok(|e| e.status_code.map(f64::from)),
+            other => Err(PyValueError::new_err(format!(
+                "Unsupported field '{}': expected duration_ms or status_code",
+                other
+            ))),
+        }
+    }
+
+    let get_a = numeric_field(field_a)?;
+    let get_b = numeric_field(field_b)?;
+    let entries = parse_entries(&log_lines);
+
+    Ok(rust_core::pearson_correlation(
+        entries.iter().filter_map(|e| Some((get_a(e)?, get_b(e)?))),
+    ))
+}
+
+/// Return the `n` slowest log entries, slowest first
+///
+/// Entries without a `duration_ms` are ignored. Uses a bounded min-heap, so
+/// the cost is O(m log n) for m entries.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+/// * `n` - Number of entries to return
+#[pyfunction]
+fn top_n_by_duration(log_lines: Vec<String>, n: usize) -> Vec<HashMap<String, String>> {
+    let timed: Vec<LogEntry> = parse_entries(&log_lines)
+        .into_iter()
+        .filter(|e| e.duration_ms.is_some())
+        .collect();
+    let durations: Vec<f64> = timed.iter().filter_map(|e| e.duration_ms).collect();
+
+    rust_core::top_n_indices(&durations, n)
+        .into_iter()
+        .map(|i| entry_to_dict(&timed[i]))
+        .collect()
+}
+
+/// Group DataRecords into fixed-size time windows
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `bucket` - Bucket size such as "30s", "5m" or "1h"
+///
+/// # Returns
+/// * Dict mapping bucket start (Unix epoch seconds) to a JSON array of records
+#[pyfunction]
+fn group_by_time_bucket(records_json: &str, bucket: &str) -> PyResult<HashMap<i64, String>> {
+    let bucket_seconds = parse_bucket_size(bucket)?;
+    let records = parse_records_json(records_json)?;
+
+    rust_core::group_by_time_bucket(&records, bucket_seconds)
+        .into_iter()
+        .map(|(start, group)| {
+            serde_json::to_string(&group)
+                .map(|json| (start, json))
+                .map_err(|e| PyValueError::new_err(e.to_string()))
+        })
+        .collect()
+}
+
+/// Compute record statistics for each fixed-size time window
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `bucket` - Bucket size such as "30s", "5m" or "1h"
+///
+/// # Returns
+/// * Dict mapping bucket start (Unix epoch seconds) to a stats dict
+#[pyfunction]
+fn compute_stats_by_time_bucket(
+    py: Python,
+    records_json: &str,
+    bucket: &str,
+) -> PyResult<HashMap<i64, PyObject>> {
+    let bucket_seconds = parse_bucket_size(bucket)?;
+    let records = parse_records_json(records_json)?;
+
+    let stats = rust_core::compute_stats_by_time_bucket(&records, bucket_seconds)
+        .map_err(processing_error_to_py)?;
+
+    Ok(stats
+        .iter()
+        .map(|(start, result)| (*start, process_result_to_dict(py, result)))
+        .collect())
+}
+
+/// Find log entries whose `duration_ms` is more than `z_threshold` standard
+/// deviations above the mean
+///
+/// Mean and (sample) standard deviation are taken over every entry with a
+/// duration; entries without one are skipped. With `include_stats`, the first
+/// element of the result is a synthetic `{"mean", "std_dev", "threshold"}`
+/// dict describing the cutoff that was applied.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+/// * `z_threshold` - Number of standard deviations above the mean (must be > 0)
+/// * `include_stats` - Prepend the synthetic stats entry
+#[pyfunction]
+#[pyo3(signature = (log_lines, z_threshold, include_stats=false))]
+fn detect_slow_requests(
+    log_lines: Vec<String>,
+    z_threshold: f64,
+    include_stats: bool,
+) -> PyResult<Vec<HashMap<String, String>>> {
+    if z_threshold.is_nan() || z_threshold <= 0.0 {
+        return Err(PyValueError::new_err("z_threshold must be greater than 0"));
+    }
+
+    let timed: Vec<LogEntry> = parse_entries(&log_lines)
+        .into_iter()
+        .filter(|e| e.duration_ms.is_some())
+        .collect();
+    let durations: Vec<f64> = timed.iter().filter_map(|e| e.duration_ms).collect();
+
+    let (mean, std_dev) = if durations.is_empty() {
+        (0.0, 0.0)
+    } else {
+        let n = durations.len() as f64;
+        let mean = durations.iter().sum::<f64>() / n;
+        let variance = if durations.len() < 2 {
+            0.0
+        } else {
+            durations.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        };
+        (mean, variance.sqrt())
+    };
+    let threshold = mean + z_threshold * std_dev;
+
+    let mut results = Vec::new();
+    if include_stats {
+        results.push(HashMap::from([
+            ("mean".to_string(), mean.to_string()),
+            ("std_dev".to_string(), std_dev.to_string()),
+            ("threshold".to_string(), threshold.to_string()),
+        ]));
+    }
+    results.extend(
+        timed
+            .iter()
+            .filter(|e| e.duration_ms.is_some_and(|d| d > threshold))
+            .map(entry_to_dict),
+    );
+
+    Ok(results)
+}
+
+/// Apply a saved filter configuration to DataRecords
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `spec_json` - JSON FilterSpec (`min_value`, `max_value`, `categories`,
+///   `metadata_filters`, `exclude_categories`; all optional)
+///
+/// # Returns
+/// * JSON array of the records passing every filter
+#[pyfunction]
+fn apply_filter_spec(records_json: &str, spec_json: &str) -> PyResult<String> {
+    let spec = rust_core::FilterSpec::from_json(spec_json).map_err(processing_error_to_py)?;
+    let records = parse_records_json(records_json)?;
+
+    let filtered = rust_core::apply_filter_spec(&records, &spec);
+    serde_json::to_string(&filtered).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Sort DataRecords by one or more keys
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `keys` - Sort key names such as `["CategoryAsc", "ValueDesc"]`; later
+///   keys break ties in earlier ones and the sort is stable
+///
+/// # Returns
+/// * JSON array of sorted records
+#[pyfunction]
+fn sort_records(records_json: &str, keys: Vec<String>) -> PyResult<String> {
+    let keys: Vec<rust_core::SortKey> = keys
+        .iter()
+        .map(|k| k.parse())
+        .collect::<Result<_, _>>()
+        .map_err(processing_error_to_py)?;
+    let mut records = parse_records_json(records_json)?;
+
+    rust_core::sort_records(&mut records, &keys);
+    serde_json::to_string(&records).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Uniformly sample `k` DataRecords using reservoir sampling
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `k` - Sample size; every record is returned if there are fewer
+/// * `seed` - RNG seed; the same seed always yields the same sample
+///
+/// # Returns
+/// * JSON array of sampled records
+#[pyfunction]
+fn reservoir_sample(records_json: &str, k: usize, seed: u64) -> PyResult<String> {
+    let records = parse_records_json(records_json)?;
+
+    let sample = rust_core::reservoir_sample_seeded(&records, k, seed);
+    serde_json::to_string(&sample).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Remove DataRecords with duplicate IDs
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `strategy` - "KeepFirst", "KeepLast" or "KeepHighestValue"
+///
+/// # Returns
+/// * JSON array of records ordered by first occurrence of each ID
+#[pyfunction]
+fn dedup_records(records_json: &str, strategy: &str) -> PyResult<String> {
+    let strategy: rust_core::DedupStrategy = strategy.parse().map_err(processing_error_to_py)?;
+    let records = parse_records_json(records_json)?;
+
+    let deduped = rust_core::dedup_records(records, strategy);
+    serde_json::to_string(&deduped).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Entries falling in one window of `time_windows`, in time order
+struct TimeWindow {
+    start: i64,
+    end: i64,
+    entries: Vec<LogEntry>,
+}
+
+/// Split log entries into consecutive, non-overlapping `window_seconds`
+/// windows starting at the earliest entry's second
+///
+/// Entries with an unparseable timestamp are skipped and empty windows are
+/// omitted. Raises `ValueError` if `window_seconds` is 0, or so large that a
+/// window would end past the latest representable timestamp.
+fn time_windows(log_lines: &[String], window_seconds: u64) -> PyResult<Vec<TimeWindow>> {
+    if window_seconds == 0 {
+        return Err(PyValueError::new_err(
+            "window_seconds must be greater than 0",
+        ));
+    }
+    let too_large = || PyValueError::new_err("window_seconds is too large");
+    let window_seconds = i64::try_from(window_seconds).map_err(|_| too_large())?;
+
+    let mut timed: Vec<(i64, LogEntry)> = parse_entries(log_lines)
+        .into_iter()
+        .filter_map(|entry| Some((parse_timestamp(&entry.timestamp)?.timestamp(), entry)))
+        .collect();
+    timed.sort_by_key(|(ts, _)| *ts);
+
+    let mut windows: Vec<TimeWindow> = Vec::new();
+    let Some(&(origin, _)) = timed.first() else {
+        return Ok(windows);
+    };
+    for (ts, entry) in timed {
+        let start = origin + (ts - origin) / window_seconds * window_seconds;
+        match windows.last_mut() {
+            Some(window) if window.start == start => window.entries.push(entry),
+            _ => {
+                let end = start
+                    .checked_add(window_seconds)
+                    .filter(|&end| Utc.timestamp_opt(end, 0).single().is_some())
+                    .ok_or_else(too_large)?;
+                windows.push(TimeWindow {
+                    start,
+                    end,
+                    entries: vec![entry],
+                });
+            }
+        }
+    }
+    Ok(windows)
+}
+
+/// Compute request volume per fixed time window
+///
+/// Entries are sorted by timestamp and split into consecutive, non-overlapping
+/// windows of `window_seconds`, starting at the earliest entry's second.
+/// Windows with no entries are omitted and entries with an unparseable
+/// timestamp are skipped. `avg_duration_ms` and `max_duration_ms` are 0 when
+/// no entry in the window has a duration. Raises `ValueError` if
+/// `window_seconds` is so large that a window would end past the latest
+/// representable timestamp.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+/// * `window_seconds` - Window width in seconds (must be > 0)
+///
+/// # Returns
+/// * List of dicts with `window_start`, `window_end`, `total_requests`,
+///   `error_requests`, `warn_requests`, `avg_duration_ms` and
+///   `max_duration_ms`, sorted by `window_start`
+#[pyfunction]
+fn compute_request_rate(
+    log_lines: Vec<String>,
+    window_seconds: u64,
+) -> PyResult<Vec<HashMap<String, String>>> {
+    let mut windows = Vec::new();
+    for window in time_windows(&log_lines, window_seconds)? {
+        let chunk = &window.entries;
+        let durations: Vec<f64> = chunk.iter().filter_map(|e| e.duration_ms).collect();
+        let avg = if durations.is_empty() {
+            0.0
+        } else {
+            durations.iter().sum::<f64>() / durations.len() as f64
+        };
+        let max = durations.iter().copied().fold(0.0, f64::max);
+        let count_level = |level: &str| chunk.iter().filter(|e| e.level == level).count();
+
+        let mut map = HashMap::new();
+        map.insert("window_start".to_string(), format_epoch(window.start));
+        map.insert("window_end".to_string(), format_epoch(window.end));
+        map.insert("total_requests".to_string(), chunk.len().to_string());
+        map.insert(
+            "error_requests".to_string(),
+            count_level("ERROR").to_string(),
+        );
+        map.insert("warn_requests".to_string(), count_level("WARN").to_string());
+        map.insert("avg_duration_ms".to_string(), avg.to_string());
+        map.insert("max_duration_ms".to_string(), max.to_string());
+        windows.push(map);
+    }
+
+    Ok(windows)
+}
+
+/// Check Apdex thresholds, raising if they are negative or out of order
+fn validate_apdex_thresholds(satisfied_ms: f64, tolerating_ms: f64) -> PyResult<()> {
+    if !(satisfied_ms >= 0.0 && tolerating_ms >= 0.0) {
+        return Err(PyValueError::new_err(
+            "Apdex thresholds must be non-negative",
+        ));
+    }
+    if satisfied_ms >= tolerating_ms {
+        return Err(PyValueError::new_err(
+            "satisfied_ms must be less than tolerating_ms",
+        ));
+    }
+    Ok(())
+}
+
+/// Apdex score `(satisfied + tolerating / 2) / total` over the given
+/// durations, or None if there are none
+fn apdex_score(
+    durations: impl IntoIterator<Item = f64>,
+    satisfied_ms: f64,
+    tolerating_ms: f64,
+) -> Option<f64> {
+    let (mut satisfied, mut tolerating, mut total) = (0usize, 0usize, 0usize);
+    for duration in durations {
+        total += 1;
+        if duration <= satisfied_ms {
+            satisfied += 1;
+        } else if duration <= tolerating_ms {
+            tolerating += 1;
+        }
+    }
+    (total > 0).then(|| (satisfied as f64 + tolerating as f64 / 2.0) / total as f64)
+}
+
+/// Compute the Apdex (Application Performance Index) score
+///
+/// Entries with `duration_ms <= satisfied_ms` are Satisfied, those up to
+/// `tolerating_ms` are Tolerating and the rest Frustrated. Entries without a
+/// duration are ignored.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+/// * `satisfied_ms` - Upper bound for a satisfied request
+/// * `tolerating_ms` - Upper bound for a tolerated request
+///
+/// # Returns
+/// * Score in `[0.0, 1.0]`, or an error if no entry has a duration
+#[pyfunction]
+fn compute_apdex(log_lines: Vec<String>, satisfied_ms: f64, tolerating_ms: f64) -> PyResult<f64> {
+    validate_apdex_thresholds(satisfied_ms, tolerating_ms)?;
+    let entries = parse_entries(&log_lines);
+
+    apdex_score(
+        entries.iter().filter_map(|e| e.duration_ms),
+        satisfied_ms,
+        tolerating_ms,
+    )
+    .ok_or_else(|| PyValueError::new_err("No log entries with duration_ms"))
+}
+
+/// Compute the Apdex score separately for 2xx, 4xx and 5xx responses
+///
+/// Only entries with both a `status_code` and a `duration_ms` are counted.
+/// Classes with no such entries are left out of the result.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+/// * `satisfied_ms` - Upper bound for a satisfied request
+/// * `tolerating_ms` - Upper bound for a tolerated request
+///
+/// # Returns
+/// * Dict mapping `"2xx"`, `"4xx"` and `"5xx"` to their Apdex score
+#[pyfunction]
+fn compute_apdex_by_status_class(
+    log_lines: Vec<String>,
+    satisfied_ms: f64,
+    tolerating_ms: f64,
+) -> PyResult<HashMap<String, f64>> {
+    validate_apdex_thresholds(satisfied_ms, tolerating_ms)?;
+    let entries = parse_entries(&log_lines);
+
+    let scores = [("2xx", 2), ("4xx", 4), ("5xx", 5)]
+        .into_iter()
+        .filter_map(|(class, hundreds)| {
+            let durations = entries.iter().filter_map(|e| match e.status_code {
+                Some(code) if code / 100 == hundreds => e.duration_ms,
+                _ => None,
+            });
+            apdex_score(durations, satisfied_ms, tolerating_ms)
+                .map(|score| (class.to_string(), score))
+        })
+        .collect();
+
+    Ok(scores)
+}
+
+/// Find bursts of ERROR entries that occur close together in time
+///
+/// ERROR entries are sorted by timestamp (ties keep input order) and
+/// consecutive entries less than `window_ms` apart are chained into a
+/// cluster. Isolated errors are dropped, as are entries whose timestamp
+/// cannot be parsed.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+/// * `window_ms` - Maximum gap in milliseconds between neighbouring errors (must be > 0)
+///
+/// # Returns
+/// * List of clusters, each a list of at least two entry dicts, in time order
+#[pyfunction]
+fn find_correlated_errors(
+    log_lines: Vec<String>,
+    window_ms: f64,
+) -> PyResult<Vec<Vec<HashMap<String, String>>>> {
+    if window_ms.is_nan() || window_ms <= 0.0 {
+        return Err(PyValueError::new_err("window_ms must be greater than 0"));
+    }
+
+    let mut errors: Vec<(DateTime<Utc>, LogEntry)> = parse_entries(&log_lines)
+        .into_iter()
+        .filter(|entry| entry.level == "ERROR")
+        .filter_map(|entry| Some((parse_timestamp(&entry.timestamp)?, entry)))
+        .collect();
+    errors.sort_by_key(|(ts, _)| *ts);
+
+    let clusters = errors
+        .chunk_by(|(a, _), (b, _)| {
+            let gap_ms = (*b - *a)
+                .num_microseconds()
+                .map_or(f64::INFINITY, |us| us as f64 / 1000.0);
+            gap_ms < window_ms
+        })
+        .filter(|cluster| cluster.len() > 1)
+        .map(|cluster| cluster.iter().map(|(_, e)| entry_to_dict(e)).collect())
+        .collect();
+
+    Ok(clusters)
+}
+
+/// List the distinct `user_id`s that appear in the logs
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+///
+/// # Returns
+/// * Sorted, deduplicated user IDs; entries without a `user_id` are ignored
+#[pyfunction]
+fn extract_unique_users(log_lines: Vec<String>) -> PyResult<Vec<String>> {
+    let mut users: Vec<String> = parse_entries(&log_lines)
+        .into_par_iter()
+        .filter_map(|entry| entry.user_id)
+        .collect();
+    users.sort_unstable();
+    users.dedup();
+    Ok(users)
+}
+
+/// Summarise activity per user
+///
+/// Entries are parsed in parallel and then aggregated in a single sequential
+/// pass. `first_seen`/`last_seen` are the lexicographic min/max of the
+/// timestamp strings, which orders ISO 8601 timestamps correctly.
+/// `avg_duration_ms` is 0 when none of the user's entries has a duration.
+/// Entries without a `user_id` are ignored.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+///
+/// # Returns
+/// * Dict mapping user ID to a dict with `request_count`, `error_count`,
+///   `avg_duration_ms`, `first_seen` and `last_seen`
+#[pyfunction]
+fn compute_user_activity(
+    log_lines: Vec<String>,
+) -> PyResult<HashMap<String, HashMap<String, String>>> {
+    #[derive(Default)]
+    struct Activity {
+        requests: usize,
+        errors: usize,
+        duration_sum: f64,
+        duration_count: usize,
+        first_seen: String,
+        last_seen: String,
+    }
+
+    let mut by_user: HashMap<String, Activity> = HashMap::new();
+    for entry in parse_entries(&log_lines) {
+        let Some(user) = entry.user_id else {
+            continue;
+        };
+        let activity = by_user.entry(user).or_default();
+        if activity.requests == 0 || entry.timestamp < activity.first_seen {
+            activity.first_seen = entry.timestamp.clone();
+        }
+        if activity.requests == 0 || entry.timestamp > activity.last_seen {
+            activity.last_seen = entry.timestamp.clone();
+        }
+        activity.requests += 1;
+        if entry.level == "ERROR" {
+            activity.errors += 1;
+        }
+        if let Some(duration) = entry.duration_ms {
+            activity.duration_sum += duration;
+            activity.duration_count += 1;
+        }
+    }
+
+    Ok(by_user
+        .into_iter()
+        .map(|(user, activity)| {
+            let avg = if activity.duration_count > 0 {
+                activity.duration_sum / activity.duration_count as f64
+            } else {
+                0.0
+            };
+            let mut map = HashMap::new();
+            map.insert("request_count".to_string(), activity.requests.to_string());
+            map.insert("error_count".to_string(), activity.errors.to_string());
+            map.insert("avg_duration_ms".to_string(), avg.to_string());
+            map.insert("first_seen".to_string(), activity.first_seen);
+            map.insert("last_seen".to_string(), activity.last_seen);
+            (user, map)
+        })
+        .collect())
+}
+
+/// Export DataRecords as RFC 4180 CSV
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+///
+/// # Returns
+/// * CSV text with an `id,value,category,timestamp,metadata` header
+#[pyfunction]
+fn records_to_csv(records_json: &str) -> PyResult<String> {
+    let records = parse_records_json(records_json)?;
+    rust_core::records_to_csv(&records).map_err(processing_error_to_py)
+}
+
+/// Parse DataRecords from CSV written by `records_to_csv`
+///
+/// # Arguments
+/// * `csv_str` - CSV text; the `metadata` column is optional
+///
+/// # Returns
+/// * JSON array of DataRecord objects
+#[pyfunction]
+fn records_from_csv(csv_str: &str) -> PyResult<String> {
+    let records = rust_core::records_from_csv(csv_str).map_err(processing_error_to_py)?;
+    serde_json::to_string(&records).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Min-max scale DataRecord values into `[0.0, 1.0]`
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+///
+/// # Returns
+/// * JSON array of records with scaled values (all 0.5 if every value is equal)
+#[pyfunction]
+fn normalize_values(records_json: &str) -> PyResult<String> {
+    let mut records = parse_records_json(records_json)?;
+    rust_core::normalize_values(&mut records);
+    serde_json::to_string(&records).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Z-score scale DataRecord values using the sample standard deviation
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+///
+/// # Returns
+/// * JSON array of records with standardized values (all 0.0 if the standard deviation is 0)
+#[pyfunction]
+fn standardize_values(records_json: &str) -> PyResult<String> {
+    let mut records = parse_records_json(records_json)?;
+    rust_core::standardize_values(&mut records);
+    serde_json::to_string(&records).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Clip DataRecord values into `[lower, upper]`
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `lower` - Lower bound
+/// * `upper` - Upper bound (must be >= `lower`)
+///
+/// # Returns
+/// * JSON array of records with clipped values
+#[pyfunction]
+fn clamp_values(records_json: &str, lower: f64, upper: f64) -> PyResult<String> {
+    if lower > upper {
+        return Err(PyValueError::new_err(format!(
+            "lower ({}) must not exceed upper ({})",
+            lower, upper
+        )));
+    }
+    let mut records = parse_records_json(records_json)?;
+    rust_core::clamp_values(&mut records, lower, upper);
+    serde_json::to_string(&records).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Split DataRecords into one list per category
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+///
+/// # Returns
+/// * JSON object mapping category (sorted) to an array of its records in input order
+#[pyfunction]
+fn split_by_category(records_json: &str) -> PyResult<String> {
+    let records = parse_records_json(records_json)?;
+
+    let buckets: BTreeMap<String, Vec<DataRecord>> =
+        rust_core::split_by_category(&records).into_iter().collect();
+    serde_json::to_string(&buckets).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Shannon entropy (in bits) of the DataRecord category distribution
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+///
+/// # Returns
+/// * Entropy in bits (0.0 for an empty list or a single category)
+#[pyfunction]
+fn compute_entropy(records_json: &str) -> PyResult<f64> {
+    let records = parse_records_json(records_json)?;
+    Ok(rust_core::compute_entropy(&records))
+}
+
+/// Category entropy scaled into `[0.0, 1.0]` by `log2(num_categories)`
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+///
+/// # Returns
+/// * 1.0 for perfectly balanced categories, 0.0 for a single category
+#[pyfunction]
+fn compute_normalized_entropy(records_json: &str) -> PyResult<f64> {
+    let records = parse_records_json(records_json)?;
+    Ok(rust_core::compute_normalized_entropy(&records))
+}
+
+/// Simple moving average of DataRecord values in timestamp order
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `window` - Number of points to average (must be > 0); the first
+///   `window - 1` points average whatever predecessors exist
+///
+/// # Returns
+/// * List of `(timestamp, smoothed_value)` tuples
+#[pyfunction]
+fn compute_sma(records_json: &str, window: usize) -> PyResult<Vec<(String, f64)>> {
+    if window == 0 {
+        return Err(PyValueError::new_err("window must be greater than 0"));
+    }
+    let records = parse_records_json(records_json)?;
+    Ok(rust_core::compute_sma(&records, window))
+}
+
+/// Exponential moving average of DataRecord values in timestamp order
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `alpha` - Smoothing factor in `(0.0, 1.0]`
+///
+/// # Returns
+/// * List of `(timestamp, smoothed_value)` tuples
+#[pyfunction]
+fn compute_ema(records_json: &str, alpha: f64) -> PyResult<Vec<(String, f64)>> {
+    let records = parse_records_json(records_json)?;
+    rust_core::compute_ema(&records, alpha).map_err(processing_error_to_py)
+}
+
+/// Compute summary statistics over DataRecords
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `use_kahan` - Use Kahan compensated summation above `parallel_threshold` records
+/// * `parallel_threshold` - Largest dataset summed with the parallel pairwise reduction
+/// * `outlier_cap` - Leave out records more than this many standard
+///   deviations from the mean (must be at least 1.0); None keeps every record
+///
+/// # Returns
+/// * Dict with `total_processed`, `total_value`, `average_value`,
+///   `min_value`, `max_value`, `categories` and `capped_count` (records
+///   left out by `outlier_cap`)
+#[pyfunction]
+#[pyo3(signature = (records_json, use_kahan=true, parallel_threshold=100_000, outlier_cap=None))]
+fn process_records(
+    py: Python,
+    records_json: &str,
+    use_kahan: bool,
+    parallel_threshold: usize,
+    outlier_cap: Option<f64>,
+) -> PyResult<PyObject> {
+    let records = parse_records_json(records_json)?;
+    let config = rust_core::ProcessingConfig {
+        use_kahan,
+        parallel_threshold,
+        outlier_cap,
+    };
+
+    let result = rust_core::process_records_with_config(&records, &config)
+        .map_err(processing_error_to_py)?;
+    Ok(process_result_to_dict(py, &result))
+}
+
+/// Compare two snapshots of DataRecords by `id`
+///
+/// A record counts as changed when its value, category or metadata differs;
+/// timestamps are ignored and missing metadata equals an empty dict.
+///
+/// # Arguments
+/// * `before_json` - JSON array of DataRecord objects (old snapshot)
+/// * `after_json` - JSON array of DataRecord objects (new snapshot)
+///
+/// # Returns
+/// * Dict with `added` and `removed` record lists, `changed` as a list of
+///   `(old, new)` tuples, and `unchanged_count`
+#[pyfunction]
+fn record_diff(py: Python, before_json: &str, after_json: &str) -> PyResult<PyObject> {
+    let before = parse_records_json(before_json)?;
+    let after = parse_records_json(after_json)?;
+    let diff = rust_core::record_diff(&before, &after);
+
+    let to_list = |records: &[DataRecord]| -> Vec<PyObject> {
+        records.iter().map(|r| record_to_dict(py, r)).collect()
+    };
+    let changed: Vec<(PyObject, PyObject)> = diff
+        .changed
+        .iter()
+        .map(|(old, new)| (record_to_dict(py, old), record_to_dict(py, new)))
+        .collect();
+
+    let dict = pyo3::types::PyDict::new(py);
+    dict.set_item("added", to_list(&diff.added))?;
+    dict.set_item("removed", to_list(&diff.removed))?;
+    dict.set_item("changed", changed)?;
+    dict.set_item("unchanged_count", diff.unchanged_count)?;
+    Ok(dict.into())
+}
+
+/// Return the page of `limit` DataRecords starting at `offset`
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects, already sorted
+/// * `offset` - Index of the first record to return
+/// * `limit` - Maximum number of records per page
+///
+/// # Returns
+/// * Dict with `items`, `total`, `offset`, `limit`, `has_next` and `has_prev`
+#[pyfunction]
+fn paginate_records(
+    py: Python,
+    records_json: &str,
+    offset: usize,
+    limit: usize,
+) -> PyResult<PyObject> {
+    let records = parse_records_json(records_json)?;
+    let page = rust_core::paginate_records(&records, offset, limit);
+
+    let items: Vec<PyObject> = page.items.iter().map(|r| record_to_dict(py, r)).collect();
+    let dict = pyo3::types::PyDict::new(py);
+    dict.set_item("items", items)?;
+    dict.set_item("total", page.total)?;
+    dict.set_item("offset", page.offset)?;
+    dict.set_item("limit", page.limit)?;
+    dict.set_item("has_next", page.has_next)?;
+    dict.set_item("has_prev", page.has_prev)?;
+    Ok(dict.into())
+}
+
+/// Return up to `limit` DataRecords after the record named by `cursor`
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects, already sorted
+/// * `cursor` - `next_cursor` from the previous page, or None to start
+/// * `limit` - Maximum number of records per page; must be greater than 0
+///
+/// # Returns
+/// * Dict with `items`, `next_cursor` (None on the last page) and `has_more`
+#[pyfunction]
+#[pyo3(signature = (records_json, cursor=None, limit=100))]
+fn cursor_paginate_records(
+    py: Python,
+    records_json: &str,
+    cursor: Option<&str>,
+    limit: usize,
+) -> PyResult<PyObject> {
+    let records = parse_records_json(records_json)?;
+    let page = rust_core::cursor_paginate_records(&records, cursor, limit)
+        .map_err(processing_error_to_py)?;
+
+    let items: Vec<PyObject> = page.items.iter().map(|r| record_to_dict(py, r)).collect();
+    let dict = pyo3::types::PyDict::new(py);
+    dict.set_item("items", items)?;
+    dict.set_item("next_cursor", page.next_cursor)?;
+    dict.set_item("has_more", page.has_more)?;
+    Ok(dict.into())
+}
+
+/// Count records by their values for two metadata keys
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `key_a` - First metadata key
+/// * `key_b` - Second metadata key
+///
+/// # Returns
+/// * Dict mapping `(value_a, value_b)` tuples to record counts
+#[pyfunction]
+fn compute_metadata_cooccurrence(
+    records_json: &str,
+    key_a: &str,
+    key_b: &str,
+) -> PyResult<HashMap<(String, String), usize>> {
+    let records = parse_records_json(records_json)?;
+    Ok(rust_core::compute_metadata_cooccurrence(
+        &records, key_a, key_b,
+    ))
+}
+
+/// Count category transitions between consecutive records in time order
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+///
+/// # Returns
+/// * Dict mapping `(from_category, to_category)` tuples to counts
+#[pyfunction]
+fn compute_category_transition_matrix(
+    records_json: &str,
+) -> PyResult<HashMap<(String, String), usize>> {
+    let records = parse_records_json(records_json)?;
+    Ok(rust_core::compute_category_transition_matrix(&records))
+}
+
+/// Get the JSON Schema for a parsed log entry
+///
+/// # Returns
+/// * Pretty-printed JSON Schema string
+#[cfg(feature = "schema")]
+#[pyfunction]
+fn get_log_entry_schema() -> String {
+    log_entry_schema()
+}
+
+/// Convert a JSON log line into an OpenTelemetry LogRecord
+///
+/// # Arguments
+/// * `log_line` - JSON log string
+///
+/// # Returns
+/// * OTLP/JSON LogRecord string
+///
+/// # Raises
+/// * ValueError if the line is not a valid log entry
+#[pyfunction]
+#[pyo3(name = "log_entry_to_otel_json")]
+fn log_entry_to_otel_json_py(log_line: &str) -> PyResult<String> {
+    let entry = validate_log_line(log_line).map_err(PyValueError::new_err)?;
+    Ok(log_entry_to_otel_json(&entry))
+}
+
+/// Convert LogStats into OpenTelemetry metrics
+///
+/// # Arguments
+/// * `stats` - LogStats from `compute_stats`
+/// * `scope_name` - Instrumentation scope name reported with the metrics
+///
+/// # Returns
+/// * OTLP/JSON `scopeMetrics` array string
+#[pyfunction]
+#[pyo3(name = "log_stats_to_otel_metrics_json")]
+fn log_stats_to_otel_metrics_json_py(stats: PyRef<LogStats>, scope_name: &str) -> String {
+    log_stats_to_otel_metrics_json(&stats, scope_name)
+}
+
+/// Batch process logs, computing statistics over the lines that validate
+///
+/// Unlike `batch_process`, invalid lines are excluded from the statistics
+/// instead of merely being reported, and this never raises: with no valid
+/// lines the returned LogStats has all counts at zero.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+///
+/// # Returns
+/// * Tuple of (LogStats over valid lines, error messages for every invalid line)
+#[pyfunction]
+fn batch_process_partial(log_lines: Vec<String>) -> (LogStats, Vec<String>) {
+    let results: Vec<Result<LogEntry, String>> = log_lines
+        .par_iter()
+        .enumerate()
+        .map(|(idx, line)| validate_log_line(line).map_err(|e| format!("Line {}: {}", idx + 1, e)))
+        .collect();
+
+    let mut entries = Vec::with_capacity(results.len());
+    let mut errors = Vec::new();
+    for result in results {
+        match result {
+            Ok(entry) => entries.push(entry),
+            Err(e) => errors.push(e),
+        }
+    }
+
+    (compute_stats_from_entries(&entries), errors)
+}
+
+/// Gini coefficient of a value distribution
+///
+/// 0.0 means all values are equal; approaches 1.0 as the total concentrates
+/// in a single value. Raises `ValueError` for invalid JSON or any negative,
+/// NaN or infinite value.
+///
+/// # Arguments
+/// * `values_json` - JSON array of numbers
+#[pyfunction]
+fn compute_gini(values_json: &str) -> PyResult<f64> {
+    let values: Vec<f64> = serde_json::from_str(values_json)
+        .map_err(|e| PyValueError::new_err(format!("JSON parse error: {}", e)))?;
+    rust_core::compute_gini(&values).map_err(processing_error_to_py)
+}
+
+/// Characters of `message` that `detect_log_storms(fuzzy=True)` groups on
+const STORM_PREFIX_CHARS: usize = 50;
+
+/// Find bursts of repeated messages
+///
+/// Entries are bucketed into windows as in `compute_request_rate`, then
+/// grouped by exact `message` within each window. With `fuzzy`, messages are
+/// grouped by their first 50 characters instead, so messages that differ
+/// only in a trailing ID or address still count as one storm.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+/// * `window_seconds` - Window width in seconds (must be > 0)
+/// * `min_count` - Occurrences within one window that make a storm
+/// * `fuzzy` - Group by message prefix rather than the exact message
+///
+/// # Returns
+/// * List of dicts with `window_start`, `window_end`, `message` (the
+///   prefix when `fuzzy`), `count`, `level` (of the first occurrence),
+///   `first_occurrence` and `last_occurrence`, sorted by `window_start`
+///   then `message`
+#[pyfunction]
+#[pyo3(signature = (log_lines, window_seconds, min_count, fuzzy=false))]
+fn detect_log_storms(
+    log_lines: Vec<String>,
+    window_seconds: u64,
+    min_count: usize,
+    fuzzy: bool,
+) -> PyResult<Vec<HashMap<String, String>>> {
+    // (window start, message key) -> window end and entries in time order
+    let mut groups: BTreeMap<(i64, String), (i64, Vec<LogEntry>)> = BTreeMap::new();
+    for window in time_windows(&log_lines, window_seconds)? {
+        for entry in window.entries {
+            let key = if fuzzy {
+                entry.message.chars().take(STORM_PREFIX_CHARS).collect()
+            } else {
+                entry.message.clone()
+            };
+            groups
+                .entry((window.start, key))
+                .or_insert_with(|| (window.end, Vec::new()))
+                .1
+                .push(entry);
+        }
+    }
+
+    let storms = groups
+        .into_iter()
+        .filter(|(_, (_, entries))| entries.len() >= min_count)
+        .map(|((start, message), (end, entries))| {
+            let first = &entries[0];
+            let last = &entries[entries.len() - 1];
+
+            let mut map = HashMap::new();
+            map.insert("window_start".to_string(), format_epoch(start));
+            map.insert("window_end".to_string(), format_epoch(end));
+            map.insert("message".to_string(), message);
+            map.insert("count".to_string(), entries.len().to_string());
+            map.insert("level".to_string(), first.level.clone());
+            map.insert("first_occurrence".to_string(), first.timestamp.clone());
+            map.insert("last_occurrence".to_string(), last.timestamp.clone());
+            map
+        })
+        .collect();
+
+    Ok(storms)
+}
+
+/// Whether `line` continues the stack trace of the entry above it
+///
+/// Trace frames are indented (Java's `\tat ...`, Python's `  File ...`);
+/// Java's unindented `Caused by: ...` headers continue the trace as well.
+fn is_stack_trace_continuation(line: &str) -> bool {
+    line.starts_with(char::is_whitespace) || line.starts_with("Caused by: ")
+}
+
+/// Reassemble multiline stack traces into single log entries
+///
+/// Lines that start with whitespace or `Caused by: ` continue the entry above
+/// them and are appended to that entry's `message` on their own line. Every
+/// other line must be a JSON log line and starts a new entry, so plain-text
+/// lines raise the same parse error `parse_logs` would. At most
+/// `max_stack_depth` continuation lines are kept per entry; the rest are
+/// dropped. Blank lines are ignored, and a continuation line before the first
+/// entry raises `ValueError`.
+///
+/// # Arguments
+/// * `log_lines` - JSON log lines interleaved with raw trace lines
+/// * `max_stack_depth` - Continuation lines to keep per entry
+///
+/// # Returns
+/// * Entry dicts in input order; reassembled entries also carry
+///   `stack_trace: "true"`
+#[pyfunction]
+#[pyo3(signature = (log_lines, max_stack_depth=100))]
+fn extract_stack_traces(
+    log_lines: Vec<String>,
+    max_stack_depth: usize,
+) -> PyResult<Vec<HashMap<String, String>>> {
+    // (entry, continuation lines kept so far)
+    let mut entries: Vec<(LogEntry, usize)> = Vec::new();
+
+    for (idx, line) in log_lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if is_stack_trace_continuation(line) {
+            match entries.last_mut() {
+                Some((entry, depth)) => {
+                    if *depth < max_stack_depth {
+                        entry.message.push('\n');
+                        entry.message.push_str(line.trim_end());
+                        *depth += 1;
+                    }
+                }
+                None => {
+                    return Err(PyValueError::new_err(format!(
+                        "Line {}: Stack trace line without a preceding log entry",
+                        idx + 1
+                    )))
+                }
+            }
+            continue;
+        }
+
+        let entry = serde_json::from_str::<LogEntry>(line)
+            .map_err(|e| PyValueError::new_err(format!("Line {}: Parse error: {}", idx + 1, e)))?;
+        entries.push((entry, 0));
+    }
+
+    Ok(entries
+        .iter()
+        .map(|(entry, depth)| {
+            let mut map = entry_to_dict(entry);
+            if *depth > 0 {
+                map.insert("stack_trace".to_string(), "true".to_string());
+            }
+            map
+        })
+        .collect())
+}
+
+/// Whether an entry counts against availability: a 5xx status or ERROR level
+fn is_failed_request(entry: &LogEntry) -> bool {
+    entry.status_code.is_some_and(|code| code >= 500) || entry.level == "ERROR"
+}
+
+/// Availability figures for a set of entries, or an error if it is empty
+fn availability_summary<'a>(
+    entries: impl IntoIterator<Item = &'a LogEntry>,
+) -> PyResult<HashMap<String, f64>> {
+    let (total, errors) = entries
+        .into_iter()
+        .fold((0usize, 0usize), |(total, errors), entry| {
+            (total + 1, errors + usize::from(is_failed_request(entry)))
+        });
+    if total == 0 {
+        return Err(PyValueError::new_err(
+            "cannot compute availability of zero requests",
+        ));
+    }
+
+    let success = total - errors;
+    let mut map = HashMap::new();
+    map.insert(
+        "availability_pct".to_string(),
+        success as f64 / total as f64 * 100.0,
+    );
+    map.insert("total_requests".to_string(), total as f64);
+    map.insert("error_requests".to_string(), errors as f64);
+    map.insert("success_requests".to_string(), success as f64);
+    Ok(map)
+}
+
+/// Service availability as the percentage of non-failed requests
+///
+/// A request fails if its `status_code` is 500 or above or its level is
+/// `ERROR`. Malformed lines are skipped.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+///
+/// # Returns
+/// * Dict with `availability_pct`, `total_requests`, `error_requests` and
+///   `success_requests`; raises `ValueError` if there are no requests
+#[pyfunction]
+fn compute_availability(log_lines: Vec<String>) -> PyResult<HashMap<String, f64>> {
+    availability_summary(&parse_entries(&log_lines))
+}
+
+/// Availability per fixed time window, for SLA burn-rate alerting
+///
+/// Windows follow `compute_request_rate`: `window_seconds` wide, starting at
+/// the earliest timestamp, with empty windows omitted. Entries with an
+/// unparseable timestamp are skipped.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+/// * `window_seconds` - Window width in seconds (must be > 0)
+///
+/// # Returns
+/// * List of dicts with `window_start`, `window_end` and the fields of
+///   `compute_availability`, sorted by `window_start`; raises `ValueError`
+///   if there are no timestamped requests
+#[pyfunction]
+fn compute_availability_windowed(
+    py: Python,
+    log_lines: Vec<String>,
+    window_seconds: u64,
+) -> PyResult<Vec<PyObject>> {
+    let windows = time_windows(&log_lines, window_seconds)?;
+    if windows.is_empty() {
+        return Err(PyValueError::new_err(
+            "cannot compute availability of zero requests",
+        ));
+    }
+
+    windows
+        .iter()
+        .map(|window| {
+            let dict = pyo3::types::PyDict::new(py);
+            dict.set_item("window_start", format_epoch(window.start))?;
+            dict.set_item("window_end", format_epoch(window.end))?;
+            for (key, value) in availability_summary(&window.entries)? {
+                dict.set_item(key, value)?;
+            }
+            Ok(dict.into())
+        })
+        .collect()
+}
+
+/// Numeric tokens in error messages, including dotted runs such as IPs and
+/// versions, compiled once per process
+fn numeric_token_regex() -> &'static Regex {
+    static NUMERIC: OnceLock<Regex> = OnceLock::new();
+    NUMERIC.get_or_init(|| Regex::new(r"\d+(?:\.\d+)*").expect("numeric pattern is valid"))
+}
+
+/// Error entries sharing one normalized message pattern
+struct ErrorCluster {
+    pattern: String,
+    count: usize,
+    example_message: String,
+    first_seen: Option<(DateTime<Utc>, String)>,
+    last_seen: Option<(DateTime<Utc>, String)>,
+    status_codes: BTreeSet<i32>,
+}
+
+impl ErrorCluster {
+    fn new(pattern: String, example_message: String) -> Self {
+        Self {
+            pattern,
+            count: 0,
+            example_message,
+            first_seen: None,
+            last_seen: None,
+            status_codes: BTreeSet::new(),
+        }
+    }
+
+    fn add(&mut self, entry: &LogEntry) {
+        self.count += 1;
+        self.status_codes.extend(entry.status_code);
+        if let Some(ts) = parse_timestamp(&entry.timestamp) {
+            self.see(
+                Some((ts, entry.timestamp.clone())),
+                Some((ts, entry.timestamp.clone())),
+            );
+        }
+    }
+
+    fn absorb(&mut self, other: ErrorCluster) {
+        self.count += other.count;
+        self.status_codes.extend(other.status_codes);
+        self.see(other.first_seen, other.last_seen);
+    }
+
+    /// Widen `first_seen`/`last_seen` to cover the given times
+    fn see(
+        &mut self,
+        first: Option<(DateTime<Utc>, String)>,
+        last: Option<(DateTime<Utc>, String)>,
+    ) {
+        if let Some(first) = first {
+            if self.first_seen.as_ref().is_none_or(|(ts, _)| first.0 < *ts) {
+                self.first_seen = Some(first);
+            }
+        }
+        if let Some(last) = last {
+            if self.last_seen.as_ref().is_none_or(|(ts, _)| last.0 > *ts) {
+                self.last_seen = Some(last);
+            }
+        }
+    }
+
+    fn into_dict(self) -> HashMap<String, String> {
+        let codes: Vec<String> = self.status_codes.iter().map(|c| c.to_string()).collect();
+        let mut map = HashMap::new();
+        map.insert("pattern".to_string(), self.pattern);
+        map.insert("count".to_string(), self.count.to_string());
+        map.insert("example_message".to_string(), self.example_message);
+        map.insert(
+            "first_seen".to_string(),
+            self.first_seen.map(|(_, ts)| ts).unwrap_or_default(),
+        );
+        map.insert(
+            "last_seen".to_string(),
+            self.last_seen.map(|(_, ts)| ts).unwrap_or_default(),
+        );
+        map.insert("status_codes".to_string(), codes.join(","));
+        map
+    }
+}
+
+/// Group ERROR entries into failure modes by normalized message
+///
+/// Messages are normalized by replacing each numeric token (IDs, ports,
+/// IPs, embedded timestamps) with `<N>`, so `Connection refused to
+/// 10.0.0.7` and `... 10.0.0.12` share one pattern. Clusters are sorted by
+/// `count`, largest first. When there are more than `max_clusters`
+/// patterns, the largest `max_clusters - 1` are kept and the rest are
+/// merged into a final cluster with pattern `"other"`.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+/// * `max_clusters` - Maximum number of dicts to return (must be > 0)
+///
+/// # Returns
+/// * List of dicts with `pattern`, `count`, `example_message` (the first
+///   matching message), `first_seen`, `last_seen` (empty if no timestamp
+///   parses) and `status_codes` (sorted, comma-separated)
+#[pyfunction]
+fn classify_errors(
+    log_lines: Vec<String>,
+    max_clusters: usize,
+) -> PyResult<Vec<HashMap<String, String>>> {
+    if max_clusters == 0 {
+        return Err(PyValueError::new_err("max_clusters must be greater than 0"));
+    }
+
+    let mut by_pattern: HashMap<String, ErrorCluster> = HashMap::new();
+    for entry in parse_entries(&log_lines)
+        .iter()
+        .filter(|entry| entry.level == "ERROR")
+    {
+        let pattern = numeric_token_regex()
+            .replace_all(&entry.message, "<N>")
+            .into_owned();
+        by_pattern
+            .entry(pattern.clone())
+            .or_insert_with(|| ErrorCluster::new(pattern, entry.message.clone()))
+            .add(entry);
+    }
+
+    let mut clusters: Vec<ErrorCluster> = by_pattern.into_values().collect();
+    clusters.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.pattern.cmp(&b.pattern))
+    });
+
+    if clusters.len() > max_clusters {
+        let mut rest = clusters.split_off(max_clusters - 1).into_iter();
+        let mut other = rest.next().expect("at least two clusters are split off");
+        other.pattern = "other".to_string();
+        for cluster in rest {
+            other.absorb(cluster);
+        }
+        clusters.push(other);
+    }
+
+    Ok(clusters.into_iter().map(ErrorCluster::into_dict).collect())
+}
+
+/// Estimate how many requests were in flight at once
+///
+/// Each entry with a `duration_ms` covers `[timestamp, timestamp +
+/// duration_ms)`; a sweep over the start and end points tracks how many
+/// intervals are open. All events at the same instant are applied together,
+/// so requests that start on the same millisecond count together and a
+/// request ending exactly when another starts does not overlap it.
+/// `avg_concurrent` and `p95_concurrent` are time-weighted over the span
+/// from the first start to the last end, idle gaps included. Entries with
+/// no duration, a negative or non-finite one, one ending past the latest
+/// representable timestamp, or an unparseable timestamp are excluded.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+///
+/// # Returns
+/// * Dict with `peak_concurrent`, `avg_concurrent`, `p95_concurrent` and
+///   `total_requests_analysed`; all zero if no entry qualifies
+#[pyfunction]
+fn compute_concurrency_estimate(log_lines: Vec<String>) -> PyResult<HashMap<String, f64>> {
+    // (instant in microseconds, +1 for a start or -1 for an end)
+    let mut events: Vec<(i64, i64)> = Vec::new();
+    for entry in parse_entries(&log_lines) {
+        let (Some(start), Some(duration_ms)) =
+            (parse_timestamp(&entry.timestamp), entry.duration_ms)
+        else {
+            continue;
+        };
+        if !duration_ms.is_finite() || duration_ms < 0.0 {
+            continue;
+        }
+        // Durations that would end past chrono's last representable instant
+        // are as unusable as unparseable ones
+        let Some(end) = start.checked_add_signed(chrono::Duration::microseconds(
+            (duration_ms * 1000.0).round() as i64,
+        )) else {
+            continue;
+        };
+        events.push((start.timestamp_micros(), 1));
+        events.push((end.timestamp_micros(), -1));
+    }
+    events.sort_unstable();
+
+    let mut peak = 0i64;
+    let mut level = 0i64;
+    let mut previous: Option<i64> = None;
+    // (concurrency level, microseconds spent at it)
+    let mut segments: Vec<(i64, i64)> = Vec::new();
+    for group in events.chunk_by(|a, b| a.0 == b.0) {
+        let instant = group[0].0;
+        if let Some(previous) = previous {
+            let dt = instant
+                .checked_sub(previous)
+                .ok_or_else(|| PyValueError::new_err("log time span is too large"))?;
+            segments.push((level, dt));
+        }
+        level += group.iter().map(|(_, delta)| delta).sum::<i64>();
+        peak = peak.max(level);
+        previous = Some(instant);
+    }
+
+    // The segments tile the span between two instants, so it fits an i64
+    let span: i64 = segments.iter().map(|(_, dt)| dt).sum();
+    let (avg, p95) = if span > 0 {
+        let weighted = segments
+            .iter()
+            .try_fold(0i64, |total, (level, dt)| {
+                total.checked_add(level.checked_mul(*dt)?)
+            })
+            .ok_or_else(|| PyValueError::new_err("log time span is too large"))?;
+        segments.sort_unstable();
+        let threshold = span as f64 * 0.95;
+        let mut elapsed = 0i64;
+        let p95 = segments
+            .iter()
+            .find(|(_, dt)| {
+                elapsed += dt;
+                elapsed as f64 >= threshold
+            })
+            .map_or(0, |(level, _)| *level);
+        (weighted as f64 / span as f64, p95 as f64)
+    } else {
+        (0.0, 0.0)
+    };
+
+    let mut map = HashMap::new();
+    map.insert("peak_concurrent".to_string(), peak as f64);
+    map.insert("avg_concurrent".to_string(), avg);
+    map.insert("p95_concurrent".to_string(), p95);
+    map.insert(
+        "total_requests_analysed".to_string(),
+        (events.len() / 2) as f64,
+    );
+    Ok(map)
+}
+
+/// HTTP methods recognised at the start of a request log message
+const HTTP_METHODS: [&str; 9] = [
+    "GET", "HEAD", "POST", "PUT", "DELETE", "CONNECT", "OPTIONS", "TRACE", "PATCH",
+];
+
+/// Extract `"METHOD /path"` from a message such as `"GET /api/users?id=7 200"`
+///
+/// The query string is dropped so requests to the same path group together.
+fn parse_endpoint(message: &str) -> Option<String> {
+    let mut tokens = message.split_whitespace();
+    let method = tokens.next().filter(|m| HTTP_METHODS.contains(m))?;
+    let path = tokens.next().filter(|p| p.starts_with('/'))?;
+    let path = path.split(['?', '#']).next().unwrap_or(path);
+    Some(format!("{} {}", method, path))
+}
+
+/// Rank endpoints by tail latency
+///
+/// The endpoint is the HTTP method and path at the start of `message` (for
+/// example `GET /api/v1/users`), without any query string. Entries whose
+/// message does not start that way, or with no finite `duration_ms`, are
+/// skipped. Ties are broken by endpoint name.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+/// * `top_n` - Number of endpoints to return
+/// * `percentile` - Quantile in `(0.0, 1.0]` to rank by, e.g. `0.95`
+///
+/// # Returns
+/// * List of dicts with `endpoint`, `request_count`, `p{N}_duration_ms`
+///   (`p95_duration_ms` for `0.95`, `p99.9_duration_ms` for `0.999`),
+///   `avg_duration_ms` and `max_duration_ms`, slowest first
+#[pyfunction]
+fn find_slow_endpoints(
+    log_lines: Vec<String>,
+    top_n: usize,
+    percentile: f64,
+) -> PyResult<Vec<HashMap<String, String>>> {
+    if !(percentile > 0.0 && percentile <= 1.0) {
+        return Err(PyValueError::new_err("percentile must be in (0.0, 1.0]"));
+    }
+    // Round away float noise such as 0.95 * 100.0 == 94.99999999999999
+    let percentile_key = format!(
+        "p{}_duration_ms",
+        (percentile * 100.0 * 1000.0).round() / 1000.0
+    );
+
+    let mut by_endpoint: HashMap<String, Vec<f64>> = HashMap::new();
+    for entry in parse_entries(&log_lines) {
+        let Some(duration) = entry.duration_ms.filter(|d| d.is_finite()) else {
+            continue;
+        };
+        if let Some(endpoint) = parse_endpoint(&entry.message) {
+            by_endpoint.entry(endpoint).or_default().push(duration);
+        }
+    }
+
+    let mut ranked: Vec<(String, f64, Vec<f64>)> = by_endpoint
+        .into_iter()
+        .map(|(endpoint, durations)| {
+            let value = rust_core::compute_percentiles(&durations, &[percentile])
+                .map_err(processing_error_to_py)?[0];
+            Ok((endpoint, value, durations))
+        })
+        .collect::<PyResult<_>>()?;
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked.truncate(top_n);
+
+    Ok(ranked
+        .into_iter()
+        .map(|(endpoint, value, durations)| {
+            let avg = durations.iter().sum::<f64>() / durations.len() as f64;
+            let max = durations.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+            let mut map = HashMap::new();
+            map.insert("endpoint".to_string(), endpoint);
+            map.insert("request_count".to_string(), durations.len().to_string());
+            map.insert(percentile_key.clone(), value.to_string());
+            map.insert("avg_duration_ms".to_string(), avg.to_string());
+            map.insert("max_duration_ms".to_string(), max.to_string());
+            map
+        })
+        .collect())
+}
+
+/// Awaitable variants of the main entry points
+///
+/// Each `async_*` function returns an asyncio awaitable that runs the
+/// matching synchronous function on Tokio's blocking-thread pool, so the
+/// event loop stays free while Rayon does the work.
+#[cfg(feature = "pyo3-asyncio")]
+mod async_api {
+    use super::*;
+    use pyo3::exceptions::PyRuntimeError;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    /// Set once the first awaitable is created; the Tokio runtime is built
+    /// lazily at that point and cannot be reconfigured afterwards
+    static RUNTIME_STARTED: AtomicBool = AtomicBool::new(false);
+
+    /// Run `f` on the blocking pool and wrap its result in an awaitable
+    fn spawn_blocking_py<'py, T, F>(py: Python<'py>, f: F) -> PyResult<&'py PyAny>
+    where
+        F: FnOnce() -> PyResult<T> + Send + 'static,
+        T: IntoPy<PyObject> + Send + 'static,
+    {
+        RUNTIME_STARTED.store(true, Ordering::SeqCst);
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            tokio::task::spawn_blocking(f)
+                .await
+                .map_err(|e| PyRuntimeError::new_err(format!("Background task failed: {}", e)))?
+        })
+    }
+
+    /// Configure the Tokio runtime behind the `async_*` functions
+    ///
+    /// `threads` caps both the worker threads and the blocking threads that
+    /// run the computations. Must be called before the first `async_*`
+    /// call; raises `RuntimeError` afterwards and `ValueError` for 0.
+    #[pyfunction]
+    fn set_tokio_runtime(threads: usize) -> PyResult<()> {
+        if threads == 0 {
+            return Err(PyValueError::new_err("threads must be greater than 0"));
+        }
+        if RUNTIME_STARTED.load(Ordering::SeqCst) {
+            return Err(PyRuntimeError::new_err(
+                "the Tokio runtime is already running; call set_tokio_runtime first",
+            ));
+        }
+
+        let mut builder = tokio::runtime::Builder::new_multi_thread();
+        builder
+            .worker_threads(threads)
+            .max_blocking_threads(threads)
+            .enable_all();
+        pyo3_asyncio::tokio::init(builder);
+        Ok(())
+    }
+
+    /// Awaitable `parse_logs`
+    #[pyfunction]
+    fn async_parse_logs<'py>(py: Python<'py>, log_lines: Vec<String>) -> PyResult<&'py PyAny> {
+        spawn_blocking_py(py, move || parse_logs(log_lines))
+    }
+
+    /// Awaitable `validate_logs`
+    #[pyfunction]
+    #[pyo3(signature = (log_lines, strict=false))]
+    fn async_validate_logs<'py>(
+        py: Python<'py>,
+        log_lines: Vec<String>,
+        strict: bool,
+    ) -> PyResult<&'py PyAny> {
+        spawn_blocking_py(py, move || validate_logs(log_lines, strict))
+    }
+
+    /// Awaitable `compute_stats`
+    #[pyfunction]
+    fn async_compute_stats<'py>(py: Python<'py>, log_lines: Vec<String>) -> PyResult<&'py PyAny> {
+        spawn_blocking_py(py, move || compute_stats(log_lines))
+    }
+
+    /// Awaitable `filter_logs`; `min_level` must be a `LogLevel`
+    #[pyfunction]
+    #[pyo3(signature = (
+        log_lines,
+        min_level=None,
+        min_duration_ms=None,
+        status_codes=None,
+        start_time=None,
+        end_time=None
+    ))]
+    fn async_filter_logs<'py>(
+        py: Python<'py>,
+        log_lines: Vec<String>,
+        min_level: Option<LogLevel>,
+        min_duration_ms: Option<f64>,
+        status_codes: Option<Vec<i32>>,
+        start_time: Option<String>,
+        end_time: Option<String>,
+    ) -> PyResult<&'py PyAny> {
+        spawn_blocking_py(py, move || {
+            filter_log_lines(
+                log_lines,
+                min_level.unwrap_or(LogLevel::Debug),
+                min_duration_ms,
+                status_codes,
+                start_time,
+                end_time,
+            )
+        })
+    }
+
+    /// Awaitable `batch_process`
+    #[pyfunction]
+    fn async_batch_process<'py>(py: Python<'py>, log_lines: Vec<String>) -> PyResult<&'py PyAny> {
+        spawn_blocking_py(py, move || batch_process(log_lines))
+    }
+
+    pub(super) fn register(m: &PyModule) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(set_tokio_runtime, m)?)?;
+        m.add_function(wrap_pyfunction!(async_parse_logs, m)?)?;
+        m.add_function(wrap_pyfunction!(async_validate_logs, m)?)?;
+        m.add_function(wrap_pyfunction!(async_compute_stats, m)?)?;
+        m.add_function(wrap_pyfunction!(async_filter_logs, m)?)?;
+        m.add_function(wrap_pyfunction!(async_batch_process, m)?)?;
+        Ok(())
+    }
+}
+
+/// Export log entries as Elasticsearch bulk API NDJSON
+///
+/// Each entry becomes an `index` action line followed by a source document
+/// with the same keys `parse_logs` returns, but with `duration_ms` and
+/// `status_code` as JSON numbers; missing optional fields are omitted. The
+/// output ends with the newline the bulk API requires.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+/// * `index_name` - Target index (must be a valid Elasticsearch index name)
+/// * `id_field` - Entry field to use as the document `_id`, e.g. `"user_id"`
+///
+/// # Returns
+/// * NDJSON string; raises `ValueError` on a malformed line, an invalid
+///   index name or an entry without `id_field`
+#[pyfunction]
+#[pyo3(signature = (log_lines, index_name, id_field=None))]
+fn export_to_elasticsearch_bulk(
+    log_lines: Vec<String>,
+    index_name: &str,
+    id_field: Option<&str>,
+) -> PyResult<String> {
+    let documents = log_lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            let entry: LogEntry = serde_json::from_str(line).map_err(|e| {
+                PyValueError::new_err(format!("Line {}: Parse error: {}", idx + 1, e))
+            })?;
+            let id = match id_field {
+                None => None,
+                Some(field) => Some(entry_to_dict(&entry).remove(field).ok_or_else(|| {
+                    PyValueError::new_err(format!("Line {}: missing field '{}'", idx + 1, field))
+                })?),
+            };
+            let mut source =
+                serde_json::to_value(&entry).map_err(|e| PyValueError::new_err(e.to_string()))?;
+            if let Some(fields) = source.as_object_mut() {
+                fields.retain(|_, value| !value.is_null());
+            }
+            Ok((id, source))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+
+    rust_core::to_elasticsearch_bulk(documents, index_name).map_err(processing_error_to_py)
+}
+
+/// Filter DataRecords whose `metadata[key]` equals `value`
+///
+/// Records without metadata or without `key` are never returned.
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `key` - Metadata key to test
+/// * `value` - Exact value to match
+///
+/// # Returns
+/// * JSON array of the matching records in input order
+#[pyfunction]
+fn filter_by_metadata(records_json: &str, key: &str, value: &str) -> PyResult<String> {
+    let records = parse_records_json(records_json)?;
+    let filtered = rust_core::filter_by_metadata(&records, key, value);
+    serde_json::to_string(&filtered).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Filter DataRecords whose `metadata[key]` starts with `prefix`
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `key` - Metadata key to test
+/// * `prefix` - Required value prefix
+///
+/// # Returns
+/// * JSON array of the matching records in input order
+#[pyfunction]
+fn filter_by_metadata_prefix(records_json: &str, key: &str, prefix: &str) -> PyResult<String> {
+    let records = parse_records_json(records_json)?;
+    let filtered = rust_core::filter_by_metadata_prefix(&records, key, prefix);
+    serde_json::to_string(&filtered).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Filter DataRecords whose `metadata[key]` matches a regular expression
+///
+/// Uses Rust `regex` syntax; the pattern matches anywhere in the value.
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `key` - Metadata key to test
+/// * `pattern` - Regular expression; raises `ValueError` if invalid
+///
+/// # Returns
+/// * JSON array of the matching records in input order
+#[pyfunction]
+fn filter_by_metadata_regex(records_json: &str, key: &str, pattern: &str) -> PyResult<String> {
+    let records = parse_records_json(records_json)?;
+    let filtered = rust_core::filter_by_metadata_regex(&records, key, pattern)
+        .map_err(processing_error_to_py)?;
+    serde_json::to_string(&filtered).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Count how often each value of a DataRecord field occurs
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `field_name` - `"category"`, `"timestamp"` (counted per UTC day,
+///   `YYYY-MM-DD`), or otherwise a metadata key
+///
+/// # Returns
+/// * Dict from field value to count; records without the field are skipped
+#[pyfunction]
+fn compute_frequency(records_json: &str, field_name: &str) -> PyResult<HashMap<String, usize>> {
+    let records = parse_records_json(records_json)?;
+    let field: rust_core::FieldName = field_name.parse().map_err(processing_error_to_py)?;
+    Ok(rust_core::compute_frequency_distribution(&records, |r| {
+        field.extract(r)
+    }))
+}
+
+/// Count log entries per level
+///
+/// Levels are counted exactly as written; malformed lines are skipped.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+///
+/// # Returns
+/// * Dict from level string to count
+#[pyfunction]
+fn compute_log_level_distribution(log_lines: Vec<String>) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for entry in parse_entries(&log_lines) {
+        *counts.entry(entry.level).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Check DataRecords against custom validation rules
+///
+/// Each rule is a single-key dict naming the rule: `{"MaxValue": 1e6}`,
+/// `{"MinValue": 0}`, `{"IdPattern": r"^[A-Z]{2}\d{6}$"}`,
+/// `{"AllowedCategories": ["A", "B"]}` or `{"RequiredMetadataKey": "owner"}`.
+/// Rules run in order and only the first failure per record is reported.
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `rules` - List of rule dicts; raises `ValueError` if one is malformed
+///   or an `IdPattern` is not a valid regular expression
+///
+/// # Returns
+/// * List of dicts with `index`, `record_id` and `message`, in input order
+#[pyfunction]
+fn validate_records_with_rules(
+    py: Python,
+    records_json: &str,
+    rules: &pyo3::types::PyList,
+) -> PyResult<Vec<PyObject>> {
+    let records = parse_records_json(records_json)?;
+    let rules_json: String = py
+        .import("json")?
+        .call_method1("dumps", (rules,))?
+        .extract()?;
+    let rules: Vec<rust_core::ValidationRule> = serde_json::from_str(&rules_json)
+        .map_err(|e| PyValueError::new_err(format!("Invalid validation rule: {}", e)))?;
+
+    rust_core::validate_records_with_rules(&records, &rules)
+        .map_err(processing_error_to_py)?
+        .into_iter()
+        .map(|(index, error)| {
+            let dict = pyo3::types::PyDict::new(py);
+            dict.set_item("index", index)?;
+            dict.set_item("record_id", error.record_id)?;
+            dict.set_item("message", error.message)?;
+            Ok(dict.into())
+        })
+        .collect()
+}
+
+/// Smoothed rate of change of DataRecord values per second
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `smoothing_window` - Number of derivative points to average (must be
+///   > 0); 1 returns the raw derivative
+///
+/// # Returns
+/// * List of `(timestamp, rate)` tuples in timestamp order, one per adjacent
+///   pair of records; raises `ValueError` with fewer than 2 timestamped records
+#[pyfunction]
+#[pyo3(signature = (records_json, smoothing_window=1))]
+fn compute_rate_of_change(
+    records_json: &str,
+    smoothing_window: usize,
+) -> PyResult<Vec<(String, f64)>> {
+    let records = parse_records_json(records_json)?;
+    rust_core::compute_rate_of_change(&records, smoothing_window).map_err(processing_error_to_py)
+}
+
+/// Compute one percentile of DataRecord values per group
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `percentile` - Quantile in `[0.0, 1.0]`; `1.0` gives each group's maximum
+/// * `group_key` - `"category"`, or otherwise a metadata key (records
+///   without it are skipped)
+///
+/// # Returns
+/// * Dict from group value to percentile
+#[pyfunction]
+#[pyo3(signature = (records_json, percentile, group_key="category"))]
+fn compute_percentile_by_group(
+    records_json: &str,
+    percentile: f64,
+    group_key: &str,
+) -> PyResult<HashMap<String, f64>> {
+    let records = parse_records_json(records_json)?;
+    let group_key: rust_core::GroupKey = group_key.parse().map_err(processing_error_to_py)?;
+    rust_core::compute_percentile_by_group(&records, percentile, group_key)
+        .map_err(processing_error_to_py)
+}
+
+/// p99 request duration per user, for per-user SLA checks
+///
+/// Lines are parsed in parallel, grouped by `user_id`, and each user's
+/// interpolated (R-7) p99 is computed in parallel. Entries without a
+/// `user_id` or a finite `duration_ms` are skipped.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+/// * `min_samples` - Users with fewer durations than this are left out
+///
+/// # Returns
+/// * Dict mapping user ID to p99 duration in milliseconds
+#[pyfunction]
+#[pyo3(signature = (log_lines, min_samples=5))]
+fn compute_p99_by_user(
+    log_lines: Vec<String>,
+    min_samples: usize,
+) -> PyResult<HashMap<String, f64>> {
+    let entries = parse_entries(&log_lines);
+
+    let mut by_user: HashMap<String, Vec<f64>> = HashMap::new();
+    for entry in entries {
+        if let (Some(user), Some(duration)) =
+            (entry.user_id, entry.duration_ms.filter(|d| d.is_finite()))
+        {
+            by_user.entry(user).or_default().push(duration);
+        }
+    }
+
+    let users: Vec<(String, Vec<f64>)> = by_user
+        .into_iter()
+        .filter(|(_, durations)| durations.len() >= min_samples)
+        .collect();
+    users
+        .into_par_iter()
+        .map(|(user, durations)| {
+            let p99 = rust_core::compute_percentiles(&durations, &[0.99])
+                .map_err(processing_error_to_py)?[0];
+            Ok((user, p99))
+        })
+        .collect()
+}
+
+/// Timed-out entries as dicts, plus the timeout count per user
+type TimeoutReport = (Vec<HashMap<String, String>>, HashMap<String, usize>);
+
+/// Reject timeout thresholds that are not positive
+fn check_timeout_threshold(threshold_ms: f64) -> PyResult<()> {
+    if threshold_ms > 0.0 {
+        Ok(())
+    } else {
+        Err(PyValueError::new_err("threshold_ms must be greater than 0"))
+    }
+}
+
+/// Requests whose `duration_ms` exceeds an SLA threshold
+///
+/// Entries without `duration_ms` are never timeouts; malformed lines are
+/// skipped.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+/// * `threshold_ms` - Timeout threshold in milliseconds (must be > 0)
+///
+/// # Returns
+/// * Tuple of (timed-out entries as dicts in input order, dict from user ID
+///   to timeout count); entries without a `user_id` count under `"<unknown>"`
+#[pyfunction]
+fn find_timeout_requests(log_lines: Vec<String>, threshold_ms: f64) -> PyResult<TimeoutReport> {
+    check_timeout_threshold(threshold_ms)?;
+
+    let timeouts: Vec<LogEntry> = parse_entries(&log_lines)
+        .into_par_iter()
+        .filter(|e| e.duration_ms.is_some_and(|d| d > threshold_ms))
+        .collect();
+
+    let mut by_user: HashMap<String, usize> = HashMap::new();
+    for entry in &timeouts {
+        let user = entry.user_id.as_deref().unwrap_or("<unknown>");
+        *by_user.entry(user.to_string()).or_insert(0) += 1;
+    }
+
+    Ok((timeouts.iter().map(entry_to_dict).collect(), by_user))
+}
+
+/// Fraction of requests whose `duration_ms` exceeds an SLA threshold
+///
+/// Every parsed entry counts as a request, including entries without a
+/// duration (which are never timeouts).
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+/// * `threshold_ms` - Timeout threshold in milliseconds (must be > 0)
+///
+/// # Returns
+/// * Timeouts divided by total requests, in `[0.0, 1.0]`; raises
+///   `ValueError` if there are no requests
+#[pyfunction]
+fn compute_timeout_rate(log_lines: Vec<String>, threshold_ms: f64) -> PyResult<f64> {
+    check_timeout_threshold(threshold_ms)?;
+
+    let entries = parse_entries(&log_lines);
+    if entries.is_empty() {
+        return Err(PyValueError::new_err(
+            "No requests to compute a timeout rate from",
+        ));
+    }
+    let timeouts = entries
+        .par_iter()
+        .filter(|e| e.duration_ms.is_some_and(|d| d > threshold_ms))
+        .count();
+    Ok(timeouts as f64 / entries.len() as f64)
+}
+
+/// Measure whether log volume is growing over time
+///
+/// Entries are counted in buckets of `bucket_seconds` aligned to the Unix
+/// epoch, from the earliest to the latest bucket; buckets in between with no
+/// entries count as zero. A least-squares line is fitted to the
+/// `(bucket_index, count)` pairs, so a positive `slope_per_bucket` means
+/// volume is rising. Entries with an unparseable timestamp are skipped.
+///
+/// With fewer than two buckets there is no trend: `slope_per_bucket` and
+/// `r_squared` are 0.0. A perfectly flat series has nothing left to explain
+/// and reports an `r_squared` of 1.0.
+///
+/// # Arguments
+/// * `log_lines` - Vector of JSON log strings
+/// * `bucket_seconds` - Bucket width in seconds (must be > 0)
+///
+/// # Returns
+/// * Dict with `slope_per_bucket`, `r_squared`, `initial_count` (first
+///   bucket), `final_count` (last bucket) and `bucket_count`
+#[pyfunction]
+fn compute_log_volume_trend(
+    log_lines: Vec<String>,
+    bucket_seconds: u64,
+) -> PyResult<HashMap<String, f64>> {
+    if bucket_seconds == 0 {
+        return Err(PyValueError::new_err(
+            "bucket_seconds must be greater than 0",
+        ));
+    }
+    let bucket_seconds = i64::try_from(bucket_seconds)
+        .map_err(|_| PyValueError::new_err("bucket_seconds is too large"))?;
+
+    let mut per_bucket: BTreeMap<i64, usize> = BTreeMap::new();
+    for entry in parse_entries(&log_lines) {
+        if let Some(ts) = parse_timestamp(&entry.timestamp) {
+            *per_bucket
+                .entry(ts.timestamp().div_euclid(bucket_seconds))
+                .or_insert(0) += 1;
+        }
+    }
+
+    let (first, last) = match (per_bucket.first_key_value(), per_bucket.last_key_value()) {
+        (Some((&first, _)), Some((&last, _))) => (first, last),
+        _ => (0, -1),
+    };
+    // Number of buckets from first to last, including empty ones. Only the
+    // non-empty buckets are stored; the empty ones contribute closed-form
+    // terms below, so a wide span costs nothing extra.
+    let n = (last - first + 1) as f64;
+    let (slope, r_squared) = if n < 2.0 {
+        (0.0, 0.0)
+    } else {
+        let mean_x = (n - 1.0) / 2.0;
+        let mean_y = per_bucket.values().sum::<usize>() as f64 / n;
+        // Σ(x - x̄)² over 0..n
+        let sxx = n * (n * n - 1.0) / 12.0;
+        // Σ(x - x̄)(y - ȳ) = Σ(x - x̄)·y, and y is zero in the empty buckets
+        let mut sxy = 0.0;
+        let mut syy = (n - per_bucket.len() as f64) * mean_y * mean_y;
+        for (&bucket, &count) in &per_bucket {
+            let y = count as f64;
+            sxy += ((bucket - first) as f64 - mean_x) * y;
+            syy += (y - mean_y) * (y - mean_y);
+        }
+        let slope = sxy / sxx;
+        // r² of a simple linear fit is the squared Pearson correlation
+        let r_squared = if syy == 0.0 {
+            1.0
+        } else {
+            sxy * sxy / (sxx * syy)
+        };
+        (slope, r_squared)
+    };
+
+    Ok(HashMap::from([
+        ("slope_per_bucket".to_string(), slope),
+        ("r_squared".to_string(), r_squared),
+        (
+            "initial_count".to_string(),
+            per_bucket.values().next().map_or(0.0, |&c| c as f64),
+        ),
+        (
+            "final_count".to_string(),
+            per_bucket.values().next_back().map_or(0.0, |&c| c as f64),
+        ),
+        ("bucket_count".to_string(), n),
+    ]))
+}
+
+/// Backslash-escape the separators of a `group_by_fields` composite key
+fn escape_key_part(part: &str) -> String {
+    let mut escaped = String::with_capacity(part.len());
+    for c in part.chars() {
+        if matches!(c, '\\' | '|' | ':') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Group DataRecords by several fields at once and process each group
+///
+/// # Arguments
+/// * `records_json` - JSON array of DataRecord objects
+/// * `field_names` - Each `"category"`, `"timestamp"` (the UTC day) or
+///   otherwise a metadata key; records missing any of them are skipped
+///
+/// # Returns
+/// * JSON object mapping composite keys such as `"category:A|region:us-east"`
+///   (sorted) to the group's ProcessResult. A `\`, `|` or `:` inside a
+///   field name or value is escaped with a backslash, so distinct groups
+///   never share a key
+#[pyfunction]
+fn group_by_fields(records_json: &str, field_names: Vec<String>) -> PyResult<String> {
+    let records = parse_records_json(records_json)?;
+    let fields = field_names
+        .iter()
+        .map(|name| name.parse::<rust_core::FieldName>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(processing_error_to_py)?;
+
+    let groups: BTreeMap<String, ProcessResult> = rust_core::aggregate_by_fields(&records, &fields)
+        .map_err(processing_error_to_py)?
+        .into_iter()
+        .map(|(values, result)| {
+            let key = fields
+                .iter()
+                .zip(&values)
+                .map(|(field, value)| {
+                    format!(
+                        "{}:{}",
+                        escape_key_part(field.name()),
+                        escape_key_part(value)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("|");
+            (key, result)
+        })
+        .collect();
+    serde_json::to_string(&groups).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Python module definition
+///
+/// This is where we expose our Rust functions to Python. PyO3 handles all the
+/// type conversions and memory management automatically. The module can be
+/// imported in Python as: `import rust_processor`
+#[pymodule]
+fn rust_processor(py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(parse_logs, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_logs_logfmt, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_logs_clf, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_logs_syslog, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_logs, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_logs_detailed, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(filter_logs, m)?)?;
+    m.add_function(wrap_pyfunction!(batch_process, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_sessions, m)?)?;
+    m.add_function(wrap_pyfunction!(records_to_prometheus_text, m)?)?;
+    m.add_function(wrap_pyfunction!(records_to_html_table, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_running_error_rate, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_status_code_series, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_stats_by_user, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_duration_histogram, m)?)?;
+    m.add_function(wrap_pyfunction!(aggregate_by_category, m)?)?;
+    m.add_function(wrap_pyfunction!(join_records, m)?)?;
+    m.add_function(wrap_pyfunction!(detect_outliers, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_correlation, m)?)?;
+    m.add_function(wrap_pyfunction!(top_n_by_duration, m)?)?;
+    m.add_function(wrap_pyfunction!(group_by_time_bucket, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_stats_by_time_bucket, m)?)?;
+    m.add_function(wrap_pyfunction!(detect_slow_requests, m)?)?;
+    m.add_function(wrap_pyfunction!(apply_filter_spec, m)?)?;
+    m.add_function(wrap_pyfunction!(sort_records, m)?)?;
+    m.add_function(wrap_pyfunction!(reservoir_sample, m)?)?;
+    m.add_function(wrap_pyfunction!(dedup_records, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_request_rate, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_apdex, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_apdex_by_status_class, m)?)?;
+    m.add_function(wrap_pyfunction!(find_correlated_errors, m)?)?;
+    m.add_function(wrap_pyfunction!(extract_unique_users, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_user_activity, m)?)?;
+    m.add_function(wrap_pyfunction!(records_to_csv, m)?)?;
+    m.add_function(wrap_pyfunction!(records_from_csv, m)?)?;
+    m.add_function(wrap_pyfunction!(normalize_values, m)?)?;
+    m.add_function(wrap_pyfunction!(standardize_values, m)?)?;
+    m.add_function(wrap_pyfunction!(clamp_values, m)?)?;
+    m.add_function(wrap_pyfunction!(split_by_category, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_entropy, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_normalized_entropy, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_sma, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_ema, m)?)?;
+    m.add_function(wrap_pyfunction!(process_records, m)?)?;
+    m.add_function(wrap_pyfunction!(record_diff, m)?)?;
+    m.add_function(wrap_pyfunction!(paginate_records, m)?)?;
+    m.add_function(wrap_pyfunction!(cursor_paginate_records, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_metadata_cooccurrence, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_category_transition_matrix, m)?)?;
+    #[cfg(feature = "schema")]
+    m.add_function(wrap_pyfunction!(get_log_entry_schema, m)?)?;
+    m.add_function(wrap_pyfunction!(log_entry_to_otel_json_py, m)?)?;
+    m.add_function(wrap_pyfunction!(log_stats_to_otel_metrics_json_py, m)?)?;
+    m.add_function(wrap_pyfunction!(batch_process_partial, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_gini, m)?)?;
+    m.add_function(wrap_pyfunction!(detect_log_storms, m)?)?;
+    m.add_function(wrap_pyfunction!(extract_stack_traces, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_availability, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_availability_windowed, m)?)?;
+    m.add_function(wrap_pyfunction!(classify_errors, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_concurrency_estimate, m)?)?;
+    m.add_function(wrap_pyfunction!(find_slow_endpoints, m)?)?;
+    m.add_function(wrap_pyfunction!(export_to_elasticsearch_bulk, m)?)?;
+    m.add_function(wrap_pyfunction!(filter_by_metadata, m)?)?;
+    m.add_function(wrap_pyfunction!(filter_by_metadata_prefix, m)?)?;
+    m.add_function(wrap_pyfunction!(filter_by_metadata_regex, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_frequency, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_log_level_distribution, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_records_with_rules, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_datasets, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_rate_of_change, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_percentile_by_group, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_p99_by_user, m)?)?;
+    m.add_function(wrap_pyfunction!(find_timeout_requests, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_timeout_rate, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_log_volume_trend, m)?)?;
+    m.add_function(wrap_pyfunction!(group_by_fields, m)?)?;
+    m.add_class::<LogStats>()?;
+    m.add_class::<LogLevel>()?;
+    #[cfg(feature = "pyo3-asyncio")]
+    async_api::register(m)?;
+    m.add_class::<StatsAccumulator>()?;
+    m.add_class::<SlidingWindow>()?;
+    m.add("RustValidationError", py.get_type::<RustValidationError>())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_entry_builder() {
+        let literal = LogEntry {
+            timestamp: "2024-01-15T10:00:00Z".to_string(),
+            level: "ERROR".to_string(),
+            message: "upstream timeout".to_string(),
+            duration_ms: Some(1500.0),
+            status_code: Some(504),
+            user_id: Some("alice".to_string()),
+        };
+        let built = LogEntry::builder()
+            .timestamp("2024-01-15T10:00:00Z")
+            .level("ERROR")
+            .message("upstream timeout")
+            .duration_ms(1500.0)
+            .status_code(504)
+            .user_id("alice")
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&built).unwrap(),
+            serde_json::to_value(&literal).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_log_entry_builder_validates() {
+        assert_eq!(
+            LogEntryBuilder::default().build().unwrap_err(),
+            "Missing or empty timestamp"
+        );
+        let err = LogEntry::builder()
+            .timestamp("2024-01-15T10:00:00Z")
+            .level("INFO")
+            .status_code(700)
+            .build()
+            .unwrap_err();
+        assert_eq!(err, "Invalid status_code 700. Must be 100-599");
+    }
 }
@@ -0,0 +1,23 @@
+"""
+Shared helpers for the rust_processor test suite.
+"""
+
+import json
+
+
+def log_line(timestamp, level="INFO", message="ok", **fields):
+    """Build a JSON log line with the given fields."""
+    entry = {"timestamp": timestamp, "level": level, "message": message}
+    entry.update(fields)
+    return json.dumps(entry)
+
+
+def record(id, value, category="A", timestamp="2024-01-15T10:00:00Z", metadata=None):
+    """Build a DataRecord dict for the record-oriented functions."""
+    return {
+        "id": id,
+        "value": value,
+        "category": category,
+        "timestamp": timestamp,
+        "metadata": metadata,
+    }
@@ -0,0 +1,33 @@
+"""
+Tests for the incremental StatsAccumulator.
+"""
+
+import json
+
+import rust_processor
+
+from helpers import record
+
+
+def test_accumulator_add_remove_finalize():
+    acc = rust_processor.StatsAccumulator()
+    records = [record("1", 100.0, "A"), record("2", 200.0, "B"), record("3", 150.0, "A")]
+    for r in records:
+        acc.add_record(json.dumps(r))
+    acc.remove_record(json.dumps(records[1]))
+
+    result = acc.finalize()
+
+    assert result["total_processed"] == 2
+    assert result["total_value"] == 250.0
+    assert result["max_value"] == 150.0
+    assert result["categories"] == {"A": 2}
+
+
+def test_accumulator_checkpoint_round_trip():
+    acc = rust_processor.StatsAccumulator()
+    acc.add_record(json.dumps(record("1", 5.0)))
+
+    restored = rust_processor.StatsAccumulator.from_json(acc.to_json())
+
+    assert restored.finalize() == acc.finalize()
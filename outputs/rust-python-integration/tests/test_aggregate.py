@@ -0,0 +1,42 @@
+"""
+Tests for per-category aggregation of DataRecords.
+"""
+
+import json
+
+import pytest
+import rust_processor
+
+from helpers import record
+
+
+def test_aggregate_by_category_round_trip():
+    records = [record("1", 10.0, "B"), record("2", 20.0, "A"), record("3", 40.0, "A")]
+
+    stats = rust_processor.aggregate_by_category(json.dumps(records))
+
+    assert list(stats) == ["A", "B"]
+    assert stats["A"]["count"] == 2
+    assert stats["A"]["total_value"] == 60.0
+    assert stats["A"]["average_value"] == 30.0
+    assert stats["A"]["min_value"] == 20.0
+    assert stats["A"]["max_value"] == 40.0
+    assert stats["B"]["count"] == 1
+
+
+def test_aggregate_by_category_empty():
+    assert rust_processor.aggregate_by_category("[]") == {}
+
+
+def test_aggregate_by_category_percentiles():
+    records = [record("1", 10.0), record("2", 20.0), record("3", 40.0), record("4", 7.0, "B")]
+
+    stats = rust_processor.aggregate_by_category(json.dumps(records), [0.5, 0.75])
+
+    assert stats["A"]["percentiles"] == [(0.5, 20.0), (0.75, 30.0)]
+    assert stats["B"]["percentiles"] == [(0.5, 7.0), (0.75, 7.0)]
+
+
+def test_aggregate_by_category_rejects_bad_quantile():
+    with pytest.raises(ValueError):
+        rust_processor.aggregate_by_category(json.dumps([record("1", 1.0)]), [1.5])
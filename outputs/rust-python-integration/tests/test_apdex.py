@@ -0,0 +1,55 @@
+"""
+Tests for Apdex scoring.
+"""
+
+import pytest
+import rust_processor
+
+from helpers import log_line
+
+
+TS = "2024-01-15T10:00:00Z"
+
+
+def test_apdex_all_fast_scores_one():
+    lines = [log_line(TS, duration_ms=d) for d in (5.0, 50.0, 100.0)]
+
+    assert rust_processor.compute_apdex(lines, 100.0, 400.0) == 1.0
+
+
+def test_apdex_all_slow_scores_zero():
+    lines = [log_line(TS, duration_ms=d) for d in (401.0, 1000.0)]
+
+    assert rust_processor.compute_apdex(lines, 100.0, 400.0) == 0.0
+
+
+def test_apdex_mixed_ignores_entries_without_duration():
+    lines = [
+        log_line(TS, duration_ms=50.0),  # satisfied
+        log_line(TS, duration_ms=400.0),  # tolerating
+        log_line(TS, duration_ms=2000.0),  # frustrated
+        log_line(TS, duration_ms=90.0),  # satisfied
+        log_line(TS),
+    ]
+
+    assert rust_processor.compute_apdex(lines, 100.0, 400.0) == pytest.approx(2.5 / 4)
+
+
+@pytest.mark.parametrize("satisfied, tolerating", [(100.0, 100.0), (200.0, 100.0), (-1.0, 100.0)])
+def test_apdex_rejects_invalid_thresholds(satisfied, tolerating):
+    with pytest.raises(ValueError):
+        rust_processor.compute_apdex([log_line(TS, duration_ms=1.0)], satisfied, tolerating)
+
+
+def test_apdex_by_status_class():
+    lines = [
+        log_line(TS, duration_ms=10.0, status_code=200),
+        log_line(TS, duration_ms=200.0, status_code=201),
+        log_line(TS, duration_ms=10.0, status_code=404),
+        log_line(TS, duration_ms=900.0, status_code=503),
+        log_line(TS, status_code=500),
+    ]
+
+    scores = rust_processor.compute_apdex_by_status_class(lines, 100.0, 400.0)
+
+    assert scores == {"2xx": pytest.approx(0.75), "4xx": 1.0, "5xx": 0.0}
@@ -0,0 +1,76 @@
+"""
+Tests for the awaitable async_* functions (built with the `pyo3-asyncio`
+feature; skipped otherwise).
+"""
+
+import asyncio
+
+import pytest
+
+import rust_processor
+from rust_processor import LogLevel
+
+from helpers import log_line
+
+requires_async = pytest.mark.skipif(
+    not hasattr(rust_processor, "async_parse_logs"),
+    reason="rust_processor built without the pyo3-asyncio feature",
+)
+
+
+def make_lines(n):
+    return [
+        log_line(
+            f"2024-01-15T10:{i // 60 % 60:02d}:{i % 60:02d}Z",
+            level="ERROR" if i % 10 == 0 else "INFO",
+            duration_ms=float(i % 500),
+            status_code=500 if i % 10 == 0 else 200,
+        )
+        for i in range(n)
+    ]
+
+
+@requires_async
+@pytest.mark.asyncio
+async def test_async_results_match_sync():
+    lines = make_lines(1000)
+
+    parsed = await rust_processor.async_parse_logs(lines)
+    valid, errors = await rust_processor.async_validate_logs(lines + ["not json"])
+    stats = await rust_processor.async_compute_stats(lines)
+    filtered = await rust_processor.async_filter_logs(lines, min_level=LogLevel.Error)
+    batch_stats, batch_errors = await rust_processor.async_batch_process(lines)
+
+    assert parsed == rust_processor.parse_logs(lines)
+    assert (valid, len(errors)) == (1000, 1)
+    assert stats.total_count == 1000
+    assert stats.error_count == rust_processor.compute_stats(lines).error_count
+    assert len(filtered) == 100
+    assert batch_stats.total_count == 1000
+    assert batch_errors == []
+
+
+@requires_async
+@pytest.mark.asyncio
+async def test_async_gather_runs_concurrently_without_mixing_results():
+    batches = [make_lines(200 + i) for i in range(16)]
+
+    results = await asyncio.wait_for(
+        asyncio.gather(*(rust_processor.async_compute_stats(b) for b in batches)),
+        timeout=60,
+    )
+
+    assert [s.total_count for s in results] == [len(b) for b in batches]
+
+
+@requires_async
+@pytest.mark.asyncio
+async def test_async_errors_propagate():
+    with pytest.raises(ValueError):
+        await rust_processor.async_parse_logs(["not json"])
+
+
+@requires_async
+def test_set_tokio_runtime_validation():
+    with pytest.raises(ValueError):
+        rust_processor.set_tokio_runtime(0)
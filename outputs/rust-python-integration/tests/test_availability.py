@@ -0,0 +1,63 @@
+"""
+Tests for availability (SLA) computation.
+"""
+
+import pytest
+
+import rust_processor
+
+from helpers import log_line
+
+
+def test_availability_formula():
+    lines = [log_line("2024-01-15T10:00:00Z", status_code=200) for _ in range(1995)]
+    lines += [log_line("2024-01-15T10:00:00Z", status_code=503) for _ in range(3)]
+    # ERROR level counts even with a successful status, and is not double counted
+    lines.append(log_line("2024-01-15T10:00:00Z", level="ERROR", status_code=200))
+    lines.append(log_line("2024-01-15T10:00:00Z", level="ERROR", status_code=500))
+
+    result = rust_processor.compute_availability(lines)
+
+    assert result == {
+        "availability_pct": (2000 - 5) / 2000 * 100,
+        "total_requests": 2000.0,
+        "error_requests": 5.0,
+        "success_requests": 1995.0,
+    }
+
+
+def test_availability_without_errors_is_100():
+    lines = [log_line("2024-01-15T10:00:00Z", status_code=404) for _ in range(10)]
+
+    assert rust_processor.compute_availability(lines)["availability_pct"] == 100.0
+
+
+def test_availability_no_requests_raises():
+    with pytest.raises(ValueError):
+        rust_processor.compute_availability([])
+    with pytest.raises(ValueError):
+        rust_processor.compute_availability_windowed([], 60)
+
+
+def test_availability_windowed():
+    lines = [
+        log_line("2024-01-15T10:00:05Z", status_code=200),
+        log_line("2024-01-15T10:00:50Z", status_code=500),
+        log_line("2024-01-15T10:01:10Z", status_code=200),
+        log_line("2024-01-15T10:01:20Z", status_code=201),
+    ]
+
+    windows = rust_processor.compute_availability_windowed(lines, 60)
+
+    assert [w["window_start"] for w in windows] == ["2024-01-15T10:00:05Z", "2024-01-15T10:01:05Z"]
+    assert windows[0]["window_end"] == "2024-01-15T10:01:05Z"
+    assert windows[0]["availability_pct"] == 50.0
+    assert windows[0]["error_requests"] == 1.0
+    assert windows[1]["availability_pct"] == 100.0
+    assert windows[1]["total_requests"] == 2.0
+
+
+def test_availability_windowed_rejects_window_past_last_timestamp():
+    lines = [log_line("2024-01-15T10:00:00Z", status_code=200)]
+    with pytest.raises(ValueError):
+        rust_processor.compute_availability_windowed(lines, 2**63 - 1)
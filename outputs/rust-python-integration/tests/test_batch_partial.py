@@ -0,0 +1,44 @@
+"""
+Tests for batch processing that tolerates invalid log lines.
+"""
+
+import rust_processor
+
+from helpers import log_line
+
+
+def test_partial_batch_uses_only_valid_lines():
+    lines = [log_line("2024-01-15T10:00:00Z", duration_ms=10.0) for _ in range(90)]
+    lines += ["not json"] * 5 + [log_line("2024-01-15T10:00:00Z", level="FATAL")] * 5
+
+    stats, errors = rust_processor.batch_process_partial(lines)
+
+    assert stats.total_count == 90
+    assert stats.avg_duration_ms == 10.0
+    assert len(errors) == 10
+    assert errors[0].startswith("Line 91:")
+    assert errors[-1].startswith("Line 100:")
+
+
+def test_partial_batch_all_invalid_does_not_raise():
+    stats, errors = rust_processor.batch_process_partial(["bad", "worse"])
+
+    assert stats.total_count == 0
+    assert len(errors) == 2
+
+
+def test_partial_batch_every_line_failing_gives_zeroed_stats():
+    lines = ["not json", log_line("2024-01-15T10:00:00Z", level="FATAL")]
+
+    stats, errors = rust_processor.batch_process_partial(lines)
+
+    assert [e.split(":")[0] for e in errors] == ["Line 1", "Line 2"]
+    assert stats.total_count == 0
+    assert stats.error_count == stats.warn_count == stats.info_count == 0
+    assert stats.duration_count == 0
+    assert stats.avg_duration_ms == 0.0
+    assert stats.total_duration_ms == 0.0
+    assert stats.p99_duration_ms == 0.0
+    assert stats.std_dev_duration_ms == 0.0
+    assert stats.status_code_distribution == {}
+    assert stats.error_count_by_code == {}
@@ -0,0 +1,52 @@
+"""
+Tests for error classification.
+"""
+
+import pytest
+
+import rust_processor
+
+from helpers import log_line
+
+
+def test_classify_errors_groups_varying_ips():
+    lines = [
+        log_line(
+            f"2024-01-15T10:{i // 60 % 60:02d}:{i % 60:02d}Z",
+            level="ERROR",
+            message=f"Connection refused to 10.0.0.{i % 256}",
+            status_code=502 if i % 2 else 503,
+        )
+        for i in range(500)
+    ]
+    lines.append(log_line("2024-01-15T10:00:00Z", level="INFO", message="Connection refused to 10.0.0.1"))
+
+    clusters = rust_processor.classify_errors(lines, 10)
+
+    assert len(clusters) == 1
+    cluster = clusters[0]
+    assert cluster["pattern"] == "Connection refused to <N>"
+    assert cluster["count"] == "500"
+    assert cluster["example_message"] == "Connection refused to 10.0.0.0"
+    assert cluster["first_seen"] == "2024-01-15T10:00:00Z"
+    assert cluster["last_seen"] == "2024-01-15T10:08:19Z"
+    assert cluster["status_codes"] == "502,503"
+
+
+def test_classify_errors_folds_small_clusters_into_other():
+    lines = []
+    for count, message in [(5, "timeout after 30s"), (3, "disk full"), (2, "bad token"), (1, "oom")]:
+        lines += [log_line("2024-01-15T10:00:00Z", level="ERROR", message=message)] * count
+
+    clusters = rust_processor.classify_errors(lines, 2)
+
+    assert [(c["pattern"], c["count"]) for c in clusters] == [
+        ("timeout after <N>s", "5"),
+        ("other", "6"),
+    ]
+    assert clusters[1]["example_message"] == "disk full"
+
+
+def test_classify_errors_rejects_zero_clusters():
+    with pytest.raises(ValueError):
+        rust_processor.classify_errors([], 0)
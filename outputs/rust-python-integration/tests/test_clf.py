@@ -0,0 +1,58 @@
+"""
+Tests for Common Log Format parsing.
+"""
+
+import rust_processor
+
+
+STANDARD = (
+    '127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 2326 '
+    '"http://www.example.com/start.html" "Mozilla/4.08"'
+)
+
+
+def test_parse_clf_standard_example():
+    entries, errors = rust_processor.parse_logs_clf([STANDARD])
+
+    assert errors == []
+    assert entries == [
+        {
+            "remote_host": "127.0.0.1",
+            "timestamp": "2000-10-10T13:55:36-07:00",
+            "level": "INFO",
+            "message": "GET /apache_pb.gif HTTP/1.0",
+            "status_code": "200",
+            "user_id": "frank",
+            "bytes_sent": "2326",
+            "referer": "http://www.example.com/start.html",
+            "user_agent": "Mozilla/4.08",
+        }
+    ]
+
+
+def test_parse_clf_dash_fields_and_levels():
+    lines = [
+        '10.0.0.1 - - [10/Oct/2000:13:55:36 +0000] "POST /login HTTP/1.1" 404 -',
+        '10.0.0.2 - - [10/Oct/2000:13:55:37 +0000] "GET / HTTP/1.1" 503 12 "-" "-"',
+    ]
+
+    entries, errors = rust_processor.parse_logs_clf(lines)
+
+    assert errors == []
+    assert entries[0] == {
+        "remote_host": "10.0.0.1",
+        "timestamp": "2000-10-10T13:55:36Z",
+        "level": "WARN",
+        "message": "POST /login HTTP/1.1",
+        "status_code": "404",
+    }
+    assert entries[1]["level"] == "ERROR"
+    assert entries[1]["bytes_sent"] == "12"
+    assert "referer" not in entries[1]
+
+
+def test_parse_clf_collects_non_matching_lines():
+    entries, errors = rust_processor.parse_logs_clf([STANDARD, "not a log line"])
+
+    assert len(entries) == 1
+    assert errors == ["Line 1: does not match Common Log Format"]
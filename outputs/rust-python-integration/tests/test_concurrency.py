@@ -0,0 +1,68 @@
+"""
+Tests for the concurrency estimate.
+"""
+
+import pytest
+
+import rust_processor
+
+from helpers import log_line
+
+
+def test_concurrency_peak_of_ten():
+    # Ten requests all in flight during 10:00:05.000-10:00:06.000: five start
+    # on the same millisecond, five more start a little later
+    lines = [log_line("2024-01-15T10:00:05.000Z", duration_ms=1000.0) for _ in range(5)]
+    lines += [log_line(f"2024-01-15T10:00:04.{i}00Z", duration_ms=2000.0) for i in range(5)]
+    # Sequential requests before and after never overlap the burst
+    lines += [log_line(f"2024-01-15T10:00:0{s}Z", duration_ms=1000.0) for s in (0, 1, 2, 8)]
+    # A request ending exactly when another starts does not overlap it
+    lines.append(log_line("2024-01-15T10:00:07Z", duration_ms=1000.0))
+    lines.append(log_line("2024-01-15T10:00:09Z"))
+
+    result = rust_processor.compute_concurrency_estimate(lines)
+
+    assert result["peak_concurrent"] == 10.0
+    assert result["total_requests_analysed"] == 15.0
+    assert 0.0 < result["avg_concurrent"] < 10.0
+    assert result["p95_concurrent"] <= result["peak_concurrent"]
+
+
+def test_concurrency_time_weighted_stats():
+    # One request alone for 9s, then a second overlapping for the last 1s
+    lines = [
+        log_line("2024-01-15T10:00:00Z", duration_ms=10000.0),
+        log_line("2024-01-15T10:00:09Z", duration_ms=1000.0),
+    ]
+
+    result = rust_processor.compute_concurrency_estimate(lines)
+
+    assert result["peak_concurrent"] == 2.0
+    assert result["avg_concurrent"] == pytest.approx(1.1)
+    assert result["p95_concurrent"] == 2.0
+
+
+def test_concurrency_without_durations():
+    result = rust_processor.compute_concurrency_estimate([log_line("2024-01-15T10:00:00Z")])
+
+    assert result == {
+        "peak_concurrent": 0.0,
+        "avg_concurrent": 0.0,
+        "p95_concurrent": 0.0,
+        "total_requests_analysed": 0.0,
+    }
+
+
+def test_concurrency_skips_durations_past_the_last_instant():
+    lines = [
+        log_line("2024-01-15T10:00:00Z", duration_ms=1000.0),
+        # About 317 years: late, but still representable
+        log_line("2024-01-15T10:00:00Z", duration_ms=1e10),
+        log_line("2024-01-15T10:00:00Z", duration_ms=1e18),
+        log_line("2024-01-15T10:00:00Z", duration_ms=1e300),
+    ]
+
+    result = rust_processor.compute_concurrency_estimate(lines)
+
+    assert result["total_requests_analysed"] == 2.0
+    assert result["peak_concurrent"] == 2.0
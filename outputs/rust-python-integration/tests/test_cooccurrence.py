@@ -0,0 +1,39 @@
+"""
+Tests for metadata co-occurrence counts and category transition matrices.
+"""
+
+import json
+
+import rust_processor
+
+from helpers import record
+
+
+def test_cooccurrence_order_matters():
+    records = [
+        record("1", 1.0, metadata={"x": "A", "y": "B"}),
+        record("2", 1.0, metadata={"x": "A", "y": "B"}),
+        record("3", 1.0, metadata={"x": "B", "y": "A"}),
+        record("4", 1.0, metadata={"x": "A"}),
+        record("5", 1.0),
+    ]
+
+    counts = rust_processor.compute_metadata_cooccurrence(json.dumps(records), "x", "y")
+
+    assert counts == {("A", "B"): 2, ("B", "A"): 1}
+
+
+def test_transition_matrix_sorts_by_timestamp():
+    records = [
+        record("3", 1.0, category="A", timestamp="2024-01-15T10:02:00Z"),
+        record("1", 1.0, category="A", timestamp="2024-01-15T10:00:00Z"),
+        record("2", 1.0, category="B", timestamp="2024-01-15T10:01:00Z"),
+    ]
+
+    matrix = rust_processor.compute_category_transition_matrix(json.dumps(records))
+
+    assert matrix == {("A", "B"): 1, ("B", "A"): 1}
+
+
+def test_transition_matrix_needs_two_records():
+    assert rust_processor.compute_category_transition_matrix(json.dumps([record("1", 1.0)])) == {}
@@ -0,0 +1,54 @@
+"""
+Tests for clustering co-occurring errors.
+"""
+
+import pytest
+import rust_processor
+
+from helpers import log_line
+
+
+def at(seconds, millis, level="ERROR", **fields):
+    return log_line(f"2024-01-15T10:00:{seconds:02d}.{millis:03d}Z", level=level, **fields)
+
+
+def test_three_planted_bursts():
+    lines = [
+        # Burst 1: three errors within 20ms, including identical timestamps
+        at(1, 0, message="db timeout"),
+        at(1, 0, message="db timeout again"),
+        at(1, 20),
+        # Isolated error and info noise during the quiet period
+        at(10, 0),
+        at(10, 30, level="INFO"),
+        # Burst 2
+        at(20, 500),
+        at(20, 540),
+        # Burst 3, given out of order
+        at(40, 90),
+        at(40, 10),
+        at(40, 50),
+    ]
+
+    clusters = rust_processor.find_correlated_errors(lines, 50.0)
+
+    assert [len(c) for c in clusters] == [3, 2, 3]
+    assert [e["message"] for e in clusters[0][:2]] == ["db timeout", "db timeout again"]
+    assert [e["timestamp"] for e in clusters[2]] == [
+        "2024-01-15T10:00:40.010Z",
+        "2024-01-15T10:00:40.050Z",
+        "2024-01-15T10:00:40.090Z",
+    ]
+    assert all(e["level"] == "ERROR" for c in clusters for e in c)
+
+
+def test_gap_equal_to_window_splits_clusters():
+    lines = [at(1, 0), at(1, 50), at(1, 100)]
+
+    assert rust_processor.find_correlated_errors(lines, 50.0) == []
+
+
+@pytest.mark.parametrize("window_ms", [0.0, -5.0])
+def test_correlated_errors_rejects_non_positive_window(window_ms):
+    with pytest.raises(ValueError):
+        rust_processor.find_correlated_errors([], window_ms)
@@ -0,0 +1,32 @@
+"""
+Tests for correlating numeric log entry fields.
+"""
+
+import pytest
+
+import rust_processor
+
+from helpers import log_line
+
+
+def test_correlation_perfect():
+    lines = [
+        log_line("2024-01-15T10:00:00Z", duration_ms=10.0 * i, status_code=200 + i)
+        for i in range(10)
+    ]
+    lines.append(log_line("2024-01-15T10:00:00Z", duration_ms=1.0))
+
+    r = rust_processor.compute_correlation(lines, "duration_ms", "status_code")
+
+    assert r == pytest.approx(1.0)
+
+
+def test_correlation_undefined():
+    lines = [log_line("2024-01-15T10:00:00Z", duration_ms=5.0, status_code=200)]
+
+    assert rust_processor.compute_correlation(lines, "duration_ms", "status_code") is None
+
+
+def test_correlation_unknown_field():
+    with pytest.raises(ValueError):
+        rust_processor.compute_correlation([], "duration_ms", "user_id")
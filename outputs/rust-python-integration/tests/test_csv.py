@@ -0,0 +1,37 @@
+"""
+Tests for CSV import/export of DataRecords.
+"""
+
+import json
+
+import pytest
+import rust_processor
+
+from helpers import record
+
+
+def test_csv_round_trip_with_awkward_fields():
+    records = [
+        record("1,a", 0.1 + 0.2, category='quoted "cat"', metadata={"note": "first line\nsecond, line", "k": "v"}),
+        record("2", 1e-300),
+        record("3", -42.0, metadata={}),
+    ]
+
+    csv = rust_processor.records_to_csv(json.dumps(records))
+    assert csv.splitlines()[0] == "id,value,category,timestamp,metadata"
+
+    parsed = json.loads(rust_processor.records_from_csv(csv))
+
+    assert len(parsed) == 3
+    assert parsed[0]["id"] == "1,a"
+    assert parsed[0]["value"] == 0.1 + 0.2
+    assert parsed[0]["category"] == 'quoted "cat"'
+    assert parsed[0]["metadata"] == {"note": "first line\nsecond, line", "k": "v"}
+    assert parsed[1]["value"] == 1e-300
+    assert parsed[1].get("metadata") is None
+    assert parsed[2]["metadata"] == {}
+
+
+def test_records_from_csv_rejects_bad_header():
+    with pytest.raises(ValueError):
+        rust_processor.records_from_csv("name,amount\nx,1\n")
@@ -0,0 +1,30 @@
+"""
+Tests for removing duplicate DataRecords.
+"""
+
+import json
+
+import pytest
+
+import rust_processor
+
+from helpers import record
+
+
+RECORDS = [record("a", 1.0, "first"), record("b", 2.0), record("a", 9.0, "middle"), record("a", 3.0, "last")]
+
+
+@pytest.mark.parametrize(
+    "strategy, category",
+    [("KeepFirst", "first"), ("KeepLast", "last"), ("KeepHighestValue", "middle")],
+)
+def test_dedup_records(strategy, category):
+    deduped = json.loads(rust_processor.dedup_records(json.dumps(RECORDS), strategy))
+
+    assert [r["id"] for r in deduped] == ["a", "b"]
+    assert deduped[0]["category"] == category
+
+
+def test_dedup_records_unknown_strategy():
+    with pytest.raises(ValueError):
+        rust_processor.dedup_records("[]", "keep_first")
@@ -0,0 +1,57 @@
+"""
+Tests for the Elasticsearch bulk exporter.
+"""
+
+import json
+
+import pytest
+
+import rust_processor
+
+from helpers import log_line
+
+
+def make_lines():
+    return [
+        log_line("2024-01-15T10:00:00Z", message="ok", duration_ms=12.5, status_code=200, user_id="u1"),
+        log_line("2024-01-15T10:00:01Z", level="ERROR", message="boom", user_id="u2"),
+        log_line("2024-01-15T10:00:02Z", message="no user"),
+    ]
+
+
+def test_bulk_has_action_and_source_per_entry():
+    ndjson = rust_processor.export_to_elasticsearch_bulk(make_lines(), "logs")
+
+    assert ndjson.endswith("\n")
+    lines = ndjson.splitlines()
+    assert len(lines) == 2 * 3
+    actions = [json.loads(line) for line in lines[0::2]]
+    sources = [json.loads(line) for line in lines[1::2]]
+    assert actions == [{"index": {"_index": "logs"}}] * 3
+    assert sources[0] == {
+        "timestamp": "2024-01-15T10:00:00Z",
+        "level": "INFO",
+        "message": "ok",
+        "duration_ms": 12.5,
+        "status_code": 200,
+        "user_id": "u1",
+    }
+    assert set(sources[2]) == set(rust_processor.parse_logs(make_lines())[2])
+
+
+def test_bulk_id_field():
+    ndjson = rust_processor.export_to_elasticsearch_bulk(make_lines()[:2], "logs", id_field="user_id")
+
+    actions = [json.loads(line) for line in ndjson.splitlines()[0::2]]
+    assert [a["index"]["_id"] for a in actions] == ["u1", "u2"]
+
+    with pytest.raises(ValueError):
+        rust_processor.export_to_elasticsearch_bulk(make_lines(), "logs", id_field="user_id")
+
+
+def test_bulk_rejects_bad_input():
+    with pytest.raises(ValueError):
+        rust_processor.export_to_elasticsearch_bulk(make_lines(), "Logs")
+    with pytest.raises(ValueError):
+        rust_processor.export_to_elasticsearch_bulk(["not json"], "logs")
+    assert rust_processor.export_to_elasticsearch_bulk([], "logs") == ""
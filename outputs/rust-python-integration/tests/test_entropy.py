@@ -0,0 +1,34 @@
+"""
+Tests for category entropy.
+"""
+
+import json
+import math
+
+import pytest
+import rust_processor
+
+from helpers import record
+
+
+def records_for(categories):
+    return json.dumps([record(str(i), 1.0, c) for i, c in enumerate(categories)])
+
+
+def test_entropy_three_categories_hand_computed():
+    # p = (0.5, 0.25, 0.25) -> 0.5*1 + 0.25*2 + 0.25*2 = 1.5 bits
+    data = records_for(["A", "A", "B", "C"])
+
+    assert rust_processor.compute_entropy(data) == pytest.approx(1.5)
+    assert rust_processor.compute_normalized_entropy(data) == pytest.approx(1.5 / math.log2(3))
+
+
+def test_entropy_uniform_split():
+    assert rust_processor.compute_entropy(records_for(["A", "B"])) == 1.0
+    assert rust_processor.compute_entropy(records_for(["A", "B", "C"])) == pytest.approx(math.log2(3))
+    assert rust_processor.compute_normalized_entropy(records_for(["A", "B", "C"] * 5)) == 1.0
+
+
+def test_entropy_degenerate_inputs():
+    assert rust_processor.compute_entropy("[]") == 0.0
+    assert rust_processor.compute_normalized_entropy(records_for(["A", "A"])) == 0.0
@@ -0,0 +1,35 @@
+"""
+Tests for error rate time series.
+"""
+
+import pytest
+import rust_processor
+
+from helpers import log_line
+
+
+def test_running_error_rate():
+    lines = [
+        log_line("2024-01-15T10:00:03Z", level="ERROR"),
+        log_line("2024-01-15T10:00:00Z", level="INFO"),
+        log_line("2024-01-15T10:00:02Z", level="INFO"),
+        log_line("2024-01-15T10:00:01Z", level="ERROR"),
+    ]
+
+    series = rust_processor.compute_running_error_rate(lines)
+
+    assert [ts for ts, _ in series] == [
+        "2024-01-15T10:00:00Z",
+        "2024-01-15T10:00:01Z",
+        "2024-01-15T10:00:02Z",
+        "2024-01-15T10:00:03Z",
+    ]
+    assert [rate for _, rate in series] == pytest.approx([0.0, 0.5, 1 / 3, 0.5])
+
+
+def test_running_error_rate_all_info():
+    lines = [log_line(f"2024-01-15T10:00:0{i}Z") for i in range(3)]
+
+    series = rust_processor.compute_running_error_rate(lines)
+
+    assert [rate for _, rate in series] == [0.0, 0.0, 0.0]
@@ -0,0 +1,36 @@
+"""
+Tests for mapping core processing errors to Python exceptions.
+"""
+
+import json
+
+import pytest
+
+import rust_processor
+
+from helpers import record
+
+
+def test_validation_failure_raises_rust_validation_error():
+    records = [record("", 1.0), record("2", -5.0)]
+
+    with pytest.raises(rust_processor.RustValidationError) as exc_info:
+        rust_processor.compute_stats_by_time_bucket(json.dumps(records), "1h")
+
+    errors = sorted(exc_info.value.errors, key=lambda e: e["record_id"])
+    assert [e["record_id"] for e in errors] == ["", "2"]
+    assert errors[0]["message"] == "ID cannot be empty"
+
+
+def test_rust_validation_error_is_value_error():
+    assert issubclass(rust_processor.RustValidationError, ValueError)
+
+    with pytest.raises(ValueError):
+        rust_processor.compute_stats_by_time_bucket(json.dumps([record("", 1.0)]), "1h")
+
+
+def test_invalid_argument_raises_value_error():
+    with pytest.raises(ValueError) as exc_info:
+        rust_processor.compute_duration_histogram([], 0)
+
+    assert not isinstance(exc_info.value, rust_processor.RustValidationError)
@@ -0,0 +1,46 @@
+"""
+Tests for filter_logs.
+"""
+
+import pytest
+import rust_processor
+
+from helpers import log_line
+
+LINES = [
+    log_line("2024-01-15T09:59:59Z", message="before"),
+    log_line("2024-01-15T10:00:00Z", message="start"),
+    log_line("2024-01-15T10:30:00+00:00", message="middle"),
+    log_line("2024-01-15T11:00:00Z", message="end"),
+    log_line("2024-01-15T11:00:01Z", message="after"),
+    log_line("not a timestamp", message="garbage"),
+]
+
+
+def messages(entries):
+    return [e["message"] for e in entries]
+
+
+def test_time_range_includes_boundaries():
+    filtered = rust_processor.filter_logs(
+        LINES, start_time="2024-01-15T10:00:00Z", end_time="2024-01-15T11:00:00Z"
+    )
+
+    assert messages(filtered) == ["start", "middle", "end"]
+
+
+def test_open_ended_ranges():
+    after = rust_processor.filter_logs(LINES, start_time="2024-01-15T11:00:00Z")
+    before = rust_processor.filter_logs(LINES, end_time="2024-01-15T10:00:00Z")
+
+    assert messages(after) == ["end", "after"]
+    assert messages(before) == ["before", "start"]
+
+
+def test_no_range_keeps_unparseable_timestamps():
+    assert len(rust_processor.filter_logs(LINES)) == len(LINES)
+
+
+def test_invalid_bound_raises():
+    with pytest.raises(ValueError):
+        rust_processor.filter_logs(LINES, start_time="yesterday")
@@ -0,0 +1,37 @@
+"""
+Tests for applying serialized FilterSpecs to DataRecords.
+"""
+
+import json
+
+import pytest
+
+import rust_processor
+
+from helpers import record
+
+
+RECORDS = [
+    record("1", 50.0, "A", metadata={"region": "eu"}),
+    record("2", 5.0, "A"),
+    record("3", 80.0, "B"),
+]
+
+
+def apply(spec):
+    return json.loads(rust_processor.apply_filter_spec(json.dumps(RECORDS), json.dumps(spec)))
+
+
+def test_empty_spec_returns_all_records():
+    assert [r["id"] for r in apply({})] == ["1", "2", "3"]
+
+
+def test_filters_are_combined_with_and():
+    assert [r["id"] for r in apply({"min_value": 10, "categories": ["A"]})] == ["1"]
+    assert [r["id"] for r in apply({"metadata_filters": {"region": "eu"}})] == ["1"]
+    assert [r["id"] for r in apply({"exclude_categories": ["A"], "max_value": 100})] == ["3"]
+
+
+def test_invalid_spec():
+    with pytest.raises(ValueError):
+        rust_processor.apply_filter_spec("[]", '{"categories": "A"}')
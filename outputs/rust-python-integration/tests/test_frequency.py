@@ -0,0 +1,49 @@
+"""
+Tests for compute_frequency and compute_log_level_distribution.
+"""
+
+import json
+
+import pytest
+
+import rust_processor
+
+from helpers import log_line, record
+
+RECORDS = [
+    record("1", 1.0, "A", "2024-01-15T10:00:00Z", {"region": "us"}),
+    record("2", 2.0, "B", "2024-01-15T23:59:59Z", {"region": "eu"}),
+    record("3", 3.0, "A", "2024-01-16T00:00:00Z", {"region": "us"}),
+]
+
+
+@pytest.mark.parametrize("field", ["category", "timestamp", "region"])
+def test_counts_sum_to_record_count(field):
+    counts = rust_processor.compute_frequency(json.dumps(RECORDS), field)
+    assert sum(counts.values()) == len(RECORDS)
+
+
+def test_timestamp_is_counted_per_day():
+    counts = rust_processor.compute_frequency(json.dumps(RECORDS), "timestamp")
+    assert counts == {"2024-01-15": 2, "2024-01-16": 1}
+
+
+def test_records_without_metadata_key_are_skipped():
+    records = RECORDS + [record("4", 4.0, "C")]
+    counts = rust_processor.compute_frequency(json.dumps(records), "region")
+    assert counts == {"us": 2, "eu": 1}
+
+
+def test_empty_field_name_raises():
+    with pytest.raises(ValueError):
+        rust_processor.compute_frequency(json.dumps(RECORDS), "")
+
+
+def test_log_level_distribution():
+    lines = [
+        log_line("2024-01-15T10:00:00Z", "INFO", "a"),
+        log_line("2024-01-15T10:00:01Z", "ERROR", "b"),
+        log_line("2024-01-15T10:00:02Z", "INFO", "c"),
+        "not json",
+    ]
+    assert rust_processor.compute_log_level_distribution(lines) == {"INFO": 2, "ERROR": 1}
@@ -0,0 +1,31 @@
+"""
+Tests for the Gini coefficient.
+"""
+
+import json
+
+import pytest
+
+import rust_processor
+
+
+def test_gini_equal_distribution_is_zero():
+    assert rust_processor.compute_gini(json.dumps([7.0] * 20)) == 0.0
+
+
+def test_gini_single_nonzero_value_approaches_one():
+    values = [0.0] * 999 + [50.0]
+
+    assert rust_processor.compute_gini(json.dumps(values)) == pytest.approx(0.999)
+
+
+def test_gini_empty_and_single_values():
+    assert rust_processor.compute_gini("[]") == 0.0
+    assert rust_processor.compute_gini("[3.5]") == 0.0
+
+
+def test_gini_rejects_negative_values_and_bad_json():
+    with pytest.raises(ValueError):
+        rust_processor.compute_gini("[1.0, -2.0]")
+    with pytest.raises(ValueError):
+        rust_processor.compute_gini("not json")
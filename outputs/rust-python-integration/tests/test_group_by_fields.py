@@ -0,0 +1,79 @@
+"""
+Tests for group_by_fields.
+"""
+
+import json
+
+import pytest
+
+import rust_processor
+
+from helpers import record
+
+RECORDS = json.dumps([
+    record("1", 10.0, "A", metadata={"region": "us-east"}),
+    record("2", 20.0, "A", metadata={"region": "eu-west"}),
+    record("3", 30.0, "B", metadata={"region": "us-east"}),
+    record("4", 50.0, "A", metadata={"region": "us-east"}),
+    record("5", 99.0, "B"),
+])
+
+
+def test_composite_keys():
+    groups = json.loads(rust_processor.group_by_fields(RECORDS, ["category", "region"]))
+
+    # Record 5 has no region, so it is skipped
+    assert list(groups) == [
+        "category:A|region:eu-west",
+        "category:A|region:us-east",
+        "category:B|region:us-east",
+    ]
+    assert groups["category:A|region:us-east"]["total_processed"] == 2
+    assert groups["category:A|region:us-east"]["total_value"] == 60.0
+    assert groups["category:B|region:us-east"]["max_value"] == 30.0
+
+
+def test_single_field_matches_aggregate_by_category():
+    groups = json.loads(rust_processor.group_by_fields(RECORDS, ["category"]))
+    by_category = rust_processor.aggregate_by_category(RECORDS)
+
+    assert list(groups) == [f"category:{c}" for c in by_category]
+    for category, stats in by_category.items():
+        result = groups[f"category:{category}"]
+        assert result["total_processed"] == stats["count"]
+        assert result["total_value"] == stats["total_value"]
+        assert result["average_value"] == stats["average_value"]
+        assert result["min_value"] == stats["min_value"]
+        assert result["max_value"] == stats["max_value"]
+
+
+def test_timestamp_groups_by_day():
+    records = json.dumps([
+        record("1", 1.0, timestamp="2024-01-15T10:00:00Z"),
+        record("2", 2.0, timestamp="2024-01-15T23:59:59Z"),
+        record("3", 3.0, timestamp="2024-01-16T00:00:00Z"),
+    ])
+    groups = json.loads(rust_processor.group_by_fields(records, ["timestamp"]))
+    assert {k: v["total_processed"] for k, v in groups.items()} == {
+        "timestamp:2024-01-15": 2,
+        "timestamp:2024-01-16": 1,
+    }
+
+
+def test_empty_field_name_raises():
+    with pytest.raises(ValueError):
+        rust_processor.group_by_fields(RECORDS, ["category", ""])
+
+
+def test_separators_in_values_are_escaped():
+    records = json.dumps([
+        record("1", 1.0, "A|region:x", metadata={"region": "y"}),
+        record("2", 2.0, "A", metadata={"region": "x|region:y"}),
+        record("3", 3.0, "C\\", metadata={"region": "z"}),
+    ])
+    groups = json.loads(rust_processor.group_by_fields(records, ["category", "region"]))
+
+    assert groups["category:A\\|region\\:x|region:y"]["total_value"] == 1.0
+    assert groups["category:A|region:x\\|region\\:y"]["total_value"] == 2.0
+    assert groups["category:C\\\\|region:z"]["total_value"] == 3.0
+    assert len(groups) == 3
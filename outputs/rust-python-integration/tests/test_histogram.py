@@ -0,0 +1,26 @@
+"""
+Tests for duration histograms.
+"""
+
+import pytest
+
+import rust_processor
+
+from helpers import log_line
+
+
+def test_duration_histogram_counts_sum_to_finite_values():
+    lines = [log_line("2024-01-15T10:00:00Z", duration_ms=d) for d in (10.0, 20.0, 30.0, 40.0)]
+    lines.append(log_line("2024-01-15T10:00:01Z"))
+
+    histogram = rust_processor.compute_duration_histogram(lines, 3)
+
+    assert len(histogram) == 3
+    assert histogram[0][0] == 10.0
+    assert histogram[-1][1] == 40.0
+    assert sum(count for _, _, count in histogram) == 4
+
+
+def test_duration_histogram_zero_buckets():
+    with pytest.raises(ValueError):
+        rust_processor.compute_duration_histogram([], 0)
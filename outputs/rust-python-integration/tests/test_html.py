@@ -0,0 +1,20 @@
+"""
+Tests for HTML table rendering of records.
+"""
+
+import json
+
+import rust_processor
+
+from helpers import record
+
+
+def test_records_to_html_table_truncates():
+    records = [record(str(i), float(i)) for i in range(5)]
+
+    html = rust_processor.records_to_html_table(json.dumps(records), max_rows=2)
+
+    assert "<thead>" in html
+    body = html[html.index("<tbody>"):html.index("</tbody>")]
+    assert body.count("<tr>") == 2
+    assert "... 3 more rows" in html
@@ -0,0 +1,91 @@
+"""
+Tests for InfluxDB line protocol output from LogStats.
+"""
+
+import pytest
+import rust_processor
+
+from helpers import log_line
+
+
+def split_unescaped(text, sep):
+    """Split on `sep` where it is not preceded by a backslash escape."""
+    parts, current, escaped = [], "", False
+    for ch in text:
+        if escaped:
+            current += ch
+            escaped = False
+        elif ch == "\\":
+            current += ch
+            escaped = True
+        elif ch == sep:
+            parts.append(current)
+            current = ""
+        else:
+            current += ch
+    parts.append(current)
+    return parts
+
+
+def unescape(text):
+    return text.replace("\\,", ",").replace("\\=", "=").replace("\\ ", " ")
+
+
+def parse_line(line):
+    """Minimal line protocol parser: measurement, tags, fields, timestamp."""
+    series, field_set, timestamp = split_unescaped(line, " ")
+    measurement, *tag_parts = split_unescaped(series, ",")
+    tags = dict(tuple(unescape(p) for p in split_unescaped(t, "=")) for t in tag_parts)
+    fields = {}
+    for field in field_set.split(","):
+        key, raw = field.split("=")
+        fields[key] = int(raw[:-1]) if raw.endswith("i") else float(raw)
+    return unescape(measurement), tags, fields, int(timestamp)
+
+
+def make_stats():
+    return rust_processor.compute_stats([
+        log_line("2024-01-15T10:00:00Z", "INFO", duration_ms=10.0, status_code=200),
+        log_line("2024-01-15T10:00:01Z", "INFO", duration_ms=15.0, status_code=200),
+        log_line("2024-01-15T10:00:02Z", "ERROR", duration_ms=20.0, status_code=500),
+    ])
+
+
+def test_line_protocol_point():
+    stats = make_stats()
+    line = stats.to_influxdb_line_protocol("logs", {"host": "web 1", "region": "eu,west"}, 1609459200000000000)
+
+    measurement, tags, fields, timestamp = parse_line(line)
+
+    assert line.startswith("logs,host=web\\ 1,region=eu\\,west ")
+    assert measurement == "logs"
+    assert tags == {"host": "web 1", "region": "eu,west"}
+    assert fields["total_count"] == 3
+    assert fields["error_count"] == 1
+    assert "total_count=3i" in line
+    assert fields["avg_duration_ms"] == pytest.approx(15.0)
+    assert timestamp == 1609459200000000000
+
+
+def test_line_protocol_omits_unknown_percentiles():
+    stats = make_stats()
+    merged = stats.merge(stats)
+
+    _, _, fields, _ = parse_line(merged.to_influxdb_line_protocol("logs", {}, 1))
+
+    assert "p95_duration_ms" not in fields
+    assert fields["total_count"] == 6
+
+
+def test_series_has_one_line_per_status_code():
+    stats = make_stats()
+    lines = stats.to_influxdb_series("responses", {"env": "prod"}, 42)
+
+    parsed = [parse_line(line) for line in lines]
+
+    assert [p[1] for p in parsed] == [
+        {"env": "prod", "status_code": "200"},
+        {"env": "prod", "status_code": "500"},
+    ]
+    assert [p[2] for p in parsed] == [{"count": 2, "error_count": 0}, {"count": 1, "error_count": 1}]
+    assert all(p[3] == 42 for p in parsed)
@@ -0,0 +1,36 @@
+"""
+Tests for joining DataRecord sets.
+"""
+
+import json
+
+import pytest
+
+import rust_processor
+
+from helpers import record
+
+
+LEFT = [record("1", 10.0, metadata={"source": "left"}), record("2", 20.0)]
+RIGHT = [record("1", 99.0, metadata={"source": "right", "tier": "gold"}), record("3", 30.0)]
+
+
+def join(strategy):
+    return json.loads(rust_processor.join_records(json.dumps(LEFT), json.dumps(RIGHT), strategy))
+
+
+def test_join_inner_merges_metadata():
+    [joined] = join("inner")
+
+    assert joined["value"] == 10.0
+    assert joined["metadata"] == {"source": "right", "tier": "gold"}
+
+
+def test_join_outer_strategies():
+    assert [r["id"] for r in join("left_outer")] == ["1", "2"]
+    assert [r["id"] for r in join("right_outer")] == ["1", "3"]
+
+
+def test_join_unknown_strategy():
+    with pytest.raises(ValueError):
+        join("full")
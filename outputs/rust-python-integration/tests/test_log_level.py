@@ -0,0 +1,52 @@
+"""
+Tests for the LogLevel enum and its use in filter_logs.
+"""
+
+import pytest
+
+import rust_processor
+from rust_processor import LogLevel
+
+from helpers import log_line
+
+LINES = [
+    log_line("2024-01-15T10:00:00Z", level="DEBUG", message="d"),
+    log_line("2024-01-15T10:00:01Z", level="INFO", message="i"),
+    log_line("2024-01-15T10:00:02Z", level="WARN", message="w"),
+    log_line("2024-01-15T10:00:03Z", level="ERROR", message="e"),
+]
+
+
+@pytest.mark.parametrize("name", ["error", "ERROR", "Error"])
+def test_from_str_is_case_insensitive(name):
+    assert LogLevel.from_str(name) == LogLevel.Error
+
+
+def test_from_str_accepts_warning_alias_and_rejects_unknown():
+    assert LogLevel.from_str("warning") == LogLevel.Warn
+    with pytest.raises(ValueError):
+        LogLevel.from_str("fatal")
+
+
+def test_repr_str_and_display_name():
+    assert repr(LogLevel.Warn) == "LogLevel.Warn"
+    assert str(LogLevel.Warn) == "WARN"
+    assert LogLevel.Info.display_name() == "INFO"
+
+
+def test_filter_logs_with_log_level():
+    filtered = rust_processor.filter_logs(LINES, min_level=LogLevel.Warn)
+
+    assert [e["message"] for e in filtered] == ["w", "e"]
+
+
+def test_filter_logs_string_level_is_deprecated():
+    with pytest.warns(DeprecationWarning):
+        filtered = rust_processor.filter_logs(LINES, min_level="ERROR")
+
+    assert [e["message"] for e in filtered] == ["e"]
+
+
+def test_filter_logs_rejects_other_types():
+    with pytest.raises(TypeError):
+        rust_processor.filter_logs(LINES, min_level=3)
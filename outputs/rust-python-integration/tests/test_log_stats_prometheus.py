@@ -0,0 +1,129 @@
+"""
+Tests for exporting LogStats in the Prometheus text format.
+"""
+
+import re
+
+import pytest
+import rust_processor
+
+from helpers import log_line
+
+COMMENT_RE = re.compile(r"^# (HELP|TYPE) [a-zA-Z_:][a-zA-Z0-9_:]* .+$")
+SAMPLE_RE = re.compile(
+    r'^[a-zA-Z_:][a-zA-Z0-9_:]*(\{[a-zA-Z_][a-zA-Z0-9_]*="(?:[^"\\]|\\.)*"(,[a-zA-Z_][a-zA-Z0-9_]*="(?:[^"\\]|\\.)*")*\})? (NaN|[+-]Inf|-?[0-9.eE+-]+)$'
+)
+
+
+def make_stats():
+    lines = [
+        log_line("2024-01-15T10:00:00Z", duration_ms=10.0, status_code=200),
+        log_line("2024-01-15T10:00:01Z", duration_ms=30.0, status_code=200),
+        log_line("2024-01-15T10:00:02Z", level="ERROR", duration_ms=90.0, status_code=503),
+    ]
+    return rust_processor.compute_stats(lines)
+
+
+def test_to_prometheus_line_structure():
+    text = make_stats().to_prometheus("api", {"service": "checkout svc", "env": 'prod "eu"'})
+
+    assert text.endswith("\n")
+    for line in text.splitlines():
+        assert COMMENT_RE.match(line) or SAMPLE_RE.match(line), line
+        if not line.startswith("#"):
+            # Strip quoted label values; no spaces may remain except before the value
+            unquoted = re.sub(r'"(?:[^"\\]|\\.)*"', '""', line)
+            assert unquoted.count(" ") == 1, line
+
+
+def test_to_prometheus_metrics_and_labels():
+    text = make_stats().to_prometheus("api", {"service": "checkout", "env": "prod"})
+    lines = text.splitlines()
+
+    assert "# TYPE api_log_total counter" in lines
+    assert 'api_log_total{env="prod",service="checkout"} 3' in lines
+    assert 'api_log_errors_total{env="prod",service="checkout"} 1' in lines
+    assert "# TYPE api_log_duration_milliseconds gauge" in lines
+    assert 'api_log_duration_milliseconds_avg{env="prod",service="checkout"} 43.333333333333336' in lines
+    assert any(line.startswith('api_log_duration_milliseconds{env="prod",service="checkout",quantile="0.99"}') for line in lines)
+    assert 'api_log_responses_total{env="prod",service="checkout",status_code="200"} 2' in lines
+    assert 'api_log_responses_total{env="prod",service="checkout",status_code="503"} 1' in lines
+
+
+def test_to_prometheus_merged_percentiles_are_nan():
+    stats = make_stats()
+    text = stats.merge(stats).to_prometheus("api", {})
+
+    assert 'api_log_duration_milliseconds{quantile="0.5"} NaN' in text.splitlines()
+    assert "api_log_total 6" in text.splitlines()
+
+
+def histogram_buckets(text, name):
+    buckets = []
+    for line in text.splitlines():
+        match = re.match(name + r'_bucket\{.*le="([^"]+)"\} (\d+)$', line)
+        if match:
+            buckets.append((match.group(1), int(match.group(2))))
+    return buckets
+
+
+def test_to_prometheus_histogram_structure():
+    text = make_stats().to_prometheus_histogram("api_duration_ms", {"service": "checkout"})
+    lines = text.splitlines()
+
+    assert "# TYPE api_duration_ms histogram" in lines
+    for line in lines:
+        assert COMMENT_RE.match(line) or SAMPLE_RE.match(line), line
+    bounds = [le for le, _ in histogram_buckets(text, "api_duration_ms")]
+    assert bounds == ["1", "5", "10", "25", "50", "100", "250", "500", "1000", "2500", "5000", "+Inf"]
+    assert 'api_duration_ms_sum{service="checkout"} 130' in lines
+    assert 'api_duration_ms_count{service="checkout"} 3' in lines
+
+
+def test_to_prometheus_histogram_is_monotonic_and_inf_is_total():
+    lines = [log_line("2024-01-15T10:00:00Z", duration_ms=float(d)) for d in range(1, 3001, 7)]
+    lines.append(log_line("2024-01-15T10:00:00Z"))
+    stats = rust_processor.compute_stats(lines)
+
+    for candidate in (stats, stats.merge(stats)):
+        buckets = histogram_buckets(candidate.to_prometheus_histogram("d", {}), "d")
+        counts = [count for _, count in buckets]
+        assert counts == sorted(counts)
+        assert buckets[-1] == ("+Inf", candidate.total_count)
+        assert counts[-2] <= candidate.duration_count
+
+
+def test_to_prometheus_histogram_without_durations():
+    stats = rust_processor.compute_stats([log_line("2024-01-15T10:00:00Z")])
+    buckets = histogram_buckets(stats.to_prometheus_histogram("d", {}), "d")
+    assert [count for _, count in buckets[:-1]] == [0] * 11
+    assert buckets[-1] == ("+Inf", 1)
+
+
+@pytest.mark.parametrize(
+    "prefix,labels",
+    [
+        ("api-v2", {}),
+        ("1api", {}),
+        ("api", {"bad-key": "x"}),
+        ("api", {"__internal": "x"}),
+        ("api", {"quantile": "0.5"}),
+        ("api", {"status_code": "200"}),
+    ],
+)
+def test_to_prometheus_rejects_bad_names(prefix, labels):
+    with pytest.raises(ValueError):
+        make_stats().to_prometheus(prefix, labels)
+
+
+@pytest.mark.parametrize(
+    "metric_name,labels",
+    [
+        ("api duration", {}),
+        ("api_duration_ms", {"bad-key": "x"}),
+        ("api_duration_ms", {"le": "1"}),
+    ],
+)
+def test_to_prometheus_histogram_rejects_bad_names(metric_name, labels):
+    with pytest.raises(ValueError):
+        make_stats().to_prometheus_histogram(metric_name, labels)
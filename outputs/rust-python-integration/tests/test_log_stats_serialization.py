@@ -0,0 +1,78 @@
+"""
+Tests for LogStats JSON and dict round-trips.
+"""
+
+import json
+
+import pytest
+
+import rust_processor
+
+from helpers import log_line
+
+
+def make_stats():
+    lines = [
+        log_line("2024-01-15T10:00:00Z", duration_ms=12.5, status_code=200),
+        log_line("2024-01-15T10:00:01Z", duration_ms=40.0, status_code=404),
+        log_line("2024-01-15T10:00:02Z", level="ERROR", duration_ms=95.0, status_code=503),
+        log_line("2024-01-15T10:00:03Z", level="WARN"),
+    ]
+    return rust_processor.compute_stats(lines)
+
+
+def test_json_round_trip_is_identical():
+    stats = make_stats()
+    restored = rust_processor.LogStats.from_json(stats.to_json())
+    assert restored.to_dict() == stats.to_dict()
+    assert json.loads(restored.to_json()) == json.loads(stats.to_json())
+
+
+def test_json_status_codes_are_string_keys():
+    data = json.loads(make_stats().to_json())
+    assert data["status_code_distribution"] == {"200": 1, "404": 1, "503": 1}
+    assert data["error_count_by_code"] == {"404": 1, "503": 1}
+
+
+def test_merged_stats_round_trip():
+    stats = make_stats().merge(make_stats())
+    restored = rust_processor.LogStats.from_json(stats.to_json())
+    assert restored.p50_duration_ms == -1.0
+    assert restored.to_dict() == stats.to_dict()
+
+
+def test_json_rejects_non_finite_durations():
+    lines = [
+        log_line("2024-01-15T10:00:00Z", duration_ms=1e308),
+        log_line("2024-01-15T10:00:01Z", duration_ms=1e308),
+    ]
+    stats = rust_processor.compute_stats(lines)
+    assert stats.total_duration_ms == float("inf")
+    with pytest.raises(ValueError, match="total_duration_ms"):
+        stats.to_json()
+
+
+def test_to_dict_keeps_integer_codes():
+    data = make_stats().to_dict()
+    assert data["total_count"] == 4
+    assert data["status_code_distribution"] == {200: 1, 404: 1, 503: 1}
+    assert data["avg_duration_ms"] == pytest.approx(49.1666666)
+
+
+def test_dict_round_trip_is_identical():
+    stats = make_stats()
+    restored = rust_processor.LogStats.from_dict(stats.to_dict())
+    assert restored.to_dict() == stats.to_dict()
+    assert repr(restored) == repr(stats)
+
+
+def test_from_dict_missing_key_raises():
+    data = make_stats().to_dict()
+    del data["p95_duration_ms"]
+    with pytest.raises(KeyError):
+        rust_processor.LogStats.from_dict(data)
+
+
+def test_from_json_invalid_raises():
+    with pytest.raises(ValueError):
+        rust_processor.LogStats.from_json('{"total_count": 1}')
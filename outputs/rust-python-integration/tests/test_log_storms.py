@@ -0,0 +1,63 @@
+"""
+Tests for log storm detection.
+"""
+
+import pytest
+
+import rust_processor
+
+from helpers import log_line
+
+
+def normal_traffic():
+    # One distinct message every 6 seconds for 10 minutes
+    return [
+        log_line(f"2024-01-15T10:{i // 10:02d}:{i % 10 * 6:02d}Z", message=f"request {i} ok")
+        for i in range(100)
+    ]
+
+
+def test_log_storms_finds_planted_storm():
+    storm = [
+        log_line(f"2024-01-15T10:04:1{i % 5}Z", level="ERROR", message="db connection lost")
+        for i in range(200)
+    ]
+    lines = normal_traffic() + storm
+
+    storms = rust_processor.detect_log_storms(lines, 60, 50)
+
+    assert len(storms) == 1
+    entry = storms[0]
+    assert entry["message"] == "db connection lost"
+    assert entry["count"] == "200"
+    assert entry["level"] == "ERROR"
+    assert entry["window_start"] == "2024-01-15T10:04:00Z"
+    assert entry["window_end"] == "2024-01-15T10:05:00Z"
+    assert entry["first_occurrence"] == "2024-01-15T10:04:10Z"
+    assert entry["last_occurrence"] == "2024-01-15T10:04:14Z"
+
+
+def test_log_storms_fuzzy_groups_by_prefix():
+    prefix = "upstream timed out while reading response header from "
+    lines = [
+        log_line(f"2024-01-15T10:00:{i % 60:02d}Z", level="WARN", message=f"{prefix}10.0.0.{i}")
+        for i in range(60)
+    ]
+
+    assert rust_processor.detect_log_storms(lines, 60, 50) == []
+
+    storms = rust_processor.detect_log_storms(lines, 60, 50, fuzzy=True)
+    assert len(storms) == 1
+    assert storms[0]["message"] == prefix[:50]
+    assert storms[0]["count"] == "60"
+
+
+def test_log_storms_empty_and_invalid_window():
+    assert rust_processor.detect_log_storms([], 60, 1) == []
+    with pytest.raises(ValueError):
+        rust_processor.detect_log_storms(normal_traffic(), 0, 1)
+
+
+def test_log_storms_rejects_window_past_last_timestamp():
+    with pytest.raises(ValueError):
+        rust_processor.detect_log_storms(normal_traffic(), 2**63 - 1, 1)
@@ -0,0 +1,75 @@
+"""
+Tests for compute_log_volume_trend.
+"""
+
+from datetime import datetime, timedelta, timezone
+
+import pytest
+
+import rust_processor
+
+from helpers import log_line
+
+START = datetime(2024, 1, 15, tzinfo=timezone.utc)
+
+
+def volume(counts, bucket_seconds=60):
+    """Log lines with counts[i] entries spread through bucket i."""
+    lines = []
+    for i, count in enumerate(counts):
+        for j in range(count):
+            ts = START + timedelta(seconds=i * bucket_seconds + j % bucket_seconds)
+            lines.append(log_line(ts.isoformat()))
+    return lines
+
+
+def test_linear_growth_fits_perfectly():
+    trend = rust_processor.compute_log_volume_trend(volume([5, 10, 15, 20, 25]), 60)
+    assert trend["slope_per_bucket"] == pytest.approx(5.0)
+    assert trend["r_squared"] >= 0.99
+    assert trend["initial_count"] == 5.0
+    assert trend["final_count"] == 25.0
+    assert trend["bucket_count"] == 5.0
+
+
+def test_flat_volume_has_no_slope():
+    trend = rust_processor.compute_log_volume_trend(volume([12] * 8), 60)
+    assert abs(trend["slope_per_bucket"]) < 0.001
+    assert trend["bucket_count"] == 8.0
+
+
+def test_declining_volume_and_empty_buckets():
+    # The empty middle bucket still counts, as zero
+    lines = volume([30, 0, 10])
+    trend = rust_processor.compute_log_volume_trend(lines + ["not json"], 60)
+    assert trend["slope_per_bucket"] == pytest.approx(-10.0)
+    assert trend["bucket_count"] == 3.0
+    assert 0.0 < trend["r_squared"] < 1.0
+
+
+def test_fewer_than_two_buckets_has_no_trend():
+    for lines in ([], volume([4])):
+        trend = rust_processor.compute_log_volume_trend(lines, 60)
+        assert trend["slope_per_bucket"] == 0.0
+        assert trend["r_squared"] == 0.0
+
+
+def test_zero_bucket_seconds_raises():
+    with pytest.raises(ValueError):
+        rust_processor.compute_log_volume_trend(volume([1, 2]), 0)
+
+
+def test_wide_span_does_not_materialize_empty_buckets():
+    # 126M one-second buckets between the two lines, almost all empty
+    lines = [log_line("2020-01-01T00:00:00Z"), log_line("2024-01-01T00:00:00Z")] * 2
+    lines.append(log_line("2024-01-01T00:00:00Z"))
+    trend = rust_processor.compute_log_volume_trend(lines, 1)
+    assert trend["bucket_count"] == 126_230_401.0
+    assert trend["initial_count"] == 2.0
+    assert trend["final_count"] == 3.0
+    assert trend["slope_per_bucket"] > 0.0
+    assert 0.0 < trend["r_squared"] < 1.0
+
+    extremes = [log_line("0001-01-01T00:00:00Z"), log_line("9999-12-31T23:59:59Z")]
+    trend = rust_processor.compute_log_volume_trend(extremes, 1)
+    assert trend["bucket_count"] > 3e11
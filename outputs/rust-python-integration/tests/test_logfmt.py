@@ -0,0 +1,46 @@
+"""
+Tests for logfmt log parsing.
+"""
+
+import pytest
+
+import rust_processor
+
+
+def test_parse_logfmt_empty_input():
+    assert rust_processor.parse_logs_logfmt([]) == []
+
+
+def test_parse_logfmt_maps_known_keys():
+    line = 'ts=2024-01-15T10:00:00Z lvl=error msg="upstream timed out" duration=120.5 status=504'
+
+    [entry] = rust_processor.parse_logs_logfmt([line])
+
+    assert entry == {
+        "timestamp": "2024-01-15T10:00:00Z",
+        "level": "error",
+        "message": "upstream timed out",
+        "duration_ms": "120.5",
+        "status_code": "504",
+    }
+
+
+def test_parse_logfmt_preserves_unknown_keys():
+    [entry] = rust_processor.parse_logs_logfmt(['service=api region="us east" debug'])
+
+    assert entry == {"service": "api", "region": "us east", "debug": ""}
+
+
+def test_parse_logfmt_values_containing_equals():
+    line = 'query=a=b filter="x=1 y=2" note="say \\"hi\\""'
+
+    [entry] = rust_processor.parse_logs_logfmt([line])
+
+    assert entry["query"] == "a=b"
+    assert entry["filter"] == "x=1 y=2"
+    assert entry["note"] == 'say "hi"'
+
+
+def test_parse_logfmt_unterminated_quote():
+    with pytest.raises(ValueError):
+        rust_processor.parse_logs_logfmt(['msg="never closed'])
@@ -0,0 +1,69 @@
+"""
+Tests for merge_datasets.
+"""
+
+import json
+
+import pytest
+
+import rust_processor
+
+from helpers import record
+
+PRIMARY = [
+    record("1", 10.0, metadata={"source": "primary"}),
+    record("2", 5.0),
+]
+SECONDARY = [
+    record("3", 1.0, metadata={"source": "secondary"}),
+    record("1", 20.0, metadata={"source": "secondary", "region": "eu"}),
+]
+STRATEGIES = ["keep_primary", "keep_secondary", "merge_metadata", "use_highest_value"]
+
+
+def merge(primary, secondary, strategy):
+    merged = rust_processor.merge_datasets(json.dumps(primary), json.dumps(secondary), strategy)
+    return json.loads(merged)
+
+
+def test_keep_primary():
+    merged = merge(PRIMARY, SECONDARY, "keep_primary")
+    assert [r["id"] for r in merged] == ["1", "2", "3"]
+    assert merged[0]["value"] == 10.0
+    assert merged[0]["metadata"] == {"source": "primary"}
+
+
+def test_keep_secondary():
+    merged = merge(PRIMARY, SECONDARY, "keep_secondary")
+    assert [r["id"] for r in merged] == ["1", "2", "3"]
+    assert merged[0]["value"] == 20.0
+
+
+def test_merge_metadata_adds_only_missing_keys():
+    merged = merge(PRIMARY, SECONDARY, "merge_metadata")
+    assert merged[0]["value"] == 10.0
+    assert merged[0]["metadata"] == {"source": "primary", "region": "eu"}
+
+
+def test_use_highest_value_takes_whole_record():
+    merged = merge(PRIMARY, SECONDARY, "use_highest_value")
+    assert merged[0]["value"] == 20.0
+    assert merged[0]["metadata"]["source"] == "secondary"
+
+
+@pytest.mark.parametrize("strategy", STRATEGIES)
+def test_non_overlapping_ids_are_concatenated(strategy):
+    merged = merge(PRIMARY, [record("9", 1.0)], strategy)
+    assert [r["id"] for r in merged] == ["1", "2", "9"]
+
+
+@pytest.mark.parametrize("strategy", STRATEGIES)
+def test_empty_inputs(strategy):
+    assert [r["id"] for r in merge([], SECONDARY, strategy)] == ["3", "1"]
+    assert [r["id"] for r in merge(PRIMARY, [], strategy)] == ["1", "2"]
+    assert merge([], [], strategy) == []
+
+
+def test_unknown_strategy_raises():
+    with pytest.raises(ValueError):
+        merge(PRIMARY, SECONDARY, "newest")
@@ -0,0 +1,50 @@
+"""
+Tests for merging LogStats computed on separate shards.
+"""
+
+import pytest
+
+import rust_processor
+
+from helpers import log_line
+
+
+def make_lines():
+    lines = []
+    for i in range(20):
+        level = "ERROR" if i % 5 == 0 else "INFO"
+        lines.append(log_line("2024-01-15T10:00:00Z", level=level, duration_ms=float(i * 7 % 13), status_code=200 + (i % 2) * 300))
+    lines.append(log_line("2024-01-15T10:00:00Z"))
+    return lines
+
+
+def test_merge_halves_matches_full_dataset():
+    lines = make_lines()
+    full = rust_processor.compute_stats(lines)
+    first = rust_processor.compute_stats(lines[:8])
+    second = rust_processor.compute_stats(lines[8:])
+
+    merged = first.merge(second)
+
+    assert merged.total_count == full.total_count
+    assert merged.error_count == full.error_count
+    assert merged.duration_count == full.duration_count
+    assert merged.total_duration_ms == pytest.approx(full.total_duration_ms)
+    assert merged.avg_duration_ms == pytest.approx(full.avg_duration_ms)
+    assert merged.variance_duration_ms == pytest.approx(full.variance_duration_ms)
+    assert merged.min_duration_ms == full.min_duration_ms
+    assert merged.max_duration_ms == full.max_duration_ms
+    assert merged.status_code_distribution == full.status_code_distribution
+    assert merged.error_count_by_code == full.error_count_by_code
+    assert merged.p95_duration_ms == -1.0
+
+
+def test_merge_with_shard_without_durations():
+    timed = rust_processor.compute_stats([log_line("2024-01-15T10:00:00Z", duration_ms=40.0)])
+    untimed = rust_processor.compute_stats([log_line("2024-01-15T10:00:00Z")])
+
+    merged = untimed.merge(timed)
+
+    assert merged.total_count == 2
+    assert merged.avg_duration_ms == 40.0
+    assert merged.min_duration_ms == 40.0
@@ -0,0 +1,39 @@
+"""
+Tests for the metadata filters.
+"""
+
+import json
+
+import pytest
+
+import rust_processor
+
+from helpers import record
+
+RECORDS = json.dumps([
+    record("1", 1.0, metadata={"region": "us"}),
+    record("2", 2.0, metadata={"region": "us-east"}),
+    record("3", 3.0, metadata={"region": "eu-us"}),
+    record("4", 4.0, metadata={"zone": "us"}),
+    record("5", 5.0),
+])
+
+
+def ids(records_json):
+    return [r["id"] for r in json.loads(records_json)]
+
+
+def test_exact_prefix_and_regex_are_distinct():
+    assert ids(rust_processor.filter_by_metadata(RECORDS, "region", "us")) == ["1"]
+    assert ids(rust_processor.filter_by_metadata_prefix(RECORDS, "region", "us")) == ["1", "2"]
+    assert ids(rust_processor.filter_by_metadata_regex(RECORDS, "region", "us")) == ["1", "2", "3"]
+
+
+def test_records_without_metadata_never_match():
+    assert "5" not in ids(rust_processor.filter_by_metadata_prefix(RECORDS, "region", ""))
+    assert "5" not in ids(rust_processor.filter_by_metadata_regex(RECORDS, "region", ".*"))
+
+
+def test_invalid_regex_raises():
+    with pytest.raises(ValueError):
+        rust_processor.filter_by_metadata_regex(RECORDS, "region", "(")
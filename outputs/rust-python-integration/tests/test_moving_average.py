@@ -0,0 +1,48 @@
+"""
+Tests for moving averages over DataRecord values.
+"""
+
+import json
+
+import pytest
+import rust_processor
+
+from helpers import record
+
+
+VALUES = [0.1, 0.2, 0.7, 1.3, 5.0]
+RECORDS = json.dumps(
+    [record(str(i), v, timestamp=f"2024-01-15T10:00:{i:02d}Z") for i, v in reversed(list(enumerate(VALUES)))]
+)
+
+
+def test_sma_window_one_is_identity():
+    points = rust_processor.compute_sma(RECORDS, 1)
+
+    assert [v for _, v in points] == VALUES
+    assert points[0][0] == "2024-01-15T10:00:00Z"
+
+
+def test_sma_warm_up_averages_available_values():
+    points = rust_processor.compute_sma(RECORDS, 3)
+
+    assert [v for _, v in points] == pytest.approx([0.1, 0.15, 1.0 / 3, 2.2 / 3, 7.0 / 3])
+
+
+def test_ema_alpha_one_is_identity():
+    assert [v for _, v in rust_processor.compute_ema(RECORDS, 1.0)] == VALUES
+
+
+def test_ema_smoothing():
+    assert [v for _, v in rust_processor.compute_ema(RECORDS, 0.5)][:3] == pytest.approx([0.1, 0.15, 0.425])
+
+
+@pytest.mark.parametrize("alpha", [0.0, 1.5, -1.0])
+def test_ema_rejects_alpha_out_of_range(alpha):
+    with pytest.raises(ValueError):
+        rust_processor.compute_ema(RECORDS, alpha)
+
+
+def test_sma_rejects_zero_window():
+    with pytest.raises(ValueError):
+        rust_processor.compute_sma(RECORDS, 0)
@@ -0,0 +1,73 @@
+"""
+Tests for OpenTelemetry log and metric mapping.
+
+Field names follow the OTel Logs Data Model and the OTLP/JSON encoding of
+opentelemetry-proto v1.3.2.
+"""
+
+import json
+
+import pytest
+import rust_processor
+
+from helpers import log_line
+
+
+def attributes(obj):
+    return {a["key"]: a["value"] for a in obj.get("attributes", [])}
+
+
+def test_log_entry_maps_to_log_record():
+    line = log_line("2024-01-15T10:00:00Z", "ERROR", "boom", duration_ms=12.5, status_code=500, user_id="u1")
+
+    record = json.loads(rust_processor.log_entry_to_otel_json(line))
+
+    assert record["timeUnixNano"] == "1705312800000000000"
+    assert record["severityNumber"] == 17
+    assert record["severityText"] == "ERROR"
+    assert record["body"] == {"stringValue": "boom"}
+    assert attributes(record) == {
+        "duration_ms": {"doubleValue": 12.5},
+        "http.status_code": {"intValue": "500"},
+        "user.id": {"stringValue": "u1"},
+    }
+
+
+@pytest.mark.parametrize("level,number", [("DEBUG", 5), ("INFO", 9), ("WARN", 13), ("ERROR", 17)])
+def test_severity_numbers(level, number):
+    record = json.loads(rust_processor.log_entry_to_otel_json(log_line("2024-01-15T10:00:00Z", level)))
+
+    assert record["severityNumber"] == number
+    assert attributes(record) == {}
+
+
+def test_invalid_log_line_raises():
+    with pytest.raises(ValueError):
+        rust_processor.log_entry_to_otel_json("not json")
+
+
+def test_stats_map_to_scope_metrics():
+    lines = [
+        log_line("2024-01-15T10:00:00Z", "INFO", duration_ms=10.0, status_code=200),
+        log_line("2024-01-15T10:00:01Z", "ERROR", duration_ms=30.0, status_code=500),
+    ]
+    stats = rust_processor.compute_stats(lines)
+
+    scopes = json.loads(rust_processor.log_stats_to_otel_metrics_json(stats, "my.service"))
+
+    assert len(scopes) == 1
+    assert scopes[0]["scope"] == {"name": "my.service"}
+    metrics = {m["name"]: m for m in scopes[0]["metrics"]}
+    gauges = [name for name, m in metrics.items() if "gauge" in m]
+    assert len(gauges) == 5
+    assert metrics["log.duration.avg"]["gauge"]["dataPoints"][0]["asDouble"] == pytest.approx(20.0)
+
+    entries = metrics["log.entries"]["sum"]
+    assert entries["isMonotonic"] is True
+    assert entries["aggregationTemporality"] == 2
+    assert entries["dataPoints"][0]["asInt"] == "2"
+    assert metrics["log.errors"]["sum"]["dataPoints"][0]["asInt"] == "1"
+
+    responses = metrics["log.responses"]["sum"]["dataPoints"]
+    assert [attributes(p)["http.status_code"] for p in responses] == [{"intValue": "200"}, {"intValue": "500"}]
+    assert all(p["timeUnixNano"] != "0" for p in responses)
@@ -0,0 +1,27 @@
+"""
+Tests for IQR-based duration outlier detection.
+"""
+
+import rust_processor
+
+from helpers import log_line
+
+
+def test_detect_outliers_returns_planted_values():
+    durations = [100.0 + i for i in range(10)] + [5000.0, 1.0]
+    lines = [
+        log_line(f"2024-01-15T10:00:{i:02d}Z", duration_ms=d, user_id=f"u{i}")
+        for i, d in enumerate(durations)
+    ]
+
+    outliers = rust_processor.detect_outliers(lines)
+
+    assert sorted(float(o["duration_ms"]) for o in outliers) == [1.0, 5000.0]
+    assert outliers[0]["user_id"] == "u10"
+    assert outliers[0]["timestamp"] == "2024-01-15T10:00:10Z"
+
+
+def test_detect_outliers_too_few_entries():
+    lines = [log_line("2024-01-15T10:00:00Z", duration_ms=d) for d in (1.0, 2.0, 900.0)]
+
+    assert rust_processor.detect_outliers(lines, iqr_multiplier=1.5) == []
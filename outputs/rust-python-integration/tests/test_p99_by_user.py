@@ -0,0 +1,35 @@
+"""
+Tests for compute_p99_by_user.
+"""
+
+import pytest
+
+import rust_processor
+
+from helpers import log_line
+
+TS = "2024-01-15T10:00:00Z"
+
+
+def test_constant_durations_give_exact_p99():
+    lines = [log_line(TS, duration_ms=100.0, user_id="alice") for _ in range(100)]
+    assert rust_processor.compute_p99_by_user(lines) == {"alice": 100.0}
+
+
+def test_p99_is_interpolated():
+    lines = [log_line(TS, duration_ms=float(d), user_id="bob") for d in range(1, 101)]
+    result = rust_processor.compute_p99_by_user(lines)
+    assert result["bob"] == pytest.approx(99.01)
+
+
+def test_entries_without_user_are_excluded():
+    lines = [log_line(TS, duration_ms=5000.0) for _ in range(10)]
+    lines += [log_line(TS, duration_ms=10.0, user_id="alice") for _ in range(5)]
+    assert rust_processor.compute_p99_by_user(lines) == {"alice": 10.0}
+
+
+def test_min_samples():
+    lines = [log_line(TS, duration_ms=10.0, user_id="carol") for _ in range(4)]
+    lines.append(log_line(TS, user_id="carol"))
+    assert rust_processor.compute_p99_by_user(lines) == {}
+    assert rust_processor.compute_p99_by_user(lines, min_samples=4) == {"carol": 10.0}
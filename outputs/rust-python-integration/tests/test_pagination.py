@@ -0,0 +1,63 @@
+"""
+Tests for offset and cursor pagination of DataRecords.
+"""
+
+import json
+
+import pytest
+
+import rust_processor
+
+from helpers import record
+
+
+RECORDS = json.dumps([record(f"r{i}", float(i)) for i in range(23)])
+
+
+def test_offset_pagination_visits_every_record_once():
+    seen = []
+    offset = 0
+    while True:
+        page = rust_processor.paginate_records(RECORDS, offset, 5)
+        assert page["total"] == 23
+        assert page["has_prev"] == (offset > 0)
+        seen.extend(r["id"] for r in page["items"])
+        if not page["has_next"]:
+            break
+        offset += page["limit"]
+
+    assert seen == [f"r{i}" for i in range(23)]
+
+
+def test_offset_past_end_is_empty():
+    page = rust_processor.paginate_records(RECORDS, 100, 5)
+
+    assert page["items"] == []
+    assert not page["has_next"]
+    assert page["has_prev"]
+
+
+def test_cursor_pagination_visits_every_record_once():
+    seen = []
+    cursor = None
+    while True:
+        page = rust_processor.cursor_paginate_records(RECORDS, cursor, 10)
+        seen.extend(r["id"] for r in page["items"])
+        if not page["has_more"]:
+            assert page["next_cursor"] is None
+            break
+        cursor = page["next_cursor"]
+
+    assert seen == [f"r{i}" for i in range(23)]
+
+
+def test_unknown_cursor_returns_empty_page():
+    page = rust_processor.cursor_paginate_records(RECORDS, "bogus!", 10)
+
+    assert page["items"] == []
+    assert not page["has_more"]
+
+
+def test_cursor_pagination_rejects_zero_limit():
+    with pytest.raises(ValueError, match="limit"):
+        rust_processor.cursor_paginate_records(RECORDS, None, 0)
@@ -0,0 +1,34 @@
+"""
+Tests for compute_percentile_by_group.
+"""
+
+import json
+
+import pytest
+
+import rust_processor
+
+from helpers import record
+
+RECORDS = [
+    record("1", 10.0, "A", metadata={"endpoint": "/users"}),
+    record("2", 30.0, "A", metadata={"endpoint": "/users"}),
+    record("3", 20.0, "A", metadata={"endpoint": "/orders"}),
+    record("4", 5.0, "B"),
+    record("5", 7.0, "B"),
+]
+
+
+def test_percentile_one_is_category_max():
+    result = rust_processor.compute_percentile_by_group(json.dumps(RECORDS), 1.0, "category")
+    assert result == {"A": 30.0, "B": 7.0}
+
+
+def test_group_by_metadata_key_skips_missing():
+    result = rust_processor.compute_percentile_by_group(json.dumps(RECORDS), 0.5, "endpoint")
+    assert result == {"/users": pytest.approx(20.0), "/orders": 20.0}
+
+
+def test_invalid_percentile_raises():
+    with pytest.raises(ValueError):
+        rust_processor.compute_percentile_by_group(json.dumps(RECORDS), 99.0, "category")
@@ -0,0 +1,58 @@
+"""
+Tests for DataRecord processing with summation settings.
+"""
+
+import json
+
+import pytest
+import rust_processor
+
+from helpers import record
+
+
+def test_process_records_defaults():
+    records = [record("1", 10.0), record("2", 30.0, "B")]
+
+    result = rust_processor.process_records(json.dumps(records))
+
+    assert result["total_processed"] == 2
+    assert result["total_value"] == 40.0
+    assert result["average_value"] == 20.0
+    assert result["categories"] == {"A": 1, "B": 1}
+
+
+def test_process_records_kahan_path_matches_pairwise():
+    records = json.dumps([record(str(i), 0.1) for i in range(20_000)])
+
+    kahan = rust_processor.process_records(records, use_kahan=True, parallel_threshold=10)
+    pairwise = rust_processor.process_records(records, use_kahan=False)
+
+    assert kahan["total_value"] == pytest.approx(2000.0, abs=1e-9)
+    assert pairwise["total_value"] == pytest.approx(2000.0, abs=1e-9)
+
+
+def test_process_records_empty_raises():
+    with pytest.raises(ValueError):
+        rust_processor.process_records("[]")
+
+
+def test_process_records_outlier_cap():
+    records = [record(str(i), 1.0 + (i % 3) * 0.01) for i in range(999)]
+    records.append(record("typo", 1e15, "B"))
+    payload = json.dumps(records)
+
+    capped = rust_processor.process_records(payload, outlier_cap=3.0)
+    assert capped["average_value"] == pytest.approx(1.0, abs=0.02)
+    assert capped["capped_count"] == 1
+    assert capped["total_processed"] == 999
+    assert capped["categories"] == {"A": 999}
+
+    uncapped = rust_processor.process_records(payload)
+    assert uncapped["capped_count"] == 0
+    assert uncapped["average_value"] > 1e11
+
+
+@pytest.mark.parametrize("cap", [0.5, -1.0, float("nan")])
+def test_process_records_rejects_bad_outlier_cap(cap):
+    with pytest.raises(ValueError):
+        rust_processor.process_records(json.dumps([record("1", 1.0)]), outlier_cap=cap)
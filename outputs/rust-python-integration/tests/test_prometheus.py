@@ -0,0 +1,43 @@
+"""
+Tests for Prometheus text-format exporters.
+"""
+
+import json
+import re
+
+import pytest
+
+import rust_processor
+
+from helpers import record
+
+SAMPLE_RE = re.compile(r'^[a-zA-Z_:][a-zA-Z0-9_:]*\{([a-zA-Z_][a-zA-Z0-9_]*="(?:[^"\\]|\\.)*",?)*\} \S+$')
+
+
+def test_records_to_prometheus_text():
+    records = [
+        record("1", 100.0, metadata={"region": "us-east"}),
+        record("2", 2.5),
+    ]
+
+    text = rust_processor.records_to_prometheus_text(json.dumps(records), "record_value", ["region"])
+    lines = text.splitlines()
+
+    assert lines[0] == "# TYPE record_value gauge"
+    assert lines[1] == 'record_value{id="1",region="us-east"} 100'
+    assert all(SAMPLE_RE.match(line) for line in lines[1:])
+
+
+@pytest.mark.parametrize(
+    "metric_name,label_keys",
+    [
+        ("record-value", []),
+        ("record_value", ["id"]),
+        ("record_value", ["bad-key"]),
+        ("record_value", ["region", "region"]),
+    ],
+)
+def test_records_to_prometheus_text_rejects_bad_names(metric_name, label_keys):
+    records = json.dumps([record("1", 1.0)])
+    with pytest.raises(ValueError):
+        rust_processor.records_to_prometheus_text(records, metric_name, label_keys)
@@ -0,0 +1,44 @@
+"""
+Tests for compute_rate_of_change.
+"""
+
+import json
+from datetime import datetime, timedelta, timezone
+
+import pytest
+
+import rust_processor
+
+from helpers import record
+
+START = datetime(2024, 1, 15, tzinfo=timezone.utc)
+
+
+def series(points):
+    return json.dumps([
+        record(str(t), value, timestamp=(START + timedelta(seconds=t)).isoformat())
+        for t, value in points
+    ])
+
+
+def test_linear_series_has_unit_rate():
+    records = series((t, float(t)) for t in [0, 3, 10, 11, 30, 60])
+    for window in (1, 2, 4):
+        rates = rust_processor.compute_rate_of_change(records, window)
+        assert len(rates) == 5
+        assert all(rate == pytest.approx(1.0) for _, rate in rates)
+
+
+def test_window_of_one_is_raw_derivative():
+    records = series([(0, 0.0), (10, 10.0), (20, 40.0)])
+    rates = rust_processor.compute_rate_of_change(records, 1)
+    assert [rate for _, rate in rates] == pytest.approx([1.0, 3.0])
+    smoothed = rust_processor.compute_rate_of_change(records, 2)
+    assert [rate for _, rate in smoothed] == pytest.approx([1.0, 2.0])
+
+
+def test_too_few_records_raises():
+    with pytest.raises(ValueError):
+        rust_processor.compute_rate_of_change(series([(0, 1.0)]), 1)
+    with pytest.raises(ValueError):
+        rust_processor.compute_rate_of_change(series([(0, 1.0), (1, 2.0)]), 0)
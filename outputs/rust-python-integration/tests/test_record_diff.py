@@ -0,0 +1,41 @@
+"""
+Tests for diffing DataRecord snapshots.
+"""
+
+import json
+
+import rust_processor
+
+from helpers import record
+
+
+def diff(before, after):
+    return rust_processor.record_diff(json.dumps(before), json.dumps(after))
+
+
+def test_record_diff_additions_removals_and_changes():
+    before = [record("1", 1.0), record("2", 2.0), record("3", 3.0, metadata={"k": "v"})]
+    after = [record("1", 1.0), record("3", 3.0, metadata={"k": "changed"}), record("4", 4.0)]
+
+    result = diff(before, after)
+
+    assert [r["id"] for r in result["added"]] == ["4"]
+    assert [r["id"] for r in result["removed"]] == ["2"]
+    assert len(result["changed"]) == 1
+    old, new = result["changed"][0]
+    assert old["metadata"] == {"k": "v"}
+    assert new["metadata"] == {"k": "changed"}
+    assert result["unchanged_count"] == 1
+
+
+def test_record_diff_value_only_change():
+    result = diff([record("1", 1.0)], [record("1", 1.5, timestamp="2024-02-01T00:00:00Z")])
+
+    assert [(old["value"], new["value"]) for old, new in result["changed"]] == [(1.0, 1.5)]
+    assert result["added"] == [] and result["removed"] == []
+
+
+def test_record_diff_empty_inputs():
+    assert diff([], []) == {"added": [], "removed": [], "changed": [], "unchanged_count": 0}
+    assert len(diff([], [record("1", 1.0)])["added"]) == 1
+    assert len(diff([record("1", 1.0)], [])["removed"]) == 1
@@ -0,0 +1,58 @@
+"""
+Tests for per-window request rate.
+"""
+
+import pytest
+import rust_processor
+
+from helpers import log_line
+
+
+def make_lines():
+    lines = []
+    # Window 1 (10:00:00-10:01:00): 4 requests, 1 error
+    for second, level, duration in [(0, "INFO", 10.0), (15, "ERROR", 50.0), (30, "INFO", 30.0), (59, "WARN", 10.0)]:
+        lines.append(log_line(f"2024-01-15T10:00:{second:02d}Z", level=level, duration_ms=duration))
+    # Window 2 (10:01:00-10:02:00): 2 requests, 2 errors
+    lines.append(log_line("2024-01-15T10:01:20Z", level="ERROR", duration_ms=200.0))
+    lines.append(log_line("2024-01-15T10:01:40Z", level="ERROR", duration_ms=100.0))
+    # Window 4 (10:03:00-10:04:00): 1 request, window 3 is empty
+    lines.append(log_line("2024-01-15T10:03:05Z"))
+    return lines
+
+
+def test_request_rate_counts_per_window():
+    lines = make_lines()
+    # Input order should not matter
+    windows = rust_processor.compute_request_rate(list(reversed(lines)), 60)
+
+    assert [w["window_start"] for w in windows] == [
+        "2024-01-15T10:00:00Z",
+        "2024-01-15T10:01:00Z",
+        "2024-01-15T10:03:00Z",
+    ]
+    assert windows[0]["window_end"] == "2024-01-15T10:01:00Z"
+
+    counts = [(w["total_requests"], w["error_requests"], w["warn_requests"]) for w in windows]
+    assert counts == [("4", "1", "1"), ("2", "2", "0"), ("1", "0", "0")]
+
+    assert float(windows[0]["avg_duration_ms"]) == pytest.approx(25.0)
+    assert float(windows[0]["max_duration_ms"]) == 50.0
+    assert float(windows[1]["avg_duration_ms"]) == pytest.approx(150.0)
+    assert float(windows[2]["avg_duration_ms"]) == 0.0
+
+
+def test_request_rate_empty_input():
+    assert rust_processor.compute_request_rate([], 60) == []
+
+
+def test_request_rate_rejects_zero_window():
+    with pytest.raises(ValueError):
+        rust_processor.compute_request_rate([], 0)
+
+
+def test_request_rate_rejects_window_past_last_timestamp():
+    lines = [log_line("2024-01-15T10:00:00Z")]
+    for window in (2**63 - 1, 2**62):
+        with pytest.raises(ValueError):
+            rust_processor.compute_request_rate(lines, window)
@@ -0,0 +1,23 @@
+"""
+Tests for reservoir sampling of DataRecords.
+"""
+
+import json
+
+import rust_processor
+
+from helpers import record
+
+
+RECORDS = json.dumps([record(str(i), float(i)) for i in range(100)])
+
+
+def test_reservoir_sample_is_deterministic():
+    first = rust_processor.reservoir_sample(RECORDS, 10, 42)
+
+    assert first == rust_processor.reservoir_sample(RECORDS, 10, 42)
+    assert len(json.loads(first)) == 10
+
+
+def test_reservoir_sample_k_exceeds_records():
+    assert len(json.loads(rust_processor.reservoir_sample(RECORDS, 500, 1))) == 100
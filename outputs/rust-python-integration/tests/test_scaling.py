@@ -0,0 +1,48 @@
+"""
+Tests for value scaling transforms.
+"""
+
+import json
+
+import pytest
+import rust_processor
+
+from helpers import record
+
+
+RECORDS = [record("1", 10.0), record("2", 40.0, "B"), record("3", 25.0)]
+
+
+def test_normalize_values_spans_unit_interval():
+    scaled = json.loads(rust_processor.normalize_values(json.dumps(RECORDS)))
+
+    values = [r["value"] for r in scaled]
+    assert min(values) == 0.0
+    assert max(values) == 1.0
+    assert values[2] == 0.5
+    assert [r["id"] for r in scaled] == ["1", "2", "3"]
+
+
+def test_normalize_values_degenerate_and_empty():
+    same = [record("1", 3.0), record("2", 3.0)]
+    assert [r["value"] for r in json.loads(rust_processor.normalize_values(json.dumps(same)))] == [0.5, 0.5]
+    assert json.loads(rust_processor.normalize_values("[]")) == []
+
+
+def test_standardize_values():
+    scaled = json.loads(rust_processor.standardize_values(json.dumps(RECORDS)))
+    values = [r["value"] for r in scaled]
+
+    assert sum(values) == pytest.approx(0.0, abs=1e-12)
+    assert values == pytest.approx([-1.0, 1.0, 0.0])
+
+
+def test_clamp_values():
+    clipped = json.loads(rust_processor.clamp_values(json.dumps(RECORDS), 15.0, 30.0))
+
+    assert [r["value"] for r in clipped] == [15.0, 30.0, 25.0]
+
+
+def test_clamp_values_rejects_inverted_bounds():
+    with pytest.raises(ValueError):
+        rust_processor.clamp_values(json.dumps(RECORDS), 5.0, 1.0)
@@ -0,0 +1,16 @@
+"""
+Tests for the LogEntry JSON Schema.
+"""
+
+import json
+
+import rust_processor
+
+
+def test_log_entry_schema_is_json_schema():
+    schema = json.loads(rust_processor.get_log_entry_schema())
+
+    assert "$schema" in schema
+    assert schema["type"] == "object"
+    assert set(schema["required"]) == {"timestamp", "level", "message"}
+    assert "description" in schema["properties"]["level"]
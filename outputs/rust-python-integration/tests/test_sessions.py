@@ -0,0 +1,47 @@
+"""
+Tests for per-user session analysis.
+"""
+
+import pytest
+
+import rust_processor
+
+from helpers import log_line
+
+
+def test_idle_gap_splits_sessions():
+    lines = [
+        log_line("2024-01-15T10:00:00Z", user_id="alice"),
+        log_line("2024-01-15T10:05:00Z", user_id="alice"),
+        log_line("2024-01-15T11:00:00Z", level="ERROR", user_id="alice"),
+    ]
+
+    sessions = rust_processor.compute_sessions(lines, 30)
+
+    assert len(sessions) == 2
+    assert sessions[0]["session_start"] == "2024-01-15T10:00:00Z"
+    assert sessions[0]["session_end"] == "2024-01-15T10:05:00Z"
+    assert sessions[0]["entry_count"] == 2
+    assert sessions[0]["has_error"] is False
+    assert sessions[1]["entry_count"] == 1
+    assert sessions[1]["has_error"] is True
+
+
+def test_entries_without_user_are_skipped():
+    lines = [
+        log_line("2024-01-15T10:00:00Z"),
+        log_line("2024-01-15T10:01:00Z", user_id="bob"),
+    ]
+
+    sessions = rust_processor.compute_sessions(lines, 30)
+
+    assert len(sessions) == 1
+    assert sessions[0]["user_id"] == "bob"
+
+
+def test_timeout_too_large_raises():
+    lines = [log_line("2024-01-15T10:00:00Z", user_id="bob")]
+
+    for minutes in (2**63, 2**62):
+        with pytest.raises(ValueError):
+            rust_processor.compute_sessions(lines, minutes)
@@ -0,0 +1,48 @@
+"""
+Tests for SlidingWindow.
+"""
+
+import json
+
+import pytest
+
+import rust_processor
+
+from helpers import record
+
+
+def push(window, id, value, timestamp, category="A"):
+    window.push(json.dumps(record(id, value, category=category, timestamp=timestamp)))
+
+
+def test_sliding_window_evicts_old_records():
+    window = rust_processor.SlidingWindow(60)
+    push(window, "1", 10.0, "2024-01-15T10:00:00Z")
+    push(window, "2", 20.0, "2024-01-15T10:00:30Z")
+    push(window, "3", 30.0, "2024-01-15T10:00:45Z")
+    assert window.current_count() == 3
+
+    window.advance_to("2024-01-15T10:01:15Z")
+
+    assert window.current_count() == 2
+    assert window.oldest_timestamp() == "2024-01-15T10:00:30Z"
+    assert window.newest_timestamp() == "2024-01-15T10:00:45Z"
+    stats = window.current_stats()
+    assert stats["total_processed"] == 2
+    assert stats["average_value"] == 25.0
+
+    push(window, "4", 5.0, "2024-01-15T10:05:00Z", category="B")
+
+    assert window.current_count() == 1
+    assert window.current_stats()["categories"] == {"B": 1}
+
+
+def test_sliding_window_empty_and_invalid():
+    window = rust_processor.SlidingWindow(10)
+
+    assert window.current_count() == 0
+    assert window.oldest_timestamp() is None
+    assert window.current_stats()["total_processed"] == 0
+    with pytest.raises(ValueError):
+        push(window, "1", 1.0, "not a timestamp")
+    assert repr(window) == "SlidingWindow(duration_seconds=10, count=0)"
@@ -0,0 +1,50 @@
+"""
+Tests for per-endpoint latency ranking.
+"""
+
+import pytest
+
+import rust_processor
+
+from helpers import log_line
+
+
+def make_lines():
+    lines = []
+    for i in range(100):
+        lines.append(log_line("2024-01-15T10:00:00Z", message=f"GET /api/v1/users?page={i} 200", duration_ms=10.0 + i % 10))
+        lines.append(log_line("2024-01-15T10:00:00Z", message="POST /api/v1/orders 201", duration_ms=100.0 + i))
+        lines.append(log_line("2024-01-15T10:00:00Z", message="GET /api/v1/reports 200", duration_ms=1000.0 + 10 * i))
+    # Not request lines: ignored
+    lines.append(log_line("2024-01-15T10:00:00Z", message="cache warmed", duration_ms=99999.0))
+    lines.append(log_line("2024-01-15T10:00:00Z", message="GET /api/v1/users"))
+    return lines
+
+
+def test_slow_endpoints_ranked_by_percentile():
+    endpoints = rust_processor.find_slow_endpoints(make_lines(), 3, 0.95)
+
+    assert [e["endpoint"] for e in endpoints] == [
+        "GET /api/v1/reports",
+        "POST /api/v1/orders",
+        "GET /api/v1/users",
+    ]
+    reports = endpoints[0]
+    assert reports["request_count"] == "100"
+    assert float(reports["p95_duration_ms"]) == pytest.approx(1940.5)
+    assert float(reports["avg_duration_ms"]) == pytest.approx(1495.0)
+    assert float(reports["max_duration_ms"]) == 1990.0
+    assert endpoints[2]["request_count"] == "100"
+
+
+def test_slow_endpoints_top_n_and_key_name():
+    endpoints = rust_processor.find_slow_endpoints(make_lines(), 1, 0.999)
+
+    assert len(endpoints) == 1
+    assert "p99.9_duration_ms" in endpoints[0]
+
+
+@pytest.mark.parametrize("percentile", [0.0, -0.5, 1.5, float("nan")])
+def test_slow_endpoints_rejects_bad_percentile(percentile):
+    with pytest.raises(ValueError):
+        rust_processor.find_slow_endpoints(make_lines(), 3, percentile)
@@ -0,0 +1,39 @@
+"""
+Tests for z-score based slow request detection.
+"""
+
+import pytest
+
+import rust_processor
+
+from helpers import log_line
+
+
+def bimodal_lines():
+    fast = [log_line("2024-01-15T10:00:00Z", duration_ms=10.0 + i % 3, user_id="fast") for i in range(30)]
+    slow = [log_line("2024-01-15T10:00:00Z", duration_ms=900.0 + i, user_id="slow") for i in range(3)]
+    return fast + slow + [log_line("2024-01-15T10:00:00Z")]
+
+
+def test_detect_slow_requests_bimodal():
+    slow = rust_processor.detect_slow_requests(bimodal_lines(), 2.0)
+
+    assert len(slow) == 3
+    assert all(e["user_id"] == "slow" for e in slow)
+
+
+def test_detect_slow_requests_include_stats():
+    result = rust_processor.detect_slow_requests(bimodal_lines(), 2.0, include_stats=True)
+
+    stats = result[0]
+    assert set(stats) == {"mean", "std_dev", "threshold"}
+    assert float(stats["threshold"]) == pytest.approx(
+        float(stats["mean"]) + 2.0 * float(stats["std_dev"])
+    )
+    assert len(result) == 4
+
+
+@pytest.mark.parametrize("z", [0.0, -1.0])
+def test_detect_slow_requests_rejects_non_positive_threshold(z):
+    with pytest.raises(ValueError):
+        rust_processor.detect_slow_requests([], z)
@@ -0,0 +1,32 @@
+"""
+Tests for sorting DataRecords.
+"""
+
+import json
+
+import pytest
+
+import rust_processor
+
+from helpers import record
+
+
+def sort(records, keys):
+    return [r["id"] for r in json.loads(rust_processor.sort_records(json.dumps(records), keys))]
+
+
+def test_sort_records_multiple_keys():
+    records = [record("1", 5.0, "B"), record("2", 7.0, "A"), record("3", 9.0, "B")]
+
+    assert sort(records, ["CategoryAsc", "ValueDesc"]) == ["2", "3", "1"]
+
+
+def test_sort_records_is_stable():
+    records = [record(str(i), float(i % 2), "A") for i in range(6)]
+
+    assert sort(records, ["ValueAsc"]) == ["0", "2", "4", "1", "3", "5"]
+
+
+def test_sort_records_unknown_key():
+    with pytest.raises(ValueError):
+        rust_processor.sort_records("[]", ["value"])
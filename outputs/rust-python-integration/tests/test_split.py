@@ -0,0 +1,26 @@
+"""
+Tests for splitting records by category.
+"""
+
+import json
+
+import rust_processor
+
+from helpers import record
+
+
+def test_split_by_category_is_a_partition():
+    records = [record(str(i), float(i), "ABC"[i % 3]) for i in range(300)]
+
+    buckets = json.loads(rust_processor.split_by_category(json.dumps(records)))
+
+    assert list(buckets) == ["A", "B", "C"]
+    all_ids = sorted(r["id"] for bucket in buckets.values() for r in bucket)
+    assert all_ids == sorted(r["id"] for r in records)
+    for category, bucket in buckets.items():
+        assert all(r["category"] == category for r in bucket)
+        assert [r["value"] for r in bucket] == sorted(r["value"] for r in bucket)
+
+
+def test_split_by_category_empty():
+    assert json.loads(rust_processor.split_by_category("[]")) == {}
@@ -0,0 +1,97 @@
+"""
+Tests for multiline stack trace reconstruction.
+"""
+
+import pytest
+
+import rust_processor
+
+from helpers import log_line
+
+JAVA_TRACE = [
+    log_line(
+        "2024-01-15T10:00:01Z",
+        level="ERROR",
+        message="Request failed: java.lang.IllegalStateException: order rejected",
+        user_id="u1",
+    ),
+    "\tat com.example.orders.OrderService.place(OrderService.java:81)",
+    "\tat com.example.orders.OrderController.create(OrderController.java:35)",
+    "\tat jdk.internal.reflect.GeneratedMethodAccessor12.invoke(Unknown Source)",
+    "\tat java.base/java.lang.reflect.Method.invoke(Method.java:568)",
+    "\tat org.springframework.web.servlet.DispatcherServlet.doDispatch(DispatcherServlet.java:1072)",
+    "\tat java.base/java.lang.Thread.run(Thread.java:833)",
+    "Caused by: java.lang.NullPointerException: Cannot invoke \"String.length()\" because \"name\" is null",
+    "\tat com.example.orders.OrderValidator.validateName(OrderValidator.java:42)",
+    "\tat com.example.orders.OrderValidator.validate(OrderValidator.java:18)",
+    "\tat com.example.orders.OrderService.place(OrderService.java:77)",
+    "\tat org.springframework.web.method.support.InvocableHandlerMethod.doInvoke(InvocableHandlerMethod.java:205)",
+    "\tat javax.servlet.http.HttpServlet.service(HttpServlet.java:750)",
+    "\tat org.apache.catalina.core.StandardWrapperValve.invoke(StandardWrapperValve.java:197)",
+    "\t... 12 more",
+]
+
+
+def test_java_trace_becomes_single_entry():
+    assert len(JAVA_TRACE) == 15
+    lines = [log_line("2024-01-15T10:00:00Z")] + JAVA_TRACE + [log_line("2024-01-15T10:00:02Z")]
+
+    entries = rust_processor.extract_stack_traces(lines)
+
+    assert len(entries) == 3
+    error = entries[1]
+    assert error["level"] == "ERROR"
+    assert error["timestamp"] == "2024-01-15T10:00:01Z"
+    assert error["user_id"] == "u1"
+    assert error["stack_trace"] == "true"
+    assert len(error["message"].split("\n")) == 15
+    assert error["message"].startswith("Request failed: java.lang.IllegalStateException")
+    assert "\nCaused by: java.lang.NullPointerException" in error["message"]
+    assert error["message"].endswith("\t... 12 more")
+    assert "stack_trace" not in entries[0]
+    assert "stack_trace" not in entries[2]
+
+
+def test_python_traceback_continues_entry():
+    lines = [
+        log_line("2024-01-15T10:00:00Z", level="ERROR", message="worker crashed: KeyError: 'id'"),
+        '  File "worker.py", line 12, in run',
+        "    handle(job)",
+        "",
+        '  File "jobs.py", line 3, in handle',
+        "    return job['id']",
+        log_line("2024-01-15T10:00:01Z"),
+    ]
+
+    entries = rust_processor.extract_stack_traces(lines)
+
+    assert len(entries) == 2
+    assert entries[0]["message"].count("\n") == 4
+    assert entries[0]["message"].endswith("return job['id']")
+
+
+def test_max_stack_depth_limits_joined_lines():
+    lines = [log_line("2024-01-15T10:00:00Z", message="deep")]
+    lines += [f"  frame {i}" for i in range(500)]
+
+    entries = rust_processor.extract_stack_traces(lines, max_stack_depth=10)
+
+    assert len(entries) == 1
+    assert entries[0]["message"].count("\n") == 10
+
+
+def test_invalid_head_line_raises():
+    with pytest.raises(ValueError):
+        rust_processor.extract_stack_traces([log_line("2024-01-15T10:00:00Z"), "{not json"])
+
+
+def test_unindented_plain_text_line_is_not_merged():
+    lines = [log_line("2024-01-15T10:00:00Z", level="ERROR"), "java.lang.RuntimeException: boom"]
+    with pytest.raises(ValueError, match="Line 2: Parse error"):
+        rust_processor.extract_stack_traces(lines)
+
+
+def test_leading_continuation_line_is_reported():
+    lines = ["\tat com.example.Orphan.run(Orphan.java:1)", log_line("2024-01-15T10:00:00Z")]
+    with pytest.raises(ValueError, match="Line 1"):
+        rust_processor.extract_stack_traces(lines)
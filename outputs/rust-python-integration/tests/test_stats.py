@@ -0,0 +1,48 @@
+"""
+Tests for compute_stats and LogStats.
+"""
+
+import math
+
+import pytest
+import rust_processor
+
+from helpers import log_line
+
+
+def test_duration_variance_and_std_dev():
+    durations = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]
+    lines = [log_line("2024-01-15T10:00:00Z", duration_ms=d) for d in durations]
+
+    stats = rust_processor.compute_stats(lines)
+
+    # Squared deviations from the mean of 5 sum to 32; sample variance is 32 / 7
+    assert stats.variance_duration_ms == pytest.approx(32 / 7)
+    assert stats.std_dev_duration_ms == pytest.approx(math.sqrt(32 / 7))
+    assert "std_dev" in repr(stats)
+    assert "Std dev duration" in stats.summary()
+
+
+def test_variance_is_zero_for_single_sample():
+    stats = rust_processor.compute_stats([log_line("2024-01-15T10:00:00Z", duration_ms=10.0)])
+
+    assert stats.variance_duration_ms == 0.0
+    assert stats.std_dev_duration_ms == 0.0
+
+
+def test_stats_by_user_sums_to_global_total():
+    lines = [
+        log_line("2024-01-15T10:00:00Z", level="ERROR", duration_ms=5.0, user_id="alice"),
+        log_line("2024-01-15T10:00:01Z", duration_ms=15.0, user_id="alice"),
+        log_line("2024-01-15T10:00:02Z", level="WARN", duration_ms=25.0, user_id="bob"),
+        log_line("2024-01-15T10:00:03Z", duration_ms=35.0),
+    ]
+
+    overall = rust_processor.compute_stats(lines)
+    by_user = rust_processor.compute_stats_by_user(lines)
+
+    assert set(by_user) == {"alice", "bob", "<unknown>"}
+    assert sum(s.total_count for s in by_user.values()) == overall.total_count
+    assert sum(s.error_count for s in by_user.values()) == overall.error_count
+    assert sum(s.warn_count for s in by_user.values()) == overall.warn_count
+    assert by_user["alice"].avg_duration_ms == pytest.approx(10.0)
@@ -0,0 +1,43 @@
+"""
+Tests for status code time series.
+"""
+
+import pytest
+import rust_processor
+
+from helpers import log_line
+
+EXPECTED_KEYS = {"bucket", "2xx_count", "3xx_count", "4xx_count", "5xx_count", "total"}
+
+
+def test_status_code_series_buckets():
+    lines = [
+        log_line("2024-01-15T10:00:00Z", status_code=200),
+        log_line("2024-01-15T10:01:00Z", status_code=404),
+        log_line("2024-01-15T10:02:00Z"),
+        log_line("2024-01-15T10:06:00Z", status_code=503),
+        log_line("2024-01-15T10:07:00Z", status_code=301),
+        log_line("2024-01-15T10:12:00Z"),
+    ]
+
+    series = rust_processor.compute_status_code_series(lines, 5)
+
+    assert len(series) == 2
+    assert all(set(bucket) == EXPECTED_KEYS for bucket in series)
+
+    first, second = series
+    assert first["bucket"] == "2024-01-15T10:00:00Z"
+    assert (first["2xx_count"], first["4xx_count"], first["total"]) == (1, 1, 3)
+    assert second["bucket"] == "2024-01-15T10:05:00Z"
+    assert (second["3xx_count"], second["5xx_count"], second["total"]) == (1, 1, 2)
+
+
+def test_status_code_series_rejects_zero_bucket():
+    with pytest.raises(ValueError):
+        rust_processor.compute_status_code_series([], 0)
+
+
+def test_status_code_series_rejects_overflowing_bucket():
+    for minutes in (2**64 // 60 + 1, 2**63 // 60 + 1):
+        with pytest.raises(ValueError):
+            rust_processor.compute_status_code_series([], minutes)
@@ -0,0 +1,73 @@
+"""
+Tests for RFC 5424 syslog parsing.
+"""
+
+import pytest
+
+import rust_processor
+
+
+RFC_EXAMPLE = (
+    '<165>1 2003-10-11T22:14:15.003Z mymachine.example.com evntslog - ID47 '
+    '[exampleSDID@32473 iut="3" eventSource="Application" eventID="1011"] '
+    "An application event log entry..."
+)
+
+
+def test_parse_syslog_rfc_example():
+    [entry] = rust_processor.parse_logs_syslog([RFC_EXAMPLE])
+
+    assert entry == {
+        "timestamp": "2003-10-11T22:14:15.003Z",
+        "hostname": "mymachine.example.com",
+        "level": "INFO",
+        "service": "evntslog",
+        "message_id": "ID47",
+        "message": "An application event log entry...",
+        "iut": "3",
+        "eventSource": "Application",
+        "eventID": "1011",
+    }
+
+
+@pytest.mark.parametrize(
+    "pri, level",
+    [(8, "ERROR"), (33, "ERROR"), (34, "WARN"), (11, "WARN"), (12, "INFO"), (13, "INFO"), (14, "DEBUG"), (191, "DEBUG")],
+)
+def test_parse_syslog_severity_levels(pri, level):
+    line = f"<{pri}>1 2024-01-15T10:00:00Z host app 42 - - msg"
+
+    [entry] = rust_processor.parse_logs_syslog([line])
+
+    assert entry["level"] == level
+    assert entry["pid"] == "42"
+    assert "message_id" not in entry
+
+
+def test_parse_syslog_escaped_structured_data():
+    line = r'<34>1 2024-01-15T10:00:00Z host su - - [a@1 path="C:\\tmp" quote="say \"hi\"" br="[x\]"][b@2 k="v"]'
+
+    [entry] = rust_processor.parse_logs_syslog([line])
+
+    assert entry["path"] == "C:\\tmp"
+    assert entry["quote"] == 'say "hi"'
+    assert entry["br"] == "[x]"
+    assert entry["k"] == "v"
+    assert entry["message"] == ""
+
+
+def test_parse_syslog_malformed_lines_are_kept_in_place():
+    lines = ["garbage", RFC_EXAMPLE, "<999>1 2024-01-15T10:00:00Z h a - - - m"]
+
+    entries = rust_processor.parse_logs_syslog(lines)
+
+    assert len(entries) == 3
+    assert entries[0] == {"raw": "garbage", "parse_error": "does not match RFC 5424 syslog format"}
+    assert entries[1]["service"] == "evntslog"
+    assert entries[2]["raw"] == lines[2]
+    assert entries[2]["parse_error"] == "invalid PRI value 999"
+
+
+def test_parse_syslog_rejects_non_sequence():
+    with pytest.raises(ValueError):
+        rust_processor.parse_logs_syslog(42)
@@ -0,0 +1,38 @@
+"""
+Tests for grouping DataRecords into time buckets.
+"""
+
+import json
+
+import pytest
+
+import rust_processor
+
+from helpers import record
+
+
+RECORDS = [
+    record("1", 10.0, timestamp="2024-01-15T23:59:30Z"),
+    record("2", 20.0, timestamp="2024-01-16T00:00:10Z"),
+    record("3", 40.0, timestamp="2024-01-16T00:04:59Z"),
+]
+
+
+def test_group_by_time_bucket_across_midnight():
+    groups = rust_processor.group_by_time_bucket(json.dumps(RECORDS), "5m")
+
+    assert sorted(groups) == [1705363200 - 300, 1705363200]
+    assert [r["id"] for r in json.loads(groups[1705363200])] == ["2", "3"]
+
+
+def test_compute_stats_by_time_bucket_wide_bucket():
+    stats = rust_processor.compute_stats_by_time_bucket(json.dumps(RECORDS), "1d")
+
+    assert stats[1705363200]["total_processed"] == 2
+    assert stats[1705276800]["total_value"] == 10.0
+
+
+@pytest.mark.parametrize("spec", ["", "0s", "5x", "m"])
+def test_invalid_bucket_size(spec):
+    with pytest.raises(ValueError):
+        rust_processor.group_by_time_bucket("[]", spec)
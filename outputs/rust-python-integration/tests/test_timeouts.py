@@ -0,0 +1,47 @@
+"""
+Tests for find_timeout_requests and compute_timeout_rate.
+"""
+
+import pytest
+
+import rust_processor
+
+from helpers import log_line
+
+TS = "2024-01-15T10:00:00Z"
+
+LINES = [
+    log_line(TS, message="fast", duration_ms=100.0, user_id="alice"),
+    log_line(TS, message="slow", duration_ms=900.0, user_id="alice"),
+    log_line(TS, message="edge", duration_ms=500.0, user_id="bob"),
+    log_line(TS, message="anon", duration_ms=750.0),
+    log_line(TS, message="no duration", user_id="carol"),
+    "not json",
+]
+
+
+def test_find_timeout_requests():
+    entries, by_user = rust_processor.find_timeout_requests(LINES, 500.0)
+    assert [e["message"] for e in entries] == ["slow", "anon"]
+    assert entries[0]["duration_ms"] == "900"
+    assert by_user == {"alice": 1, "<unknown>": 1}
+
+
+def test_timeout_rate_is_timeouts_over_requests():
+    # 2 timeouts out of 5 parsed requests; the malformed line is not a request
+    assert rust_processor.compute_timeout_rate(LINES, 500.0) == pytest.approx(2 / 5)
+    assert rust_processor.compute_timeout_rate(LINES, 50.0) == pytest.approx(4 / 5)
+    assert rust_processor.compute_timeout_rate(LINES, 1000.0) == 0.0
+
+
+@pytest.mark.parametrize("threshold", [0.0, -5.0])
+def test_non_positive_threshold_raises(threshold):
+    with pytest.raises(ValueError):
+        rust_processor.find_timeout_requests(LINES, threshold)
+    with pytest.raises(ValueError):
+        rust_processor.compute_timeout_rate(LINES, threshold)
+
+
+def test_timeout_rate_without_requests_raises():
+    with pytest.raises(ValueError):
+        rust_processor.compute_timeout_rate([], 500.0)
@@ -0,0 +1,26 @@
+"""
+Tests for retrieving the slowest log entries.
+"""
+
+import rust_processor
+
+from helpers import log_line
+
+
+def test_top_n_by_duration():
+    durations = [30.0, 5.0, 120.0, 80.0]
+    lines = [log_line("2024-01-15T10:00:00Z", duration_ms=d) for d in durations]
+    lines.append(log_line("2024-01-15T10:00:00Z"))
+
+    top = rust_processor.top_n_by_duration(lines, 2)
+
+    assert [float(e["duration_ms"]) for e in top] == [120.0, 80.0]
+
+
+def test_top_n_by_duration_n_exceeds_entries():
+    lines = [log_line("2024-01-15T10:00:00Z", duration_ms=d) for d in (1.0, 3.0, 2.0)]
+
+    top = rust_processor.top_n_by_duration(lines, 10)
+
+    assert [float(e["duration_ms"]) for e in top] == [3.0, 2.0, 1.0]
+    assert len(rust_processor.top_n_by_duration(lines, 2**60)) == 3
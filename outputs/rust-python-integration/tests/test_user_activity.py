@@ -0,0 +1,43 @@
+"""
+Tests for per-user activity extraction.
+"""
+
+import pytest
+import rust_processor
+
+from helpers import log_line
+
+
+LINES = [
+    log_line("2024-01-15T10:05:00Z", user_id="bob", duration_ms=100.0),
+    log_line("2024-01-15T10:00:00Z", user_id="alice", duration_ms=10.0),
+    log_line("2024-01-15T10:09:00Z", level="ERROR", user_id="alice", duration_ms=30.0),
+    log_line("2024-01-15T10:03:00Z", user_id="alice"),
+    log_line("2024-01-15T10:04:00Z", level="ERROR"),
+    "not json",
+]
+
+
+def test_extract_unique_users_sorted_and_deduplicated():
+    assert rust_processor.extract_unique_users(LINES) == ["alice", "bob"]
+
+
+def test_extract_unique_users_empty():
+    assert rust_processor.extract_unique_users([log_line("2024-01-15T10:00:00Z")]) == []
+
+
+def test_compute_user_activity_counts_are_exact():
+    activity = rust_processor.compute_user_activity(LINES)
+
+    assert set(activity) == {"alice", "bob"}
+    assert activity["alice"] == {
+        "request_count": "3",
+        "error_count": "1",
+        "avg_duration_ms": "20",
+        "first_seen": "2024-01-15T10:00:00Z",
+        "last_seen": "2024-01-15T10:09:00Z",
+    }
+    assert activity["bob"]["request_count"] == "1"
+    assert activity["bob"]["error_count"] == "0"
+    assert float(activity["bob"]["avg_duration_ms"]) == pytest.approx(100.0)
+    assert activity["bob"]["first_seen"] == activity["bob"]["last_seen"]
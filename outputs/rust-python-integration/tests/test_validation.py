@@ -0,0 +1,53 @@
+"""
+Tests for structured log validation.
+"""
+
+import json
+
+import rust_processor
+
+from helpers import log_line
+
+
+def test_validate_logs_detailed_mixed_batch():
+    lines = [log_line("2024-01-15T10:00:00Z", id=f"ok-{i}") for i in range(10)]
+    invalid = [
+        log_line("2024-01-15T10:00:00Z", level="FATAL", id="bad-0"),
+        log_line("2024-01-15T10:00:00Z", duration_ms=-1.0, id="bad-1"),
+        log_line("2024-01-15T10:00:00Z", status_code=42, id="bad-2"),
+        log_line("", id="bad-3"),
+        json.dumps({"id": "bad-4"}),
+    ]
+    for i, line in enumerate(invalid):
+        lines.insert(i * 3, line)
+
+    errors = rust_processor.validate_logs_detailed(lines)
+
+    assert [e["id"] for e in errors] == ["bad-0", "bad-1", "bad-2", "bad-3", "bad-4"]
+    assert [e["line"] for e in errors] == [1, 4, 7, 10, 13]
+    assert errors[0]["error"].startswith("Invalid log level 'FATAL'")
+    assert errors[4]["error"].startswith("JSON parse error")
+
+
+def test_validate_logs_detailed_missing_id():
+    [error] = rust_processor.validate_logs_detailed(["not json"])
+
+    assert error["id"] is None
+    assert error["line"] == 1
+
+
+def test_validate_logs_messages_unchanged():
+    _, errors = rust_processor.validate_logs([log_line("2024-01-15T10:00:00Z", level="FATAL")])
+
+    assert errors[0].startswith("Line 1: Invalid log level 'FATAL'")
+
+
+def test_validate_logs_strict_rejects_bad_timestamp():
+    lines = [log_line("2024-13-01T00:00:00Z"), log_line("2024-01-15T10:00:00Z")]
+
+    assert rust_processor.validate_logs(lines) == (2, [])
+
+    valid_count, errors = rust_processor.validate_logs(lines, strict=True)
+    assert valid_count == 1
+    assert len(errors) == 1
+    assert errors[0].startswith("Line 1: Invalid timestamp")
@@ -0,0 +1,53 @@
+"""
+Tests for validate_records_with_rules.
+"""
+
+import json
+
+import pytest
+
+import rust_processor
+
+from helpers import record
+
+
+def failures(records, rules):
+    return rust_processor.validate_records_with_rules(json.dumps(records), rules)
+
+
+def test_id_pattern():
+    records = [record("abc123", 1.0), record("AB123456", 1.0)]
+    result = failures(records, [{"IdPattern": r"^[A-Z]{2}\d{6}$"}])
+    assert [(f["index"], f["record_id"]) for f in result] == [(0, "abc123")]
+
+
+def test_first_failing_rule_is_reported():
+    records = [record("1", 5_000_000.0, "Z")]
+    result = failures(records, [{"MaxValue": 1_000_000}, {"AllowedCategories": ["A"]}])
+    assert len(result) == 1
+    assert "at most" in result[0]["message"]
+
+
+def test_value_category_and_metadata_rules():
+    records = [
+        record("1", -1.0),
+        record("2", 1.0, "B"),
+        record("3", 1.0),
+        record("4", 1.0, metadata={"owner": "ops"}),
+    ]
+    rules = [
+        {"MinValue": 0},
+        {"AllowedCategories": ["A"]},
+        {"RequiredMetadataKey": "owner"},
+    ]
+    assert [f["index"] for f in failures(records, rules)] == [0, 1, 2]
+
+
+def test_malformed_rule_raises():
+    with pytest.raises(ValueError):
+        failures([record("1", 1.0)], [{"NoSuchRule": 1}])
+
+
+def test_invalid_id_pattern_raises():
+    with pytest.raises(ValueError):
+        failures([record("1", 1.0)], [{"MaxValue": 10}, {"IdPattern": "("}])
@@ -8,8 +8,12 @@
 #![deny(clippy::all)]
 
 use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
 use napi_derive::napi;
-use rust_core::{self, DataRecord as CoreDataRecord, ProcessResult as CoreProcessResult};
+use rust_core::{
+    self, DataRecord as CoreDataRecord, ProcessResult as CoreProcessResult, ProcessingError,
+    ValidationMode,
+};
 use std::collections::HashMap;
 
 /// A single data record
@@ -59,6 +63,8 @@ pub struct ProcessResult {
     pub min_value: f64,
     pub max_value: f64,
     pub categories: HashMap<String, u32>,
+    /// Records left out by `ProcessingConfig.outlierCap`
+    pub capped_count: u32,
 }
 
 impl From<CoreProcessResult> for ProcessResult {
@@ -74,6 +80,33 @@ impl From<CoreProcessResult> for ProcessResult {
                 .into_iter()
                 .map(|(k, v)| (k, v as u32))
                 .collect(),
+            capped_count: result.capped_count as u32,
+        }
+    }
+}
+
+/// Options for `processRecords`; omitted fields keep their defaults
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct ProcessingConfig {
+    /// Use Kahan summation above `parallelThreshold` records (default true)
+    pub use_kahan: Option<bool>,
+    /// Largest dataset summed with the parallel pairwise reduction (default 100000)
+    pub parallel_threshold: Option<u32>,
+    /// Leave out records more than this many standard deviations from the
+    /// mean, counting them in `cappedCount`; must be at least 1
+    pub outlier_cap: Option<f64>,
+}
+
+impl From<ProcessingConfig> for rust_core::ProcessingConfig {
+    fn from(config: ProcessingConfig) -> Self {
+        let defaults = rust_core::ProcessingConfig::default();
+        rust_core::ProcessingConfig {
+            use_kahan: config.use_kahan.unwrap_or(defaults.use_kahan),
+            parallel_threshold: config
+                .parallel_threshold
+                .map_or(defaults.parallel_threshold, |n| n as usize),
+            outlier_cap: config.outlier_cap,
         }
     }
 }
@@ -88,27 +121,270 @@ pub struct CategoryStats {
     pub average_value: f64,
     pub min_value: f64,
     pub max_value: f64,
+    pub variance: f64,
+    pub std_dev: f64,
+}
+
+/// A partial update applied by `patchRecordsById`
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct DataRecordPatch {
+    pub value: Option<f64>,
+    pub category: Option<String>,
+    pub metadata_updates: Option<HashMap<String, String>>,
+}
+
+impl From<DataRecordPatch> for rust_core::DataRecordPatch {
+    fn from(patch: DataRecordPatch) -> Self {
+        rust_core::DataRecordPatch {
+            value: patch.value,
+            category: patch.category,
+            metadata_updates: patch.metadata_updates,
+        }
+    }
+}
+
+/// Metadata type expectations for `validateMetadataSchema`
+///
+/// Every listed key is required; omitted lists impose no constraints.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct MetadataSchema {
+    pub required_numeric: Option<Vec<String>>,
+    pub required_boolean: Option<Vec<String>>,
+    pub required_non_empty: Option<Vec<String>>,
+}
+
+impl From<MetadataSchema> for rust_core::MetadataSchema {
+    fn from(schema: MetadataSchema) -> Self {
+        rust_core::MetadataSchema {
+            required_numeric: schema.required_numeric.unwrap_or_default(),
+            required_boolean: schema.required_boolean.unwrap_or_default(),
+            required_non_empty: schema.required_non_empty.unwrap_or_default(),
+        }
+    }
+}
+
+/// A record paired with its percentile rank
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct PercentileRank {
+    pub record: DataRecord,
+    pub percentile_rank: f64,
+}
+
+/// A record paired with its k-means cluster index
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ClusteredRecord {
+    pub record: DataRecord,
+    pub cluster: u32,
+}
+
+/// Records returned by `enrichRecords` along with how many were enriched
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct EnrichResult {
+    pub records: Vec<DataRecord>,
+    pub enriched_count: u32,
+}
+
+/// One row of a category × time-bucket heatmap
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct HeatmapRow {
+    pub time_bucket: String,
+    pub category_averages: HashMap<String, f64>,
+}
+
+/// Which unmatched records `joinRecords` keeps
+#[napi]
+pub enum JoinStrategy {
+    Inner,
+    LeftOuter,
+    RightOuter,
+}
+
+impl From<JoinStrategy> for rust_core::JoinStrategy {
+    fn from(strategy: JoinStrategy) -> Self {
+        match strategy {
+            JoinStrategy::Inner => rust_core::JoinStrategy::Inner,
+            JoinStrategy::LeftOuter => rust_core::JoinStrategy::LeftOuter,
+            JoinStrategy::RightOuter => rust_core::JoinStrategy::RightOuter,
+        }
+    }
+}
+
+/// Which record `mergeDatasets` keeps when both sides share an ID
+#[napi]
+pub enum ConflictResolution {
+    KeepPrimary,
+    KeepSecondary,
+    MergeMetadata,
+    UseHighestValue,
+}
+
+impl From<ConflictResolution> for rust_core::ConflictResolution {
+    fn from(conflict: ConflictResolution) -> Self {
+        match conflict {
+            ConflictResolution::KeepPrimary => rust_core::ConflictResolution::KeepPrimary,
+            ConflictResolution::KeepSecondary => rust_core::ConflictResolution::KeepSecondary,
+            ConflictResolution::MergeMetadata => rust_core::ConflictResolution::MergeMetadata,
+            ConflictResolution::UseHighestValue => rust_core::ConflictResolution::UseHighestValue,
+        }
+    }
+}
+
+/// Records falling in one fixed-size time window
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct TimeBucketGroup {
+    /// Unix epoch second at which the bucket starts
+    pub bucket_start: i64,
+    pub records: Vec<DataRecord>,
+}
+
+/// Processing statistics for one fixed-size time window
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct TimeBucketStats {
+    /// Unix epoch second at which the bucket starts
+    pub bucket_start: i64,
+    pub stats: ProcessResult,
+}
+
+/// One equal-width histogram bucket
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct HistogramBucket {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: u32,
+}
+
+/// Validation outcome for one record in `validateRecordsBatch`
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ValidationResult {
+    pub id: String,
+    pub valid: bool,
+    pub error: Option<String>,
+}
+
+/// Map a core processing error to a napi error with a matching status
+fn to_napi_error(e: ProcessingError) -> Error {
+    let status = match e {
+        ProcessingError::EmptyInput
+        | ProcessingError::ValidationFailed { .. }
+        | ProcessingError::ParseError { .. }
+        | ProcessingError::InvalidArgument(_)
+        | ProcessingError::DuplicateId(_) => Status::InvalidArg,
+        ProcessingError::Overflow => Status::GenericFailure,
+    };
+    Error::new(status, e.to_string())
+}
+
+/// Outcome of `validateRecord`
+///
+/// `field` and `message` are set exactly when `valid` is false. The
+/// generated TypeScript is a union narrowed on `valid`.
+#[napi(object)]
+pub struct RecordValidationResult {
+    pub valid: bool,
+    pub field: Option<String>,
+    pub message: Option<String>,
+}
+
+impl From<std::result::Result<(), rust_core::ValidationError>> for RecordValidationResult {
+    fn from(result: std::result::Result<(), rust_core::ValidationError>) -> Self {
+        match result {
+            Ok(()) => RecordValidationResult {
+                valid: true,
+                field: None,
+                message: None,
+            },
+            Err(e) => RecordValidationResult {
+                valid: false,
+                field: Some(e.field),
+                message: Some(e.message),
+            },
+        }
+    }
 }
 
 /// Validate a single record
 ///
-/// Returns an error message if validation fails, or null if valid.
+/// Returns `{ valid: true }`, or `{ valid: false, field, message }` naming
+/// the first field that failed.
 ///
 /// # Example (TypeScript)
 /// ```typescript
-/// const record = { id: "1", value: 100, category: "A", timestamp: "2024-01-15T10:00:00Z" };
-/// const error = validateRecord(record);
-/// if (error) {
-///   console.error("Validation failed:", error);
+/// const record = { id: "1", value: -5, category: "A", timestamp: "2024-01-15T10:00:00Z" };
+/// const result = validateRecord(record);
+/// if (!result.valid) {
+///   showFieldError(result.field, result.message); // "value", "Value must be positive, got -5"
 /// }
 /// ```
+#[napi(
+    ts_return_type = "{ valid: true } | { valid: false; field: 'id' | 'value' | 'category' | 'timestamp'; message: string }"
+)]
+pub fn validate_record(record: DataRecord) -> RecordValidationResult {
+    let core_record: CoreDataRecord = record.into();
+    rust_core::validate_record(&core_record, ValidationMode::Lenient).into()
+}
+
+/// Validate a record, also requiring an RFC 3339 timestamp
+///
+/// Same as `validateRecord`, but rejects timestamps such as
+/// `"2024-13-01T00:00:00Z"` that are non-empty yet unparseable.
+#[napi(
+    ts_return_type = "{ valid: true } | { valid: false; field: 'id' | 'value' | 'category' | 'timestamp'; message: string }"
+)]
+pub fn strict_validate_record(record: DataRecord) -> RecordValidationResult {
+    let core_record: CoreDataRecord = record.into();
+    rust_core::validate_record(&core_record, ValidationMode::Strict).into()
+}
+
+/// Validate a record's metadata against a schema
+///
+/// Returns a list of violation messages; empty if the record conforms.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const errors = validateMetadataSchema(record, {
+///   requiredNumeric: ["retries"],
+///   requiredBoolean: ["cached"],
+/// });
+/// errors.forEach(e => console.error(e));
+/// ```
 #[napi]
-pub fn validate_record(record: DataRecord) -> Option<String> {
+pub fn validate_metadata_schema(record: DataRecord, schema: MetadataSchema) -> Vec<String> {
     let core_record: CoreDataRecord = record.into();
-    match rust_core::validate_record(&core_record) {
-        Ok(_) => None,
-        Err(e) => Some(e.message),
-    }
+    rust_core::validate_metadata_schema(&core_record, &schema.into())
+}
+
+/// Validate many records in one call
+///
+/// Results are in input order, so they can be zipped with the records.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const results = validateRecordsBatch(records);
+/// const invalid = results.filter(r => !r.valid).map(r => `${r.id}: ${r.error}`);
+/// ```
+#[napi]
+pub fn validate_records_batch(records: Vec<DataRecord>) -> Vec<ValidationResult> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    rust_core::validate_records_batch(&core_records)
+        .results
+        .into_iter()
+        .map(|r| ValidationResult {
+            id: r.id,
+            valid: r.valid,
+            error: r.error,
+        })
+        .collect()
 }
 
 /// Process a batch of records and compute statistics
@@ -123,16 +399,74 @@ pub fn validate_record(record: DataRecord) -> Option<String> {
 /// ];
 ///
 /// const result = processRecords(records);
-/// console.log(`Processed ${result.total_processed} records`);
-/// console.log(`Average value: ${result.average_value}`);
+/// console.log(`Processed ${result.totalProcessed} records`);
+/// console.log(`Average value: ${result.averageValue}`);
+///
+/// // Ignore data entry errors far from the rest of the values
+/// const robust = processRecords(records, { outlierCap: 3 });
+/// console.log(`Left out ${robust.cappedCount} outliers`);
+/// ```
+#[napi]
+pub fn process_records(
+    records: Vec<DataRecord>,
+    config: Option<ProcessingConfig>,
+) -> Result<ProcessResult> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+    let config: rust_core::ProcessingConfig = config.unwrap_or_default().into();
+
+    rust_core::process_records_with_config(&core_records, &config)
+        .map(|result| result.into())
+        .map_err(to_napi_error)
+}
+
+/// Encode records into a compact binary buffer for IPC
+///
+/// Pass the buffer to `decodeRecordsBinary` or `processRecordsBinary`, in
+/// this process or a worker. Much faster than `JSON.stringify`, but only
+/// readable by the same build of this package.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const buf = encodeRecordsBinary(records);
+/// worker.postMessage(buf, [buf.buffer]);
 /// ```
 #[napi]
-pub fn process_records(records: Vec<DataRecord>) -> Result<ProcessResult> {
+pub fn encode_records_binary(records: Vec<DataRecord>) -> Result<Buffer> {
     let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
 
+    rust_core::encode_records_binary(&core_records)
+        .map(Buffer::from)
+        .map_err(to_napi_error)
+}
+
+/// Decode a buffer produced by `encodeRecordsBinary`
+///
+/// Throws if the buffer is truncated or malformed.
+#[napi]
+pub fn decode_records_binary(buf: Buffer) -> Result<Vec<DataRecord>> {
+    rust_core::decode_records_binary(&buf)
+        .map(|records| records.into_iter().map(|r| r.into()).collect())
+        .map_err(to_napi_error)
+}
+
+/// Process records straight from an `encodeRecordsBinary` buffer
+///
+/// Same result as `processRecords`, without converting every record to and
+/// from a JavaScript object or JSON text.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// worker.on("message", (buf: Buffer) => {
+///   const result = processRecordsBinary(buf);
+/// });
+/// ```
+#[napi]
+pub fn process_records_binary(buf: Buffer) -> Result<ProcessResult> {
+    let core_records = rust_core::decode_records_binary(&buf).map_err(to_napi_error)?;
+
     rust_core::process_records(&core_records)
         .map(|result| result.into())
-        .map_err(|e| Error::new(Status::InvalidArg, e))
+        .map_err(to_napi_error)
 }
 
 /// Filter records by category
@@ -181,7 +515,7 @@ pub fn filter_by_value(records: Vec<DataRecord>, min_value: f64) -> Vec<DataReco
 /// ```typescript
 /// const stats = getCategoryStats(records, "A");
 /// if (stats) {
-///   console.log(`Category A: ${stats.count} records, avg ${stats.average_value}`);
+///   console.log(`Category A: ${stats.count} records, avg ${stats.averageValue}`);
 /// }
 /// ```
 #[napi]
@@ -195,6 +529,8 @@ pub fn get_category_stats(records: Vec<DataRecord>, category: String) -> Option<
         average_value: stats.average_value,
         min_value: stats.min_value,
         max_value: stats.max_value,
+        variance: stats.variance,
+        std_dev: stats.std_dev,
     })
 }
 
@@ -213,72 +549,1529 @@ pub fn get_unique_categories(records: Vec<DataRecord>) -> Vec<String> {
     rust_core::get_unique_categories(&core_records)
 }
 
-/// Benchmark helper: Process records and return processing time in milliseconds
+/// Compute a category × time-bucket heatmap of average values
 ///
-/// This is useful for benchmarking to compare Rust vs pure JavaScript performance.
+/// Each row holds the average value per category for one bucket of
+/// `bucket_minutes`. Categories with no records in a bucket get 0. Throws
+/// if a bucket would start outside the representable timestamp range.
 ///
 /// # Example (TypeScript)
 /// ```typescript
-/// const result = benchmarkProcess(records);
-/// console.log(`Processed in ${result.duration_ms}ms`);
-/// console.log(`Throughput: ${result.records_per_second} records/sec`);
+/// const rows = computeHeatmap(records, 15);
+/// rows.forEach(row => console.log(row.timeBucket, row.categoryAverages));
 /// ```
-#[napi(object)]
-pub struct BenchmarkResult {
-    pub result: ProcessResult,
-    pub duration_ms: f64,
-    pub records_per_second: f64,
+#[napi]
+pub fn compute_heatmap(records: Vec<DataRecord>, bucket_minutes: u32) -> Result<Vec<HeatmapRow>> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    let rows =
+        rust_core::compute_heatmap(&core_records, bucket_minutes as u64).map_err(to_napi_error)?;
+    Ok(rows
+        .into_iter()
+        .map(|row| HeatmapRow {
+            time_bucket: row.time_bucket,
+            category_averages: row.category_averages,
+        })
+        .collect())
 }
 
+/// Get the metadata keys present in at least one record
+///
+/// Returns a sorted list of key names.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const keys = metadataKeysUnion(records);
+/// console.log(`Seen metadata keys: ${keys.join(", ")}`);
+/// ```
 #[napi]
-pub fn benchmark_process(records: Vec<DataRecord>) -> Result<BenchmarkResult> {
-    use std::time::Instant;
+pub fn metadata_keys_union(records: Vec<DataRecord>) -> Vec<String> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+    rust_core::metadata_keys_union(&core_records)
+}
+
+/// Get the metadata keys present in every record
+///
+/// Returns a sorted list of key names. Empty if any record has no metadata.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const keys = metadataKeysIntersection(records);
+/// console.log(`Common metadata keys: ${keys.join(", ")}`);
+/// ```
+#[napi]
+pub fn metadata_keys_intersection(records: Vec<DataRecord>) -> Vec<String> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+    rust_core::metadata_keys_intersection(&core_records)
+}
 
+/// Compute the entropy of the value distribution within each category
+///
+/// Values are bucketed into `bucket_count` equal-width buckets per category;
+/// higher entropy means a more even spread.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const entropy = computeValueEntropyPerCategory(records, 10);
+/// console.log(`Category A entropy: ${entropy["A"]} bits`);
+/// ```
+#[napi]
+pub fn compute_value_entropy_per_category(
+    records: Vec<DataRecord>,
+    bucket_count: u32,
+) -> HashMap<String, f64> {
     let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+    rust_core::compute_value_entropy_per_category(&core_records, bucket_count as usize)
+}
 
-    let start = Instant::now();
-    let result = rust_core::process_records(&core_records)
-        .map_err(|e| Error::new(Status::InvalidArg, e))?;
-    let duration = start.elapsed();
+/// Apply partial updates to records by ID
+///
+/// Returns the records with each matching patch applied. Metadata updates are
+/// merged into existing metadata; records without a patch are unchanged.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const patched = patchRecordsById(records, {
+///   record_1: { value: 42, metadataUpdates: { reviewed: "true" } },
+/// });
+/// ```
+#[napi]
+pub fn patch_records_by_id(
+    records: Vec<DataRecord>,
+    patches: HashMap<String, DataRecordPatch>,
+) -> Vec<DataRecord> {
+    let mut core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+    let core_patches: HashMap<String, rust_core::DataRecordPatch> =
+        patches.into_iter().map(|(id, p)| (id, p.into())).collect();
 
-    let duration_ms = duration.as_secs_f64() * 1000.0;
-    let records_per_second = result.total_processed as f64 / duration.as_secs_f64();
+    rust_core::patch_records_by_id(&mut core_records, &core_patches);
 
-    Ok(BenchmarkResult {
-        result: result.into(),
-        duration_ms,
-        records_per_second,
-    })
+    core_records.into_iter().map(|r| r.into()).collect()
 }
 
-/// Generate sample test data
+/// Compute the autocorrelation of record values for lags 1 through `max_lag`
 ///
-/// Creates a specified number of random data records for testing.
+/// Records must already be sorted by timestamp. Coefficients for lags above
+/// half the record count are unreliable.
 ///
 /// # Example (TypeScript)
 /// ```typescript
-/// const testData = generateSampleData(1000);
-/// console.log(`Generated ${testData.length} test records`);
+/// const acf = computeAutocorrelation(records, 24);
+/// const peakLag = acf.indexOf(Math.max(...acf)) + 1;
 /// ```
 #[napi]
-pub fn generate_sample_data(count: u32) -> Vec<DataRecord> {
-    use std::collections::HashMap;
+pub fn compute_autocorrelation(records: Vec<DataRecord>, max_lag: u32) -> Vec<f64> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+    rust_core::compute_autocorrelation(&core_records, max_lag as usize)
+}
+
+/// Pair each record with its percentile rank (0-100) in the value distribution
+///
+/// Ties share the same rank (dense ranking). Results are in input order.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const ranked = computePercentileRanks(records);
+/// ranked.forEach(r => console.log(r.record.id, r.percentileRank));
+/// ```
+#[napi]
+pub fn compute_percentile_ranks(records: Vec<DataRecord>) -> Vec<PercentileRank> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    rust_core::compute_percentile_ranks(&core_records)
+        .into_iter()
+        .map(|(record, percentile_rank)| PercentileRank {
+            record: record.into(),
+            percentile_rank,
+        })
+        .collect()
+}
+
+/// Render records as an HTML table for debugging
+///
+/// When `max_rows` is given and exceeded, a footer row reports how many
+/// records were omitted. All values are HTML-escaped.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const html = recordsToHtmlTable(records, 50);
+/// fs.writeFileSync("records.html", html);
+/// ```
+#[napi]
+pub fn records_to_html_table(records: Vec<DataRecord>, max_rows: Option<u32>) -> String {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+    rust_core::records_to_html_table(&core_records, max_rows.map(|n| n as usize))
+}
+
+/// Sample records with probability proportional to their value
+///
+/// Draws `n` records with replacement using a seeded RNG. Records with value 0
+/// are never selected; throws if all values are zero or any is negative or
+/// non-finite.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const sample = weightedSample(records, 100, 42);
+/// ```
+#[napi]
+pub fn weighted_sample(records: Vec<DataRecord>, n: u32, seed: u32) -> Result<Vec<DataRecord>> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    rust_core::weighted_sample(&core_records, n as usize, seed as u64)
+        .map(|sample| sample.into_iter().map(|r| r.into()).collect())
+        .map_err(to_napi_error)
+}
+
+/// Cluster records by value using 1D k-means
+///
+/// Returns each record with its cluster index in `0..k`. The same seed always
+/// produces the same clustering. Throws if any value is NaN or infinite.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const clustered = kmeansCluster(records, 3, 100, 42);
+/// const firstCluster = clustered.filter(c => c.cluster === 0);
+/// ```
+#[napi]
+pub fn kmeans_cluster(
+    records: Vec<DataRecord>,
+    k: u32,
+    max_iter: u32,
+    seed: u32,
+) -> Result<Vec<ClusteredRecord>> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    rust_core::kmeans_cluster(&core_records, k as usize, max_iter as usize, seed as u64)
+        .map(|clustered| {
+            clustered
+                .into_iter()
+                .map(|(record, cluster)| ClusteredRecord {
+                    record: record.into(),
+                    cluster: cluster as u32,
+                })
+                .collect()
+        })
+        .map_err(to_napi_error)
+}
 
-    let categories = vec!["A", "B", "C", "D"];
-    let mut records = Vec::with_capacity(count as usize);
+/// Left-join reference metadata onto records
+///
+/// `keyField` is `"id"`, `"category"`, or a metadata key name. Matching
+/// records get the lookup entries merged into their metadata.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const { records: enriched, enrichedCount } = enrichRecords(records, {
+///   A: { region: "us-east" },
+/// }, "category");
+/// ```
+#[napi]
+pub fn enrich_records(
+    records: Vec<DataRecord>,
+    lookup: HashMap<String, HashMap<String, String>>,
+    key_field: String,
+) -> EnrichResult {
+    let mut core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
 
-    for i in 0..count {
-        let mut metadata = HashMap::new();
-        metadata.insert("index".to_string(), i.to_string());
+    let enriched_count = rust_core::enrich_records(&mut core_records, &lookup, &key_field);
 
-        records.push(DataRecord {
-            id: format!("record_{}", i),
-            value: ((i % 1000) as f64) * 10.5,
-            category: categories[(i % 4) as usize].to_string(),
-            timestamp: format!("2024-01-15T10:{:02}:{:02}Z", (i / 60) % 60, i % 60),
-            metadata: Some(metadata),
-        });
+    EnrichResult {
+        records: core_records.into_iter().map(|r| r.into()).collect(),
+        enriched_count: enriched_count as u32,
     }
+}
 
-    records
+/// One point of a smoothed time series
+#[napi(object)]
+pub struct SmoothedPoint {
+    pub timestamp: String,
+    pub value: f64,
+}
+
+/// An old and new version of a record with the same ID
+#[napi(object)]
+pub struct RecordChange {
+    pub before: DataRecord,
+    pub after: DataRecord,
+}
+
+/// Differences between two record snapshots
+#[napi(object)]
+pub struct RecordDiff {
+    pub added: Vec<DataRecord>,
+    pub removed: Vec<DataRecord>,
+    pub changed: Vec<RecordChange>,
+    pub unchanged_count: u32,
+}
+
+/// One page of records from offset pagination
+#[napi(object)]
+pub struct PaginatedResult {
+    pub items: Vec<DataRecord>,
+    pub total: u32,
+    pub offset: u32,
+    pub limit: u32,
+    pub has_next: bool,
+    pub has_prev: bool,
+}
+
+/// One page of records from cursor pagination
+#[napi(object)]
+pub struct CursorPaginatedResult {
+    pub items: Vec<DataRecord>,
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+/// A record that failed validation
+#[napi(object)]
+pub struct ValidationError {
+    pub record_id: String,
+    /// `"id"`, `"value"`, `"category"`, `"timestamp"` or `"metadata"`
+    pub field: String,
+    pub message: String,
+    /// `"invalid_field"`, `"invalid_timestamp"` or `"rule_violation"`
+    pub kind: String,
+}
+
+impl From<rust_core::ValidationError> for ValidationError {
+    fn from(error: rust_core::ValidationError) -> Self {
+        let kind = match error.kind {
+            rust_core::ValidationErrorKind::InvalidField => "invalid_field",
+            rust_core::ValidationErrorKind::InvalidTimestamp => "invalid_timestamp",
+            rust_core::ValidationErrorKind::RuleViolation => "rule_violation",
+        };
+        ValidationError {
+            record_id: error.record_id,
+            field: error.field,
+            message: error.message,
+            kind: kind.to_string(),
+        }
+    }
+}
+
+/// A record that failed a rule in `validateRecordsWithRules`
+#[napi(object)]
+pub struct RuleViolation {
+    /// Position of the record in the input
+    pub index: u32,
+    pub error: ValidationError,
+}
+
+/// Statistics over the valid records plus the errors for the rest
+#[napi(object)]
+pub struct PartialProcessResult {
+    pub result: ProcessResult,
+    pub errors: Vec<ValidationError>,
+}
+
+/// Count of records for an ordered pair of values
+#[napi(object)]
+pub struct PairCount {
+    pub first: String,
+    pub second: String,
+    pub count: u32,
+}
+
+/// Incrementally maintained statistics for streaming input
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const acc = new StatsAccumulator();
+/// for (const page of pages) {
+///   page.forEach(r => acc.addRecord(r));
+/// }
+/// const result = acc.finalize();
+/// ```
+#[napi]
+pub struct StatsAccumulator {
+    inner: rust_core::StatsAccumulator,
+}
+
+#[napi]
+impl StatsAccumulator {
+    #[napi(constructor)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        StatsAccumulator {
+            inner: rust_core::StatsAccumulator::new(),
+        }
+    }
+
+    /// Restore an accumulator from a `toJson` checkpoint
+    #[napi(factory)]
+    pub fn from_json(json: String) -> Result<Self> {
+        serde_json::from_str(&json)
+            .map(|inner| StatsAccumulator { inner })
+            .map_err(|e| Error::new(Status::InvalidArg, format!("JSON parse error: {}", e)))
+    }
+
+    /// Add a record to the running statistics
+    #[napi]
+    pub fn add_record(&mut self, record: DataRecord) {
+        self.inner.add_record(&record.into());
+    }
+
+    /// Remove a previously added record
+    #[napi]
+    pub fn remove_record(&mut self, record: DataRecord) {
+        self.inner.remove_record(&record.into());
+    }
+
+    /// Compute the current statistics in O(1)
+    #[napi]
+    pub fn finalize(&self) -> ProcessResult {
+        self.inner.finalize().into()
+    }
+
+    /// Serialize the accumulator state for checkpointing
+    #[napi]
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(&self.inner)
+            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+    }
+}
+
+/// Bucket values into equal-width histogram buckets spanning [min, max]
+///
+/// NaN and infinite values are skipped. Throws if `bucketCount` is zero.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const buckets = computeHistogram(records.map(r => r.value), 20);
+/// buckets.forEach(b => console.log(`${b.lower}-${b.upper}: ${b.count}`));
+/// ```
+#[napi]
+pub fn compute_histogram(values: Vec<f64>, bucket_count: u32) -> Result<Vec<HistogramBucket>> {
+    rust_core::compute_histogram(&values, bucket_count as usize)
+        .map(|buckets| {
+            buckets
+                .into_iter()
+                .map(|(lower, upper, count)| HistogramBucket {
+                    lower,
+                    upper,
+                    count: count as u32,
+                })
+                .collect()
+        })
+        .map_err(to_napi_error)
+}
+
+/// Join two record sets on `id`
+///
+/// Matched records keep the left record's fields with metadata merged (right
+/// wins on conflicts). Outer joins keep unmatched records unchanged.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const enriched = joinRecords(transactions, reference, JoinStrategy.LeftOuter);
+/// ```
+#[napi]
+pub fn join_records(
+    left: Vec<DataRecord>,
+    right: Vec<DataRecord>,
+    strategy: JoinStrategy,
+) -> Vec<DataRecord> {
+    let core_left: Vec<CoreDataRecord> = left.into_iter().map(|r| r.into()).collect();
+    let core_right: Vec<CoreDataRecord> = right.into_iter().map(|r| r.into()).collect();
+
+    rust_core::join_records(&core_left, &core_right, strategy.into())
+        .into_iter()
+        .map(|r| r.into())
+        .collect()
+}
+
+/// Union two record sets, resolving records that share an ID
+///
+/// Output follows `primary` order, followed by the records only found in
+/// `secondary`. `MergeMetadata` keeps the primary record and adds the
+/// secondary's missing metadata keys; `UseHighestValue` keeps whichever
+/// record has the larger value.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const merged = mergeDatasets(replicaA, replicaB, ConflictResolution.MergeMetadata);
+/// ```
+#[napi]
+pub fn merge_datasets(
+    primary: Vec<DataRecord>,
+    secondary: Vec<DataRecord>,
+    conflict: ConflictResolution,
+) -> Vec<DataRecord> {
+    let core_primary: Vec<CoreDataRecord> = primary.into_iter().map(|r| r.into()).collect();
+    let core_secondary: Vec<CoreDataRecord> = secondary.into_iter().map(|r| r.into()).collect();
+
+    rust_core::merge_datasets(core_primary, core_secondary, conflict.into())
+        .into_iter()
+        .map(|r| r.into())
+        .collect()
+}
+
+/// Return the `n` highest-value records, sorted by value descending
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const top10 = topNByValue(records, 10);
+/// ```
+#[napi]
+pub fn top_n_by_value(records: Vec<DataRecord>, n: u32) -> Vec<DataRecord> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    rust_core::top_n_by_value(&core_records, n as usize)
+        .into_iter()
+        .map(|r| r.into())
+        .collect()
+}
+
+/// Group records into fixed-size time windows
+///
+/// Returns one entry per non-empty bucket, ordered by bucket start. Records
+/// with an unparseable timestamp are skipped.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const hourly = groupByTimeBucket(records, 3600);
+/// hourly.forEach(b => console.log(new Date(b.bucketStart * 1000), b.records.length));
+/// ```
+#[napi]
+pub fn group_by_time_bucket(records: Vec<DataRecord>, bucket_seconds: u32) -> Vec<TimeBucketGroup> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    let mut groups: Vec<TimeBucketGroup> =
+        rust_core::group_by_time_bucket(&core_records, bucket_seconds as u64)
+            .into_iter()
+            .map(|(bucket_start, group)| TimeBucketGroup {
+                bucket_start,
+                records: group.into_iter().map(|r| r.clone().into()).collect(),
+            })
+            .collect();
+    groups.sort_by_key(|g| g.bucket_start);
+    groups
+}
+
+/// Compute processing statistics for each fixed-size time window
+///
+/// Returns one entry per non-empty bucket, ordered by bucket start. Throws if
+/// any record fails validation.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const perMinute = computeStatsByTimeBucket(records, 60);
+/// ```
+#[napi]
+pub fn compute_stats_by_time_bucket(
+    records: Vec<DataRecord>,
+    bucket_seconds: u32,
+) -> Result<Vec<TimeBucketStats>> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    let mut buckets: Vec<TimeBucketStats> =
+        rust_core::compute_stats_by_time_bucket(&core_records, bucket_seconds as u64)
+            .map_err(to_napi_error)?
+            .into_iter()
+            .map(|(bucket_start, stats)| TimeBucketStats {
+                bucket_start,
+                stats: stats.into(),
+            })
+            .collect();
+    buckets.sort_by_key(|b| b.bucket_start);
+    Ok(buckets)
+}
+
+/// Process records in fixed-size chunks and merge the results
+///
+/// Produces the same result as `processRecords`; the merged average is exact.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const result = processRecordsChunked(records, 10000);
+/// ```
+#[napi]
+pub fn process_records_chunked(records: Vec<DataRecord>, chunk_size: u32) -> Result<ProcessResult> {
+    rust_core::process_records_chunked(
+        records.into_iter().map(CoreDataRecord::from),
+        chunk_size as usize,
+    )
+    .map(|result| result.into())
+    .map_err(to_napi_error)
+}
+
+/// Apply a saved filter configuration to records
+///
+/// `specJson` holds any of `min_value`, `max_value`, `categories`,
+/// `metadata_filters` and `exclude_categories`; all present filters must pass.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const spec = JSON.stringify({ min_value: 10, categories: ["A"] });
+/// const filtered = applyFilterSpec(records, spec);
+/// ```
+#[napi]
+pub fn apply_filter_spec(records: Vec<DataRecord>, spec_json: String) -> Result<Vec<DataRecord>> {
+    let spec = rust_core::FilterSpec::from_json(&spec_json).map_err(to_napi_error)?;
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    Ok(rust_core::apply_filter_spec(&core_records, &spec)
+        .into_iter()
+        .map(|r| r.into())
+        .collect())
+}
+
+/// Sort records by one or more keys
+///
+/// Later keys break ties in earlier ones; the sort is stable. Timestamps are
+/// compared as parsed instants, not strings.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const sorted = sortRecords(records, ["CategoryAsc", "ValueDesc"]);
+/// ```
+#[napi(
+    ts_args_type = "records: DataRecord[], keys: Array<'ValueAsc' | 'ValueDesc' | 'CategoryAsc' | 'CategoryDesc' | 'TimestampAsc' | 'TimestampDesc' | 'IdAsc' | 'IdDesc'>"
+)]
+pub fn sort_records(records: Vec<DataRecord>, keys: Vec<String>) -> Result<Vec<DataRecord>> {
+    let keys: Vec<rust_core::SortKey> = keys
+        .iter()
+        .map(|k| k.parse())
+        .collect::<std::result::Result<_, _>>()
+        .map_err(to_napi_error)?;
+    let mut core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    rust_core::sort_records(&mut core_records, &keys);
+
+    Ok(core_records.into_iter().map(|r| r.into()).collect())
+}
+
+/// Uniformly sample `k` records using reservoir sampling
+///
+/// The same seed always yields the same sample. Returns every record if there
+/// are fewer than `k`.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const sample = reservoirSample(records, 1000, 42);
+/// ```
+#[napi]
+pub fn reservoir_sample(records: Vec<DataRecord>, k: u32, seed: u32) -> Vec<DataRecord> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    rust_core::reservoir_sample_seeded(&core_records, k as usize, seed as u64)
+        .into_iter()
+        .map(|r| r.into())
+        .collect()
+}
+
+/// Remove records with duplicate IDs
+///
+/// Output order follows the first occurrence of each ID.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const unique = dedupRecords(records, "KeepLast");
+/// ```
+#[napi(
+    ts_args_type = "records: DataRecord[], strategy: 'KeepFirst' | 'KeepLast' | 'KeepHighestValue'"
+)]
+pub fn dedup_records(records: Vec<DataRecord>, strategy: String) -> Result<Vec<DataRecord>> {
+    let strategy: rust_core::DedupStrategy = strategy.parse().map_err(to_napi_error)?;
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    Ok(rust_core::dedup_records(core_records, strategy)
+        .into_iter()
+        .map(|r| r.into())
+        .collect())
+}
+
+/// Background task behind `processRecordsAsync`
+pub struct ProcessRecordsTask {
+    records: Vec<CoreDataRecord>,
+}
+
+impl Task for ProcessRecordsTask {
+    type Output = CoreProcessResult;
+    type JsValue = ProcessResult;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        rust_core::process_records(&self.records).map_err(to_napi_error)
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output.into())
+    }
+}
+
+/// Process records on the libuv thread pool without blocking the event loop
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const result = await processRecordsAsync(records);
+/// ```
+#[napi(ts_return_type = "Promise<ProcessResult>")]
+pub fn process_records_async(records: Vec<DataRecord>) -> AsyncTask<ProcessRecordsTask> {
+    AsyncTask::new(ProcessRecordsTask {
+        records: records.into_iter().map(|r| r.into()).collect(),
+    })
+}
+
+/// Which filter a `FilterRecordsTask` applies
+enum RecordFilter {
+    Category(String),
+    MinValue(f64),
+}
+
+/// Background task behind `filterByCategoryAsync` and `filterByValueAsync`
+pub struct FilterRecordsTask {
+    records: Vec<CoreDataRecord>,
+    filter: RecordFilter,
+}
+
+impl Task for FilterRecordsTask {
+    type Output = Vec<CoreDataRecord>;
+    type JsValue = Vec<DataRecord>;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        Ok(match &self.filter {
+            RecordFilter::Category(category) => {
+                rust_core::filter_by_category(&self.records, category)
+            }
+            RecordFilter::MinValue(min_value) => {
+                rust_core::filter_by_value(&self.records, *min_value)
+            }
+        })
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output.into_iter().map(|r| r.into()).collect())
+    }
+}
+
+/// Filter records by category on the libuv thread pool
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const filtered = await filterByCategoryAsync(records, "A");
+/// ```
+#[napi(ts_return_type = "Promise<DataRecord[]>")]
+pub fn filter_by_category_async(
+    records: Vec<DataRecord>,
+    category: String,
+) -> AsyncTask<FilterRecordsTask> {
+    AsyncTask::new(FilterRecordsTask {
+        records: records.into_iter().map(|r| r.into()).collect(),
+        filter: RecordFilter::Category(category),
+    })
+}
+
+/// Filter records by minimum value on the libuv thread pool
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const expensive = await filterByValueAsync(records, 1000.0);
+/// ```
+#[napi(ts_return_type = "Promise<DataRecord[]>")]
+pub fn filter_by_value_async(
+    records: Vec<DataRecord>,
+    min_value: f64,
+) -> AsyncTask<FilterRecordsTask> {
+    AsyncTask::new(FilterRecordsTask {
+        records: records.into_iter().map(|r| r.into()).collect(),
+        filter: RecordFilter::MinValue(min_value),
+    })
+}
+
+/// Background task behind `benchmarkProcessAsync`
+pub struct BenchmarkProcessTask {
+    records: Vec<CoreDataRecord>,
+}
+
+impl Task for BenchmarkProcessTask {
+    type Output = BenchmarkResult;
+    type JsValue = BenchmarkResult;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        run_benchmark(&self.records)
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+/// Benchmark record processing on the libuv thread pool
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const { durationMs } = await benchmarkProcessAsync(records);
+/// ```
+#[napi(ts_return_type = "Promise<BenchmarkResult>")]
+pub fn benchmark_process_async(records: Vec<DataRecord>) -> AsyncTask<BenchmarkProcessTask> {
+    AsyncTask::new(BenchmarkProcessTask {
+        records: records.into_iter().map(|r| r.into()).collect(),
+    })
+}
+
+/// Split records into one array per category
+///
+/// Each array keeps the records' original relative order.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const byCategory = splitByCategory(records);
+/// console.log(`Category A has ${byCategory["A"]?.length ?? 0} records`);
+/// ```
+#[napi(ts_return_type = "Record<string, DataRecord[]>")]
+pub fn split_by_category(records: Vec<DataRecord>) -> HashMap<String, Vec<DataRecord>> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    rust_core::split_by_category(&core_records)
+        .into_iter()
+        .map(|(category, records)| (category, records.into_iter().map(|r| r.into()).collect()))
+        .collect()
+}
+
+/// Hash index over a record set for O(1) lookups by ID
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const index = RecordIndex.build(records);
+/// const record = index.get("42");
+/// const [a, b] = index.lookup(["1", "2"]);
+/// if (index.contains("7")) { /* ... */ }
+/// ```
+#[napi]
+pub struct RecordIndex {
+    inner: rust_core::RecordIndex<'static>,
+}
+
+#[napi]
+impl RecordIndex {
+    /// Index `records` by ID; throws if any ID appears more than once
+    #[napi(factory)]
+    pub fn build(records: Vec<DataRecord>) -> Result<Self> {
+        let records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+        let inner = rust_core::RecordIndex::build(records).map_err(to_napi_error)?;
+        Ok(RecordIndex { inner })
+    }
+
+    /// Look up a record by ID
+    #[napi]
+    pub fn get(&self, id: String) -> Option<DataRecord> {
+        self.inner.get(&id).map(|record| record.clone().into())
+    }
+
+    /// Look up several IDs; missing IDs yield `null`
+    #[napi]
+    pub fn lookup(&self, ids: Vec<String>) -> Vec<Option<DataRecord>> {
+        ids.into_iter().map(|id| self.get(id)).collect()
+    }
+
+    /// Whether a record with this ID is indexed
+    #[napi]
+    pub fn contains(&self, id: String) -> bool {
+        self.inner.contains(&id)
+    }
+
+    /// Number of indexed records
+    #[napi(getter)]
+    pub fn size(&self) -> u32 {
+        self.inner.len() as u32
+    }
+}
+
+/// Simple moving average of values in timestamp order
+///
+/// The first `window - 1` points average whatever predecessors exist.
+/// Throws if `window` is zero.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const smoothed = computeSMA(records, 5);
+/// smoothed.forEach(p => console.log(p.timestamp, p.value));
+/// ```
+#[napi(js_name = "computeSMA")]
+pub fn compute_sma(records: Vec<DataRecord>, window: u32) -> Result<Vec<SmoothedPoint>> {
+    if window == 0 {
+        return Err(Error::new(
+            Status::InvalidArg,
+            "window must be greater than 0".to_string(),
+        ));
+    }
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    Ok(rust_core::compute_sma(&core_records, window as usize)
+        .into_iter()
+        .map(|(timestamp, value)| SmoothedPoint { timestamp, value })
+        .collect())
+}
+
+/// Exponential moving average of values in timestamp order
+///
+/// Throws unless `alpha` is in `(0, 1]`.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const smoothed = computeEMA(records, 0.3);
+/// ```
+#[napi(js_name = "computeEMA")]
+pub fn compute_ema(records: Vec<DataRecord>, alpha: f64) -> Result<Vec<SmoothedPoint>> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    rust_core::compute_ema(&core_records, alpha)
+        .map(|points| {
+            points
+                .into_iter()
+                .map(|(timestamp, value)| SmoothedPoint { timestamp, value })
+                .collect()
+        })
+        .map_err(to_napi_error)
+}
+
+/// Run a chain of transforms over records in one pass
+///
+/// `pipelineJson` is an array of `{op, params}` steps applied in order:
+/// `scale {multiplier}`, `clamp {lower, upper}`, `add_offset {offset}` and
+/// `metadata_filter {key, value}` (which drops non-matching records).
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const pipeline = JSON.stringify([
+///   { op: "scale", params: { multiplier: 1.1 } },
+///   { op: "clamp", params: { lower: 0, upper: 1000 } },
+/// ]);
+/// const adjusted = applyPipeline(records, pipeline);
+/// ```
+#[napi]
+pub fn apply_pipeline(records: Vec<DataRecord>, pipeline_json: String) -> Result<Vec<DataRecord>> {
+    let pipeline =
+        rust_core::TransformPipeline::from_json(&pipeline_json).map_err(to_napi_error)?;
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    Ok(rust_core::apply_pipeline(&core_records, &pipeline)
+        .into_iter()
+        .map(|r| r.into())
+        .collect())
+}
+
+/// Compare two snapshots of records by ID
+///
+/// A record counts as changed when its value, category or metadata differs;
+/// timestamps are ignored.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const diff = diffRecords(yesterday, today);
+/// console.log(`${diff.added.length} added, ${diff.changed.length} changed`);
+/// ```
+#[napi]
+pub fn diff_records(before: Vec<DataRecord>, after: Vec<DataRecord>) -> RecordDiff {
+    let before: Vec<CoreDataRecord> = before.into_iter().map(|r| r.into()).collect();
+    let after: Vec<CoreDataRecord> = after.into_iter().map(|r| r.into()).collect();
+
+    let diff = rust_core::record_diff(&before, &after);
+    RecordDiff {
+        added: diff.added.into_iter().map(|r| r.into()).collect(),
+        removed: diff.removed.into_iter().map(|r| r.into()).collect(),
+        changed: diff
+            .changed
+            .into_iter()
+            .map(|(old, new)| RecordChange {
+                before: old.into(),
+                after: new.into(),
+            })
+            .collect(),
+        unchanged_count: diff.unchanged_count as u32,
+    }
+}
+
+/// Return the page of `limit` records starting at `offset`
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const page = paginateRecords(records, 40, 20);
+/// if (page.hasNext) { /* fetch offset 60 */ }
+/// ```
+#[napi]
+pub fn paginate_records(records: Vec<DataRecord>, offset: u32, limit: u32) -> PaginatedResult {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    let page = rust_core::paginate_records(&core_records, offset as usize, limit as usize);
+    PaginatedResult {
+        items: page.items.into_iter().map(|r| r.into()).collect(),
+        total: page.total as u32,
+        offset: page.offset as u32,
+        limit: page.limit as u32,
+        has_next: page.has_next,
+        has_prev: page.has_prev,
+    }
+}
+
+/// Return up to `limit` records after the record named by `cursor`
+///
+/// Pass the previous page's `nextCursor` to continue; omit it to start.
+/// Throws if `limit` is 0.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// let page = cursorPaginateRecords(records, undefined, 100);
+/// while (page.nextCursor) {
+///   page = cursorPaginateRecords(records, page.nextCursor, 100);
+/// }
+/// ```
+#[napi]
+pub fn cursor_paginate_records(
+    records: Vec<DataRecord>,
+    cursor: Option<String>,
+    limit: u32,
+) -> Result<CursorPaginatedResult> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    let page = rust_core::cursor_paginate_records(&core_records, cursor.as_deref(), limit as usize)
+        .map_err(to_napi_error)?;
+    Ok(CursorPaginatedResult {
+        items: page.items.into_iter().map(|r| r.into()).collect(),
+        next_cursor: page.next_cursor,
+        has_more: page.has_more,
+    })
+}
+
+/// Flatten a pair-count map, most frequent pairs first
+fn to_pair_counts(counts: HashMap<(String, String), usize>) -> Vec<PairCount> {
+    let mut pairs: Vec<PairCount> = counts
+        .into_iter()
+        .map(|((first, second), count)| PairCount {
+            first,
+            second,
+            count: count as u32,
+        })
+        .collect();
+    pairs.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.first.cmp(&b.first))
+            .then_with(|| a.second.cmp(&b.second))
+    });
+    pairs
+}
+
+/// Count records by their values for two metadata keys
+///
+/// `first` holds the value of `keyA` and `second` the value of `keyB`.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const pairs = computeMetadataCooccurrence(records, 'brand', 'color');
+/// ```
+#[napi]
+pub fn compute_metadata_cooccurrence(
+    records: Vec<DataRecord>,
+    key_a: String,
+    key_b: String,
+) -> Vec<PairCount> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+    to_pair_counts(rust_core::compute_metadata_cooccurrence(
+        &core_records,
+        &key_a,
+        &key_b,
+    ))
+}
+
+/// Count category transitions between consecutive records in time order
+///
+/// Each entry is a `first` → `second` transition.
+#[napi]
+pub fn compute_category_transition_matrix(records: Vec<DataRecord>) -> Vec<PairCount> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+    to_pair_counts(rust_core::compute_category_transition_matrix(&core_records))
+}
+
+/// Get the JSON Schema for `DataRecord` as a pretty-printed string
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const schema = JSON.parse(getDataRecordSchema());
+/// ```
+#[napi]
+pub fn get_data_record_schema() -> String {
+    rust_core::data_record_schema()
+}
+
+/// Process the valid records and report the invalid ones instead of throwing
+///
+/// With no valid records, `result.totalProcessed` is 0 and
+/// `result.averageValue` is NaN.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const { result, errors } = processRecordsPartial(records);
+/// errors.forEach(e => console.warn(`${e.recordId}: ${e.message}`));
+/// ```
+#[napi]
+pub fn process_records_partial(records: Vec<DataRecord>) -> PartialProcessResult {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    let (result, errors) = rust_core::process_records_partial(&core_records);
+    PartialProcessResult {
+        result: result.into(),
+        errors: errors.into_iter().map(|e| e.into()).collect(),
+    }
+}
+
+/// Gini coefficient of a value distribution
+///
+/// 0 means all values are equal; approaches 1 as the total concentrates in a
+/// single value. Throws if any value is negative, NaN or infinite.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const gini = computeGini(records.map(r => r.value));
+/// ```
+#[napi]
+pub fn compute_gini(values: Vec<f64>) -> Result<f64> {
+    rust_core::compute_gini(&values).map_err(to_napi_error)
+}
+
+/// Records from a trailing time span with O(1) statistics
+///
+/// The window covers the last `durationSeconds` before the newest timestamp
+/// pushed (or passed to `advanceTo`); older records are evicted.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const window = new SlidingWindow(300);
+/// stream.on('record', r => window.push(r));
+/// setInterval(() => {
+///   window.advanceTo(new Date().toISOString());
+///   console.log(window.currentCount(), window.currentStats().averageValue);
+/// }, 1000);
+/// ```
+#[napi]
+pub struct SlidingWindow {
+    inner: rust_core::SlidingWindow,
+}
+
+#[napi]
+impl SlidingWindow {
+    #[napi(constructor)]
+    pub fn new(duration_seconds: u32) -> Self {
+        SlidingWindow {
+            inner: rust_core::SlidingWindow::new(duration_seconds.into()),
+        }
+    }
+
+    /// Add a record and evict expired ones; throws on an invalid timestamp
+    #[napi]
+    pub fn push(&mut self, record: DataRecord) -> Result<()> {
+        self.inner.push(record.into()).map_err(to_napi_error)
+    }
+
+    /// Move the window's clock forward without adding a record
+    #[napi]
+    pub fn advance_to(&mut self, timestamp: String) -> Result<()> {
+        self.inner.advance_to(&timestamp).map_err(to_napi_error)
+    }
+
+    /// Statistics over the records currently in the window
+    #[napi]
+    pub fn current_stats(&self) -> ProcessResult {
+        self.inner.current_stats().into()
+    }
+
+    #[napi]
+    pub fn current_count(&self) -> u32 {
+        self.inner.current_count() as u32
+    }
+
+    #[napi]
+    pub fn oldest_timestamp(&self) -> Option<String> {
+        self.inner.oldest_timestamp()
+    }
+
+    #[napi]
+    pub fn newest_timestamp(&self) -> Option<String> {
+        self.inner.newest_timestamp()
+    }
+}
+
+/// Incremental processing of newline-delimited JSON records
+///
+/// Feed lines one at a time with `push` instead of buffering a whole file;
+/// `flush` returns the same statistics `processRecords` would for every
+/// record pushed. Records are not kept, but memory still grows with the
+/// number of distinct values seen. The optional callback receives the running
+/// statistics after every 10,000 records. It is invoked through a threadsafe
+/// function, so it runs on a later tick rather than inside `push`, and it does
+/// not keep the process alive.
+///
+/// This is a plain class, not a `stream.Transform`; wrap it in one to pipe a
+/// file through it (see `logProcessorStream` in
+/// `typescript-app/src/node-example.ts`).
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const processor = new LogProcessor(stats => console.log(stats.totalProcessed));
+/// for await (const line of readline.createInterface({ input: fs.createReadStream(path) })) {
+///   processor.push(line);
+/// }
+/// const result = processor.flush();
+/// ```
+#[napi]
+pub struct LogProcessor {
+    inner: rust_core::LineProcessor,
+    on_stats: Option<ThreadsafeFunction<ProcessResult, ErrorStrategy::Fatal>>,
+}
+
+#[napi]
+impl LogProcessor {
+    #[napi(constructor, ts_args_type = "onStats?: (stats: ProcessResult) => void")]
+    pub fn new(
+        env: Env,
+        on_stats: Option<ThreadsafeFunction<ProcessResult, ErrorStrategy::Fatal>>,
+    ) -> Result<Self> {
+        let on_stats = match on_stats {
+            Some(mut callback) => {
+                callback.unref(&env)?;
+                Some(callback)
+            }
+            None => None,
+        };
+        Ok(LogProcessor {
+            inner: rust_core::LineProcessor::new(),
+            on_stats,
+        })
+    }
+
+    /// Parse and add one JSON record; blank lines are ignored
+    ///
+    /// Throws if the line is not a valid record; the processor is unchanged.
+    #[napi]
+    pub fn push(&mut self, line: String) -> Result<()> {
+        let batch_stats = self.inner.push_line(&line).map_err(to_napi_error)?;
+        if let (Some(stats), Some(callback)) = (batch_stats, &self.on_stats) {
+            callback.call(stats.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+        Ok(())
+    }
+
+    /// Statistics over every record pushed since creation or the last `reset`
+    ///
+    /// Throws if no records were pushed.
+    #[napi]
+    pub fn flush(&self) -> Result<ProcessResult> {
+        self.inner.flush().map(|r| r.into()).map_err(to_napi_error)
+    }
+
+    /// Discard all pushed records and statistics
+    #[napi]
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    /// Number of records pushed since creation or the last `reset`
+    ///
+    /// A JavaScript number, so it stays exact past 2^32 records.
+    #[napi(getter)]
+    pub fn count(&self) -> f64 {
+        self.inner.count() as f64
+    }
+}
+
+/// Filter records whose `metadata[key]` equals `value`
+///
+/// Records without metadata or without `key` are never returned.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const usEast = filterByMetadata(records, "region", "us-east-1");
+/// ```
+#[napi]
+pub fn filter_by_metadata(records: Vec<DataRecord>, key: String, value: String) -> Vec<DataRecord> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    rust_core::filter_by_metadata(&core_records, &key, &value)
+        .into_iter()
+        .map(|r| r.into())
+        .collect()
+}
+
+/// Filter records whose `metadata[key]` starts with `prefix`
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const us = filterByMetadataPrefix(records, "region", "us-");
+/// ```
+#[napi]
+pub fn filter_by_metadata_prefix(
+    records: Vec<DataRecord>,
+    key: String,
+    prefix: String,
+) -> Vec<DataRecord> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    rust_core::filter_by_metadata_prefix(&core_records, &key, &prefix)
+        .into_iter()
+        .map(|r| r.into())
+        .collect()
+}
+
+/// Filter records whose `metadata[key]` matches a Rust regular expression
+///
+/// The pattern matches anywhere in the value; anchor with `^`/`$` for a full
+/// match. Throws if the pattern is invalid.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const canary = filterByMetadataRegex(records, "host", "^canary-\\d+$");
+/// ```
+#[napi]
+pub fn filter_by_metadata_regex(
+    records: Vec<DataRecord>,
+    key: String,
+    pattern: String,
+) -> Result<Vec<DataRecord>> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    rust_core::filter_by_metadata_regex(&core_records, &key, &pattern)
+        .map(|found| found.into_iter().map(|r| r.into()).collect())
+        .map_err(to_napi_error)
+}
+
+/// Count how often each value of a field occurs
+///
+/// `field` is `"category"`, `"timestamp"` (counted per UTC day,
+/// `YYYY-MM-DD`), or otherwise a metadata key. Records without the field are
+/// skipped.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const byRegion = computeFrequency(records, "region"); // { "us": 12, "eu": 7 }
+/// ```
+#[napi]
+pub fn compute_frequency(records: Vec<DataRecord>, field: String) -> Result<HashMap<String, u32>> {
+    let field: rust_core::FieldName = field.parse().map_err(to_napi_error)?;
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    Ok(
+        rust_core::compute_frequency_distribution(&core_records, |r| field.extract(r))
+            .into_iter()
+            .map(|(value, count)| (value, count as u32))
+            .collect(),
+    )
+}
+
+/// Check records against custom validation rules
+///
+/// `rulesJson` is a JSON array of externally tagged rules: `MaxValue`,
+/// `MinValue`, `IdPattern`, `AllowedCategories` or `RequiredMetadataKey`.
+/// Rules run in order and only the first failure per record is reported.
+/// Throws if an `IdPattern` is not a valid regular expression.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const violations = validateRecordsWithRules(records, JSON.stringify([
+///   { IdPattern: "^[A-Z]{2}\\d{6}$" },
+///   { MaxValue: 1_000_000 },
+///   { AllowedCategories: ["A", "B"] },
+/// ]));
+/// violations.forEach(v => console.error(v.index, v.error.message));
+/// ```
+#[napi]
+pub fn validate_records_with_rules(
+    records: Vec<DataRecord>,
+    rules_json: String,
+) -> Result<Vec<RuleViolation>> {
+    let rules: Vec<rust_core::ValidationRule> = serde_json::from_str(&rules_json)
+        .map_err(|e| Error::new(Status::InvalidArg, format!("JSON parse error: {}", e)))?;
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    let violations =
+        rust_core::validate_records_with_rules(&core_records, &rules).map_err(to_napi_error)?;
+    Ok(violations
+        .into_iter()
+        .map(|(index, error)| RuleViolation {
+            index: index as u32,
+            error: error.into(),
+        })
+        .collect())
+}
+
+/// Smoothed rate of change of values per second, in timestamp order
+///
+/// Each point is the derivative between a record and its predecessor,
+/// averaged over up to `smoothingWindow` points (1 returns the raw
+/// derivative). Throws if fewer than 2 records have valid timestamps or
+/// `smoothingWindow` is zero.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const rates = computeRateOfChange(records, 5);
+/// const rising = rates.filter(p => p.value > 0);
+/// ```
+#[napi]
+pub fn compute_rate_of_change(
+    records: Vec<DataRecord>,
+    smoothing_window: u32,
+) -> Result<Vec<SmoothedPoint>> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    rust_core::compute_rate_of_change(&core_records, smoothing_window as usize)
+        .map(|points| {
+            points
+                .into_iter()
+                .map(|(timestamp, value)| SmoothedPoint { timestamp, value })
+                .collect()
+        })
+        .map_err(to_napi_error)
+}
+
+/// Compute one percentile of `value` per group
+///
+/// `percentile` is a quantile in `[0, 1]` (`1` gives each group's maximum).
+/// `groupKey` is `"category"` or otherwise a metadata key; records without
+/// that key are skipped.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const p99ByEndpoint = computePercentileByGroup(records, 0.99, "endpoint");
+/// ```
+#[napi]
+pub fn compute_percentile_by_group(
+    records: Vec<DataRecord>,
+    percentile: f64,
+    group_key: String,
+) -> Result<HashMap<String, f64>> {
+    let group_key: rust_core::GroupKey = group_key.parse().map_err(to_napi_error)?;
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    rust_core::compute_percentile_by_group(&core_records, percentile, group_key)
+        .map_err(to_napi_error)
+}
+
+/// Statistics for one `groupByFields` group
+#[napi(object)]
+pub struct FieldGroup {
+    /// One value per requested field, in the order the fields were given
+    pub key: Vec<String>,
+    pub result: ProcessResult,
+}
+
+/// Group records by several fields at once and process each group
+///
+/// Each entry of `fields` is `"category"`, `"timestamp"` (the UTC day) or
+/// otherwise a metadata key. Records missing any of the fields are skipped.
+/// Groups are sorted by key.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// for (const { key: [category, region], result } of groupByFields(records, ["category", "region"])) {
+///   console.log(category, region, result.averageValue);
+/// }
+/// ```
+#[napi]
+pub fn group_by_fields(records: Vec<DataRecord>, fields: Vec<String>) -> Result<Vec<FieldGroup>> {
+    let fields = fields
+        .iter()
+        .map(|f| f.parse::<rust_core::FieldName>())
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(to_napi_error)?;
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    let mut groups: Vec<FieldGroup> = rust_core::aggregate_by_fields(&core_records, &fields)
+        .map_err(to_napi_error)?
+        .into_iter()
+        .map(|(key, result)| FieldGroup {
+            key,
+            result: result.into(),
+        })
+        .collect();
+    groups.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(groups)
+}
+
+/// Benchmark helper: Process records and return processing time in milliseconds
+///
+/// This is useful for benchmarking to compare Rust vs pure JavaScript performance.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const result = benchmarkProcess(records);
+/// console.log(`Processed in ${result.durationMs}ms`);
+/// console.log(`Throughput: ${result.recordsPerSecond} records/sec`);
+/// ```
+#[napi(object)]
+pub struct BenchmarkResult {
+    pub result: ProcessResult,
+    pub duration_ms: f64,
+    pub records_per_second: f64,
+}
+
+#[napi]
+pub fn benchmark_process(records: Vec<DataRecord>) -> Result<BenchmarkResult> {
+    let core_records: Vec<CoreDataRecord> = records.into_iter().map(|r| r.into()).collect();
+
+    run_benchmark(&core_records)
+}
+
+/// Time `process_records`, shared by the sync and async benchmarks
+fn run_benchmark(core_records: &[CoreDataRecord]) -> Result<BenchmarkResult> {
+    use std::time::Instant;
+
+    let start = Instant::now();
+    let result = rust_core::process_records(core_records).map_err(to_napi_error)?;
+    let duration = start.elapsed();
+
+    let duration_ms = duration.as_secs_f64() * 1000.0;
+    let records_per_second = result.total_processed as f64 / duration.as_secs_f64();
+
+    Ok(BenchmarkResult {
+        result: result.into(),
+        duration_ms,
+        records_per_second,
+    })
+}
+
+/// Generate sample test data
+///
+/// Creates a specified number of random data records for testing.
+///
+/// # Example (TypeScript)
+/// ```typescript
+/// const testData = generateSampleData(1000);
+/// console.log(`Generated ${testData.length} test records`);
+/// ```
+#[napi]
+pub fn generate_sample_data(count: u32) -> Vec<DataRecord> {
+    rust_core::generate_sample_data(count as usize)
+        .into_iter()
+        .map(DataRecord::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_records_task_matches_sync() {
+        let records = rust_core::generate_sample_data(10_000);
+
+        let sync = rust_core::process_records(&records).unwrap();
+        let mut task = ProcessRecordsTask {
+            records: records.clone(),
+        };
+        let result = task.compute().unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::to_value(&sync).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_process_records_task_reports_errors() {
+        let mut task = ProcessRecordsTask {
+            records: Vec::new(),
+        };
+        let error = task.compute().unwrap_err();
+        assert_eq!(error.status, Status::InvalidArg);
+    }
 }
@@ -0,0 +1,51 @@
+//! JSON vs bincode as the wire format for `process_records` on 100K records
+//!
+//! Each benchmark starts from the encoded bytes, as a worker receiving a
+//! batch over IPC would, and includes decoding plus processing.
+//!
+//! ```text
+//! cargo bench --features bincode --bench binary_ipc
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust_core::{
+    decode_records_binary, encode_records_binary, generate_sample_data, process_records, DataRecord,
+};
+
+const RECORDS: usize = 100_000;
+
+fn bench_encode(c: &mut Criterion) {
+    let records = generate_sample_data(RECORDS);
+    let mut group = c.benchmark_group("ipc_encode/100000");
+    group.bench_function("json", |b| {
+        b.iter(|| serde_json::to_vec(black_box(&records)).unwrap())
+    });
+    group.bench_function("bincode", |b| {
+        b.iter(|| encode_records_binary(black_box(&records)).unwrap())
+    });
+    group.finish();
+}
+
+fn bench_decode_and_process(c: &mut Criterion) {
+    let records = generate_sample_data(RECORDS);
+    let json = serde_json::to_vec(&records).unwrap();
+    let binary = encode_records_binary(&records).unwrap();
+
+    let mut group = c.benchmark_group("ipc_process/100000");
+    group.bench_function("json", |b| {
+        b.iter(|| {
+            let records: Vec<DataRecord> = serde_json::from_slice(black_box(&json)).unwrap();
+            process_records(&records).unwrap()
+        })
+    });
+    group.bench_function("bincode", |b| {
+        b.iter(|| {
+            let records = decode_records_binary(black_box(&binary)).unwrap();
+            process_records(&records).unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_encode, bench_decode_and_process);
+criterion_main!(benches);
@@ -0,0 +1,84 @@
+//! Criterion benchmarks for the core record functions
+//!
+//! Every dataset comes from `generate_sample_data`, so the benchmarks see the
+//! same record shape as the bindings' `generateSampleData`: four categories,
+//! 1000 distinct values and one metadata entry per record.
+//!
+//! ```text
+//! cargo bench --bench processing
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use rust_core::{
+    aggregate_by_category, compute_percentiles, dedup_records, filter_by_category,
+    generate_sample_data, process_records, reservoir_sample_seeded, DedupStrategy,
+};
+
+const LARGE: usize = 1_000_000;
+
+fn bench_process_records(c: &mut Criterion) {
+    let mut group = c.benchmark_group("process_records");
+    for size in [10_000, 100_000, LARGE] {
+        let records = generate_sample_data(size);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &records, |b, records| {
+            b.iter(|| process_records(black_box(records)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_filter_by_category(c: &mut Criterion) {
+    let records = generate_sample_data(LARGE);
+    c.bench_function("filter_by_category/1000000", |b| {
+        b.iter(|| filter_by_category(black_box(&records), black_box("A")))
+    });
+}
+
+fn bench_aggregate_by_category(c: &mut Criterion) {
+    let records = generate_sample_data(LARGE);
+    c.bench_function("aggregate_by_category/1000000", |b| {
+        b.iter(|| aggregate_by_category(black_box(&records), &[0.5, 0.95, 0.99]).unwrap())
+    });
+}
+
+fn bench_dedup_records_keep_first(c: &mut Criterion) {
+    // Every record appears twice, so half of the input is dropped
+    let mut records = generate_sample_data(LARGE / 2);
+    records.extend(records.clone());
+    c.bench_function("dedup_records_keep_first/1000000", |b| {
+        b.iter_batched(
+            || records.clone(),
+            |records| dedup_records(records, DedupStrategy::KeepFirst),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+fn bench_compute_percentiles_1000_quantiles(c: &mut Criterion) {
+    let values: Vec<f64> = generate_sample_data(LARGE)
+        .iter()
+        .map(|r| r.value)
+        .collect();
+    let quantiles: Vec<f64> = (0..1000).map(|i| i as f64 / 999.0).collect();
+    c.bench_function("compute_percentiles_1000_quantiles/1000000", |b| {
+        b.iter(|| compute_percentiles(black_box(&values), black_box(&quantiles)).unwrap())
+    });
+}
+
+fn bench_reservoir_sample(c: &mut Criterion) {
+    let records = generate_sample_data(LARGE);
+    c.bench_function("reservoir_sample/1000000", |b| {
+        b.iter(|| reservoir_sample_seeded(black_box(&records), 1000, 42))
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_process_records,
+    bench_filter_by_category,
+    bench_aggregate_by_category,
+    bench_dedup_records_keep_first,
+    bench_compute_percentiles_1000_quantiles,
+    bench_reservoir_sample,
+);
+criterion_main!(benches);
@@ -0,0 +1,47 @@
+//! Encode/decode cost of 10K records in JSON, MessagePack and bincode
+//!
+//! ```text
+//! cargo bench --bench serialization
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust_core::{generate_sample_data, DataRecord};
+
+const RECORDS: usize = 10_000;
+
+fn bench_encode(c: &mut Criterion) {
+    let records = generate_sample_data(RECORDS);
+    let mut group = c.benchmark_group("encode");
+    group.bench_function("json", |b| {
+        b.iter(|| serde_json::to_vec(black_box(&records)).unwrap())
+    });
+    group.bench_function("msgpack", |b| {
+        b.iter(|| rmp_serde::to_vec_named(black_box(&records)).unwrap())
+    });
+    group.bench_function("bincode", |b| {
+        b.iter(|| bincode::serialize(black_box(&records)).unwrap())
+    });
+    group.finish();
+}
+
+fn bench_decode(c: &mut Criterion) {
+    let records = generate_sample_data(RECORDS);
+    let json = serde_json::to_vec(&records).unwrap();
+    let msgpack = rmp_serde::to_vec_named(&records).unwrap();
+    let bincode_bytes = bincode::serialize(&records).unwrap();
+
+    let mut group = c.benchmark_group("decode");
+    group.bench_function("json", |b| {
+        b.iter(|| serde_json::from_slice::<Vec<DataRecord>>(black_box(&json)).unwrap())
+    });
+    group.bench_function("msgpack", |b| {
+        b.iter(|| rmp_serde::from_slice::<Vec<DataRecord>>(black_box(&msgpack)).unwrap())
+    });
+    group.bench_function("bincode", |b| {
+        b.iter(|| bincode::deserialize::<Vec<DataRecord>>(black_box(&bincode_bytes)).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_encode, bench_decode);
+criterion_main!(benches);
@@ -0,0 +1,90 @@
+//! Encode sensor readings on a `no_std` device and decode them with rust-core
+//!
+//! ```text
+//! cargo run --example embedded --features postcard
+//! ```
+//!
+//! rust-core itself needs `std` (rayon, regex and the `HashMap` in
+//! `DataRecord::metadata`), so it runs on the host. The device does not link
+//! it: `device` below uses only `core`, `alloc` and `postcard`, and borrows
+//! its strings instead of owning them. postcard is not self-describing, so
+//! the bytes only have to match `DataRecord`'s field order and types: `&str`
+//! encodes like `String`, and a slice of pairs like a map.
+//!
+//! The crate is `#![no_std]` so that `device` cannot reach `std` by accident;
+//! only `main`, which stands in for the host, names it.
+
+#![no_std]
+
+extern crate alloc;
+extern crate std;
+
+mod device {
+    use alloc::vec::Vec;
+    use serde::Serialize;
+
+    /// Wire-compatible with `rust_core::DataRecord`
+    #[derive(Serialize)]
+    pub struct Reading<'a> {
+        pub id: &'a str,
+        pub value: f64,
+        pub category: &'a str,
+        pub timestamp: &'a str,
+        pub metadata: Option<&'a [(&'a str, &'a str)]>,
+    }
+
+    /// Encode a batch in the format `records_from_postcard` expects
+    pub fn encode(readings: &[Reading]) -> postcard::Result<Vec<u8>> {
+        postcard::to_allocvec(readings)
+    }
+}
+
+fn main() {
+    use device::Reading;
+    use std::println;
+
+    let tags: &[(&str, &str)] = &[("sensor", "t1"), ("unit", "celsius")];
+    let readings = [
+        Reading {
+            id: "t1-0001",
+            value: 21.5,
+            category: "temperature",
+            timestamp: "2024-01-15T10:00:00Z",
+            metadata: Some(tags),
+        },
+        Reading {
+            id: "t1-0002",
+            value: 22.0,
+            category: "temperature",
+            timestamp: "2024-01-15T10:01:00Z",
+            metadata: Some(tags),
+        },
+        Reading {
+            id: "h1-0001",
+            value: 40.0,
+            category: "humidity",
+            timestamp: "2024-01-15T10:00:00Z",
+            metadata: None,
+        },
+    ];
+
+    let bytes = device::encode(&readings).expect("readings encode");
+    println!(
+        "device: {} readings in {} bytes",
+        readings.len(),
+        bytes.len()
+    );
+
+    let records = rust_core::records_from_postcard(&bytes).expect("host decodes device bytes");
+    assert_eq!(records.len(), readings.len());
+    assert_eq!(records[0].metadata.as_ref().unwrap()["unit"], "celsius");
+    assert!(records[2].metadata.is_none());
+
+    let result = rust_core::process_records(&records).expect("valid readings");
+    println!(
+        "host: {} records, {} categories, average {:.2}",
+        result.total_processed,
+        result.categories.len(),
+        result.average_value
+    );
+}
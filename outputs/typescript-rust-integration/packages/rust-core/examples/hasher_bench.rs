@@ -0,0 +1,73 @@
+//! Time `aggregate_by_category` and `process_records` on 1M records
+//!
+//! Run once per hasher and compare:
+//!
+//! ```text
+//! cargo run --release --example hasher_bench
+//! cargo run --release --example hasher_bench --features ahash
+//! ```
+//!
+//! Measured in a single-core x86_64 Linux container, 1M records over 16
+//! categories (median of 5 runs, two builds each):
+//!
+//! ```text
+//!                         siphash    ahash
+//! aggregate_by_category   ~490 ms    ~480 ms   (within noise)
+//! process_records         ~86 ms     ~74 ms    (~15% faster)
+//! ```
+//!
+//! `aggregate_by_category` spends nearly all its time in per-category scans
+//! and only hashes once per record to find the categories, so the hasher
+//! barely matters there; `process_records` hashes every record's category.
+
+use rust_core::{aggregate_by_category, process_records, DataRecord};
+use std::time::Instant;
+
+const RECORDS: usize = 1_000_000;
+const CATEGORIES: usize = 16;
+const RUNS: usize = 5;
+
+fn median_ms(mut f: impl FnMut()) -> f64 {
+    let mut times: Vec<f64> = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed().as_secs_f64() * 1000.0
+        })
+        .collect();
+    times.sort_by(|a, b| a.total_cmp(b));
+    times[RUNS / 2]
+}
+
+fn main() {
+    let records: Vec<DataRecord> = (0..RECORDS)
+        .map(|i| {
+            DataRecord::builder()
+                .id(&format!("record_{}", i))
+                .value((i % 1000) as f64 * 10.5)
+                .category(&format!("category_{}", i % CATEGORIES))
+                .timestamp("2024-01-15T10:00:00Z")
+                .build()
+                .unwrap()
+        })
+        .collect();
+
+    let hasher = if cfg!(feature = "ahash") {
+        "ahash"
+    } else {
+        "siphash"
+    };
+    println!("hasher: {}", hasher);
+    println!(
+        "aggregate_by_category: {:.1} ms",
+        median_ms(|| {
+            aggregate_by_category(&records, &[]).unwrap();
+        })
+    );
+    println!(
+        "process_records: {:.1} ms",
+        median_ms(|| {
+            process_records(&records).unwrap();
+        })
+    );
+}
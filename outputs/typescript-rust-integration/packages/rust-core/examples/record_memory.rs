@@ -0,0 +1,87 @@
+//! Measure heap usage of 1M `DataRecord`s
+//!
+//! Run once per string representation and compare:
+//!
+//! ```text
+//! cargo run --release --example record_memory
+//! cargo run --release --example record_memory --features compact_str
+//! ```
+//!
+//! With IDs like `record_123456` and categories like `category_7`, both of
+//! which fit inline in a `CompactString`:
+//!
+//! ```text
+//!                   String       compact_str
+//! live heap bytes   163.3 MiB    141.1 MiB
+//! allocations       3,000,001    1,000,001
+//! ```
+//!
+//! `DataRecord` is 128 bytes either way, so the `Vec` buffer itself accounts
+//! for 122 MiB; the remaining allocation per record is the `timestamp`.
+
+use rust_core::DataRecord;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// System allocator that tracks live bytes and the number of allocations
+struct CountingAllocator;
+
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LIVE_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const RECORDS: usize = 1_000_000;
+
+fn main() {
+    let bytes_before = LIVE_BYTES.load(Ordering::Relaxed);
+    let allocations_before = ALLOCATIONS.load(Ordering::Relaxed);
+
+    let mut records: Vec<DataRecord> = Vec::with_capacity(RECORDS);
+    for i in 0..RECORDS {
+        let record = DataRecord::builder()
+            .id(&format!("record_{}", i))
+            .value(i as f64)
+            .category(&format!("category_{}", i % 16))
+            .timestamp("2024-01-15T10:00:00Z")
+            .build()
+            .unwrap();
+        records.push(record);
+    }
+
+    let bytes = LIVE_BYTES.load(Ordering::Relaxed) - bytes_before;
+    // Each record also formats two temporary strings, freed straight away
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations_before - 2 * RECORDS;
+
+    let repr = if cfg!(feature = "compact_str") {
+        "compact_str"
+    } else {
+        "String"
+    };
+    println!("representation: {}", repr);
+    println!(
+        "size_of::<DataRecord>(): {} bytes",
+        std::mem::size_of::<DataRecord>()
+    );
+    println!(
+        "live heap bytes: {} ({:.1} MiB)",
+        bytes,
+        bytes as f64 / 1024.0 / 1024.0
+    );
+    println!("allocations: {}", allocations);
+    assert_eq!(records.len(), RECORDS);
+}
@@ -7,22 +7,193 @@
  * Key principle: Keep this pure Rust with no platform-specific code.
  */
 
-use rayon::prelude::*;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use indexmap::IndexMap;
+use par::*;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet, VecDeque};
+
+#[cfg(all(
+    target_arch = "wasm32",
+    feature = "parallel",
+    not(feature = "no-rayon")
+))]
+compile_error!(
+    "rayon spawns OS threads, which panic in the browser; build rust-core for wasm32 \
+     with `features = [\"no-rayon\"]`"
+);
+
+/// Hasher for internal counting maps: fixed-key aHash with the `ahash`
+/// feature, std's SipHash otherwise
+///
+/// Only used for maps that never leave the crate, so public signatures and
+/// serialized output keep the std `HashMap` either way.
+#[cfg(feature = "ahash")]
+type FastHasher = std::hash::BuildHasherDefault<ahash::AHasher>;
+#[cfg(not(feature = "ahash"))]
+type FastHasher = std::collections::hash_map::RandomState;
+
+type FastHashMap<K, V> = HashMap<K, V, FastHasher>;
+type FastHashSet<T> = HashSet<T, FastHasher>;
+
+/// Parallel iteration via rayon, or plain std iterators when the `parallel`
+/// feature is off or `no-rayon` is set
+///
+/// The sequential shim mirrors the rayon method names used in this crate, so
+/// call sites read the same under both configurations.
+mod par {
+    #[cfg(all(feature = "parallel", not(feature = "no-rayon")))]
+    pub use rayon::{join, prelude::*};
+
+    #[cfg(not(all(feature = "parallel", not(feature = "no-rayon"))))]
+    pub use self::sequential::*;
+
+    #[cfg(not(all(feature = "parallel", not(feature = "no-rayon"))))]
+    mod sequential {
+        /// `par_iter`/`par_iter_mut` on slices, backed by `iter`/`iter_mut`
+        pub trait ParallelSlice<T> {
+            fn par_iter(&self) -> std::slice::Iter<'_, T>;
+            fn par_iter_mut(&mut self) -> std::slice::IterMut<'_, T>;
+        }
+
+        impl<T> ParallelSlice<T> for [T] {
+            fn par_iter(&self) -> std::slice::Iter<'_, T> {
+                self.iter()
+            }
+
+            fn par_iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+                self.iter_mut()
+            }
+        }
+
+        /// `into_par_iter`, backed by `into_iter`
+        pub trait IntoParallelIterator: IntoIterator + Sized {
+            fn into_par_iter(self) -> Self::IntoIter {
+                self.into_iter()
+            }
+        }
+
+        impl<I: IntoIterator> IntoParallelIterator for I {}
+
+        /// Run both closures one after the other
+        pub fn join<A, B, RA, RB>(a: A, b: B) -> (RA, RB)
+        where
+            A: FnOnce() -> RA,
+            B: FnOnce() -> RB,
+        {
+            (a(), b())
+        }
+    }
+}
+
+/// String type of `DataRecord::id` and `category`
+///
+/// `CompactString` with the `compact_str` feature, which keeps strings of up
+/// to 24 bytes inline instead of on the heap; `String` otherwise. Both deref
+/// to `str` and serialize identically.
+#[cfg(feature = "compact_str")]
+pub type RecordString = compact_str::CompactString;
+#[cfg(not(feature = "compact_str"))]
+pub type RecordString = String;
 
 /// A single data record for processing
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct DataRecord {
-    pub id: String,
+    /// Unique identifier of the record
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub id: RecordString,
+    /// Numeric value being measured
     pub value: f64,
-    pub category: String,
+    /// Grouping key used by the aggregations
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub category: RecordString,
+    /// RFC 3339 timestamp of when the value was recorded
     pub timestamp: String,
+    /// Free-form string tags
     pub metadata: Option<HashMap<String, String>>,
 }
 
+impl DataRecord {
+    /// Start building a record field by field
+    ///
+    /// ```
+    /// use rust_core::DataRecord;
+    ///
+    /// let record = DataRecord::builder()
+    ///     .id("42")
+    ///     .value(99.5)
+    ///     .category("A")
+    ///     .timestamp("2024-01-15T10:00:00Z")
+    ///     .metadata_entry("region", "eu")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(record.metadata.unwrap()["region"], "eu");
+    /// ```
+    pub fn builder() -> DataRecordBuilder {
+        DataRecordBuilder::default()
+    }
+}
+
+/// Fluent builder for `DataRecord`
+///
+/// The default builder has empty `id`, `category` and `timestamp`, so `build`
+/// fails until they are set.
+#[derive(Debug, Clone, Default)]
+pub struct DataRecordBuilder {
+    record: DataRecord,
+}
+
+impl DataRecordBuilder {
+    pub fn id(mut self, id: &str) -> Self {
+        self.record.id = id.into();
+        self
+    }
+
+    pub fn value(mut self, value: f64) -> Self {
+        self.record.value = value;
+        self
+    }
+
+    pub fn category(mut self, category: &str) -> Self {
+        self.record.category = category.into();
+        self
+    }
+
+    pub fn timestamp(mut self, timestamp: &str) -> Self {
+        self.record.timestamp = timestamp.to_string();
+        self
+    }
+
+    /// Add one metadata key/value pair, creating the map if needed
+    pub fn metadata_entry(mut self, key: &str, value: &str) -> Self {
+        self.record
+            .metadata
+            .get_or_insert_with(HashMap::new)
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Finish the record, applying the same checks as lenient `validate_record`
+    pub fn build(self) -> Result<DataRecord, ProcessingError> {
+        match validate_record(&self.record, ValidationMode::Lenient) {
+            Ok(()) => Ok(self.record),
+            Err(error) => Err(ProcessingError::ValidationFailed {
+                errors: vec![error],
+            }),
+        }
+    }
+}
+
 /// Result of processing a batch of records
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ProcessResult {
     pub total_processed: usize,
     pub total_value: f64,
@@ -30,10 +201,75 @@ pub struct ProcessResult {
     pub min_value: f64,
     pub max_value: f64,
     pub categories: HashMap<String, usize>,
+    /// Records left out of the statistics by `ProcessingConfig::outlier_cap`
+    #[serde(default)]
+    pub capped_count: usize,
+}
+
+impl ProcessResult {
+    /// Combine another result into this one
+    ///
+    /// Counts, totals and per-category counts are summed and min/max widened.
+    /// `average_value` is recomputed as `total_value / total_processed`, so the
+    /// merged average is exact rather than an average of averages. Merging
+    /// with a result that processed nothing leaves the other side unchanged
+    /// apart from `capped_count`, which is always summed.
+    pub fn merge(&mut self, other: &ProcessResult) {
+        self.capped_count += other.capped_count;
+        if other.total_processed == 0 {
+            return;
+        }
+        if self.total_processed == 0 {
+            let capped_count = self.capped_count;
+            *self = other.clone();
+            self.capped_count = capped_count;
+            return;
+        }
+
+        self.total_processed += other.total_processed;
+        self.total_value += other.total_value;
+        self.min_value = self.min_value.min(other.min_value);
+        self.max_value = self.max_value.max(other.max_value);
+        self.average_value = self.total_value / self.total_processed as f64;
+        for (category, count) in &other.categories {
+            *self.categories.entry(category.clone()).or_insert(0) += count;
+        }
+    }
+}
+
+/// Incrementally maintained statistics for streaming or chunked input
+///
+/// Keeps running count, sum, sum of squares, min, max and per-category counts
+/// so `finalize` is O(1) regardless of how many records were added. The sums
+/// are compensated, so removing a large value does not take the precision of
+/// the remaining small ones with it. A multiset of seen values is also kept so
+/// `remove_record` can restore exact min/max. The state is therefore not
+/// bounded: it grows with the number of distinct values added, up to one entry
+/// per record, and a checkpoint serializes every one of them.
+///
+/// Percentiles are deliberately not tracked: computing them would require
+/// materializing and sorting every value, defeating the purpose of the
+/// accumulator. Use `process_records` on the full data when they are needed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatsAccumulator {
+    count: usize,
+    sum: f64,
+    /// Low-order bits rounded off `sum`; see `add_compensated`
+    #[serde(default)]
+    sum_compensation: f64,
+    sum_of_squares: f64,
+    #[serde(default)]
+    sum_of_squares_compensation: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+    categories: HashMap<String, usize>,
+    /// Order-preserving f64 bit patterns mapped to occurrence counts
+    values: BTreeMap<u64, usize>,
 }
 
 /// Statistics for a specific category
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CategoryStats {
     pub category: String,
     pub count: usize,
@@ -41,134 +277,1106 @@ pub struct CategoryStats {
     pub average_value: f64,
     pub min_value: f64,
     pub max_value: f64,
+    /// Sample variance of values (0.0 with fewer than two records)
+    pub variance: f64,
+    /// Sample standard deviation of values (0.0 with fewer than two records)
+    pub std_dev: f64,
+    /// `(quantile, value)` pairs requested via `aggregate_by_category`
+    #[serde(default)]
+    pub percentiles: Vec<(f64, f64)>,
+}
+
+/// A partial update applied to a record by `patch_records_by_id`
+///
+/// `None` fields are left unchanged. `metadata_updates` is merged into the
+/// record's existing metadata rather than replacing it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DataRecordPatch {
+    pub value: Option<f64>,
+    pub category: Option<String>,
+    pub metadata_updates: Option<HashMap<String, String>>,
+}
+
+/// Type expectations for metadata values, checked by `validate_metadata_schema`
+///
+/// Every listed key is required to be present in the record's metadata.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetadataSchema {
+    /// Keys whose values must parse as `f64`
+    pub required_numeric: Vec<String>,
+    /// Keys whose values must be `"true"` or `"false"`
+    pub required_boolean: Vec<String>,
+    /// Keys whose values must not be empty
+    pub required_non_empty: Vec<String>,
+}
+
+/// One row of a category × time-bucket heatmap
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeatmapRow {
+    pub time_bucket: String,
+    pub category_averages: HashMap<String, f64>,
+}
+
+/// Value used for heatmap cells where a category has no records in a bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MissingCellFill {
+    #[default]
+    Zero,
+    NaN,
+}
+
+impl MissingCellFill {
+    fn value(self) -> f64 {
+        match self {
+            MissingCellFill::Zero => 0.0,
+            MissingCellFill::NaN => f64::NAN,
+        }
+    }
+}
+
+/// A serializable set of record filters, combined with AND logic
+///
+/// Every `None` field is ignored, so `FilterSpec::default()` keeps all
+/// records. Value bounds are inclusive.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FilterSpec {
+    pub min_value: Option<f64>,
+    pub max_value: Option<f64>,
+    /// Keep only records in one of these categories
+    pub categories: Option<Vec<String>>,
+    /// Keep only records whose metadata has every one of these key/value pairs
+    pub metadata_filters: Option<HashMap<String, String>>,
+    /// Drop records in any of these categories
+    pub exclude_categories: Option<Vec<String>>,
+}
+
+impl FilterSpec {
+    /// Serialize the spec to a JSON string
+    pub fn to_json(&self) -> Result<String, ProcessingError> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Parse a spec from a JSON string; omitted fields become `None`
+    pub fn from_json(s: &str) -> Result<Self, ProcessingError> {
+        Ok(serde_json::from_str(s)?)
+    }
+
+    /// Whether a single record passes every configured filter
+    pub fn matches(&self, record: &DataRecord) -> bool {
+        if self.min_value.is_some_and(|min| record.value < min) {
+            return false;
+        }
+        if self.max_value.is_some_and(|max| record.value > max) {
+            return false;
+        }
+        if let Some(categories) = &self.categories {
+            if !categories.iter().any(|c| c == record.category.as_str()) {
+                return false;
+            }
+        }
+        if let Some(excluded) = &self.exclude_categories {
+            if excluded.iter().any(|c| c == record.category.as_str()) {
+                return false;
+            }
+        }
+        if let Some(filters) = &self.metadata_filters {
+            let metadata = record.metadata.as_ref();
+            return filters
+                .iter()
+                .all(|(k, v)| metadata.and_then(|m| m.get(k)) == Some(v));
+        }
+        true
+    }
+}
+
+/// One step of a `TransformPipeline`
+///
+/// Serialized as `{"op": "scale", "params": {"multiplier": 2.0}}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", content = "params", rename_all = "snake_case")]
+pub enum TransformStep {
+    /// Multiply the value
+    Scale { multiplier: f64 },
+    /// Clip the value into `[lower, upper]`
+    Clamp { lower: f64, upper: f64 },
+    /// Add a constant to the value
+    AddOffset { offset: f64 },
+    /// Drop records whose metadata does not have `key` set to `value`
+    MetadataFilter { key: String, value: String },
+}
+
+/// An ordered chain of record transforms applied in a single pass
+///
+/// Steps run in the order they were added, so `scale` then `clamp` differs
+/// from `clamp` then `scale`. Serializes as a JSON array of `TransformStep`s.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TransformPipeline {
+    steps: Vec<TransformStep>,
+}
+
+impl TransformPipeline {
+    /// Start an empty pipeline, which passes records through unchanged
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a pipeline from a JSON array of `{op, params}` objects
+    pub fn from_json(s: &str) -> Result<Self, ProcessingError> {
+        Ok(serde_json::from_str(s)?)
+    }
+
+    /// Append a multiplication of the value
+    pub fn scale(mut self, multiplier: f64) -> Self {
+        self.steps.push(TransformStep::Scale { multiplier });
+        self
+    }
+
+    /// Append a clip into `[lower, upper]`; with `lower > upper` values end up at `upper`
+    pub fn clamp(mut self, lower: f64, upper: f64) -> Self {
+        self.steps.push(TransformStep::Clamp { lower, upper });
+        self
+    }
+
+    /// Append an addition to the value
+    pub fn add_offset(mut self, offset: f64) -> Self {
+        self.steps.push(TransformStep::AddOffset { offset });
+        self
+    }
+
+    /// Append a filter keeping only records whose metadata has `key` == `value`
+    pub fn apply_metadata_filter(mut self, key: &str, value: &str) -> Self {
+        self.steps.push(TransformStep::MetadataFilter {
+            key: key.to_string(),
+            value: value.to_string(),
+        });
+        self
+    }
+
+    /// The steps in application order
+    pub fn steps(&self) -> &[TransformStep] {
+        &self.steps
+    }
+
+    /// Run every step on one record, or return `None` if a filter drops it
+    pub fn apply(&self, mut record: DataRecord) -> Option<DataRecord> {
+        for step in &self.steps {
+            match step {
+                TransformStep::Scale { multiplier } => record.value *= multiplier,
+                TransformStep::Clamp { lower, upper } => {
+                    record.value = record.value.max(*lower).min(*upper)
+                }
+                TransformStep::AddOffset { offset } => record.value += offset,
+                TransformStep::MetadataFilter { key, value } => {
+                    let metadata = record.metadata.as_ref();
+                    if metadata.and_then(|m| m.get(key)) != Some(value) {
+                        return None;
+                    }
+                }
+            }
+        }
+        Some(record)
+    }
+
+    /// Snapshot the pipeline as a standalone `Send + Sync` closure
+    ///
+    /// The closure returns `None` for records removed by a metadata filter.
+    pub fn build(&self) -> impl Fn(DataRecord) -> Option<DataRecord> + Send + Sync {
+        let pipeline = self.clone();
+        move |record| pipeline.apply(record)
+    }
+}
+
+/// A field and direction to sort records by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortKey {
+    ValueAsc,
+    ValueDesc,
+    CategoryAsc,
+    CategoryDesc,
+    TimestampAsc,
+    TimestampDesc,
+    IdAsc,
+    IdDesc,
+}
+
+impl std::str::FromStr for SortKey {
+    type Err = ProcessingError;
+
+    /// Parse a variant name such as `"ValueDesc"`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(s.to_string()))
+            .map_err(|_| ProcessingError::InvalidArgument(format!("Unknown sort key '{}'", s)))
+    }
+}
+
+/// Which record `dedup_records` keeps when several share an ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DedupStrategy {
+    KeepFirst,
+    KeepLast,
+    /// Keep the duplicate with the highest value (the first, on ties)
+    KeepHighestValue,
+}
+
+impl std::str::FromStr for DedupStrategy {
+    type Err = ProcessingError;
+
+    /// Parse a variant name such as `"KeepLast"`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(s.to_string())).map_err(|_| {
+            ProcessingError::InvalidArgument(format!("Unknown dedup strategy '{}'", s))
+        })
+    }
+}
+
+/// A record field that `compute_frequency_distribution` counts and
+/// `group_by_fields` groups by
+///
+/// Bindings take it as a string: `"category"`, `"timestamp"`, or otherwise
+/// the name of a metadata key, following `enrich_records`' `key_field`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FieldName {
+    Category,
+    /// The UTC calendar day (`YYYY-MM-DD`); unparseable timestamps count as missing
+    Timestamp,
+    MetadataKey(String),
+}
+
+impl FieldName {
+    /// The field's value on `record`, or None if the record doesn't have it
+    pub fn extract(&self, record: &DataRecord) -> Option<String> {
+        match self {
+            FieldName::Category => Some(record.category.to_string()),
+            FieldName::Timestamp => {
+                parse_timestamp(&record.timestamp).map(|dt| dt.date_naive().to_string())
+            }
+            FieldName::MetadataKey(key) => record.metadata.as_ref()?.get(key).cloned(),
+        }
+    }
+
+    /// The string this field parses from
+    pub fn name(&self) -> &str {
+        match self {
+            FieldName::Category => "category",
+            FieldName::Timestamp => "timestamp",
+            FieldName::MetadataKey(key) => key,
+        }
+    }
+}
+
+impl std::str::FromStr for FieldName {
+    type Err = ProcessingError;
+
+    /// Parse `"category"`, `"timestamp"` or a metadata key name
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err(ProcessingError::InvalidArgument(
+                "Field name must not be empty".to_string(),
+            )),
+            "category" => Ok(FieldName::Category),
+            "timestamp" => Ok(FieldName::Timestamp),
+            key => Ok(FieldName::MetadataKey(key.to_string())),
+        }
+    }
+}
+
+/// How `compute_percentile_by_group` groups records
+///
+/// Bindings take it as a string: `"category"`, or otherwise the name of a
+/// metadata key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GroupKey {
+    Category,
+    MetadataKey(String),
+}
+
+impl GroupKey {
+    /// The group `record` belongs to, or None if it lacks the metadata key
+    fn group_of<'r>(&self, record: &'r DataRecord) -> Option<&'r str> {
+        match self {
+            GroupKey::Category => Some(record.category.as_str()),
+            GroupKey::MetadataKey(key) => record.metadata.as_ref()?.get(key).map(String::as_str),
+        }
+    }
+}
+
+impl std::str::FromStr for GroupKey {
+    type Err = ProcessingError;
+
+    /// Parse `"category"` or a metadata key name
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err(ProcessingError::InvalidArgument(
+                "Group key must not be empty".to_string(),
+            )),
+            "category" => Ok(GroupKey::Category),
+            key => Ok(GroupKey::MetadataKey(key.to_string())),
+        }
+    }
+}
+
+/// Which unmatched records `join_records` keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JoinStrategy {
+    /// Only records whose ID appears on both sides
+    Inner,
+    /// Every left record, merged where a right match exists
+    LeftOuter,
+    /// Every right record, merged where a left match exists
+    RightOuter,
+}
+
+/// Which record `merge_datasets` keeps when both sides share an ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConflictResolution {
+    KeepPrimary,
+    KeepSecondary,
+    /// Keep the primary record, adding secondary metadata keys it lacks
+    MergeMetadata,
+    /// Keep whichever record has the larger value (the primary, on ties)
+    UseHighestValue,
+}
+
+/// Which check a record failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationErrorKind {
+    /// A required field is empty or out of range
+    #[default]
+    InvalidField,
+    /// The timestamp is not RFC 3339 (strict validation only)
+    InvalidTimestamp,
+    /// A caller-supplied `ValidationRule` failed
+    RuleViolation,
 }
 
 /// Validation error details
 #[derive(Debug, Clone)]
 pub struct ValidationError {
     pub record_id: String,
+    /// The record field that failed: `"id"`, `"value"`, `"category"`,
+    /// `"timestamp"` or (for rules only) `"metadata"`
+    pub field: String,
     pub message: String,
+    pub kind: ValidationErrorKind,
 }
 
 impl ValidationError {
-    pub fn new(record_id: String, message: String) -> Self {
-        Self { record_id, message }
+    pub fn new(record_id: String, field: &str, message: String) -> Self {
+        Self {
+            record_id,
+            field: field.to_string(),
+            message,
+            kind: ValidationErrorKind::InvalidField,
+        }
+    }
+
+    /// Error for a timestamp that failed `validate_timestamp_format`
+    pub fn invalid_timestamp(record_id: String, message: String) -> Self {
+        Self {
+            record_id,
+            field: "timestamp".to_string(),
+            message,
+            kind: ValidationErrorKind::InvalidTimestamp,
+        }
+    }
+
+    /// Error for a record that failed a `ValidationRule` on `field`
+    pub fn rule_violation(record_id: String, field: &str, message: String) -> Self {
+        Self {
+            record_id,
+            field: field.to_string(),
+            message,
+            kind: ValidationErrorKind::RuleViolation,
+        }
+    }
+}
+
+/// A domain-specific check for `validate_records_with_rules`
+///
+/// Deserializes from externally tagged JSON such as `{"MaxValue": 1000000}`
+/// or `{"AllowedCategories": ["A", "B"]}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ValidationRule {
+    /// Value must be at most this
+    MaxValue(f64),
+    /// Value must be at least this
+    MinValue(f64),
+    /// ID must match this regular expression (unanchored; use `^`/`$`)
+    IdPattern(String),
+    /// Category must be one of these
+    AllowedCategories(Vec<String>),
+    /// Metadata must contain this key
+    RequiredMetadataKey(String),
+}
+
+impl ValidationRule {
+    /// The record field this rule checks
+    pub fn field(&self) -> &'static str {
+        match self {
+            ValidationRule::MaxValue(_) | ValidationRule::MinValue(_) => "value",
+            ValidationRule::IdPattern(_) => "id",
+            ValidationRule::AllowedCategories(_) => "category",
+            ValidationRule::RequiredMetadataKey(_) => "metadata",
+        }
+    }
+}
+
+/// How thoroughly `validate_record` checks a record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationMode {
+    /// Only require a non-empty timestamp
+    #[default]
+    Lenient,
+    /// Also require the timestamp to pass `validate_timestamp_format`
+    Strict,
+}
+
+/// Outcome of validating one record in a batch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordValidation {
+    pub id: String,
+    pub valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Per-record validation results for a batch, in input order
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct BatchValidation {
+    pub results: Vec<RecordValidation>,
+}
+
+impl BatchValidation {
+    /// Number of records that passed validation
+    pub fn valid_count(&self) -> usize {
+        self.results.iter().filter(|r| r.valid).count()
+    }
+
+    /// Number of records that failed validation
+    pub fn error_count(&self) -> usize {
+        self.results.len() - self.valid_count()
+    }
+}
+
+/// Differences between two record snapshots, matched by `id`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecordDiff {
+    /// Records whose ID only appears in the new snapshot
+    pub added: Vec<DataRecord>,
+    /// Records whose ID only appears in the old snapshot
+    pub removed: Vec<DataRecord>,
+    /// `(old, new)` pairs whose value, category or metadata differ
+    pub changed: Vec<(DataRecord, DataRecord)>,
+    pub unchanged_count: usize,
+}
+
+/// One page of records from offset pagination
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PaginatedResult {
+    pub items: Vec<DataRecord>,
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+    pub has_next: bool,
+    pub has_prev: bool,
+}
+
+/// One page of records from cursor pagination
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CursorPaginatedResult {
+    pub items: Vec<DataRecord>,
+    /// Opaque cursor to pass as `after_id` for the next page
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+/// Tuning options for `process_records_with_config`
+///
+/// Missing fields take their default when deserialized.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProcessingConfig {
+    /// Sum values with Kahan compensation once the dataset exceeds
+    /// `parallel_threshold` records
+    pub use_kahan: bool,
+    /// Largest dataset summed with the parallel pairwise reduction when
+    /// `use_kahan` is set
+    pub parallel_threshold: usize,
+    /// Leave out records whose value is more than this many standard
+    /// deviations from the mean; must be at least 1.0
+    pub outlier_cap: Option<f64>,
+}
+
+impl Default for ProcessingConfig {
+    fn default() -> Self {
+        ProcessingConfig {
+            use_kahan: true,
+            parallel_threshold: 100_000,
+            outlier_cap: None,
+        }
     }
 }
 
+/// Errors returned by processing functions
+#[derive(Debug, thiserror::Error)]
+pub enum ProcessingError {
+    /// The input contained no records
+    #[error("Cannot process empty record set")]
+    EmptyInput,
+    /// One or more records failed `validate_record`
+    #[error("Validation errors: {}", format_validation_errors(errors))]
+    ValidationFailed { errors: Vec<ValidationError> },
+    /// Input JSON could not be deserialized
+    #[error("Parse error: {source}")]
+    ParseError {
+        #[from]
+        source: serde_json::Error,
+    },
+    /// An argument was outside the range the function accepts
+    #[error("{0}")]
+    InvalidArgument(String),
+    /// An accumulated value exceeded the range of `f64`
+    #[error("Arithmetic overflow: accumulated value is not finite")]
+    Overflow,
+    /// Two records share an ID where IDs must be unique
+    #[error("Duplicate record ID: {0}")]
+    DuplicateId(String),
+}
+
+fn format_validation_errors(errors: &[ValidationError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("Record {}: {}", e.record_id, e.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 /// Validate a single record
 ///
 /// Checks:
-/// - Value is positive
+/// - Value is positive and not NaN
 /// - Category is not empty
 /// - ID is not empty
 /// - Timestamp is not empty
-pub fn validate_record(record: &DataRecord) -> Result<(), ValidationError> {
+pub fn validate_record(record: &DataRecord, mode: ValidationMode) -> Result<(), ValidationError> {
     if record.id.is_empty() {
         return Err(ValidationError::new(
-            record.id.clone(),
+            record.id.to_string(),
+            "id",
             "ID cannot be empty".to_string(),
         ));
     }
 
-    if record.value < 0.0 {
+    if record.value.is_nan() || record.value < 0.0 {
         return Err(ValidationError::new(
-            record.id.clone(),
+            record.id.to_string(),
+            "value",
             format!("Value must be positive, got {}", record.value),
         ));
     }
 
     if record.category.is_empty() {
         return Err(ValidationError::new(
-            record.id.clone(),
+            record.id.to_string(),
+            "category",
             "Category cannot be empty".to_string(),
         ));
     }
 
     if record.timestamp.is_empty() {
         return Err(ValidationError::new(
-            record.id.clone(),
+            record.id.to_string(),
+            "timestamp",
             "Timestamp cannot be empty".to_string(),
         ));
     }
 
+    if mode == ValidationMode::Strict {
+        if let Err(e) = validate_timestamp_format(&record.timestamp) {
+            return Err(ValidationError::invalid_timestamp(
+                record.id.to_string(),
+                e.to_string(),
+            ));
+        }
+    }
+
     Ok(())
 }
 
-/// Process a batch of records and compute statistics
+/// Parse a timestamp, accepting RFC 3339 and `YYYY-MM-DDTHH:MM:SSZ`
 ///
-/// This is the main computation function that demonstrates Rust's performance
-/// advantages. It uses parallel processing to handle large datasets efficiently.
-pub fn process_records(records: &[DataRecord]) -> Result<ProcessResult, String> {
-    if records.is_empty() {
-        return Err("Cannot process empty record set".to_string());
+/// Out-of-range components (such as month 13) are rejected.
+pub fn validate_timestamp_format(ts: &str) -> Result<DateTime<Utc>, ProcessingError> {
+    if let Some(parsed) = parse_timestamp(ts) {
+        return Ok(parsed);
     }
+    NaiveDateTime::parse_from_str(ts, "%Y-%m-%dT%H:%M:%SZ")
+        .map(|naive| naive.and_utc())
+        .map_err(|_| {
+            ProcessingError::InvalidArgument(format!(
+                "Invalid timestamp '{}': expected RFC 3339 or YYYY-MM-DDTHH:MM:SSZ",
+                ts
+            ))
+        })
+}
 
-    // Validate all records first
-    let validation_errors: Vec<_> = records
-        .par_iter()
-        .filter_map(|record| validate_record(record).err())
-        .collect();
+/// Validate a record's metadata against a schema
+///
+/// Returns one message per violation; an empty vec means the record conforms.
+pub fn validate_metadata_schema(record: &DataRecord, schema: &MetadataSchema) -> Vec<String> {
+    let mut errors = Vec::new();
+    let lookup = |key: &str| record.metadata.as_ref().and_then(|m| m.get(key));
 
-    if !validation_errors.is_empty() {
-        let error_msg = validation_errors
-            .iter()
-            .map(|e| format!("Record {}: {}", e.record_id, e.message))
-            .collect::<Vec<_>>()
-            .join("; ");
-        return Err(format!("Validation errors: {}", error_msg));
+    for key in &schema.required_numeric {
+        match lookup(key) {
+            None => errors.push(format!("Missing required metadata key '{}'", key)),
+            Some(v) if v.parse::<f64>().is_err() => errors.push(format!(
+                "Metadata key '{}' must be numeric, got '{}'",
+                key, v
+            )),
+            Some(_) => {}
+        }
     }
 
-    // Compute statistics in parallel
-    let total_processed = records.len();
-
-    let total_value: f64 = records.par_iter().map(|r| r.value).sum();
-
-    let values: Vec<f64> = records.par_iter().map(|r| r.value).collect();
-    let min_value = values
-        .par_iter()
-        .min_by(|a, b| a.partial_cmp(b).unwrap())
-        .copied()
-        .unwrap_or(0.0);
-    let max_value = values
-        .par_iter()
-        .max_by(|a, b| a.partial_cmp(b).unwrap())
-        .copied()
-        .unwrap_or(0.0);
-
-    let average_value = total_value / total_processed as f64;
+    for key in &schema.required_boolean {
+        match lookup(key) {
+            None => errors.push(format!("Missing required metadata key '{}'", key)),
+            Some(v) if v != "true" && v != "false" => errors.push(format!(
+                "Metadata key '{}' must be \"true\" or \"false\", got '{}'",
+                key, v
+            )),
+            Some(_) => {}
+        }
+    }
 
-    // Count by category
-    let mut categories: HashMap<String, usize> = HashMap::new();
-    for record in records {
-        *categories.entry(record.category.clone()).or_insert(0) += 1;
+    for key in &schema.required_non_empty {
+        match lookup(key) {
+            None => errors.push(format!("Missing required metadata key '{}'", key)),
+            Some(v) if v.is_empty() => {
+                errors.push(format!("Metadata key '{}' cannot be empty", key))
+            }
+            Some(_) => {}
+        }
     }
 
-    Ok(ProcessResult {
-        total_processed,
-        total_value,
-        average_value,
-        min_value,
-        max_value,
-        categories,
-    })
+    errors
 }
 
-/// Filter records by category
+/// Validate every record in parallel
 ///
-/// Returns all records matching the specified category.
-/// Uses parallel iteration for performance on large datasets.
-pub fn filter_by_category(records: &[DataRecord], category: &str) -> Vec<DataRecord> {
-    records
+/// Results keep the input order, so callers can zip them with their records.
+pub fn validate_records_batch(records: &[DataRecord]) -> BatchValidation {
+    let results = records
         .par_iter()
-        .filter(|record| record.category == category)
-        .cloned()
-        .collect()
+        .map(|record| {
+            let error = validate_record(record, ValidationMode::Lenient)
+                .err()
+                .map(|e| e.message);
+            RecordValidation {
+                id: record.id.to_string(),
+                valid: error.is_none(),
+                error,
+            }
+        })
+        .collect();
+
+    BatchValidation { results }
 }
 
-/// Filter records by value threshold
-///
-/// Returns all records with value >= min_value.
+/// Message for a record that failed `rule`
+fn rule_failure_message(rule: &ValidationRule, record: &DataRecord) -> String {
+    match rule {
+        ValidationRule::MaxValue(max) => {
+            format!("Value must be at most {}, got {}", max, record.value)
+        }
+        ValidationRule::MinValue(min) => {
+            format!("Value must be at least {}, got {}", min, record.value)
+        }
+        ValidationRule::IdPattern(pattern) => {
+            format!("ID '{}' does not match '{}'", record.id, pattern)
+        }
+        ValidationRule::AllowedCategories(_) => {
+            format!("Category '{}' is not allowed", record.category)
+        }
+        ValidationRule::RequiredMetadataKey(key) => {
+            format!("Missing required metadata key '{}'", key)
+        }
+    }
+}
+
+/// Check every record against caller-supplied rules
+///
+/// Rules run in the order given and only the first failure per record is
+/// reported, as `(index, error)` pairs in input order. Returns
+/// `InvalidArgument` if an `IdPattern` is not a valid regular expression.
+pub fn validate_records_with_rules(
+    records: &[DataRecord],
+    rules: &[ValidationRule],
+) -> Result<Vec<(usize, ValidationError)>, ProcessingError> {
+    // Compile each pattern once rather than per record
+    let patterns: Vec<Option<regex::Regex>> = rules
+        .iter()
+        .map(|rule| match rule {
+            ValidationRule::IdPattern(pattern) => {
+                regex::Regex::new(pattern).map(Some).map_err(|e| {
+                    ProcessingError::InvalidArgument(format!(
+                        "Invalid ID pattern '{}': {}",
+                        pattern, e
+                    ))
+                })
+            }
+            _ => Ok(None),
+        })
+        .collect::<Result<_, _>>()?;
+
+    // (field, message) of the first failing rule
+    let check = |record: &DataRecord| -> Option<(&'static str, String)> {
+        rules.iter().zip(&patterns).find_map(|(rule, pattern)| {
+            let failed = match (rule, pattern) {
+                (ValidationRule::MaxValue(max), _) => record.value > *max,
+                (ValidationRule::MinValue(min), _) => record.value < *min,
+                (_, Some(regex)) => !regex.is_match(&record.id),
+                (ValidationRule::AllowedCategories(allowed), _) => !allowed
+                    .iter()
+                    .any(|c| c.as_str() == record.category.as_str()),
+                (ValidationRule::RequiredMetadataKey(key), _) => !record
+                    .metadata
+                    .as_ref()
+                    .is_some_and(|m| m.contains_key(key)),
+                (ValidationRule::IdPattern(_), None) => unreachable!("patterns are compiled"),
+            };
+            failed.then(|| (rule.field(), rule_failure_message(rule, record)))
+        })
+    };
+
+    let failures: Vec<Option<ValidationError>> = records
+        .par_iter()
+        .map(|record| {
+            check(record).map(|(field, message)| {
+                ValidationError::rule_violation(record.id.to_string(), field, message)
+            })
+        })
+        .collect();
+
+    Ok(failures
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, error)| error.map(|e| (index, e)))
+        .collect())
+}
+
+/// Process a batch of records and compute statistics
+///
+/// This is the main computation function that demonstrates Rust's performance
+/// advantages. It uses parallel processing to handle large datasets efficiently.
+pub fn process_records(records: &[DataRecord]) -> Result<ProcessResult, ProcessingError> {
+    process_records_with_config(records, &ProcessingConfig::default())
+}
+
+/// Process records with explicit summation and outlier settings
+///
+/// `total_value` is summed with sequential Kahan summation for datasets
+/// larger than `config.parallel_threshold` when `config.use_kahan` is set,
+/// and with a parallel pairwise reduction otherwise.
+///
+/// With `config.outlier_cap` set to `k`, a first pass computes the mean and
+/// population standard deviation of all values, and records with
+/// `|value - mean| > k * std_dev` are then left out of every statistic,
+/// including `total_processed` and `categories`, and counted in
+/// `capped_count` instead. This is a single pass, not repeated until no
+/// outliers remain. `k` must be finite and at least 1.0, which guarantees
+/// that some records are kept; anything else returns `InvalidArgument`.
+pub fn process_records_with_config(
+    records: &[DataRecord],
+    config: &ProcessingConfig,
+) -> Result<ProcessResult, ProcessingError> {
+    process_borrowed_records(records, config)
+}
+
+/// `process_records_with_config` over owned or borrowed records, so groups
+/// of `&DataRecord` can be processed without cloning them
+fn process_borrowed_records<R>(
+    records: &[R],
+    config: &ProcessingConfig,
+) -> Result<ProcessResult, ProcessingError>
+where
+    R: std::borrow::Borrow<DataRecord> + Sync,
+{
+    if let Some(cap) = config.outlier_cap {
+        if !(cap.is_finite() && cap >= 1.0) {
+            return Err(ProcessingError::InvalidArgument(format!(
+                "outlier_cap must be a finite number of at least 1.0, got {}",
+                cap
+            )));
+        }
+    }
+    if records.is_empty() {
+        return Err(ProcessingError::EmptyInput);
+    }
+
+    // Validate all records first
+    let validation_errors: Vec<_> = records
+        .par_iter()
+        .filter_map(|record| validate_record(record.borrow(), ValidationMode::Lenient).err())
+        .collect();
+
+    if !validation_errors.is_empty() {
+        return Err(ProcessingError::ValidationFailed {
+            errors: validation_errors,
+        });
+    }
+
+    let result = match config.outlier_cap {
+        Some(cap) => {
+            let kept = without_outliers(records, cap);
+            let mut result = summarize_records(&kept, config);
+            result.capped_count = records.len() - kept.len();
+            result
+        }
+        None => summarize_records(records, config),
+    };
+    // Kahan turns an overflow into NaN (inf - inf), so check both
+    if !result.total_value.is_finite() && records.iter().all(|r| r.borrow().value.is_finite()) {
+        return Err(ProcessingError::Overflow);
+    }
+
+    Ok(result)
+}
+
+/// Records within `cap` population standard deviations of the mean
+///
+/// If the mean or deviation is not finite, nothing compares as an outlier
+/// and every record is kept.
+fn without_outliers<R>(records: &[R], cap: f64) -> Vec<&DataRecord>
+where
+    R: std::borrow::Borrow<DataRecord> + Sync,
+{
+    let values: Vec<f64> = records.par_iter().map(|r| r.borrow().value).collect();
+    let n = values.len() as f64;
+    let mean = sum_values_pairwise(&values) / n;
+    let squared_deviations: Vec<f64> = values.par_iter().map(|v| (v - mean).powi(2)).collect();
+    let limit = cap * (sum_values_pairwise(&squared_deviations) / n).sqrt();
+
+    records
+        .par_iter()
+        .map(|r| r.borrow())
+        .filter(|r| !limit.is_finite() || (r.value - mean).abs() <= limit)
+        .collect()
+}
+
+/// Process the valid records and report the invalid ones instead of failing
+///
+/// Statistics cover only records that pass lenient `validate_record`; every
+/// failure is returned alongside, in input order. With no valid records the
+/// result has `total_processed = 0`, zero totals and min/max, and a NaN
+/// average. Overflow is not an error here: `total_value` is left infinite.
+pub fn process_records_partial(records: &[DataRecord]) -> (ProcessResult, Vec<ValidationError>) {
+    let checked: Vec<Result<&DataRecord, ValidationError>> = records
+        .par_iter()
+        .map(|record| validate_record(record, ValidationMode::Lenient).map(|()| record))
+        .collect();
+
+    let mut valid = Vec::with_capacity(checked.len());
+    let mut errors = Vec::new();
+    for result in checked {
+        match result {
+            Ok(record) => valid.push(record),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    (
+        summarize_records(&valid, &ProcessingConfig::default()),
+        errors,
+    )
+}
+
+/// Compute `ProcessResult` statistics over already-validated records
+fn summarize_records<R>(records: &[R], config: &ProcessingConfig) -> ProcessResult
+where
+    R: std::borrow::Borrow<DataRecord> + Sync,
+{
+    // Compute statistics in parallel
+    let total_processed = records.len();
+
+    let values: Vec<f64> = records.par_iter().map(|r| r.borrow().value).collect();
+
+    let total_value = if config.use_kahan && values.len() > config.parallel_threshold {
+        sum_values_kahan(&values)
+    } else {
+        sum_values_pairwise(&values)
+    };
+
+    let min_value = values
+        .par_iter()
+        .min_by(|a, b| a.total_cmp(b))
+        .copied()
+        .unwrap_or(0.0);
+    let max_value = values
+        .par_iter()
+        .max_by(|a, b| a.total_cmp(b))
+        .copied()
+        .unwrap_or(0.0);
+
+    let average_value = total_value / total_processed as f64;
+
+    // Count by category
+    let mut counts: FastHashMap<&str, usize> = FastHashMap::default();
+    for record in records {
+        *counts.entry(record.borrow().category.as_str()).or_insert(0) += 1;
+    }
+    let categories: HashMap<String, usize> = counts
+        .into_iter()
+        .map(|(category, count)| (category.to_string(), count))
+        .collect();
+
+    ProcessResult {
+        total_processed,
+        total_value,
+        average_value,
+        min_value,
+        max_value,
+        categories,
+        capped_count: 0,
+    }
+}
+
+/// Sum values with Kahan compensated summation
+///
+/// Carries the low-order bits lost by each addition into the next one, so the
+/// error stays within a few ULPs of the exact sum regardless of length,
+/// instead of growing with the number of values as a naive loop does.
+pub fn sum_values_kahan(values: &[f64]) -> f64 {
+    let mut sum = 0.0;
+    let mut compensation = 0.0;
+    for &value in values {
+        let y = value - compensation;
+        let t = sum + y;
+        compensation = (t - sum) - y;
+        sum = t;
+    }
+    sum
+}
+
+/// Sum values by recursive halving, splitting the halves across threads
+///
+/// Pairwise summation's error grows with `log n` rather than `n`.
+fn sum_values_pairwise(values: &[f64]) -> f64 {
+    const LEAF_SIZE: usize = 1024;
+    if values.len() <= LEAF_SIZE {
+        return values.iter().sum();
+    }
+    let (left, right) = values.split_at(values.len() / 2);
+    let (left, right) = join(|| sum_values_pairwise(left), || sum_values_pairwise(right));
+    left + right
+}
+
+/// Process records from an iterator in fixed-size chunks
+///
+/// Only one chunk is held in memory at a time, so datasets larger than RAM
+/// can be streamed through. Each chunk goes through `process_records` and the
+/// results are combined with `ProcessResult::merge`, which keeps the overall
+/// `average_value` exact.
+///
+/// # Errors
+/// `InvalidArgument` if `chunk_size` is zero, `EmptyInput` if the iterator
+/// yields nothing, or the first error any chunk produces.
+pub fn process_records_chunked<I>(
+    iter: I,
+    chunk_size: usize,
+) -> Result<ProcessResult, ProcessingError>
+where
+    I: Iterator<Item = DataRecord>,
+{
+    if chunk_size == 0 {
+        return Err(ProcessingError::InvalidArgument(
+            "chunk_size must be greater than zero".to_string(),
+        ));
+    }
+
+    let mut merged: Option<ProcessResult> = None;
+    let mut chunk = Vec::with_capacity(chunk_size);
+    let mut iter = iter.peekable();
+
+    while iter.peek().is_some() {
+        chunk.clear();
+        chunk.extend(iter.by_ref().take(chunk_size));
+        let result = process_records(&chunk)?;
+        match merged.as_mut() {
+            Some(total) => total.merge(&result),
+            None => merged = Some(result),
+        }
+    }
+
+    merged.ok_or(ProcessingError::EmptyInput)
+}
+
+/// Filter records by category
+///
+/// Returns all records matching the specified category.
+/// Uses parallel iteration for performance on large datasets.
+pub fn filter_by_category(records: &[DataRecord], category: &str) -> Vec<DataRecord> {
+    records
+        .par_iter()
+        .filter(|record| record.category == category)
+        .cloned()
+        .collect()
+}
+
+/// Split records into one bucket per category in a single pass
+///
+/// Runs as a rayon `fold`/`reduce`, which combines partial results in input
+/// order, so each bucket keeps the records' original relative order.
+#[cfg(all(feature = "parallel", not(feature = "no-rayon")))]
+pub fn split_by_category(records: &[DataRecord]) -> HashMap<String, Vec<DataRecord>> {
+    records
+        .par_iter()
+        .fold(
+            FastHashMap::default,
+            |mut buckets: FastHashMap<&str, Vec<DataRecord>>, record| {
+                buckets
+                    .entry(record.category.as_str())
+                    .or_default()
+                    .push(record.clone());
+                buckets
+            },
+        )
+        .reduce(FastHashMap::default, |mut left, right| {
+            for (category, records) in right {
+                left.entry(category).or_default().extend(records);
+            }
+            left
+        })
+        .into_iter()
+        .map(|(category, records)| (category.to_string(), records))
+        .collect()
+}
+
+/// Split records into one bucket per category in a single pass
+///
+/// Each bucket keeps the records' original relative order.
+#[cfg(not(all(feature = "parallel", not(feature = "no-rayon"))))]
+pub fn split_by_category(records: &[DataRecord]) -> HashMap<String, Vec<DataRecord>> {
+    let mut buckets: FastHashMap<&str, Vec<DataRecord>> = FastHashMap::default();
+    for record in records {
+        buckets
+            .entry(record.category.as_str())
+            .or_default()
+            .push(record.clone());
+    }
+    buckets
+        .into_iter()
+        .map(|(category, records)| (category.to_string(), records))
+        .collect()
+}
+
+/// Filter records by value threshold
+///
+/// Returns all records with value >= min_value.
 pub fn filter_by_value(records: &[DataRecord], min_value: f64) -> Vec<DataRecord> {
     records
         .par_iter()
@@ -177,6 +1385,17 @@ pub fn filter_by_value(records: &[DataRecord], min_value: f64) -> Vec<DataRecord
         .collect()
 }
 
+/// Sample variance (n - 1 denominator) of values around a known mean
+///
+/// Returns 0.0 when fewer than two values are present.
+fn sample_variance(values: &[f64], mean: f64) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let sum_sq: f64 = values.iter().map(|v| (v - mean).powi(2)).sum();
+    sum_sq / (values.len() - 1) as f64
+}
+
 /// Get statistics for a specific category
 pub fn get_category_stats(records: &[DataRecord], category: &str) -> Option<CategoryStats> {
     let filtered: Vec<&DataRecord> = records
@@ -196,15 +1415,17 @@ pub fn get_category_stats(records: &[DataRecord], category: &str) -> Option<Cate
 
     let min_value = values
         .iter()
-        .min_by(|a, b| a.partial_cmp(b).unwrap())
+        .min_by(|a, b| a.total_cmp(b))
         .copied()
         .unwrap_or(0.0);
     let max_value = values
         .iter()
-        .max_by(|a, b| a.partial_cmp(b).unwrap())
+        .max_by(|a, b| a.total_cmp(b))
         .copied()
         .unwrap_or(0.0);
 
+    let variance = sample_variance(&values, average_value);
+
     Some(CategoryStats {
         category: category.to_string(),
         count,
@@ -212,6 +1433,9 @@ pub fn get_category_stats(records: &[DataRecord], category: &str) -> Option<Cate
         average_value,
         min_value,
         max_value,
+        variance,
+        std_dev: variance.sqrt(),
+        percentiles: Vec::new(),
     })
 }
 
@@ -224,13 +1448,67 @@ pub fn transform_values(records: &mut [DataRecord], multiplier: f64) {
     });
 }
 
+/// Min-max scale all values into `[0.0, 1.0]`
+///
+/// Uses the global min and max across `records`. When every value is the
+/// same, all values become 0.5. No-op on an empty slice.
+pub fn normalize_values(records: &mut [DataRecord]) {
+    let (min, max) = records
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), r| {
+            (min.min(r.value), max.max(r.value))
+        });
+    let range = max - min;
+
+    records.par_iter_mut().for_each(|record| {
+        record.value = if range > 0.0 {
+            (record.value - min) / range
+        } else {
+            0.5
+        };
+    });
+}
+
+/// Z-score scale all values: subtract the mean, divide by the standard deviation
+///
+/// Uses the sample standard deviation, as `CategoryStats` does. When it is
+/// zero (fewer than two records or all values equal), all values become 0.0.
+/// No-op on an empty slice.
+pub fn standardize_values(records: &mut [DataRecord]) {
+    if records.is_empty() {
+        return;
+    }
+    let values: Vec<f64> = records.par_iter().map(|r| r.value).collect();
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let std_dev = sample_variance(&values, mean).sqrt();
+
+    records.par_iter_mut().for_each(|record| {
+        record.value = if std_dev > 0.0 {
+            (record.value - mean) / std_dev
+        } else {
+            0.0
+        };
+    });
+}
+
+/// Clip every value into `[lower, upper]`
+///
+/// Unlike `f64::clamp` this does not panic when `lower > upper`; values then
+/// all end up at `upper`.
+pub fn clamp_values(records: &mut [DataRecord], lower: f64, upper: f64) {
+    records.par_iter_mut().for_each(|record| {
+        record.value = record.value.max(lower).min(upper);
+    });
+}
+
 /// Get unique categories from records
 pub fn get_unique_categories(records: &[DataRecord]) -> Vec<String> {
     let mut categories: Vec<String> = records
         .iter()
-        .map(|r| r.category.clone())
-        .collect::<std::collections::HashSet<_>>()
+        .map(|r| r.category.as_str())
+        .collect::<FastHashSet<_>>()
         .into_iter()
+        .map(str::to_string)
         .collect();
 
     categories.sort();
@@ -238,86 +1516,5078 @@ pub fn get_unique_categories(records: &[DataRecord]) -> Vec<String> {
 }
 
 /// Aggregate records by category, returning stats for each
-pub fn aggregate_by_category(records: &[DataRecord]) -> HashMap<String, CategoryStats> {
+///
+/// Each category's `percentiles` holds one `(quantile, value)` pair per entry
+/// of `percentile_targets`, computed with [`compute_percentiles`]. Targets
+/// outside `[0.0, 1.0]` return `InvalidArgument`.
+pub fn aggregate_by_category(
+    records: &[DataRecord],
+    percentile_targets: &[f64],
+) -> Result<HashMap<String, CategoryStats>, ProcessingError> {
+    validate_quantiles(percentile_targets)?;
     let categories = get_unique_categories(records);
 
     categories
         .into_iter()
         .filter_map(|cat| get_category_stats(records, &cat).map(|stats| (cat, stats)))
+        .map(|(cat, mut stats)| {
+            if !percentile_targets.is_empty() {
+                let values: Vec<f64> = records
+                    .iter()
+                    .filter(|r| r.category == cat)
+                    .map(|r| r.value)
+                    .collect();
+                let computed = compute_percentiles(&values, percentile_targets)?;
+                stats.percentiles = percentile_targets.iter().copied().zip(computed).collect();
+            }
+            Ok((cat, stats))
+        })
         .collect()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Parse an RFC 3339 timestamp into UTC, returning None if it is malformed
+fn parse_timestamp(timestamp: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
 
-    fn create_test_record(id: &str, value: f64, category: &str) -> DataRecord {
-        DataRecord {
-            id: id.to_string(),
-            value,
-            category: category.to_string(),
-            timestamp: "2024-01-15T10:00:00Z".to_string(),
-            metadata: None,
-        }
-    }
+/// Compute a category × time-bucket heatmap of average values
+///
+/// Missing cells are filled with `0.0`. See `compute_heatmap_with_fill`.
+pub fn compute_heatmap(
+    records: &[DataRecord],
+    bucket_minutes: u64,
+) -> Result<Vec<HeatmapRow>, ProcessingError> {
+    compute_heatmap_with_fill(records, bucket_minutes, MissingCellFill::Zero)
+}
 
-    #[test]
-    fn test_validate_record() {
-        let valid = create_test_record("1", 100.0, "A");
-        assert!(validate_record(&valid).is_ok());
+/// Compute a category × time-bucket heatmap of average values
+///
+/// Records are grouped into buckets of `bucket_minutes` aligned to the Unix
+/// epoch. Each row holds the average value of every category seen in the
+/// dataset; categories with no records in a bucket get the `fill` value.
+/// Rows are sorted by bucket start. Records with unparseable timestamps are
+/// skipped, and a `bucket_minutes` of zero yields no rows. Errors with
+/// `InvalidArgument` if `bucket_minutes` is so large that a bucket would
+/// start outside the range of representable timestamps.
+pub fn compute_heatmap_with_fill(
+    records: &[DataRecord],
+    bucket_minutes: u64,
+    fill: MissingCellFill,
+) -> Result<Vec<HeatmapRow>, ProcessingError> {
+    if bucket_minutes == 0 {
+        return Ok(Vec::new());
+    }
+    let too_large = || {
+        ProcessingError::InvalidArgument(format!("bucket_minutes {} is too large", bucket_minutes))
+    };
+    let bucket_seconds = bucket_minutes
+        .checked_mul(60)
+        .and_then(|s| i64::try_from(s).ok())
+        .ok_or_else(too_large)?;
 
-        let invalid_empty_id = DataRecord {
-            id: "".to_string(),
-            ..valid.clone()
+    // bucket start -> category -> (sum, count)
+    let mut buckets: BTreeMap<DateTime<Utc>, HashMap<&str, (f64, usize)>> = BTreeMap::new();
+    for record in records {
+        let Some(ts) = parse_timestamp(&record.timestamp) else {
+            continue;
         };
-        assert!(validate_record(&invalid_empty_id).is_err());
+        let bucket = ts
+            .timestamp()
+            .div_euclid(bucket_seconds)
+            .checked_mul(bucket_seconds)
+            .and_then(|start| Utc.timestamp_opt(start, 0).single())
+            .ok_or_else(too_large)?;
+        let cell = buckets
+            .entry(bucket)
+            .or_default()
+            .entry(record.category.as_str())
+            .or_insert((0.0, 0));
+        cell.0 += record.value;
+        cell.1 += 1;
+    }
 
-        let invalid_negative = DataRecord {
-            value: -10.0,
-            ..valid.clone()
-        };
-        assert!(validate_record(&invalid_negative).is_err());
+    let categories = get_unique_categories(records);
+
+    Ok(buckets
+        .into_iter()
+        .map(|(bucket, cells)| {
+            let category_averages = categories
+                .iter()
+                .map(|cat| {
+                    let avg = match cells.get(cat.as_str()) {
+                        Some((sum, count)) => sum / *count as f64,
+                        None => fill.value(),
+                    };
+                    (cat.clone(), avg)
+                })
+                .collect();
+            HeatmapRow {
+                time_bucket: bucket.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                category_averages,
+            }
+        })
+        .collect())
+}
+
+/// Get the metadata keys present in at least one record
+///
+/// Returns a sorted list. Records with no metadata contribute no keys.
+pub fn metadata_keys_union(records: &[DataRecord]) -> Vec<String> {
+    let mut keys: Vec<String> = records
+        .iter()
+        .filter_map(|r| r.metadata.as_ref())
+        .flat_map(|m| m.keys().cloned())
+        .collect::<std::collections::HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    keys.sort();
+    keys
+}
+
+/// Get the metadata keys present in every record
+///
+/// Returns a sorted list. If any record has no metadata, or the record set is
+/// empty, the intersection is empty.
+pub fn metadata_keys_intersection(records: &[DataRecord]) -> Vec<String> {
+    let Some((first, rest)) = records.split_first() else {
+        return Vec::new();
+    };
+    let Some(first_metadata) = first.metadata.as_ref() else {
+        return Vec::new();
+    };
+
+    let mut keys: Vec<String> = first_metadata
+        .keys()
+        .filter(|key| {
+            rest.iter().all(|r| {
+                r.metadata
+                    .as_ref()
+                    .is_some_and(|m| m.contains_key(key.as_str()))
+            })
+        })
+        .cloned()
+        .collect();
+
+    keys.sort();
+    keys
+}
+
+/// Prometheus text exposition format helpers
+///
+/// Shared by `records_to_prometheus_metrics` and the language bindings that
+/// render their own metrics, so every exporter escapes and formats samples
+/// the same way.
+pub mod prometheus {
+    /// Format an f64 as a sample value, spelling non-finite values `NaN`,
+    /// `+Inf` and `-Inf`
+    pub fn format_value(value: f64) -> String {
+        if value.is_nan() {
+            "NaN".to_string()
+        } else if value.is_infinite() {
+            if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+        } else {
+            value.to_string()
+        }
     }
 
-    #[test]
-    fn test_process_records() {
-        let records = vec![
-            create_test_record("1", 100.0, "A"),
-            create_test_record("2", 200.0, "B"),
-            create_test_record("3", 150.0, "A"),
-        ];
+    /// Escape a label value (backslash, double quote and newline)
+    pub fn escape_label_value(value: &str) -> String {
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    }
 
-        let result = process_records(&records).unwrap();
-        assert_eq!(result.total_processed, 3);
-        assert_eq!(result.total_value, 450.0);
-        assert_eq!(result.average_value, 150.0);
-        assert_eq!(result.min_value, 100.0);
-        assert_eq!(result.max_value, 200.0);
+    /// Whether `name` matches `[a-zA-Z_:][a-zA-Z0-9_:]*`
+    pub fn is_valid_metric_name(name: &str) -> bool {
+        let mut chars = name.chars();
+        chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == ':')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
     }
 
-    #[test]
-    fn test_filter_by_category() {
-        let records = vec![
-            create_test_record("1", 100.0, "A"),
-            create_test_record("2", 200.0, "B"),
-            create_test_record("3", 150.0, "A"),
-        ];
+    /// Whether `name` matches `[a-zA-Z_][a-zA-Z0-9_]*` and does not start
+    /// with `__`, which Prometheus reserves for internal labels
+    pub fn is_valid_label_name(name: &str) -> bool {
+        let mut chars = name.chars();
+        !name.starts_with("__")
+            && chars
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+}
 
-        let filtered = filter_by_category(&records, "A");
-        assert_eq!(filtered.len(), 2);
+/// Export records as Prometheus text-format gauge samples
+///
+/// Emits a `# TYPE` header followed by one sample per record:
+/// `{metric_name}{id="...",{label_key}="...",...} {value}`.
+/// `label_keys` name metadata keys to attach as labels; records without a
+/// given key get an empty label value. Errors with `InvalidArgument` if
+/// `metric_name` or a label key is not a valid Prometheus name, or if a key
+/// is `id` or repeats, since a sample cannot carry the same label twice.
+pub fn records_to_prometheus_metrics(
+    records: &[DataRecord],
+    metric_name: &str,
+    label_keys: &[&str],
+) -> Result<String, ProcessingError> {
+    if !prometheus::is_valid_metric_name(metric_name) {
+        return Err(ProcessingError::InvalidArgument(format!(
+            "Invalid Prometheus metric name '{}'",
+            metric_name
+        )));
+    }
+    let mut seen = HashSet::from(["id"]);
+    for key in label_keys {
+        if !prometheus::is_valid_label_name(key) {
+            return Err(ProcessingError::InvalidArgument(format!(
+                "Invalid Prometheus label name '{}'",
+                key
+            )));
+        }
+        if !seen.insert(*key) {
+            return Err(ProcessingError::InvalidArgument(format!(
+                "Duplicate Prometheus label '{}'",
+                key
+            )));
+        }
     }
 
-    #[test]
-    fn test_get_category_stats() {
-        let records = vec![
-            create_test_record("1", 100.0, "A"),
-            create_test_record("2", 200.0, "A"),
-            create_test_record("3", 150.0, "B"),
-        ];
+    let mut output = format!("# TYPE {} gauge\n", metric_name);
 
-        let stats = get_category_stats(&records, "A").unwrap();
+    for record in records {
+        let mut labels = vec![format!(
+            "id=\"{}\"",
+            prometheus::escape_label_value(&record.id)
+        )];
+        for key in label_keys {
+            let value = record
+                .metadata
+                .as_ref()
+                .and_then(|m| m.get(*key))
+                .map(String::as_str)
+                .unwrap_or("");
+            labels.push(format!(
+                "{}=\"{}\"",
+                key,
+                prometheus::escape_label_value(value)
+            ));
+        }
+
+        output.push_str(&format!(
+            "{}{{{}}} {}\n",
+            metric_name,
+            labels.join(","),
+            prometheus::format_value(record.value)
+        ));
+    }
+
+    Ok(output)
+}
+
+/// Levenshtein edit distance between two strings, counted in chars
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b_chars.len()).collect();
+    let mut curr = vec![0; b_chars.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b_chars.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b_chars.len()]
+}
+
+/// Deduplicate records whose IDs are within `max_edit_distance` of each other
+///
+/// IDs are clustered transitively by Levenshtein distance, and each cluster
+/// keeps the record with the lexicographically smallest ID. Representatives are
+/// returned in input order.
+///
+/// Comparing every pair of IDs is O(n²), so for production datasets use
+/// `max_edit_distance == 0`, which falls back to exact matching in O(n).
+pub fn fuzzy_deduplicate(records: &[DataRecord], max_edit_distance: usize) -> Vec<DataRecord> {
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    let mut parent: Vec<usize> = (0..records.len()).collect();
+
+    if max_edit_distance == 0 {
+        let mut first_seen: HashMap<&str, usize> = HashMap::new();
+        for (i, record) in records.iter().enumerate() {
+            let root = *first_seen.entry(record.id.as_str()).or_insert(i);
+            parent[i] = root;
+        }
+    } else {
+        for i in 0..records.len() {
+            for j in (i + 1)..records.len() {
+                let (a, b) = (&records[i].id, &records[j].id);
+                // Length difference is a lower bound on edit distance
+                if a.chars().count().abs_diff(b.chars().count()) > max_edit_distance {
+                    continue;
+                }
+                if levenshtein(a, b) <= max_edit_distance {
+                    let (ri, rj) = (find(&mut parent, i), find(&mut parent, j));
+                    if ri != rj {
+                        parent[rj.max(ri)] = ri.min(rj);
+                    }
+                }
+            }
+        }
+    }
+
+    // Pick the smallest ID within each cluster
+    let mut representative: HashMap<usize, usize> = HashMap::new();
+    for i in 0..records.len() {
+        let root = find(&mut parent, i);
+        let best = representative.entry(root).or_insert(i);
+        if records[i].id < records[*best].id {
+            *best = i;
+        }
+    }
+
+    let mut keep: Vec<usize> = representative.into_values().collect();
+    keep.sort_unstable();
+    keep.into_iter().map(|i| records[i].clone()).collect()
+}
+
+/// Bucket values into `bucket_count` equal-width buckets spanning `[min, max]`
+///
+/// Returns `(bucket_lower, bucket_upper, count)` tuples. Non-finite values are
+/// skipped and the maximum value lands in the last bucket. If there are no
+/// finite values there is no range to span, so the result is empty. When all
+/// values are equal every bucket is zero-width and the first holds them all.
+///
+/// # Errors
+/// Returns an error if `bucket_count` is zero.
+pub fn compute_histogram(
+    values: &[f64],
+    bucket_count: usize,
+) -> Result<Vec<(f64, f64, usize)>, ProcessingError> {
+    if bucket_count == 0 {
+        return Err(ProcessingError::InvalidArgument(
+            "bucket_count must be greater than zero".to_string(),
+        ));
+    }
+
+    let finite: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    if finite.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let min = finite.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = finite.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let width = (max - min) / bucket_count as f64;
+
+    let mut counts = vec![0; bucket_count];
+    for v in finite {
+        let idx = if width > 0.0 {
+            (((v - min) / width) as usize).min(bucket_count - 1)
+        } else {
+            0
+        };
+        counts[idx] += 1;
+    }
+
+    Ok(counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| {
+            let lower = min + width * i as f64;
+            // Pin the last edge to max so rounding never excludes it
+            let upper = if i + 1 == bucket_count {
+                max
+            } else {
+                min + width * (i + 1) as f64
+            };
+            (lower, upper, count)
+        })
+        .collect())
+}
+
+/// Shannon entropy (in bits) of a discrete distribution given as counts
+fn entropy_from_counts(counts: impl IntoIterator<Item = usize>) -> f64 {
+    let counts: Vec<usize> = counts.into_iter().filter(|c| *c > 0).collect();
+    let total: usize = counts.iter().sum();
+    if total == 0 {
+        return 0.0;
+    }
+
+    -counts
+        .iter()
+        .map(|c| {
+            let p = *c as f64 / total as f64;
+            p * p.log2()
+        })
+        .sum::<f64>()
+}
+
+/// Number of records in each category
+fn category_counts(records: &[DataRecord]) -> FastHashMap<&str, usize> {
+    let mut counts = FastHashMap::default();
+    for record in records {
+        *counts.entry(record.category.as_str()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Shannon entropy (in bits) of the category distribution
+///
+/// `-sum(p_i * log2(p_i))` over category proportions: 0.0 for an empty slice
+/// or a single category, 1.0 for an even two-way split.
+pub fn compute_entropy(records: &[DataRecord]) -> f64 {
+    entropy_from_counts(category_counts(records).into_values())
+}
+
+/// Category entropy divided by its maximum, `log2(num_categories)`
+///
+/// Returns a value in `[0.0, 1.0]`: exactly 1.0 when every category has the
+/// same count, and 0.0 when there are fewer than two categories.
+pub fn compute_normalized_entropy(records: &[DataRecord]) -> f64 {
+    let counts = category_counts(records);
+    if counts.len() < 2 {
+        return 0.0;
+    }
+    // Avoid rounding error in the common perfectly balanced case
+    let mut values = counts.values();
+    let first = values.next().copied();
+    if values.all(|c| Some(*c) == first) {
+        return 1.0;
+    }
+
+    let max_entropy = (counts.len() as f64).log2();
+    (entropy_from_counts(counts.into_values()) / max_entropy).clamp(0.0, 1.0)
+}
+
+/// Compute the entropy of the value distribution within each category
+///
+/// Each category's values are bucketed into `bucket_count` equal-width buckets
+/// and the Shannon entropy (in bits) of the bucket probabilities is returned.
+/// A uniform spread across buckets gives the maximum `log2(bucket_count)`; a
+/// single spike gives zero.
+pub fn compute_value_entropy_per_category(
+    records: &[DataRecord],
+    bucket_count: usize,
+) -> HashMap<String, f64> {
+    let mut values_by_category: HashMap<&str, Vec<f64>> = HashMap::new();
+    for record in records {
+        values_by_category
+            .entry(record.category.as_str())
+            .or_default()
+            .push(record.value);
+    }
+
+    values_by_category
+        .into_par_iter()
+        .map(|(category, values)| {
+            let counts = compute_histogram(&values, bucket_count)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(_, _, count)| count);
+            let entropy = entropy_from_counts(counts);
+            (category.to_string(), entropy)
+        })
+        .collect()
+}
+
+/// Apply patches to the records whose IDs match a patch key
+///
+/// Records without a matching patch are left untouched.
+pub fn patch_records_by_id(records: &mut [DataRecord], patches: &HashMap<String, DataRecordPatch>) {
+    records.par_iter_mut().for_each(|record| {
+        let Some(patch) = patches.get(record.id.as_str()) else {
+            return;
+        };
+
+        if let Some(value) = patch.value {
+            record.value = value;
+        }
+        if let Some(ref category) = patch.category {
+            record.category = category.as_str().into();
+        }
+        if let Some(ref updates) = patch.metadata_updates {
+            record
+                .metadata
+                .get_or_insert_with(HashMap::new)
+                .extend(updates.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+    });
+}
+
+/// Pearson correlation coefficient of a series of `(x, y)` pairs
+///
+/// Computed in a single pass with Welford-style running means and co-moments,
+/// which avoids the catastrophic cancellation of the naive sum-of-products
+/// formula when values are large relative to their spread.
+///
+/// Returns None if fewer than two pairs exist or either series is constant.
+pub fn pearson_correlation(pairs: impl IntoIterator<Item = (f64, f64)>) -> Option<f64> {
+    let (mut n, mut mean_x, mut mean_y) = (0usize, 0.0, 0.0);
+    let (mut m2_x, mut m2_y, mut co_moment) = (0.0, 0.0, 0.0);
+
+    for (x, y) in pairs {
+        n += 1;
+        let dx = x - mean_x;
+        let dy = y - mean_y;
+        mean_x += dx / n as f64;
+        mean_y += dy / n as f64;
+        m2_x += dx * (x - mean_x);
+        m2_y += dy * (y - mean_y);
+        co_moment += dx * (y - mean_y);
+    }
+
+    if n < 2 || m2_x == 0.0 || m2_y == 0.0 {
+        return None;
+    }
+    Some((co_moment / (m2_x.sqrt() * m2_y.sqrt())).clamp(-1.0, 1.0))
+}
+
+/// Correlate a numeric metadata field with `DataRecord::value`
+///
+/// Records where `metadata_key` is missing or does not parse as `f64` are
+/// excluded. Returns None if fewer than two records remain or either series
+/// has zero variance.
+pub fn compute_correlation(records: &[DataRecord], metadata_key: &str) -> Option<f64> {
+    pearson_correlation(records.iter().filter_map(|record| {
+        let raw = record.metadata.as_ref()?.get(metadata_key)?;
+        let parsed = raw.trim().parse::<f64>().ok()?;
+        Some((parsed, record.value))
+    }))
+}
+
+/// Compute the autocorrelation of record values for lags `1..=max_lag`
+///
+/// Records are assumed to already be sorted by timestamp. Each coefficient is
+/// the Pearson correlation between the series and itself shifted by the lag,
+/// so a signal repeating every `k` records peaks at lag `k`. Lags where the
+/// correlation is undefined (too few pairs or a constant window) yield `0.0`.
+///
+/// Only `records.len() - lag` pairs contribute to each coefficient, so values
+/// for `lag > records.len() / 2` are based on little data and are unreliable.
+pub fn compute_autocorrelation(records: &[DataRecord], max_lag: usize) -> Vec<f64> {
+    let values: Vec<f64> = records.iter().map(|r| r.value).collect();
+
+    (1..=max_lag)
+        .into_par_iter()
+        .map(|lag| {
+            if lag >= values.len() {
+                return 0.0;
+            }
+            let pairs = values.iter().copied().zip(values[lag..].iter().copied());
+            pearson_correlation(pairs).unwrap_or(0.0)
+        })
+        .collect()
+}
+
+/// Pair each record with its percentile rank in the value distribution
+///
+/// Ranks use dense ranking over distinct values, scaled to `[0.0, 100.0]`:
+/// the smallest value gets `0.0`, the largest `100.0`, and ties share a rank.
+/// If every record has the same value they all get `0.0`. Records are
+/// returned in input order.
+pub fn compute_percentile_ranks(records: &[DataRecord]) -> Vec<(DataRecord, f64)> {
+    let mut distinct: Vec<f64> = records.iter().map(|r| r.value).collect();
+    distinct.sort_by(|a, b| a.total_cmp(b));
+    distinct.dedup();
+
+    let max_rank = distinct.len().saturating_sub(1);
+
+    records
+        .par_iter()
+        .map(|record| {
+            let rank = distinct.partition_point(|v| *v < record.value);
+            let percentile = if max_rank == 0 {
+                0.0
+            } else {
+                rank as f64 / max_rank as f64 * 100.0
+            };
+            (record.clone(), percentile)
+        })
+        .collect()
+}
+
+/// Escape text for safe inclusion in HTML element content or attributes
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Render records as an HTML `<table>` for quick inspection
+///
+/// The header lists `id`, `value`, `category`, `timestamp` and `metadata`;
+/// metadata is rendered as JSON with sorted keys. When `max_rows` is set and
+/// exceeded, a `<tfoot>` row reports how many rows were omitted. All values
+/// are HTML-escaped.
+pub fn records_to_html_table(records: &[DataRecord], max_rows: Option<usize>) -> String {
+    let shown = max_rows.unwrap_or(records.len()).min(records.len());
+
+    let mut html = String::from("<table>\n<thead>\n<tr>");
+    for column in ["id", "value", "category", "timestamp", "metadata"] {
+        html.push_str(&format!("<th>{}</th>", column));
+    }
+    html.push_str("</tr>\n</thead>\n<tbody>\n");
+
+    for record in &records[..shown] {
+        let metadata = record
+            .metadata
+            .as_ref()
+            .map(|m| {
+                let sorted: BTreeMap<&String, &String> = m.iter().collect();
+                serde_json::to_string(&sorted).unwrap_or_default()
+            })
+            .unwrap_or_default();
+
+        html.push_str("<tr>");
+        for cell in [
+            record.id.as_str(),
+            &record.value.to_string(),
+            &record.category,
+            &record.timestamp,
+            &metadata,
+        ] {
+            html.push_str(&format!("<td>{}</td>", escape_html(cell)));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</tbody>\n");
+
+    if shown < records.len() {
+        html.push_str(&format!(
+            "<tfoot>\n<tr><td colspan=\"5\">... {} more rows</td></tr>\n</tfoot>\n",
+            records.len() - shown
+        ));
+    }
+
+    html.push_str("</table>");
+    html
+}
+
+/// Sample `n` records with probability proportional to their value
+///
+/// Draws with replacement using the cumulative distribution of values and a
+/// `SmallRng` seeded from `seed`, so results are reproducible. Records with a
+/// value of zero are never selected. Returns an error if any value is
+/// negative or non-finite, or if every value is zero.
+pub fn weighted_sample(
+    records: &[DataRecord],
+    n: usize,
+    seed: u64,
+) -> Result<Vec<DataRecord>, ProcessingError> {
+    if records.is_empty() {
+        return Err(ProcessingError::EmptyInput);
+    }
+    if let Some(record) = records
+        .iter()
+        .find(|r| !r.value.is_finite() || r.value < 0.0)
+    {
+        return Err(ProcessingError::InvalidArgument(format!(
+            "Weighted sampling requires finite non-negative values, record {} has {}",
+            record.id, record.value
+        )));
+    }
+
+    let cdf: Vec<f64> = records
+        .iter()
+        .scan(0.0, |acc, r| {
+            *acc += r.value;
+            Some(*acc)
+        })
+        .collect();
+    let total = cdf.last().copied().unwrap_or(0.0);
+    if total.is_infinite() {
+        return Err(ProcessingError::Overflow);
+    }
+    if total <= 0.0 {
+        return Err(ProcessingError::InvalidArgument(
+            "Cannot sample: all record values are zero".to_string(),
+        ));
+    }
+
+    let mut rng = SmallRng::seed_from_u64(seed);
+    let sample = (0..n)
+        .map(|_| {
+            let target = rng.gen::<f64>() * total;
+            let idx = cdf.partition_point(|c| *c <= target).min(records.len() - 1);
+            records[idx].clone()
+        })
+        .collect();
+
+    Ok(sample)
+}
+
+/// Cluster records by value using Lloyd's k-means in one dimension
+///
+/// Initial centroids are the values of `k` distinct records chosen with a
+/// `SmallRng` seeded from `seed`. Lloyd's iterations are fully deterministic
+/// after initialization, so the same seed always yields the same clustering.
+/// Iteration stops after `max_iter` rounds or once no centroid moves by more
+/// than `1e-6`. Returns each record paired with its cluster index in `0..k`.
+/// Errors if any value is NaN or infinite, since it has no distance to a
+/// centroid.
+pub fn kmeans_cluster(
+    records: &[DataRecord],
+    k: usize,
+    max_iter: usize,
+    seed: u64,
+) -> Result<Vec<(DataRecord, usize)>, ProcessingError> {
+    const TOLERANCE: f64 = 1e-6;
+
+    if k == 0 {
+        return Err(ProcessingError::InvalidArgument(
+            "k must be at least 1".to_string(),
+        ));
+    }
+    if k > records.len() {
+        return Err(ProcessingError::InvalidArgument(format!(
+            "k ({}) cannot exceed the number of records ({})",
+            k,
+            records.len()
+        )));
+    }
+    if let Some(record) = records.iter().find(|r| !r.value.is_finite()) {
+        return Err(ProcessingError::InvalidArgument(format!(
+            "k-means requires finite values, record {} has {}",
+            record.id, record.value
+        )));
+    }
+
+    // Partial Fisher-Yates shuffle to pick k distinct starting records
+    let mut rng = SmallRng::seed_from_u64(seed);
+    let mut indices: Vec<usize> = (0..records.len()).collect();
+    for i in 0..k {
+        let j = rng.gen_range(i..indices.len());
+        indices.swap(i, j);
+    }
+    let mut centroids: Vec<f64> = indices[..k].iter().map(|&i| records[i].value).collect();
+
+    let nearest = |value: f64, centroids: &[f64]| -> usize {
+        centroids
+            .iter()
+            .enumerate()
+            .min_by(|a, b| (value - a.1).abs().total_cmp(&(value - b.1).abs()))
+            .map(|(i, _)| i)
+            .unwrap_or(0)
+    };
+
+    let mut assignments = vec![0; records.len()];
+    for _ in 0..max_iter {
+        assignments = records
+            .par_iter()
+            .map(|r| nearest(r.value, &centroids))
+            .collect();
+
+        let mut sums = vec![(0.0, 0usize); k];
+        for (record, &cluster) in records.iter().zip(&assignments) {
+            sums[cluster].0 += record.value;
+            sums[cluster].1 += 1;
+        }
+
+        let mut max_shift: f64 = 0.0;
+        for (centroid, (sum, count)) in centroids.iter_mut().zip(sums) {
+            // Empty clusters keep their previous centroid
+            if count > 0 {
+                let updated = sum / count as f64;
+                max_shift = max_shift.max((updated - *centroid).abs());
+                *centroid = updated;
+            }
+        }
+
+        if max_shift < TOLERANCE {
+            break;
+        }
+    }
+
+    // Final assignment against the converged centroids
+    if max_iter > 0 {
+        assignments = records
+            .par_iter()
+            .map(|r| nearest(r.value, &centroids))
+            .collect();
+    }
+
+    Ok(records.iter().cloned().zip(assignments).collect())
+}
+
+/// Left-join reference metadata onto records
+///
+/// `key_field` selects the join key: `"id"`, `"category"`, or otherwise the
+/// name of a metadata key. For every record whose key value appears in
+/// `lookup`, the lookup entries are merged into the record's metadata (lookup
+/// values win on conflict). Records without a match are left unchanged.
+/// Returns the number of records enriched.
+pub fn enrich_records(
+    records: &mut [DataRecord],
+    lookup: &HashMap<String, HashMap<String, String>>,
+    key_field: &str,
+) -> usize {
+    records
+        .par_iter_mut()
+        .map(|record| {
+            let key = match key_field {
+                "id" => Some(record.id.as_str()),
+                "category" => Some(record.category.as_str()),
+                other => record
+                    .metadata
+                    .as_ref()
+                    .and_then(|m| m.get(other))
+                    .map(String::as_str),
+            };
+            let Some(extra) = key.and_then(|k| lookup.get(k)) else {
+                return false;
+            };
+
+            record
+                .metadata
+                .get_or_insert_with(HashMap::new)
+                .extend(extra.iter().map(|(k, v)| (k.clone(), v.clone())));
+            true
+        })
+        .filter(|enriched| *enriched)
+        .count()
+}
+
+/// Add `value` to a running sum with Neumaier compensation
+///
+/// `compensation` collects what rounding drops from `sum`, so `sum +
+/// compensation` stays accurate when a large value is later subtracted
+/// again: after adding 1e17 and 1.0 and removing 1e17, the total is still
+/// 1.0. Once `sum` stops being finite the compensation is left alone.
+fn add_compensated(sum: &mut f64, compensation: &mut f64, value: f64) {
+    let total = *sum + value;
+    if total.is_finite() {
+        *compensation += if sum.abs() >= value.abs() {
+            (*sum - total) + value
+        } else {
+            (value - total) + *sum
+        };
+    }
+    *sum = total;
+}
+
+/// The value of a sum maintained by `add_compensated`
+fn compensated_total(sum: f64, compensation: f64) -> f64 {
+    if sum.is_finite() {
+        sum + compensation
+    } else {
+        sum
+    }
+}
+
+/// Map an f64 to a u64 whose unsigned ordering matches the float ordering
+fn f64_to_ordered_bits(value: f64) -> u64 {
+    let bits = value.to_bits();
+    if bits >> 63 == 1 {
+        !bits
+    } else {
+        bits | (1 << 63)
+    }
+}
+
+/// Inverse of `f64_to_ordered_bits`
+fn f64_from_ordered_bits(bits: u64) -> f64 {
+    if bits >> 63 == 1 {
+        f64::from_bits(bits & !(1 << 63))
+    } else {
+        f64::from_bits(!bits)
+    }
+}
+
+impl StatsAccumulator {
+    /// Create an empty accumulator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a record to the running statistics
+    pub fn add_record(&mut self, record: &DataRecord) {
+        self.count += 1;
+        add_compensated(&mut self.sum, &mut self.sum_compensation, record.value);
+        add_compensated(
+            &mut self.sum_of_squares,
+            &mut self.sum_of_squares_compensation,
+            record.value * record.value,
+        );
+        self.min = Some(self.min.map_or(record.value, |m| m.min(record.value)));
+        self.max = Some(self.max.map_or(record.value, |m| m.max(record.value)));
+        *self
+            .categories
+            .entry(record.category.to_string())
+            .or_insert(0) += 1;
+        *self
+            .values
+            .entry(f64_to_ordered_bits(record.value))
+            .or_insert(0) += 1;
+    }
+
+    /// Remove a previously added record from the running statistics
+    ///
+    /// Removing a record whose value or category was never added is a no-op.
+    pub fn remove_record(&mut self, record: &DataRecord) {
+        let key = f64_to_ordered_bits(record.value);
+        let category = record.category.as_str();
+        let (Some(&value_count), Some(&category_count)) =
+            (self.values.get(&key), self.categories.get(category))
+        else {
+            return;
+        };
+
+        if value_count == 1 {
+            self.values.remove(&key);
+        } else {
+            self.values.insert(key, value_count - 1);
+        }
+        if category_count == 1 {
+            self.categories.remove(category);
+        } else {
+            self.categories
+                .insert(category.to_string(), category_count - 1);
+        }
+
+        self.count -= 1;
+        add_compensated(&mut self.sum, &mut self.sum_compensation, -record.value);
+        add_compensated(
+            &mut self.sum_of_squares,
+            &mut self.sum_of_squares_compensation,
+            -(record.value * record.value),
+        );
+
+        // Only the extremes need the multiset; everything else is O(1)
+        if self.min == Some(record.value) {
+            self.min = self.values.keys().next().map(|b| f64_from_ordered_bits(*b));
+        }
+        if self.max == Some(record.value) {
+            self.max = self
+                .values
+                .keys()
+                .next_back()
+                .map(|b| f64_from_ordered_bits(*b));
+        }
+    }
+
+    /// Number of records currently accumulated
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Sample variance of accumulated values (0.0 with fewer than two records)
+    pub fn variance(&self) -> f64 {
+        if self.count < 2 {
+            return 0.0;
+        }
+        let n = self.count as f64;
+        let sum = compensated_total(self.sum, self.sum_compensation);
+        let sum_of_squares =
+            compensated_total(self.sum_of_squares, self.sum_of_squares_compensation);
+        ((sum_of_squares - sum * sum / n) / (n - 1.0)).max(0.0)
+    }
+
+    /// Produce a `ProcessResult` from the current state in O(1)
+    ///
+    /// An empty accumulator yields zeroed statistics.
+    pub fn finalize(&self) -> ProcessResult {
+        let total_value = compensated_total(self.sum, self.sum_compensation);
+        let average_value = if self.count == 0 {
+            0.0
+        } else {
+            total_value / self.count as f64
+        };
+
+        ProcessResult {
+            total_processed: self.count,
+            total_value,
+            average_value,
+            min_value: self.min.unwrap_or(0.0),
+            max_value: self.max.unwrap_or(0.0),
+            categories: self.categories.clone(),
+            capped_count: 0,
+        }
+    }
+}
+
+/// Join two record sets on `DataRecord::id`
+///
+/// Matched pairs keep the left record's fields (including `value`) with the
+/// two metadata maps merged, right winning on conflicting keys. Unmatched
+/// records kept by an outer join appear unchanged. Output follows left order;
+/// for `RightOuter`, unmatched right records follow in right order. If the
+/// right side repeats an ID, the last occurrence is used.
+///
+/// Runs in O(n + m) by indexing the right side by ID.
+pub fn join_records(
+    left: &[DataRecord],
+    right: &[DataRecord],
+    strategy: JoinStrategy,
+) -> Vec<DataRecord> {
+    let index: HashMap<&str, &DataRecord> = right.iter().map(|r| (r.id.as_str(), r)).collect();
+    let mut matched: HashSet<&str> = HashSet::new();
+    let mut joined = Vec::with_capacity(left.len());
+
+    for record in left {
+        match index.get(record.id.as_str()) {
+            Some(other) => {
+                matched.insert(other.id.as_str());
+                let metadata = match (&record.metadata, &other.metadata) {
+                    (None, None) => None,
+                    (left_meta, right_meta) => {
+                        let mut merged = left_meta.clone().unwrap_or_default();
+                        merged.extend(right_meta.clone().unwrap_or_default());
+                        Some(merged)
+                    }
+                };
+                joined.push(DataRecord {
+                    metadata,
+                    ..record.clone()
+                });
+            }
+            None if strategy == JoinStrategy::LeftOuter => joined.push(record.clone()),
+            None => {}
+        }
+    }
+
+    if strategy == JoinStrategy::RightOuter {
+        joined.extend(
+            right
+                .iter()
+                .filter(|r| !matched.contains(r.id.as_str()))
+                .cloned(),
+        );
+    }
+
+    joined
+}
+
+/// Union two record sets, resolving records that share an ID
+///
+/// Output follows primary order with conflicts resolved in place, followed
+/// by the secondary-only records in secondary order. If the secondary side
+/// repeats an ID, only its last occurrence is used, whether it is compared
+/// against a primary record or appended; the others are dropped.
+///
+/// Runs in O(n + m) by indexing the secondary side by ID.
+pub fn merge_datasets(
+    primary: Vec<DataRecord>,
+    secondary: Vec<DataRecord>,
+    conflict: ConflictResolution,
+) -> Vec<DataRecord> {
+    let index: HashMap<String, usize> = secondary
+        .iter()
+        .enumerate()
+        .map(|(i, r)| (r.id.to_string(), i))
+        .collect();
+    let mut matched = vec![false; secondary.len()];
+    let mut merged = Vec::with_capacity(primary.len() + secondary.len());
+
+    for mut record in primary {
+        let Some(&i) = index.get(record.id.as_str()) else {
+            merged.push(record);
+            continue;
+        };
+        matched[i] = true;
+        let other = &secondary[i];
+
+        merged.push(match conflict {
+            ConflictResolution::KeepPrimary => record,
+            ConflictResolution::KeepSecondary => other.clone(),
+            ConflictResolution::MergeMetadata => {
+                if let Some(extra) = &other.metadata {
+                    let metadata = record.metadata.get_or_insert_with(HashMap::new);
+                    for (key, value) in extra {
+                        metadata.entry(key.clone()).or_insert_with(|| value.clone());
+                    }
+                }
+                record
+            }
+            ConflictResolution::UseHighestValue if other.value > record.value => other.clone(),
+            ConflictResolution::UseHighestValue => record,
+        });
+    }
+
+    merged.extend(
+        secondary
+            .into_iter()
+            .enumerate()
+            .filter(|(i, r)| index[r.id.as_str()] == *i && !matched[*i])
+            .map(|(_, r)| r),
+    );
+    merged
+}
+
+/// Linearly interpolated quantile (`q` in `[0, 1]`) of an ascending slice
+fn quantile_sorted(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let lower = pos.floor() as usize;
+    let upper = pos.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (pos - lower as f64)
+}
+
+/// Reject quantiles outside `[0.0, 1.0]` (including NaN)
+fn validate_quantiles(quantiles: &[f64]) -> Result<(), ProcessingError> {
+    match quantiles.iter().find(|q| !(0.0..=1.0).contains(*q)) {
+        Some(q) => Err(ProcessingError::InvalidArgument(format!(
+            "quantile {} is outside [0.0, 1.0]",
+            q
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Compute arbitrary quantiles of `values` by linear interpolation
+///
+/// Uses R's type 7 definition: the `q` quantile of `n` sorted values sits at
+/// zero-based position `q * (n - 1)`, interpolating between neighbours.
+/// `values` need not be sorted. Returns one value per entry of `quantiles`.
+pub fn compute_percentiles(values: &[f64], quantiles: &[f64]) -> Result<Vec<f64>, ProcessingError> {
+    if values.is_empty() {
+        return Err(ProcessingError::EmptyInput);
+    }
+    validate_quantiles(quantiles)?;
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    Ok(quantiles
+        .iter()
+        .map(|&q| quantile_sorted(&sorted, q))
+        .collect())
+}
+
+/// Compute one percentile of `value` per group
+///
+/// `percentile` is a quantile in `[0.0, 1.0]` interpolated as in
+/// `compute_percentiles`, so `1.0` gives each group's maximum. Records
+/// missing the `MetadataKey` are skipped.
+pub fn compute_percentile_by_group(
+    records: &[DataRecord],
+    percentile: f64,
+    group_key: GroupKey,
+) -> Result<HashMap<String, f64>, ProcessingError> {
+    validate_quantiles(&[percentile])?;
+
+    let mut groups: HashMap<&str, Vec<f64>> = HashMap::new();
+    for record in records {
+        if let Some(group) = group_key.group_of(record) {
+            groups.entry(group).or_default().push(record.value);
+        }
+    }
+
+    Ok(groups
+        .into_par_iter()
+        .map(|(group, mut values)| {
+            values.sort_by(f64::total_cmp);
+            (group.to_string(), quantile_sorted(&values, percentile))
+        })
+        .collect())
+}
+
+/// Group records by the values of several fields at once
+///
+/// Each key holds one extracted value per entry of `fields`, in the same
+/// order; records keep their input order within a group. Records missing any
+/// of the fields are skipped. With no fields every record lands in a
+/// single group under the empty key.
+pub fn group_by_fields<'r>(
+    records: &'r [DataRecord],
+    fields: &[FieldName],
+) -> HashMap<Vec<String>, Vec<&'r DataRecord>> {
+    let mut groups: HashMap<Vec<String>, Vec<&DataRecord>> = HashMap::new();
+    for record in records {
+        let key: Option<Vec<String>> = fields.iter().map(|f| f.extract(record)).collect();
+        if let Some(key) = key {
+            groups.entry(key).or_default().push(record);
+        }
+    }
+    groups
+}
+
+/// Run `process_records` on every `group_by_fields` group
+///
+/// Fails with the first group's error if any group contains invalid records.
+pub fn aggregate_by_fields(
+    records: &[DataRecord],
+    fields: &[FieldName],
+) -> Result<HashMap<Vec<String>, ProcessResult>, ProcessingError> {
+    group_by_fields(records, fields)
+        .into_par_iter()
+        .map(|(key, group)| {
+            let result = process_borrowed_records(&group, &ProcessingConfig::default())?;
+            Ok((key, result))
+        })
+        .collect()
+}
+
+/// Compute Tukey fences `[Q1 - k * IQR, Q3 + k * IQR]` for a set of values
+///
+/// Returns `None` when fewer than four finite values are present, since the
+/// quartiles are not meaningful below that.
+pub fn iqr_fences(values: &[f64], iqr_multiplier: f64) -> Option<(f64, f64)> {
+    let mut sorted: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    if sorted.len() < 4 {
+        return None;
+    }
+    sorted.sort_by(|a, b| a.total_cmp(b));
+
+    let q1 = quantile_sorted(&sorted, 0.25);
+    let q3 = quantile_sorted(&sorted, 0.75);
+    let iqr = q3 - q1;
+    Some((q1 - iqr_multiplier * iqr, q3 + iqr_multiplier * iqr))
+}
+
+/// Return the records whose value lies outside the IQR fences
+///
+/// A multiplier of 1.5 gives the conventional Tukey outlier test; 3.0 flags
+/// only extreme outliers. Fewer than four records yields an empty result.
+///
+/// # Example
+/// ```
+/// # use rust_core::{detect_outliers_iqr, DataRecord};
+/// # let records: Vec<DataRecord> = Vec::new();
+/// let outliers = detect_outliers_iqr(&records, 1.5);
+/// ```
+pub fn detect_outliers_iqr(records: &[DataRecord], iqr_multiplier: f64) -> Vec<DataRecord> {
+    let values: Vec<f64> = records.iter().map(|r| r.value).collect();
+    let Some((lower, upper)) = iqr_fences(&values, iqr_multiplier) else {
+        return Vec::new();
+    };
+
+    records
+        .par_iter()
+        .filter(|r| r.value < lower || r.value > upper)
+        .cloned()
+        .collect()
+}
+
+/// Heap entry ranking a value by `f64::total_cmp`, earlier index first on ties
+#[derive(PartialEq)]
+struct RankedValue {
+    value: f64,
+    index: usize,
+}
+
+impl Eq for RankedValue {}
+
+impl PartialOrd for RankedValue {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RankedValue {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.value
+            .total_cmp(&other.value)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+/// Indices of the `n` largest values, ordered from largest to smallest
+///
+/// Keeps a min-heap of size `n`, so the cost is O(m log n) rather than the
+/// O(m log m) of a full sort. Values are compared with `f64::total_cmp` and
+/// ties keep input order, matching a stable descending sort.
+pub fn top_n_indices(values: &[f64], n: usize) -> Vec<usize> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut heap: BinaryHeap<Reverse<RankedValue>> =
+        BinaryHeap::with_capacity(n.min(values.len()) + 1);
+    for (index, &value) in values.iter().enumerate() {
+        heap.push(Reverse(RankedValue { value, index }));
+        if heap.len() > n {
+            heap.pop();
+        }
+    }
+
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(ranked)| ranked.index)
+        .collect()
+}
+
+/// Return the `n` highest-value records, sorted by value descending
+///
+/// If `n >= records.len()` every record is returned in descending order.
+pub fn top_n_by_value(records: &[DataRecord], n: usize) -> Vec<DataRecord> {
+    let values: Vec<f64> = records.iter().map(|r| r.value).collect();
+
+    top_n_indices(&values, n)
+        .into_iter()
+        .map(|i| records[i].clone())
+        .collect()
+}
+
+/// Group records into fixed-size time windows
+///
+/// Each key is the Unix epoch second at which the bucket starts, i.e. the
+/// record's timestamp floored to a multiple of `bucket_seconds`. Records whose
+/// timestamp is not valid RFC 3339 are skipped, as is everything when
+/// `bucket_seconds` is zero.
+pub fn group_by_time_bucket(
+    records: &[DataRecord],
+    bucket_seconds: u64,
+) -> HashMap<i64, Vec<&DataRecord>> {
+    let mut groups: HashMap<i64, Vec<&DataRecord>> = HashMap::new();
+    let Ok(bucket) = i64::try_from(bucket_seconds) else {
+        return groups;
+    };
+    if bucket == 0 {
+        return groups;
+    }
+
+    for record in records {
+        if let Some(ts) = parse_timestamp(&record.timestamp) {
+            let start = ts.timestamp().div_euclid(bucket) * bucket;
+            groups.entry(start).or_default().push(record);
+        }
+    }
+
+    groups
+}
+
+/// Compute `process_records` statistics for each time bucket
+///
+/// Buckets are formed as in `group_by_time_bucket`. Fails if any bucket
+/// contains an invalid record.
+pub fn compute_stats_by_time_bucket(
+    records: &[DataRecord],
+    bucket_seconds: u64,
+) -> Result<HashMap<i64, ProcessResult>, ProcessingError> {
+    group_by_time_bucket(records, bucket_seconds)
+        .into_par_iter()
+        .map(|(start, group)| {
+            process_borrowed_records(&group, &ProcessingConfig::default())
+                .map(|result| (start, result))
+        })
+        .collect()
+}
+
+/// Apply every filter configured in `spec` to the records
+pub fn apply_filter_spec(records: &[DataRecord], spec: &FilterSpec) -> Vec<DataRecord> {
+    records
+        .par_iter()
+        .filter(|record| spec.matches(record))
+        .cloned()
+        .collect()
+}
+
+/// Run a `TransformPipeline` over records in parallel
+///
+/// Output keeps input order; records dropped by a metadata filter are omitted.
+pub fn apply_pipeline(records: &[DataRecord], pipeline: &TransformPipeline) -> Vec<DataRecord> {
+    records
+        .par_iter()
+        .filter_map(|record| pipeline.apply(record.clone()))
+        .collect()
+}
+
+/// Compare two optional timestamps, placing unparseable ones last
+fn compare_timestamps(a: Option<DateTime<Utc>>, b: Option<DateTime<Utc>>) -> std::cmp::Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    }
+}
+
+/// Sort records in place by one or more keys
+///
+/// Later keys break ties in earlier ones, and the sort is stable, so records
+/// equal on every key keep their relative order. Timestamps are compared as
+/// parsed RFC 3339 instants rather than strings; unparseable timestamps sort
+/// after valid ones for `TimestampAsc` (and before them for `TimestampDesc`).
+pub fn sort_records(records: &mut [DataRecord], keys: &[SortKey]) {
+    if keys.is_empty() {
+        return;
+    }
+
+    // Move the records out alongside their parsed timestamps, so each
+    // timestamp is parsed once rather than on every comparison
+    let needs_timestamps = keys
+        .iter()
+        .any(|k| matches!(k, SortKey::TimestampAsc | SortKey::TimestampDesc));
+    let mut keyed: Vec<(Option<DateTime<Utc>>, DataRecord)> = records
+        .iter_mut()
+        .map(|r| {
+            let timestamp = if needs_timestamps {
+                parse_timestamp(&r.timestamp)
+            } else {
+                None
+            };
+            (timestamp, std::mem::take(r))
+        })
+        .collect();
+
+    keyed.sort_by(|(ta, ra), (tb, rb)| {
+        keys.iter()
+            .map(|key| match key {
+                SortKey::ValueAsc => ra.value.total_cmp(&rb.value),
+                SortKey::ValueDesc => rb.value.total_cmp(&ra.value),
+                SortKey::CategoryAsc => ra.category.cmp(&rb.category),
+                SortKey::CategoryDesc => rb.category.cmp(&ra.category),
+                SortKey::TimestampAsc => compare_timestamps(*ta, *tb),
+                SortKey::TimestampDesc => compare_timestamps(*tb, *ta),
+                SortKey::IdAsc => ra.id.cmp(&rb.id),
+                SortKey::IdDesc => rb.id.cmp(&ra.id),
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    for (slot, (_, record)) in records.iter_mut().zip(keyed) {
+        *slot = record;
+    }
+}
+
+/// Draw a uniform sample of `k` records from a stream of unknown length
+///
+/// Implements Algorithm R: only the `k`-record reservoir is held in memory,
+/// and every record has an equal `k / n` chance of ending up in the result.
+/// Returns all records if the stream yields fewer than `k`.
+pub fn reservoir_sample<R: Rng>(
+    iter: impl Iterator<Item = DataRecord>,
+    k: usize,
+    rng: &mut R,
+) -> Vec<DataRecord> {
+    let mut reservoir = Vec::with_capacity(k);
+    if k == 0 {
+        return reservoir;
+    }
+
+    for (i, record) in iter.enumerate() {
+        if i < k {
+            reservoir.push(record);
+        } else {
+            let j = rng.gen_range(0..=i);
+            if j < k {
+                reservoir[j] = record;
+            }
+        }
+    }
+
+    reservoir
+}
+
+/// `reservoir_sample` over a slice with a `SmallRng` seeded from `seed`
+///
+/// The same seed always yields the same sample.
+pub fn reservoir_sample_seeded(records: &[DataRecord], k: usize, seed: u64) -> Vec<DataRecord> {
+    let mut rng = SmallRng::seed_from_u64(seed);
+    reservoir_sample(records.iter().cloned(), k, &mut rng)
+}
+
+/// Remove records with duplicate IDs
+///
+/// Output order follows the first occurrence of each ID regardless of which
+/// duplicate is kept, so re-running a pipeline does not reshuffle results.
+/// Returns a new `Vec`; the input is consumed rather than mutated in place.
+pub fn dedup_records(records: Vec<DataRecord>, strategy: DedupStrategy) -> Vec<DataRecord> {
+    // IndexMap keeps a key's original position when its value is replaced
+    let mut kept: IndexMap<String, DataRecord> = IndexMap::with_capacity(records.len());
+
+    for record in records {
+        match kept.entry(record.id.to_string()) {
+            indexmap::map::Entry::Vacant(slot) => {
+                slot.insert(record);
+            }
+            indexmap::map::Entry::Occupied(mut slot) => {
+                let replace = match strategy {
+                    DedupStrategy::KeepFirst => false,
+                    DedupStrategy::KeepLast => true,
+                    DedupStrategy::KeepHighestValue => record.value > slot.get().value,
+                };
+                if replace {
+                    slot.insert(record);
+                }
+            }
+        }
+    }
+
+    kept.into_values().collect()
+}
+
+/// Quote a CSV field per RFC 4180 when it contains a delimiter, quote or
+/// line break
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Split CSV text into rows of unescaped fields per RFC 4180
+///
+/// Quoted fields may contain commas, doubled quotes and line breaks. Both
+/// `\n` and `\r\n` row endings are accepted and blank lines are skipped.
+fn parse_csv_rows(csv: &str) -> Result<Vec<Vec<String>>, ProcessingError> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = csv.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                if row.len() > 1 || !row[0].is_empty() {
+                    rows.push(std::mem::take(&mut row));
+                } else {
+                    row.clear();
+                }
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(ProcessingError::InvalidArgument(
+            "CSV ends inside a quoted field".to_string(),
+        ));
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Export records as RFC 4180 CSV
+///
+/// The header is `id,value,category,timestamp,metadata`. Values use `f64`'s
+/// `Display`, which round-trips exactly; metadata is written as a JSON
+/// object with sorted keys, or left empty when absent.
+pub fn records_to_csv(records: &[DataRecord]) -> Result<String, ProcessingError> {
+    let mut csv = String::from("id,value,category,timestamp,metadata\n");
+
+    for record in records {
+        let metadata = match &record.metadata {
+            Some(m) => serde_json::to_string(&m.iter().collect::<BTreeMap<_, _>>())?,
+            None => String::new(),
+        };
+        let fields = [
+            escape_csv_field(&record.id),
+            record.value.to_string(),
+            escape_csv_field(&record.category),
+            escape_csv_field(&record.timestamp),
+            escape_csv_field(&metadata),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+
+    Ok(csv)
+}
+
+/// Parse records from CSV produced by [`records_to_csv`]
+///
+/// The first row must be the `id,value,category,timestamp` header, optionally
+/// followed by a `metadata` column. An empty metadata cell yields `None`.
+pub fn records_from_csv(csv: &str) -> Result<Vec<DataRecord>, ProcessingError> {
+    let mut rows = parse_csv_rows(csv)?.into_iter();
+    let header = rows
+        .next()
+        .ok_or_else(|| ProcessingError::InvalidArgument("CSV has no header row".to_string()))?;
+    let has_metadata = match header.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["id", "value", "category", "timestamp"] => false,
+        ["id", "value", "category", "timestamp", "metadata"] => true,
+        _ => {
+            return Err(ProcessingError::InvalidArgument(format!(
+                "Unexpected CSV header: {}",
+                header.join(",")
+            )))
+        }
+    };
+
+    rows.enumerate()
+        .map(|(idx, row)| {
+            let line = idx + 2;
+            if row.len() != header.len() {
+                return Err(ProcessingError::InvalidArgument(format!(
+                    "Row {}: expected {} fields, found {}",
+                    line,
+                    header.len(),
+                    row.len()
+                )));
+            }
+            let mut fields = row.into_iter();
+            let mut next = || fields.next().unwrap_or_default();
+            let id = next();
+            let value = next().parse::<f64>().map_err(|e| {
+                ProcessingError::InvalidArgument(format!("Row {}: invalid value: {}", line, e))
+            })?;
+            let category = next();
+            let timestamp = next();
+            let metadata = match next() {
+                m if has_metadata && !m.is_empty() => Some(serde_json::from_str(&m)?),
+                _ => None,
+            };
+            // Identity conversions unless the `compact_str` feature is on
+            #[allow(clippy::useless_conversion)]
+            let record = DataRecord {
+                id: id.into(),
+                value,
+                category: category.into(),
+                timestamp,
+                metadata,
+            };
+            Ok(record)
+        })
+        .collect()
+}
+
+/// Hash index from record ID to record for O(1) lookups
+///
+/// Borrows the records when built from a slice and takes ownership when built
+/// from a `Vec`, so a binding can keep a `RecordIndex<'static>` between calls.
+#[derive(Debug, Clone)]
+pub struct RecordIndex<'a> {
+    records: Cow<'a, [DataRecord]>,
+    positions: HashMap<RecordString, usize>,
+}
+
+impl<'a> RecordIndex<'a> {
+    /// Index `records` by ID, failing on the first repeated ID
+    pub fn build(records: impl Into<Cow<'a, [DataRecord]>>) -> Result<Self, ProcessingError> {
+        let records = records.into();
+        let mut positions = HashMap::with_capacity(records.len());
+        for (idx, record) in records.iter().enumerate() {
+            if positions.insert(record.id.clone(), idx).is_some() {
+                return Err(ProcessingError::DuplicateId(record.id.to_string()));
+            }
+        }
+        Ok(RecordIndex { records, positions })
+    }
+
+    /// Index `records` by ID, keeping the last occurrence of a repeated ID
+    pub fn rebuild(records: impl Into<Cow<'a, [DataRecord]>>) -> Self {
+        let records = records.into();
+        let positions = records
+            .iter()
+            .enumerate()
+            .map(|(idx, record)| (record.id.clone(), idx))
+            .collect();
+        RecordIndex { records, positions }
+    }
+
+    /// Look up a record by ID
+    pub fn get(&self, id: &str) -> Option<&DataRecord> {
+        self.positions.get(id).map(|&idx| &self.records[idx])
+    }
+
+    /// Look up several IDs, keeping `None` for IDs that are not indexed
+    pub fn get_many(&self, ids: &[&str]) -> Vec<Option<&DataRecord>> {
+        ids.iter().map(|id| self.get(id)).collect()
+    }
+
+    /// Whether `id` is indexed
+    pub fn contains(&self, id: &str) -> bool {
+        self.positions.contains_key(id)
+    }
+
+    /// Number of distinct indexed IDs
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Whether the index is empty
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+}
+
+/// `(timestamp, value)` pairs ordered by parsed timestamp
+///
+/// The sort is stable; unparseable timestamps go last in input order.
+fn values_by_time(records: &[DataRecord]) -> Vec<(String, f64)> {
+    let mut timed: Vec<(Option<DateTime<Utc>>, &DataRecord)> = records
+        .iter()
+        .map(|r| (parse_timestamp(&r.timestamp), r))
+        .collect();
+    timed.sort_by(|a, b| compare_timestamps(a.0, b.0));
+    timed
+        .into_iter()
+        .map(|(_, r)| (r.timestamp.clone(), r.value))
+        .collect()
+}
+
+/// Mean of each value and up to `window - 1` predecessors, in O(n)
+///
+/// Finite values go through a compensated running sum, so evicting a large
+/// value does not leave rounding error behind in later means. NaN and
+/// infinities are counted rather than summed, so each mean is NaN or
+/// infinite only while such a value is inside its window, as a direct sum
+/// would be.
+fn trailing_means(values: &[f64], window: usize) -> Vec<f64> {
+    #[derive(Default)]
+    struct WindowSum {
+        sum: f64,
+        compensation: f64,
+        nan: usize,
+        pos_inf: usize,
+        neg_inf: usize,
+    }
+
+    impl WindowSum {
+        fn update(&mut self, value: f64, entering: bool) {
+            if value.is_finite() {
+                let delta = if entering { value } else { -value };
+                add_compensated(&mut self.sum, &mut self.compensation, delta);
+                return;
+            }
+            let count = if value.is_nan() {
+                &mut self.nan
+            } else if value > 0.0 {
+                &mut self.pos_inf
+            } else {
+                &mut self.neg_inf
+            };
+            if entering {
+                *count += 1;
+            } else {
+                *count -= 1;
+            }
+        }
+
+        fn total(&self) -> f64 {
+            match (self.nan, self.pos_inf, self.neg_inf) {
+                (0, 0, 0) => compensated_total(self.sum, self.compensation),
+                (0, _, 0) => f64::INFINITY,
+                (0, 0, _) => f64::NEG_INFINITY,
+                _ => f64::NAN,
+            }
+        }
+    }
+
+    let window = window.max(1);
+    let mut sum = WindowSum::default();
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| {
+            sum.update(value, true);
+            if i >= window {
+                sum.update(values[i - window], false);
+            }
+            sum.total() / (i + 1).min(window) as f64
+        })
+        .collect()
+}
+
+/// Simple moving average of values in timestamp order
+///
+/// Each point averages its own value and up to `window - 1` predecessors, so
+/// the first points (the warm-up) average whatever is available. A `window`
+/// of 0 is treated as 1. Runs in O(n) using a compensated running sum.
+pub fn compute_sma(records: &[DataRecord], window: usize) -> Vec<(String, f64)> {
+    let series = values_by_time(records);
+    let values: Vec<f64> = series.iter().map(|(_, v)| *v).collect();
+
+    series
+        .into_iter()
+        .zip(trailing_means(&values, window))
+        .map(|((timestamp, _), avg)| (timestamp, avg))
+        .collect()
+}
+
+/// Exponential moving average of values in timestamp order
+///
+/// The first point is its own value; after that each point is
+/// `alpha * value + (1 - alpha) * previous`. `alpha` must be in `(0.0, 1.0]`.
+pub fn compute_ema(
+    records: &[DataRecord],
+    alpha: f64,
+) -> Result<Vec<(String, f64)>, ProcessingError> {
+    if !(alpha > 0.0 && alpha <= 1.0) {
+        return Err(ProcessingError::InvalidArgument(format!(
+            "alpha must be in (0.0, 1.0], got {}",
+            alpha
+        )));
+    }
+
+    let mut previous: Option<f64> = None;
+    Ok(values_by_time(records)
+        .into_iter()
+        .map(|(timestamp, value)| {
+            let ema = match previous {
+                Some(prev) if alpha < 1.0 => alpha * value + (1.0 - alpha) * prev,
+                _ => value,
+            };
+            previous = Some(ema);
+            (timestamp, ema)
+        })
+        .collect())
+}
+
+/// Smoothed rate of change of values, in units per second
+///
+/// Records are ordered by timestamp and the discrete derivative
+/// `(v[i] - v[i-1]) / Δt` is taken over each adjacent pair, labelled with
+/// the later record's timestamp. The derivatives are then smoothed like
+/// `compute_sma`: each point averages itself and up to
+/// `smoothing_window - 1` predecessors, so a window of 1 returns the raw
+/// derivative. Smoothing runs in O(n) using the same rolling sum as
+/// `compute_sma`. Records with unparseable timestamps are skipped, as are
+/// pairs with the same timestamp. Errors if fewer than 2 timestamped records
+/// remain or `smoothing_window` is 0.
+pub fn compute_rate_of_change(
+    records: &[DataRecord],
+    smoothing_window: usize,
+) -> Result<Vec<(String, f64)>, ProcessingError> {
+    if smoothing_window == 0 {
+        return Err(ProcessingError::InvalidArgument(
+            "smoothing_window must be greater than 0".to_string(),
+        ));
+    }
+    let mut timed: Vec<(DateTime<Utc>, &DataRecord)> = records
+        .iter()
+        .filter_map(|r| parse_timestamp(&r.timestamp).map(|t| (t, r)))
+        .collect();
+    if timed.len() < 2 {
+        return Err(ProcessingError::InvalidArgument(format!(
+            "Rate of change needs at least 2 timestamped records, got {}",
+            timed.len()
+        )));
+    }
+    timed.sort_by_key(|(t, _)| *t);
+
+    let rates: Vec<(String, f64)> = timed
+        .windows(2)
+        .filter_map(|pair| {
+            let ((t0, prev), (t1, next)) = (pair[0], pair[1]);
+            let seconds = (t1 - t0).num_milliseconds() as f64 / 1000.0;
+            (seconds > 0.0).then(|| (next.timestamp.clone(), (next.value - prev.value) / seconds))
+        })
+        .collect();
+
+    let values: Vec<f64> = rates.iter().map(|(_, rate)| *rate).collect();
+    Ok(rates
+        .into_iter()
+        .zip(trailing_means(&values, smoothing_window))
+        .map(|((timestamp, _), avg)| (timestamp, avg))
+        .collect())
+}
+
+/// Whether two metadata maps hold the same pairs, treating `None` as empty
+fn metadata_eq(a: &Option<HashMap<String, String>>, b: &Option<HashMap<String, String>>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a == b,
+        (Some(m), None) | (None, Some(m)) => m.is_empty(),
+        (None, None) => true,
+    }
+}
+
+/// Compare two snapshots of records by `id` in O(n + m)
+///
+/// A record is changed when its `value`, `category` or `metadata` differs;
+/// timestamps are ignored. A missing metadata map equals an empty one. If an
+/// ID repeats within a snapshot, its last occurrence is used. `added` and
+/// `changed` follow the order of `after`, `removed` the order of `before`.
+pub fn record_diff(before: &[DataRecord], after: &[DataRecord]) -> RecordDiff {
+    let before_by_id: HashMap<&str, &DataRecord> =
+        before.iter().map(|r| (r.id.as_str(), r)).collect();
+    let after_by_id: HashMap<&str, &DataRecord> =
+        after.iter().map(|r| (r.id.as_str(), r)).collect();
+
+    let mut diff = RecordDiff::default();
+    let mut seen: HashSet<&str> = HashSet::with_capacity(after_by_id.len());
+    for record in after {
+        let id = record.id.as_str();
+        // Only the last occurrence of an ID counts
+        if !std::ptr::eq(after_by_id[id], record) || !seen.insert(id) {
+            continue;
+        }
+        match before_by_id.get(id) {
+            None => diff.added.push(record.clone()),
+            Some(old) => {
+                let same = old.value == record.value
+                    && old.category == record.category
+                    && metadata_eq(&old.metadata, &record.metadata);
+                if same {
+                    diff.unchanged_count += 1;
+                } else {
+                    diff.changed.push(((*old).clone(), record.clone()));
+                }
+            }
+        }
+    }
+
+    let mut removed_seen: HashSet<&str> = HashSet::new();
+    for record in before {
+        let id = record.id.as_str();
+        if !after_by_id.contains_key(id)
+            && std::ptr::eq(before_by_id[id], record)
+            && removed_seen.insert(id)
+        {
+            diff.removed.push(record.clone());
+        }
+    }
+
+    diff
+}
+
+/// Return the page of `limit` records starting at `offset`
+///
+/// The slice is paged as given, so sort it first for a stable order. An
+/// offset past the end yields an empty page.
+pub fn paginate_records(records: &[DataRecord], offset: usize, limit: usize) -> PaginatedResult {
+    let total = records.len();
+    let start = offset.min(total);
+    let end = start.saturating_add(limit).min(total);
+
+    PaginatedResult {
+        items: records[start..end].to_vec(),
+        total,
+        offset,
+        limit,
+        has_next: end < total,
+        has_prev: offset > 0,
+    }
+}
+
+/// Encode a record ID as an opaque pagination cursor
+pub fn encode_cursor(id: &str) -> String {
+    URL_SAFE_NO_PAD.encode(id)
+}
+
+/// Decode a cursor produced by `encode_cursor` back into a record ID
+pub fn decode_cursor(cursor: &str) -> Option<String> {
+    let bytes = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+    String::from_utf8(bytes).ok()
+}
+
+/// Return up to `limit` records following the record named by a cursor
+///
+/// `after_id` is a cursor from a previous page's `next_cursor` (the base64
+/// encoded ID of its last item); `None` starts from the beginning. A cursor
+/// that does not decode or names an ID missing from `records` yields an
+/// empty page. `next_cursor` is only set while more records remain. Errors
+/// if `limit` is 0, since such a page could never advance.
+pub fn cursor_paginate_records(
+    records: &[DataRecord],
+    after_id: Option<&str>,
+    limit: usize,
+) -> Result<CursorPaginatedResult, ProcessingError> {
+    if limit == 0 {
+        return Err(ProcessingError::InvalidArgument(
+            "limit must be greater than 0".to_string(),
+        ));
+    }
+    let start = match after_id {
+        None => 0,
+        Some(cursor) => {
+            let position =
+                decode_cursor(cursor).and_then(|id| records.iter().position(|r| r.id == id));
+            match position {
+                Some(index) => index + 1,
+                None => return Ok(CursorPaginatedResult::default()),
+            }
+        }
+    };
+    let end = start.saturating_add(limit).min(records.len());
+    let items = records[start..end].to_vec();
+    let has_more = end < records.len();
+    let next_cursor = if has_more {
+        items.last().map(|r| encode_cursor(&r.id))
+    } else {
+        None
+    };
+
+    Ok(CursorPaginatedResult {
+        items,
+        next_cursor,
+        has_more,
+    })
+}
+
+/// Count records by the pair of values they hold for two metadata keys
+///
+/// Keys are `(value of key_a, value of key_b)`, so the order of the keys
+/// matters. Records missing either key are skipped.
+pub fn compute_metadata_cooccurrence(
+    records: &[DataRecord],
+    key_a: &str,
+    key_b: &str,
+) -> HashMap<(String, String), usize> {
+    let mut counts: HashMap<(String, String), usize> = HashMap::new();
+    for metadata in records.iter().filter_map(|r| r.metadata.as_ref()) {
+        if let (Some(a), Some(b)) = (metadata.get(key_a), metadata.get(key_b)) {
+            *counts.entry((a.clone(), b.clone())).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// Count `(from, to)` category transitions between consecutive records
+///
+/// Records are ordered by timestamp first (unparseable timestamps sort last,
+/// ties keep input order). Self-transitions such as `("A", "A")` are counted.
+pub fn compute_category_transition_matrix(
+    records: &[DataRecord],
+) -> HashMap<(String, String), usize> {
+    let mut timed: Vec<(Option<DateTime<Utc>>, &str)> = records
+        .iter()
+        .map(|r| (parse_timestamp(&r.timestamp), r.category.as_str()))
+        .collect();
+    timed.sort_by(|a, b| compare_timestamps(a.0, b.0));
+
+    let mut counts: HashMap<(String, String), usize> = HashMap::new();
+    for pair in timed.windows(2) {
+        let key = (pair[0].1.to_string(), pair[1].1.to_string());
+        *counts.entry(key).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Pretty-printed JSON Schema describing a `DataRecord`
+#[cfg(feature = "schema")]
+pub fn data_record_schema() -> String {
+    let schema = schemars::schema_for!(DataRecord);
+    serde_json::to_string_pretty(&schema).expect("JSON Schema always serializes")
+}
+
+/// Encode records in the compact binary `postcard` format
+///
+/// The format is meant for batches sent by embedded devices, but this crate
+/// is not `no_std`: rayon, regex and the `HashMap` in `DataRecord::metadata`
+/// all need `std`, and the JSON functions keep `serde_json` a required
+/// dependency. Devices instead serialize a borrowed struct with the same
+/// fields, as in `examples/embedded.rs`. `DataRecord` has no postcard
+/// `MaxSize` bound either, since its strings and metadata are unbounded.
+#[cfg(feature = "postcard")]
+pub fn records_to_postcard(records: &[DataRecord]) -> Result<Vec<u8>, ProcessingError> {
+    postcard::to_allocvec(records)
+        .map_err(|e| ProcessingError::InvalidArgument(format!("Postcard encode error: {}", e)))
+}
+
+/// Encode records with `bincode`, for IPC between builds of this crate
+///
+/// Much faster to encode and decode than JSON, but the format is not
+/// self-describing: both sides must agree on the `DataRecord` layout.
+#[cfg(feature = "bincode")]
+pub fn encode_records_binary(records: &[DataRecord]) -> Result<Vec<u8>, ProcessingError> {
+    bincode::serialize(records)
+        .map_err(|e| ProcessingError::InvalidArgument(format!("Bincode encode error: {}", e)))
+}
+
+/// Decode records produced by `encode_records_binary`
+///
+/// Fails on truncated or malformed input, or trailing bytes after the records.
+#[cfg(feature = "bincode")]
+pub fn decode_records_binary(bytes: &[u8]) -> Result<Vec<DataRecord>, ProcessingError> {
+    use bincode::Options;
+
+    // `bincode::serialize`'s encoding, but strict about trailing bytes
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .reject_trailing_bytes()
+        .deserialize(bytes)
+        .map_err(|e| ProcessingError::InvalidArgument(format!("Bincode decode error: {}", e)))
+}
+
+/// Decode records produced by `records_to_postcard`
+///
+/// Fails on truncated or malformed input, or trailing bytes after the records.
+#[cfg(feature = "postcard")]
+pub fn records_from_postcard(bytes: &[u8]) -> Result<Vec<DataRecord>, ProcessingError> {
+    let (records, rest): (Vec<DataRecord>, &[u8]) = postcard::take_from_bytes(bytes)
+        .map_err(|e| ProcessingError::InvalidArgument(format!("Postcard decode error: {}", e)))?;
+    if !rest.is_empty() {
+        return Err(ProcessingError::InvalidArgument(format!(
+            "Postcard decode error: {} trailing bytes",
+            rest.len()
+        )));
+    }
+    Ok(records)
+}
+
+/// Build `count` deterministic sample records
+///
+/// Records cycle through categories `A`–`D` and 1000 distinct values, with
+/// the record's index stored in `metadata["index"]`. Used by the bindings'
+/// `generateSampleData` and the benchmarks.
+pub fn generate_sample_data(count: usize) -> Vec<DataRecord> {
+    const CATEGORIES: [&str; 4] = ["A", "B", "C", "D"];
+    let mut records = Vec::with_capacity(count);
+
+    for i in 0..count {
+        let mut metadata = HashMap::new();
+        metadata.insert("index".to_string(), i.to_string());
+
+        // Identity conversion unless the `compact_str` feature is on
+        #[allow(clippy::useless_conversion)]
+        let record = DataRecord {
+            id: format!("record_{}", i).into(),
+            value: ((i % 1000) as f64) * 10.5,
+            category: CATEGORIES[i % 4].into(),
+            timestamp: format!("2024-01-15T10:{:02}:{:02}Z", (i / 60) % 60, i % 60),
+            metadata: Some(metadata),
+        };
+        records.push(record);
+    }
+
+    records
+}
+
+/// Gini coefficient of a non-negative value distribution
+///
+/// 0.0 means every value is equal; values approach 1.0 as the total
+/// concentrates in one element (exactly `(n - 1) / n` at the extreme).
+/// Empty and single-element slices, and all-zero slices, return 0.0.
+/// Negative, NaN or infinite values return `InvalidArgument`.
+pub fn compute_gini(values: &[f64]) -> Result<f64, ProcessingError> {
+    if values.iter().any(|v| !v.is_finite() || *v < 0.0) {
+        return Err(ProcessingError::InvalidArgument(
+            "Gini coefficient requires finite, non-negative values".to_string(),
+        ));
+    }
+    if values.len() < 2 {
+        return Ok(0.0);
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let n = sorted.len() as f64;
+    let total: f64 = sorted.iter().sum();
+    if total == 0.0 {
+        return Ok(0.0);
+    }
+    let weighted: f64 = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (i + 1) as f64 * v)
+        .sum();
+
+    Ok((2.0 * weighted - (n + 1.0) * total) / (n * total))
+}
+
+/// Records from a trailing time span, with statistics kept up to date
+///
+/// The window covers `[now - duration_seconds, now]`, where `now` is the
+/// latest timestamp pushed or passed to `advance_to`; older records are
+/// evicted as `now` moves forward. Records sit in a ring buffer ordered by
+/// timestamp, so in-order pushes append in O(1) and eviction pops from the
+/// front; a late record is inserted at its sorted position. Statistics are
+/// maintained with a `StatsAccumulator`, making `current_stats` O(1).
+#[derive(Debug, Clone)]
+pub struct SlidingWindow {
+    duration_seconds: u64,
+    span: chrono::TimeDelta,
+    entries: VecDeque<(DateTime<Utc>, DataRecord)>,
+    stats: StatsAccumulator,
+    now: Option<DateTime<Utc>>,
+}
+
+impl SlidingWindow {
+    /// Create an empty window spanning `duration_seconds`
+    pub fn new(duration_seconds: u64) -> Self {
+        let span = i64::try_from(duration_seconds)
+            .ok()
+            .and_then(chrono::TimeDelta::try_seconds)
+            .unwrap_or(chrono::TimeDelta::MAX);
+        Self {
+            duration_seconds,
+            span,
+            entries: VecDeque::new(),
+            stats: StatsAccumulator::new(),
+            now: None,
+        }
+    }
+
+    pub fn duration_seconds(&self) -> u64 {
+        self.duration_seconds
+    }
+
+    /// Add a record and evict everything that has fallen out of the window
+    ///
+    /// A record already older than the window is dropped. Returns
+    /// `InvalidArgument` if the timestamp does not parse.
+    pub fn push(&mut self, record: DataRecord) -> Result<(), ProcessingError> {
+        let at = validate_timestamp_format(&record.timestamp)?;
+        if self.cutoff().is_some_and(|cutoff| at < cutoff) {
+            return Ok(());
+        }
+
+        self.stats.add_record(&record);
+        let position = self.entries.partition_point(|(t, _)| *t <= at);
+        self.entries.insert(position, (at, record));
+        self.advance(at);
+        Ok(())
+    }
+
+    /// Move `now` forward without adding a record, evicting expired ones
+    ///
+    /// Timestamps earlier than the current `now` are ignored. Returns
+    /// `InvalidArgument` if the timestamp does not parse.
+    pub fn advance_to(&mut self, timestamp: &str) -> Result<(), ProcessingError> {
+        let at = validate_timestamp_format(timestamp)?;
+        self.advance(at);
+        Ok(())
+    }
+
+    /// Statistics over the records currently in the window
+    ///
+    /// An empty window reports zero for every field.
+    pub fn current_stats(&self) -> ProcessResult {
+        self.stats.finalize()
+    }
+
+    pub fn current_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Timestamp of the oldest record in the window, as it was pushed
+    pub fn oldest_timestamp(&self) -> Option<String> {
+        self.entries.front().map(|(_, r)| r.timestamp.clone())
+    }
+
+    /// Timestamp of the newest record in the window, as it was pushed
+    pub fn newest_timestamp(&self) -> Option<String> {
+        self.entries.back().map(|(_, r)| r.timestamp.clone())
+    }
+
+    /// Earliest timestamp still inside the window
+    fn cutoff(&self) -> Option<DateTime<Utc>> {
+        self.now?.checked_sub_signed(self.span)
+    }
+
+    fn advance(&mut self, at: DateTime<Utc>) {
+        if self.now.is_some_and(|now| now >= at) {
+            return;
+        }
+        self.now = Some(at);
+
+        let Some(cutoff) = self.cutoff() else {
+            return;
+        };
+        while self.entries.front().is_some_and(|(t, _)| *t < cutoff) {
+            if let Some((_, record)) = self.entries.pop_front() {
+                self.stats.remove_record(&record);
+            }
+        }
+    }
+}
+
+/// Records between the running statistics `LineProcessor::push_line` reports
+pub const LINE_PROCESSOR_BATCH_SIZE: usize = 10_000;
+
+/// Incremental processing of newline-delimited JSON records
+///
+/// Each line is parsed, validated and folded into a `StatsAccumulator` as it
+/// is pushed, so records are never held; `flush` returns the same statistics
+/// `process_records` would for all pushed records. The accumulator keeps one
+/// entry per distinct value, so memory grows with the number of distinct
+/// values rather than with the number of lines.
+#[derive(Debug, Clone, Default)]
+pub struct LineProcessor {
+    stats: StatsAccumulator,
+}
+
+impl LineProcessor {
+    /// Create an empty processor
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse, validate and add one JSON record
+    ///
+    /// Blank lines are ignored. Every `LINE_PROCESSOR_BATCH_SIZE` records,
+    /// returns the statistics over every record pushed so far. A line that
+    /// fails to parse or validate returns an error and leaves the processor
+    /// unchanged.
+    pub fn push_line(&mut self, line: &str) -> Result<Option<ProcessResult>, ProcessingError> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(None);
+        }
+
+        let record: DataRecord = serde_json::from_str(line)?;
+        validate_record(&record, ValidationMode::Lenient)
+            .map_err(|e| ProcessingError::ValidationFailed { errors: vec![e] })?;
+        self.stats.add_record(&record);
+
+        if !self.stats.count().is_multiple_of(LINE_PROCESSOR_BATCH_SIZE) {
+            return Ok(None);
+        }
+        Ok(Some(self.stats.finalize()))
+    }
+
+    /// Statistics over every record pushed since creation or the last `reset`
+    ///
+    /// Returns `EmptyInput` if no records were pushed and `Overflow` if the
+    /// total exceeds the range of `f64`. Pushing may continue afterwards.
+    pub fn flush(&self) -> Result<ProcessResult, ProcessingError> {
+        if self.stats.count() == 0 {
+            return Err(ProcessingError::EmptyInput);
+        }
+
+        let result = self.stats.finalize();
+        if !result.total_value.is_finite() {
+            return Err(ProcessingError::Overflow);
+        }
+        Ok(result)
+    }
+
+    /// Discard all statistics
+    pub fn reset(&mut self) {
+        self.stats = StatsAccumulator::new();
+    }
+
+    /// Number of records pushed since creation or the last `reset`
+    pub fn count(&self) -> usize {
+        self.stats.count()
+    }
+}
+
+/// Check an Elasticsearch index name against the server's naming rules
+fn validate_elasticsearch_index_name(index_name: &str) -> Result<(), ProcessingError> {
+    let invalid = index_name.is_empty()
+        || index_name == "."
+        || index_name == ".."
+        || index_name.starts_with(['-', '_', '+'])
+        || index_name
+            .chars()
+            .any(|c| c.is_uppercase() || c.is_whitespace() || "\\/*?\"<>|,#:".contains(c));
+    if invalid {
+        return Err(ProcessingError::InvalidArgument(format!(
+            "Invalid Elasticsearch index name '{}'",
+            index_name
+        )));
+    }
+    Ok(())
+}
+
+/// Build Elasticsearch bulk API NDJSON from `(document ID, source)` pairs
+///
+/// Each document becomes an `index` action line followed by its source
+/// line; `_id` is included only when an ID is given. The output ends with
+/// the trailing newline the bulk API requires. Returns `InvalidArgument`
+/// for an index name Elasticsearch would reject.
+pub fn to_elasticsearch_bulk<S, I>(
+    documents: I,
+    index_name: &str,
+) -> Result<String, ProcessingError>
+where
+    S: Serialize,
+    I: IntoIterator<Item = (Option<String>, S)>,
+{
+    validate_elasticsearch_index_name(index_name)?;
+
+    let mut ndjson = String::new();
+    for (id, source) in documents {
+        let action = match id {
+            Some(id) => serde_json::json!({ "index": { "_index": index_name, "_id": id } }),
+            None => serde_json::json!({ "index": { "_index": index_name } }),
+        };
+        ndjson.push_str(&action.to_string());
+        ndjson.push('\n');
+        ndjson.push_str(&serde_json::to_string(&source)?);
+        ndjson.push('\n');
+    }
+
+    Ok(ndjson)
+}
+
+/// Export records as Elasticsearch bulk API NDJSON
+///
+/// With `id_field`, each action sets `_id` from that field: `"id"` uses
+/// `DataRecord::id`, any other name is looked up in `metadata`. Returns
+/// `InvalidArgument` if a record lacks the field or the index name is
+/// invalid.
+pub fn records_to_elasticsearch_bulk(
+    records: &[DataRecord],
+    index_name: &str,
+    id_field: Option<&str>,
+) -> Result<String, ProcessingError> {
+    let documents = records
+        .iter()
+        .map(|record| {
+            let id = match id_field {
+                None => None,
+                Some("id") => Some(record.id.to_string()),
+                Some(field) => Some(
+                    record
+                        .metadata
+                        .as_ref()
+                        .and_then(|m| m.get(field))
+                        .cloned()
+                        .ok_or_else(|| {
+                            ProcessingError::InvalidArgument(format!(
+                                "Record '{}' has no metadata field '{}'",
+                                record.id, field
+                            ))
+                        })?,
+                ),
+            };
+            Ok((id, record))
+        })
+        .collect::<Result<Vec<_>, ProcessingError>>()?;
+
+    to_elasticsearch_bulk(documents, index_name)
+}
+
+/// Records whose `metadata[key]` satisfies `matches`; records without
+/// metadata or without the key are skipped
+fn filter_by_metadata_with<F>(records: &[DataRecord], key: &str, matches: F) -> Vec<DataRecord>
+where
+    F: Fn(&str) -> bool + Sync,
+{
+    records
+        .par_iter()
+        .filter(|record| {
+            record
+                .metadata
+                .as_ref()
+                .and_then(|m| m.get(key))
+                .is_some_and(|v| matches(v))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Filter records whose `metadata[key]` equals `value` exactly
+///
+/// Records with no metadata, or without `key`, are skipped.
+pub fn filter_by_metadata(records: &[DataRecord], key: &str, value: &str) -> Vec<DataRecord> {
+    filter_by_metadata_with(records, key, |v| v == value)
+}
+
+/// Filter records whose `metadata[key]` starts with `prefix`
+///
+/// Records with no metadata, or without `key`, are skipped.
+pub fn filter_by_metadata_prefix(
+    records: &[DataRecord],
+    key: &str,
+    prefix: &str,
+) -> Vec<DataRecord> {
+    filter_by_metadata_with(records, key, |v| v.starts_with(prefix))
+}
+
+/// Filter records whose `metadata[key]` matches the regular expression
+/// `pattern` anywhere in the value (anchor with `^`/`$` for a full match)
+///
+/// Records with no metadata, or without `key`, are skipped. Returns
+/// `InvalidArgument` if `pattern` does not compile.
+pub fn filter_by_metadata_regex(
+    records: &[DataRecord],
+    key: &str,
+    pattern: &str,
+) -> Result<Vec<DataRecord>, ProcessingError> {
+    let regex = regex::Regex::new(pattern).map_err(|e| {
+        ProcessingError::InvalidArgument(format!("Invalid pattern '{}': {}", pattern, e))
+    })?;
+    Ok(filter_by_metadata_with(records, key, |v| regex.is_match(v)))
+}
+
+/// Count how often each value of a field occurs
+///
+/// `extractor` returns the value to count for a record, or None to skip it,
+/// so the counts sum to the number of records it returned a value for. Use
+/// `FieldName::extract` for the built-in fields.
+pub fn compute_frequency_distribution(
+    records: &[DataRecord],
+    extractor: impl Fn(&DataRecord) -> Option<String> + Sync,
+) -> HashMap<String, usize> {
+    let values: Vec<String> = records.par_iter().filter_map(&extractor).collect();
+
+    let mut counts = HashMap::new();
+    for value in values {
+        *counts.entry(value).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_record(id: &str, value: f64, category: &str) -> DataRecord {
+        DataRecord {
+            id: id.into(),
+            value,
+            category: category.into(),
+            timestamp: "2024-01-15T10:00:00Z".to_string(),
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_record() {
+        let valid = create_test_record("1", 100.0, "A");
+        assert!(validate_record(&valid, ValidationMode::Lenient).is_ok());
+
+        let invalid_empty_id = DataRecord {
+            id: "".into(),
+            ..valid.clone()
+        };
+        assert!(validate_record(&invalid_empty_id, ValidationMode::Lenient).is_err());
+
+        let invalid_negative = DataRecord {
+            value: -10.0,
+            ..valid.clone()
+        };
+        let error = validate_record(&invalid_negative, ValidationMode::Lenient).unwrap_err();
+        assert_eq!(error.field, "value");
+
+        let cases = [
+            (
+                "id",
+                DataRecord {
+                    id: "".into(),
+                    ..valid.clone()
+                },
+            ),
+            (
+                "category",
+                DataRecord {
+                    category: "".into(),
+                    ..valid.clone()
+                },
+            ),
+            (
+                "timestamp",
+                DataRecord {
+                    timestamp: "".to_string(),
+                    ..valid.clone()
+                },
+            ),
+            (
+                "timestamp",
+                DataRecord {
+                    timestamp: "yesterday".to_string(),
+                    ..valid.clone()
+                },
+            ),
+        ];
+        for (field, record) in cases {
+            let error = validate_record(&record, ValidationMode::Strict).unwrap_err();
+            assert_eq!(error.field, field);
+        }
+    }
+
+    #[test]
+    fn test_process_records() {
+        let records = vec![
+            create_test_record("1", 100.0, "A"),
+            create_test_record("2", 200.0, "B"),
+            create_test_record("3", 150.0, "A"),
+        ];
+
+        let result = process_records(&records).unwrap();
+        assert_eq!(result.total_processed, 3);
+        assert_eq!(result.total_value, 450.0);
+        assert_eq!(result.average_value, 150.0);
+        assert_eq!(result.min_value, 100.0);
+        assert_eq!(result.max_value, 200.0);
+    }
+
+    #[test]
+    fn test_filter_by_category() {
+        let records = vec![
+            create_test_record("1", 100.0, "A"),
+            create_test_record("2", 200.0, "B"),
+            create_test_record("3", 150.0, "A"),
+        ];
+
+        let filtered = filter_by_category(&records, "A");
+        assert_eq!(filtered.len(), 2);
+    }
+
+    #[test]
+    fn test_get_category_stats() {
+        let records = vec![
+            create_test_record("1", 100.0, "A"),
+            create_test_record("2", 200.0, "A"),
+            create_test_record("3", 150.0, "B"),
+        ];
+
+        let stats = get_category_stats(&records, "A").unwrap();
         assert_eq!(stats.count, 2);
         assert_eq!(stats.total_value, 300.0);
         assert_eq!(stats.average_value, 150.0);
     }
+
+    #[test]
+    fn test_compute_heatmap() {
+        let mut records = vec![
+            create_test_record("1", 10.0, "A"),
+            create_test_record("2", 30.0, "A"),
+            create_test_record("3", 20.0, "B"),
+            create_test_record("4", 40.0, "A"),
+            create_test_record("5", 50.0, "B"),
+        ];
+        records[3].timestamp = "2024-01-15T10:20:00Z".to_string();
+        records[4].timestamp = "2024-01-15T10:25:00Z".to_string();
+
+        let rows = compute_heatmap(&records, 15).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].time_bucket, "2024-01-15T10:00:00Z");
+        assert_eq!(rows[1].time_bucket, "2024-01-15T10:15:00Z");
+        assert_eq!(rows[0].category_averages["A"], 20.0);
+        assert_eq!(rows[0].category_averages["B"], 20.0);
+        assert_eq!(rows[1].category_averages["A"], 40.0);
+        assert_eq!(rows[1].category_averages["B"], 50.0);
+        assert!(rows
+            .iter()
+            .flat_map(|r| r.category_averages.values())
+            .all(|v| *v != 0.0));
+    }
+
+    #[test]
+    fn test_compute_heatmap_missing_cell_fill() {
+        let mut records = vec![
+            create_test_record("1", 10.0, "A"),
+            create_test_record("2", 20.0, "B"),
+        ];
+        records[1].timestamp = "2024-01-15T11:00:00Z".to_string();
+
+        let zero = compute_heatmap(&records, 60).unwrap();
+        assert_eq!(zero[0].category_averages["B"], 0.0);
+
+        let nan = compute_heatmap_with_fill(&records, 60, MissingCellFill::NaN).unwrap();
+        assert!(nan[0].category_averages["B"].is_nan());
+        assert!(nan[1].category_averages["A"].is_nan());
+    }
+
+    #[test]
+    fn test_compute_heatmap_rejects_oversized_buckets() {
+        let mut records = vec![create_test_record("1", 10.0, "A")];
+        for bucket_minutes in [u64::MAX / 30, u64::MAX / 60] {
+            assert!(matches!(
+                compute_heatmap(&records, bucket_minutes),
+                Err(ProcessingError::InvalidArgument(_))
+            ));
+        }
+
+        // Before the epoch the bucket starts a whole bucket earlier
+        records[0].timestamp = "1969-12-31T23:59:59Z".to_string();
+        assert!(compute_heatmap(&records, 1 << 40).is_err());
+
+        // A bucket far wider than the data still starts at the epoch
+        records[0].timestamp = "2024-01-15T10:00:00Z".to_string();
+        let rows = compute_heatmap(&records, 1 << 40).unwrap();
+        assert_eq!(rows[0].time_bucket, "1970-01-01T00:00:00Z");
+        assert!(compute_heatmap(&records, 0).unwrap().is_empty());
+    }
+
+    fn with_metadata(mut record: DataRecord, keys: &[&str]) -> DataRecord {
+        record.metadata = Some(
+            keys.iter()
+                .map(|k| (k.to_string(), "x".to_string()))
+                .collect(),
+        );
+        record
+    }
+
+    #[test]
+    fn test_metadata_keys_same_keys() {
+        let records = vec![
+            with_metadata(create_test_record("1", 1.0, "A"), &["b", "a"]),
+            with_metadata(create_test_record("2", 2.0, "A"), &["a", "b"]),
+        ];
+
+        let union = metadata_keys_union(&records);
+        assert_eq!(union, vec!["a", "b"]);
+        assert_eq!(metadata_keys_intersection(&records), union);
+    }
+
+    #[test]
+    fn test_metadata_keys_disjoint() {
+        let records = vec![
+            with_metadata(create_test_record("1", 1.0, "A"), &["a"]),
+            with_metadata(create_test_record("2", 2.0, "A"), &["b"]),
+        ];
+
+        assert_eq!(metadata_keys_union(&records), vec!["a", "b"]);
+        assert!(metadata_keys_intersection(&records).is_empty());
+    }
+
+    #[test]
+    fn test_metadata_keys_with_none_metadata() {
+        let records = vec![
+            with_metadata(create_test_record("1", 1.0, "A"), &["a", "b"]),
+            create_test_record("2", 2.0, "A"),
+        ];
+
+        assert_eq!(metadata_keys_union(&records), vec!["a", "b"]);
+        assert!(metadata_keys_intersection(&records).is_empty());
+    }
+
+    #[test]
+    fn test_records_to_prometheus_metrics() {
+        let mut records = vec![
+            create_test_record("1", 100.0, "A"),
+            create_test_record("2", 2.5, "B"),
+        ];
+        let mut metadata = HashMap::new();
+        metadata.insert("region".to_string(), "us \"east\"".to_string());
+        records[0].metadata = Some(metadata);
+
+        let text = records_to_prometheus_metrics(&records, "record_value", &["region"]).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "# TYPE record_value gauge");
+        assert_eq!(lines[1], r#"record_value{id="1",region="us \"east\""} 100"#);
+        assert_eq!(lines[2], r#"record_value{id="2",region=""} 2.5"#);
+
+        // Every sample line is `name{labels} value` with a parseable value
+        for line in &lines[1..] {
+            let (series, value) = line.rsplit_once(' ').unwrap();
+            assert!(series.starts_with("record_value{") && series.ends_with('}'));
+            assert!(value.parse::<f64>().is_ok());
+        }
+        assert!(text.ends_with('\n'));
+    }
+
+    #[test]
+    fn test_records_to_prometheus_metrics_rejects_bad_names() {
+        let records = vec![create_test_record("1", 1.0, "A")];
+        for (metric, keys) in [
+            ("record-value", &["region"][..]),
+            ("0value", &[][..]),
+            ("", &[][..]),
+            ("record_value", &["id"][..]),
+            ("record_value", &["bad-key"][..]),
+            ("record_value", &["job:name"][..]),
+            ("record_value", &["__name__"][..]),
+            ("record_value", &["region", "region"][..]),
+        ] {
+            assert!(
+                matches!(
+                    records_to_prometheus_metrics(&records, metric, keys),
+                    Err(ProcessingError::InvalidArgument(_))
+                ),
+                "{} {:?}",
+                metric,
+                keys
+            );
+        }
+        assert!(records_to_prometheus_metrics(&records, "ns:record_value", &["_region2"]).is_ok());
+    }
+
+    #[test]
+    fn test_prometheus_helpers() {
+        assert_eq!(prometheus::format_value(2.5), "2.5");
+        assert_eq!(prometheus::format_value(f64::NAN), "NaN");
+        assert_eq!(prometheus::format_value(f64::NEG_INFINITY), "-Inf");
+        assert_eq!(
+            prometheus::escape_label_value("a\\b\"c\nd"),
+            r#"a\\b\"c\nd"#
+        );
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("same", "same"), 0);
+    }
+
+    #[test]
+    fn test_fuzzy_deduplicate() {
+        let records = vec![
+            create_test_record("foo", 1.0, "A"),
+            create_test_record("bar", 2.0, "A"),
+            create_test_record("fo", 3.0, "A"),
+        ];
+
+        let deduped = fuzzy_deduplicate(&records, 1);
+        let ids: Vec<&str> = deduped.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["bar", "fo"]);
+
+        let exact = fuzzy_deduplicate(&records, 0);
+        assert_eq!(exact.len(), 3);
+    }
+
+    #[test]
+    fn test_fuzzy_deduplicate_exact_match() {
+        let records = vec![
+            create_test_record("a", 1.0, "A"),
+            create_test_record("b", 2.0, "A"),
+            create_test_record("a", 3.0, "A"),
+        ];
+
+        let deduped = fuzzy_deduplicate(&records, 0);
+        assert_eq!(deduped.len(), 2);
+        assert_eq!(deduped[0].value, 1.0);
+    }
+
+    #[test]
+    fn test_compute_value_entropy_per_category() {
+        let mut records = Vec::new();
+        // Uniform: one value in each of four buckets
+        for (i, v) in [0.0, 10.0, 20.0, 30.0].iter().enumerate() {
+            records.push(create_test_record(&format!("u{}", i), *v, "uniform"));
+        }
+        // Clustered: three values together, one far away
+        for (i, v) in [0.0, 0.1, 0.2, 30.0].iter().enumerate() {
+            records.push(create_test_record(&format!("c{}", i), *v, "clustered"));
+        }
+
+        let entropy = compute_value_entropy_per_category(&records, 4);
+        assert!((entropy["uniform"] - 2.0).abs() < 1e-12);
+        assert!(entropy["uniform"] > entropy["clustered"]);
+
+        let spike = vec![
+            create_test_record("s1", 5.0, "spike"),
+            create_test_record("s2", 5.0, "spike"),
+        ];
+        assert_eq!(compute_value_entropy_per_category(&spike, 4)["spike"], 0.0);
+    }
+
+    #[test]
+    fn test_patch_records_by_id() {
+        let original = vec![
+            create_test_record("1", 100.0, "A"),
+            create_test_record("2", 200.0, "B"),
+        ];
+        let mut records = original.clone();
+        records[0].metadata = Some(HashMap::from([("keep".to_string(), "yes".to_string())]));
+
+        let patches = HashMap::from([
+            (
+                "1".to_string(),
+                DataRecordPatch {
+                    value: Some(150.0),
+                    category: Some("C".to_string()),
+                    metadata_updates: Some(HashMap::from([(
+                        "new".to_string(),
+                        "value".to_string(),
+                    )])),
+                },
+            ),
+            ("missing".to_string(), DataRecordPatch::default()),
+        ]);
+
+        patch_records_by_id(&mut records, &patches);
+
+        assert_eq!(records[0].value, 150.0);
+        assert_eq!(records[0].category, "C");
+        let metadata = records[0].metadata.as_ref().unwrap();
+        assert_eq!(metadata["keep"], "yes");
+        assert_eq!(metadata["new"], "value");
+
+        assert_eq!(records[1].id, original[1].id);
+        assert_eq!(records[1].value, original[1].value);
+        assert_eq!(records[1].category, original[1].category);
+        assert!(records[1].metadata.is_none());
+    }
+
+    #[test]
+    fn test_compute_autocorrelation_periodic_signal() {
+        let pattern = [1.0, 5.0, 2.0, 8.0];
+        let records: Vec<DataRecord> = (0..40)
+            .map(|i| create_test_record(&i.to_string(), pattern[i % 4], "A"))
+            .collect();
+
+        let acf = compute_autocorrelation(&records, 6);
+        assert_eq!(acf.len(), 6);
+
+        let peak_lag = acf
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+            .map(|(i, _)| i + 1)
+            .unwrap();
+        assert_eq!(peak_lag, 4);
+        assert!((acf[3] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_compute_autocorrelation_short_series() {
+        let records = vec![create_test_record("1", 1.0, "A")];
+        assert_eq!(compute_autocorrelation(&records, 3), vec![0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_validate_metadata_schema() {
+        let mut record = create_test_record("1", 100.0, "A");
+        record.metadata = Some(HashMap::from([
+            ("retries".to_string(), "3".to_string()),
+            ("latency".to_string(), "fast".to_string()),
+            ("cached".to_string(), "true".to_string()),
+            ("flag".to_string(), "yes".to_string()),
+            ("owner".to_string(), "team-a".to_string()),
+            ("note".to_string(), "".to_string()),
+        ]));
+
+        let valid = MetadataSchema {
+            required_numeric: vec!["retries".to_string()],
+            required_boolean: vec!["cached".to_string()],
+            required_non_empty: vec!["owner".to_string()],
+        };
+        assert!(validate_metadata_schema(&record, &valid).is_empty());
+
+        let numeric = MetadataSchema {
+            required_numeric: vec!["latency".to_string()],
+            ..Default::default()
+        };
+        let errors = validate_metadata_schema(&record, &numeric);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("numeric"));
+
+        let boolean = MetadataSchema {
+            required_boolean: vec!["flag".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(validate_metadata_schema(&record, &boolean).len(), 1);
+
+        let non_empty = MetadataSchema {
+            required_non_empty: vec!["note".to_string(), "absent".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(validate_metadata_schema(&record, &non_empty).len(), 2);
+
+        let no_metadata = create_test_record("2", 1.0, "A");
+        assert_eq!(validate_metadata_schema(&no_metadata, &valid).len(), 3);
+    }
+
+    #[test]
+    fn test_compute_percentile_ranks() {
+        let records = vec![
+            create_test_record("1", 30.0, "A"),
+            create_test_record("2", 10.0, "A"),
+            create_test_record("3", 40.0, "A"),
+            create_test_record("4", 20.0, "A"),
+        ];
+
+        let ranks = compute_percentile_ranks(&records);
+        assert_eq!(ranks.len(), 4);
+        assert_eq!(ranks[1].1, 0.0);
+        assert!((ranks[3].1 - 33.33).abs() < 0.01);
+        assert!((ranks[0].1 - 66.67).abs() < 0.01);
+        assert_eq!(ranks[2].1, 100.0);
+        assert_eq!(ranks[3].0.id, "4");
+    }
+
+    #[test]
+    fn test_compute_percentile_ranks_ties() {
+        let records = vec![
+            create_test_record("1", 10.0, "A"),
+            create_test_record("2", 10.0, "A"),
+            create_test_record("3", 20.0, "A"),
+        ];
+
+        let ranks = compute_percentile_ranks(&records);
+        assert_eq!(ranks[0].1, ranks[1].1);
+        assert_eq!(ranks[2].1, 100.0);
+    }
+
+    #[test]
+    fn test_records_to_html_table() {
+        let mut records = vec![
+            create_test_record("<script>", 1.0, "A"),
+            create_test_record("2", 2.0, "B"),
+            create_test_record("3", 3.0, "A"),
+        ];
+        records[1].metadata = Some(HashMap::from([("k".to_string(), "v&w".to_string())]));
+
+        let html = records_to_html_table(&records, None);
+        assert!(html.contains("<thead>"));
+        let body = &html[html.find("<tbody>").unwrap()..html.find("</tbody>").unwrap()];
+        assert_eq!(body.matches("<tr>").count(), 3);
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(html.contains("{&quot;k&quot;:&quot;v&amp;w&quot;}"));
+        assert!(!html.contains("<tfoot>"));
+
+        let truncated = records_to_html_table(&records, Some(1));
+        let body =
+            &truncated[truncated.find("<tbody>").unwrap()..truncated.find("</tbody>").unwrap()];
+        assert_eq!(body.matches("<tr>").count(), 1);
+        assert!(truncated.contains("<tfoot>"));
+        assert!(truncated.contains("... 2 more rows"));
+    }
+
+    #[test]
+    fn test_weighted_sample() {
+        let records = vec![
+            create_test_record("zero", 0.0, "A"),
+            create_test_record("a", 1.0, "A"),
+            create_test_record("b", 1.0, "A"),
+            create_test_record("c", 1.0, "A"),
+        ];
+
+        let sample = weighted_sample(&records, 30_000, 42).unwrap();
+        assert_eq!(sample.len(), 30_000);
+        assert!(sample.iter().all(|r| r.id != "zero"));
+
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for r in &sample {
+            *counts.entry(r.id.as_str()).or_insert(0) += 1;
+        }
+        for id in ["a", "b", "c"] {
+            let share = counts[id] as f64 / sample.len() as f64;
+            assert!((share - 1.0 / 3.0).abs() < 0.02, "{} share {}", id, share);
+        }
+
+        // Same seed gives the same sample
+        let again = weighted_sample(&records, 30_000, 42).unwrap();
+        assert!(sample.iter().zip(&again).all(|(x, y)| x.id == y.id));
+    }
+
+    #[test]
+    fn test_weighted_sample_all_zero() {
+        let records = vec![create_test_record("1", 0.0, "A")];
+        assert!(weighted_sample(&records, 5, 1).is_err());
+        assert!(weighted_sample(&[], 5, 1).is_err());
+    }
+
+    #[test]
+    fn test_weighted_sample_rejects_non_finite_values() {
+        for bad in [f64::NAN, f64::INFINITY] {
+            let records = vec![
+                create_test_record("a", 1.0, "A"),
+                create_test_record("bad", bad, "A"),
+                create_test_record("c", 1.0, "A"),
+            ];
+            assert!(matches!(
+                weighted_sample(&records, 5, 1),
+                Err(ProcessingError::InvalidArgument(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_kmeans_cluster_separated_groups() {
+        let mut records = Vec::new();
+        for i in 0..10 {
+            records.push(create_test_record(
+                &format!("low{}", i),
+                1.0 + i as f64 * 0.1,
+                "A",
+            ));
+            records.push(create_test_record(
+                &format!("high{}", i),
+                1000.0 + i as f64,
+                "A",
+            ));
+        }
+
+        let clustered = kmeans_cluster(&records, 2, 100, 7).unwrap();
+        assert_eq!(clustered.len(), records.len());
+
+        let low_cluster = clustered[0].1;
+        for (record, cluster) in &clustered {
+            if record.id.starts_with("low") {
+                assert_eq!(*cluster, low_cluster);
+            } else {
+                assert_ne!(*cluster, low_cluster);
+            }
+        }
+    }
+
+    #[test]
+    fn test_kmeans_cluster_invalid_k() {
+        let records = vec![create_test_record("1", 1.0, "A")];
+        assert!(kmeans_cluster(&records, 0, 10, 1).is_err());
+        assert!(kmeans_cluster(&records, 2, 10, 1).is_err());
+    }
+
+    #[test]
+    fn test_kmeans_cluster_rejects_non_finite_values() {
+        for bad in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let records = vec![
+                create_test_record("1", 1.0, "A"),
+                create_test_record("2", bad, "A"),
+                create_test_record("3", 5.0, "A"),
+            ];
+            assert!(matches!(
+                kmeans_cluster(&records, 2, 10, 1),
+                Err(ProcessingError::InvalidArgument(_))
+            ));
+        }
+
+        // Sums that overflow to infinity must not panic either
+        let records = vec![
+            create_test_record("1", f64::MAX, "A"),
+            create_test_record("2", f64::MAX, "A"),
+            create_test_record("3", 1.0, "A"),
+        ];
+        assert_eq!(kmeans_cluster(&records, 1, 10, 1).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_enrich_records() {
+        let mut records = vec![
+            create_test_record("1", 1.0, "A"),
+            create_test_record("2", 2.0, "B"),
+            create_test_record("3", 3.0, "C"),
+        ];
+        let lookup = HashMap::from([
+            (
+                "A".to_string(),
+                HashMap::from([("region".to_string(), "us-east".to_string())]),
+            ),
+            (
+                "B".to_string(),
+                HashMap::from([("region".to_string(), "eu-west".to_string())]),
+            ),
+        ]);
+
+        let enriched = enrich_records(&mut records, &lookup, "category");
+        assert_eq!(enriched, 2);
+        assert_eq!(records[0].metadata.as_ref().unwrap()["region"], "us-east");
+        assert_eq!(records[1].metadata.as_ref().unwrap()["region"], "eu-west");
+        assert!(records[2].metadata.is_none());
+    }
+
+    #[test]
+    fn test_enrich_records_by_metadata_key() {
+        let mut records = vec![create_test_record("1", 1.0, "A")];
+        records[0].metadata = Some(HashMap::from([("sku".to_string(), "X1".to_string())]));
+        let lookup = HashMap::from([(
+            "X1".to_string(),
+            HashMap::from([("name".to_string(), "Widget".to_string())]),
+        )]);
+
+        assert_eq!(enrich_records(&mut records, &lookup, "sku"), 1);
+        let metadata = records[0].metadata.as_ref().unwrap();
+        assert_eq!(metadata["sku"], "X1");
+        assert_eq!(metadata["name"], "Widget");
+        assert_eq!(enrich_records(&mut records, &lookup, "id"), 0);
+    }
+
+    #[test]
+    fn test_get_category_stats_variance() {
+        let records: Vec<DataRecord> = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]
+            .iter()
+            .enumerate()
+            .map(|(i, v)| create_test_record(&i.to_string(), *v, "A"))
+            .collect();
+
+        let stats = get_category_stats(&records, "A").unwrap();
+        // Squared deviations from the mean of 5 sum to 32; 32 / 7
+        assert!((stats.variance - 32.0 / 7.0).abs() < 1e-12);
+        assert!((stats.std_dev - (32.0f64 / 7.0).sqrt()).abs() < 1e-12);
+
+        let single = vec![create_test_record("1", 3.0, "B")];
+        let stats = get_category_stats(&single, "B").unwrap();
+        assert_eq!(stats.variance, 0.0);
+        assert_eq!(stats.std_dev, 0.0);
+    }
+
+    #[test]
+    fn test_stats_accumulator_matches_process_records() {
+        let records = vec![
+            create_test_record("1", 100.0, "A"),
+            create_test_record("2", 200.0, "B"),
+            create_test_record("3", 150.0, "A"),
+        ];
+
+        let mut acc = StatsAccumulator::new();
+        for record in &records {
+            acc.add_record(record);
+        }
+
+        let expected = process_records(&records).unwrap();
+        let result = acc.finalize();
+        assert_eq!(result.total_processed, expected.total_processed);
+        assert_eq!(result.total_value, expected.total_value);
+        assert_eq!(result.average_value, expected.average_value);
+        assert_eq!(result.min_value, expected.min_value);
+        assert_eq!(result.max_value, expected.max_value);
+        assert_eq!(result.categories, expected.categories);
+        assert!((acc.variance() - 2500.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stats_accumulator_remove_record() {
+        let records = vec![
+            create_test_record("1", 100.0, "A"),
+            create_test_record("2", 200.0, "B"),
+            create_test_record("3", 150.0, "A"),
+        ];
+
+        let mut acc = StatsAccumulator::default();
+        for record in &records {
+            acc.add_record(record);
+        }
+        acc.remove_record(&records[0]);
+        acc.remove_record(&records[1]);
+        // Never added, so ignored: an unseen value, then a seen value in an
+        // unseen category
+        acc.remove_record(&create_test_record("4", 999.0, "A"));
+        acc.remove_record(&create_test_record("5", 150.0, "Z"));
+
+        let result = acc.finalize();
+        assert_eq!(result.total_processed, 1);
+        assert_eq!(result.min_value, 150.0);
+        assert_eq!(result.max_value, 150.0);
+        assert_eq!(result.categories, HashMap::from([("A".to_string(), 1)]));
+
+        acc.remove_record(&records[2]);
+        let empty = acc.finalize();
+        assert_eq!(empty.total_processed, 0);
+        assert_eq!(empty.average_value, 0.0);
+    }
+
+    #[test]
+    fn test_stats_accumulator_checkpoint() {
+        let mut acc = StatsAccumulator::new();
+        acc.add_record(&create_test_record("1", -2.5, "A"));
+        acc.add_record(&create_test_record("2", 7.0, "B"));
+
+        let json = serde_json::to_string(&acc).unwrap();
+        let mut restored: StatsAccumulator = serde_json::from_str(&json).unwrap();
+        restored.remove_record(&create_test_record("2", 7.0, "B"));
+
+        assert_eq!(restored.count(), 1);
+        assert_eq!(restored.finalize().max_value, -2.5);
+    }
+
+    #[test]
+    fn test_compute_histogram() {
+        let values = [0.0, 1.0, 2.5, 5.0, 7.5, 10.0, f64::NAN, f64::INFINITY];
+        let histogram = compute_histogram(&values, 4).unwrap();
+
+        assert_eq!(histogram.len(), 4);
+        assert_eq!(histogram[0], (0.0, 2.5, 2));
+        assert_eq!(histogram[3], (7.5, 10.0, 2));
+        let total: usize = histogram.iter().map(|(_, _, count)| count).sum();
+        assert_eq!(total, 6);
+    }
+
+    #[test]
+    fn test_compute_histogram_edge_cases() {
+        assert!(compute_histogram(&[1.0], 0).is_err());
+        assert!(compute_histogram(&[f64::NAN], 3).unwrap().is_empty());
+
+        let constant = compute_histogram(&[4.0, 4.0, 4.0], 2).unwrap();
+        assert_eq!(constant, vec![(4.0, 4.0, 3), (4.0, 4.0, 0)]);
+    }
+
+    fn create_record_with_metadata(id: &str, value: f64, pairs: &[(&str, &str)]) -> DataRecord {
+        let mut record = create_test_record(id, value, "A");
+        record.metadata = Some(
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        );
+        record
+    }
+
+    #[test]
+    fn test_join_records_strategies() {
+        let left = vec![
+            create_record_with_metadata("1", 10.0, &[("source", "left"), ("region", "eu")]),
+            create_test_record("2", 20.0, "A"),
+        ];
+        let right = vec![
+            create_record_with_metadata("1", 99.0, &[("source", "right"), ("tier", "gold")]),
+            create_test_record("3", 30.0, "B"),
+        ];
+
+        let inner = join_records(&left, &right, JoinStrategy::Inner);
+        assert_eq!(inner.len(), 1);
+        assert_eq!(inner[0].value, 10.0);
+        let metadata = inner[0].metadata.as_ref().unwrap();
+        assert_eq!(metadata["source"], "right");
+        assert_eq!(metadata["region"], "eu");
+        assert_eq!(metadata["tier"], "gold");
+
+        let left_outer = join_records(&left, &right, JoinStrategy::LeftOuter);
+        let ids: Vec<&str> = left_outer.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "2"]);
+        assert!(left_outer[1].metadata.is_none());
+
+        let right_outer = join_records(&left, &right, JoinStrategy::RightOuter);
+        let ids: Vec<&str> = right_outer.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "3"]);
+        assert_eq!(right_outer[1].value, 30.0);
+    }
+
+    #[test]
+    fn test_join_records_no_overlap() {
+        let left = vec![create_test_record("1", 10.0, "A")];
+        let right = vec![create_test_record("2", 20.0, "B")];
+
+        assert!(join_records(&left, &right, JoinStrategy::Inner).is_empty());
+        assert_eq!(
+            join_records(&left, &right, JoinStrategy::LeftOuter)[0].id,
+            "1"
+        );
+        assert_eq!(
+            join_records(&left, &right, JoinStrategy::RightOuter)[0].id,
+            "2"
+        );
+    }
+
+    #[test]
+    fn test_detect_outliers_iqr() {
+        let mut records: Vec<DataRecord> = (0..10)
+            .map(|i| create_test_record(&i.to_string(), 100.0 + i as f64, "A"))
+            .collect();
+        records.push(create_test_record("high", 1000.0, "A"));
+        records.push(create_test_record("low", -500.0, "A"));
+
+        let outliers = detect_outliers_iqr(&records, 1.5);
+        let mut ids: Vec<&str> = outliers.iter().map(|r| r.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["high", "low"]);
+
+        assert!(detect_outliers_iqr(&records[..3], 1.5).is_empty());
+    }
+
+    #[test]
+    fn test_compute_correlation() {
+        let records: Vec<DataRecord> = (0..20)
+            .map(|i| {
+                let retries = (i * 3).to_string();
+                create_record_with_metadata(
+                    &i.to_string(),
+                    1e9 + i as f64,
+                    &[("retries", &retries)],
+                )
+            })
+            .collect();
+
+        let r = compute_correlation(&records, "retries").unwrap();
+        assert!((r - 1.0).abs() <= f64::EPSILON * 100.0);
+
+        let mut unmatched = records[..1].to_vec();
+        unmatched.push(create_test_record("x", 5.0, "A"));
+        assert_eq!(compute_correlation(&unmatched, "retries"), None);
+
+        let constant = vec![
+            create_record_with_metadata("1", 1.0, &[("retries", "2")]),
+            create_record_with_metadata("2", 3.0, &[("retries", "2")]),
+        ];
+        assert_eq!(compute_correlation(&constant, "retries"), None);
+    }
+
+    #[test]
+    fn test_top_n_by_value_matches_sort_and_slice() {
+        let mut rng = SmallRng::seed_from_u64(7);
+        let records: Vec<DataRecord> = (0..1_000_000)
+            .map(|i| create_test_record(&i.to_string(), rng.gen_range(0..10_000) as f64, "A"))
+            .collect();
+
+        let top = top_n_by_value(&records, 100);
+
+        let mut reference = records.clone();
+        reference.sort_by(|a, b| b.value.total_cmp(&a.value));
+        reference.truncate(100);
+
+        assert_eq!(top.len(), 100);
+        let top_ids: Vec<&str> = top.iter().map(|r| r.id.as_str()).collect();
+        let reference_ids: Vec<&str> = reference.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(top_ids, reference_ids);
+    }
+
+    #[test]
+    fn test_top_n_by_value_small_inputs() {
+        let records = vec![
+            create_test_record("1", 5.0, "A"),
+            create_test_record("2", 50.0, "A"),
+            create_test_record("3", 20.0, "A"),
+        ];
+
+        let all = top_n_by_value(&records, 10);
+        let ids: Vec<&str> = all.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["2", "3", "1"]);
+        assert!(top_n_by_value(&records, 0).is_empty());
+        assert_eq!(top_n_by_value(&records, usize::MAX).len(), 3);
+    }
+
+    #[test]
+    fn test_group_by_time_bucket_across_midnight() {
+        let mut before = create_test_record("1", 10.0, "A");
+        before.timestamp = "2024-01-15T23:59:30Z".to_string();
+        let mut after = create_test_record("2", 20.0, "A");
+        after.timestamp = "2024-01-16T00:00:10Z".to_string();
+        let mut invalid = create_test_record("3", 30.0, "A");
+        invalid.timestamp = "not a timestamp".to_string();
+        let records = vec![before, after, invalid];
+
+        let minutes = group_by_time_bucket(&records, 60);
+        assert_eq!(minutes.len(), 2);
+        assert_eq!(minutes[&1705363140][0].id, "1");
+        assert_eq!(minutes[&1705363200][0].id, "2");
+
+        let stats = compute_stats_by_time_bucket(&records, 3600).unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[&1705363200].total_value, 20.0);
+
+        assert!(group_by_time_bucket(&records, 0).is_empty());
+    }
+
+    #[test]
+    fn test_group_by_time_bucket_wider_than_span() {
+        let mut records = vec![
+            create_test_record("1", 10.0, "A"),
+            create_test_record("2", 30.0, "B"),
+        ];
+        records[1].timestamp = "2024-01-15T18:00:00Z".to_string();
+
+        let stats = compute_stats_by_time_bucket(&records, 7 * 86400).unwrap();
+        assert_eq!(stats.len(), 1);
+        let week = stats.values().next().unwrap();
+        assert_eq!(week.total_processed, 2);
+        assert_eq!(week.average_value, 20.0);
+    }
+
+    #[test]
+    fn test_processing_error_variants() {
+        assert!(matches!(
+            process_records(&[]),
+            Err(ProcessingError::EmptyInput)
+        ));
+
+        let invalid = create_test_record("", 1.0, "A");
+        match process_records(&[invalid]) {
+            Err(ProcessingError::ValidationFailed { errors }) => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].message, "ID cannot be empty");
+            }
+            other => panic!("expected ValidationFailed, got {:?}", other),
+        }
+
+        let huge = vec![
+            create_test_record("1", f64::MAX, "A"),
+            create_test_record("2", f64::MAX, "A"),
+        ];
+        assert!(matches!(
+            process_records(&huge),
+            Err(ProcessingError::Overflow)
+        ));
+
+        let err = compute_histogram(&[1.0], 0).unwrap_err();
+        assert!(matches!(err, ProcessingError::InvalidArgument(_)));
+        assert_eq!(err.to_string(), "bucket_count must be greater than zero");
+
+        let parse: ProcessingError = serde_json::from_str::<DataRecord>("{").unwrap_err().into();
+        assert!(parse.to_string().starts_with("Parse error:"));
+    }
+
+    #[test]
+    fn test_process_records_chunked_matches_process_records() {
+        let records = vec![
+            create_test_record("1", 100.0, "A"),
+            create_test_record("2", 200.0, "B"),
+            create_test_record("3", 150.0, "A"),
+            create_test_record("4", 50.0, "C"),
+            create_test_record("5", 75.0, "B"),
+        ];
+        let expected = process_records(&records).unwrap();
+
+        for chunk_size in [1, 2, 5, 10] {
+            let result = process_records_chunked(records.clone().into_iter(), chunk_size).unwrap();
+            assert_eq!(result.total_processed, expected.total_processed);
+            assert_eq!(result.total_value, expected.total_value);
+            assert_eq!(result.average_value, expected.average_value);
+            assert_eq!(result.min_value, expected.min_value);
+            assert_eq!(result.max_value, expected.max_value);
+            assert_eq!(result.categories, expected.categories);
+        }
+
+        assert!(matches!(
+            process_records_chunked(std::iter::empty(), 3),
+            Err(ProcessingError::EmptyInput)
+        ));
+        assert!(matches!(
+            process_records_chunked(records.into_iter(), 0),
+            Err(ProcessingError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_filter_spec_json_round_trip() {
+        let spec = FilterSpec {
+            min_value: Some(10.0),
+            max_value: None,
+            categories: Some(vec!["A".to_string(), "B".to_string()]),
+            metadata_filters: Some(HashMap::from([("region".to_string(), "eu".to_string())])),
+            exclude_categories: Some(vec![]),
+        };
+
+        let restored = FilterSpec::from_json(&spec.to_json().unwrap()).unwrap();
+        assert_eq!(restored, spec);
+        assert_eq!(FilterSpec::from_json("{}").unwrap(), FilterSpec::default());
+        assert!(matches!(
+            FilterSpec::from_json("{\"min_value\": \"x\"}"),
+            Err(ProcessingError::ParseError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_apply_filter_spec() {
+        let records = vec![
+            create_record_with_metadata("1", 50.0, &[("region", "eu")]),
+            create_record_with_metadata("2", 5.0, &[("region", "eu")]),
+            create_record_with_metadata("3", 50.0, &[("region", "us")]),
+            create_test_record("4", 50.0, "B"),
+        ];
+
+        assert_eq!(apply_filter_spec(&records, &FilterSpec::default()).len(), 4);
+
+        let spec = FilterSpec {
+            min_value: Some(10.0),
+            max_value: Some(100.0),
+            metadata_filters: Some(HashMap::from([("region".to_string(), "eu".to_string())])),
+            ..Default::default()
+        };
+        let filtered = apply_filter_spec(&records, &spec);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].id, "1");
+
+        let spec = FilterSpec {
+            exclude_categories: Some(vec!["A".to_string()]),
+            ..Default::default()
+        };
+        let filtered = apply_filter_spec(&records, &spec);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].id, "4");
+    }
+
+    #[test]
+    fn test_sort_records_is_stable() {
+        let mut records = vec![
+            create_test_record("1", 20.0, "B"),
+            create_test_record("2", 10.0, "A"),
+            create_test_record("3", 20.0, "A"),
+            create_test_record("4", 10.0, "B"),
+            create_test_record("5", 20.0, "B"),
+        ];
+
+        sort_records(&mut records, &[SortKey::ValueDesc]);
+        let ids: Vec<&str> = records.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "3", "5", "2", "4"]);
+
+        sort_records(&mut records, &[SortKey::CategoryAsc, SortKey::IdDesc]);
+        let ids: Vec<&str> = records.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["3", "2", "5", "4", "1"]);
+    }
+
+    #[test]
+    fn test_sort_records_large_reversed_input() {
+        let mut records: Vec<DataRecord> = (0..100_000)
+            .rev()
+            .map(|i| create_test_record(&i.to_string(), i as f64, "A"))
+            .collect();
+
+        sort_records(&mut records, &[SortKey::ValueAsc]);
+        assert!(records
+            .iter()
+            .enumerate()
+            .all(|(i, r)| r.value == i as f64 && r.id == i.to_string()));
+    }
+
+    #[test]
+    fn test_sort_records_timestamp_across_years() {
+        let timestamps = [
+            ("1", "2024-01-01T00:00:00+02:00"),
+            ("2", "2023-12-31T23:30:00Z"),
+            ("3", "invalid"),
+            ("4", "2023-12-31T21:59:00Z"),
+        ];
+        let mut records: Vec<DataRecord> = timestamps
+            .iter()
+            .map(|(id, ts)| {
+                let mut record = create_test_record(id, 1.0, "A");
+                record.timestamp = ts.to_string();
+                record
+            })
+            .collect();
+
+        // String order would put "2024-01-01T00:00:00+02:00" last; as an
+        // instant it is 2023-12-31T22:00:00Z
+        sort_records(&mut records, &[SortKey::TimestampAsc]);
+        let ids: Vec<&str> = records.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["4", "1", "2", "3"]);
+
+        assert_eq!(
+            "TimestampDesc".parse::<SortKey>().unwrap(),
+            SortKey::TimestampDesc
+        );
+        assert!("timestamp".parse::<SortKey>().is_err());
+    }
+
+    #[test]
+    fn test_reservoir_sample_deterministic_and_sized() {
+        let records: Vec<DataRecord> = (0..50)
+            .map(|i| create_test_record(&i.to_string(), i as f64, "A"))
+            .collect();
+
+        let first = reservoir_sample_seeded(&records, 10, 42);
+        let second = reservoir_sample_seeded(&records, 10, 42);
+        let ids = |sample: &[DataRecord]| -> Vec<RecordString> {
+            sample.iter().map(|r| r.id.clone()).collect()
+        };
+        assert_eq!(ids(&first), ids(&second));
+        assert_eq!(first.len(), 10);
+        assert_eq!(reservoir_sample_seeded(&records, 80, 42).len(), 50);
+        assert!(reservoir_sample_seeded(&records, 0, 42).is_empty());
+    }
+
+    #[test]
+    fn test_reservoir_sample_preserves_proportions() {
+        let categories = ["A", "B", "C", "D"];
+        let records: Vec<DataRecord> = (0..200_000)
+            .map(|i| create_test_record(&i.to_string(), 1.0, categories[i % 4]))
+            .collect();
+
+        let sample = reservoir_sample_seeded(&records, 10_000, 7);
+        assert_eq!(sample.len(), 10_000);
+
+        for category in categories {
+            let share = sample.iter().filter(|r| r.category == category).count() as f64 / 10_000.0;
+            assert!(
+                (share - 0.25).abs() <= 0.25 * 0.05,
+                "category {} share {}",
+                category,
+                share
+            );
+        }
+    }
+
+    #[test]
+    fn test_dedup_records_strategies() {
+        let records = vec![
+            create_test_record("a", 1.0, "first"),
+            create_test_record("b", 5.0, "A"),
+            create_test_record("a", 9.0, "middle"),
+            create_test_record("a", 3.0, "last"),
+        ];
+
+        let first = dedup_records(records.clone(), DedupStrategy::KeepFirst);
+        let last = dedup_records(records.clone(), DedupStrategy::KeepLast);
+        let highest = dedup_records(records.clone(), DedupStrategy::KeepHighestValue);
+
+        for result in [&first, &last, &highest] {
+            let ids: Vec<&str> = result.iter().map(|r| r.id.as_str()).collect();
+            assert_eq!(ids, vec!["a", "b"]);
+        }
+        assert_eq!(first[0].category, "first");
+        assert_eq!(last[0].category, "last");
+        assert_eq!(highest[0].category, "middle");
+        assert_eq!(records.len(), 4);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_dedup_records_unique_ids(
+            ids in proptest::collection::vec(0u8..20, 0..100),
+            strategy_index in 0usize..3,
+        ) {
+            let strategy = [
+                DedupStrategy::KeepFirst,
+                DedupStrategy::KeepLast,
+                DedupStrategy::KeepHighestValue,
+            ][strategy_index];
+            let records: Vec<DataRecord> = ids
+                .iter()
+                .enumerate()
+                .map(|(i, id)| create_test_record(&id.to_string(), i as f64, "A"))
+                .collect();
+            let input_len = records.len();
+
+            let deduped = dedup_records(records, strategy);
+
+            let unique: HashSet<&str> = deduped.iter().map(|r| r.id.as_str()).collect();
+            proptest::prop_assert_eq!(unique.len(), deduped.len());
+            proptest::prop_assert!(deduped.len() <= input_len);
+        }
+    }
+
+    #[test]
+    fn test_validate_records_batch() {
+        let mut records: Vec<DataRecord> = (0..10)
+            .map(|i| create_test_record(&format!("ok-{}", i), i as f64, "A"))
+            .collect();
+        for i in 0..5 {
+            let mut bad = create_test_record(&format!("bad-{}", i), -1.0, "A");
+            if i % 2 == 0 {
+                bad.category = RecordString::default();
+                bad.value = 1.0;
+            }
+            records.insert(i * 3, bad);
+        }
+
+        let batch = validate_records_batch(&records);
+        assert_eq!(batch.results.len(), 15);
+        assert_eq!(batch.valid_count(), 10);
+        assert_eq!(batch.error_count(), 5);
+
+        let invalid_ids: Vec<&str> = batch
+            .results
+            .iter()
+            .filter(|r| !r.valid)
+            .map(|r| r.id.as_str())
+            .collect();
+        assert_eq!(
+            invalid_ids,
+            vec!["bad-0", "bad-1", "bad-2", "bad-3", "bad-4"]
+        );
+        assert_eq!(
+            batch.results[0].error.as_deref(),
+            Some("Category cannot be empty")
+        );
+
+        let json = serde_json::to_value(&batch).unwrap();
+        assert_eq!(json[1], serde_json::json!({"id": "ok-0", "valid": true}));
+    }
+
+    #[test]
+    fn test_records_csv_round_trip() {
+        let mut records = vec![
+            create_record_with_metadata(
+                "a,1",
+                0.1 + 0.2,
+                &[("note", "line one\nline \"two\", end"), ("k", "v")],
+            ),
+            create_test_record("2", -1.5e-300, "say \"hi\""),
+        ];
+        records[1].timestamp = "2024-01-15T10:00:00Z".to_string();
+
+        let csv = records_to_csv(&records).unwrap();
+        assert!(
+            csv.starts_with("id,value,category,timestamp,metadata\n\"a,1\",0.30000000000000004,")
+        );
+
+        let parsed = records_from_csv(&csv).unwrap();
+        assert_eq!(parsed.len(), 2);
+        for (original, parsed) in records.iter().zip(&parsed) {
+            assert_eq!(original.id, parsed.id);
+            assert_eq!(original.value.to_bits(), parsed.value.to_bits());
+            assert_eq!(original.category, parsed.category);
+            assert_eq!(original.timestamp, parsed.timestamp);
+            assert_eq!(original.metadata, parsed.metadata);
+        }
+    }
+
+    #[test]
+    fn test_records_from_csv_without_metadata_column() {
+        let parsed =
+            records_from_csv("id,value,category,timestamp\r\n1,2.5,A,2024-01-15T10:00:00Z\r\n")
+                .unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].value, 2.5);
+        assert!(parsed[0].metadata.is_none());
+    }
+
+    #[test]
+    fn test_records_from_csv_errors() {
+        assert!(matches!(
+            records_from_csv("id,value\n1,2\n"),
+            Err(ProcessingError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            records_from_csv("id,value,category,timestamp\n1,abc,A,t\n"),
+            Err(ProcessingError::InvalidArgument(msg)) if msg.starts_with("Row 2")
+        ));
+        assert!(records_from_csv("id,value,category,timestamp\n\"1,2,A,t\n").is_err());
+    }
+
+    #[test]
+    fn test_compute_percentiles_median() {
+        let sorted_vec = vec![1.0, 2.0, 3.0, 4.0];
+        assert_eq!(compute_percentiles(&sorted_vec, &[0.5]).unwrap(), vec![2.5]);
+        assert_eq!(
+            compute_percentiles(&[9.0, 1.0, 5.0], &[0.5]).unwrap(),
+            vec![5.0]
+        );
+    }
+
+    #[test]
+    fn test_compute_percentiles_r7_reference() {
+        // Reference values from R: quantile(c(1, 3, 7, 15, 31), c(0, .1, .25, .9, 1), type = 7)
+        let values = [15.0, 1.0, 31.0, 3.0, 7.0];
+        let result = compute_percentiles(&values, &[0.0, 0.1, 0.25, 0.9, 1.0]).unwrap();
+        let expected = [1.0, 1.8, 3.0, 24.6, 31.0];
+        for (got, want) in result.iter().zip(expected) {
+            assert!((got - want).abs() < 1e-9, "got {}, want {}", got, want);
+        }
+    }
+
+    #[test]
+    fn test_compute_percentiles_errors() {
+        assert!(matches!(
+            compute_percentiles(&[], &[0.5]),
+            Err(ProcessingError::EmptyInput)
+        ));
+        for q in [-0.1, 1.5, f64::NAN] {
+            assert!(matches!(
+                compute_percentiles(&[1.0], &[q]),
+                Err(ProcessingError::InvalidArgument(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_aggregate_by_category_percentiles() {
+        let records = vec![
+            create_test_record("1", 10.0, "A"),
+            create_test_record("2", 20.0, "A"),
+            create_test_record("3", 40.0, "A"),
+            create_test_record("4", 5.0, "B"),
+        ];
+
+        let stats = aggregate_by_category(&records, &[0.5, 0.75]).unwrap();
+        assert_eq!(stats["A"].percentiles, vec![(0.5, 20.0), (0.75, 30.0)]);
+        assert_eq!(stats["B"].percentiles, vec![(0.5, 5.0), (0.75, 5.0)]);
+
+        let plain = aggregate_by_category(&records, &[]).unwrap();
+        assert!(plain["A"].percentiles.is_empty());
+        assert!(aggregate_by_category(&records, &[2.0]).is_err());
+    }
+
+    #[test]
+    fn test_normalize_values() {
+        let mut records = vec![
+            create_test_record("1", 10.0, "A"),
+            create_test_record("2", 30.0, "A"),
+            create_test_record("3", 20.0, "B"),
+        ];
+        normalize_values(&mut records);
+        let result = process_records(&records).unwrap();
+        assert_eq!(result.min_value, 0.0);
+        assert_eq!(result.max_value, 1.0);
+        assert_eq!(records[2].value, 0.5);
+
+        let mut same = vec![
+            create_test_record("1", 7.0, "A"),
+            create_test_record("2", 7.0, "A"),
+        ];
+        normalize_values(&mut same);
+        assert!(same.iter().all(|r| r.value == 0.5));
+
+        normalize_values(&mut []);
+    }
+
+    #[test]
+    fn test_standardize_values() {
+        let mut records: Vec<DataRecord> = [2.0, 4.0, 6.0]
+            .iter()
+            .enumerate()
+            .map(|(i, v)| create_test_record(&i.to_string(), *v, "A"))
+            .collect();
+        standardize_values(&mut records);
+        let values: Vec<f64> = records.iter().map(|r| r.value).collect();
+        assert_eq!(values, vec![-1.0, 0.0, 1.0]);
+
+        let mut single = vec![create_test_record("1", 5.0, "A")];
+        standardize_values(&mut single);
+        assert_eq!(single[0].value, 0.0);
+
+        standardize_values(&mut []);
+    }
+
+    #[test]
+    fn test_clamp_values() {
+        let mut records = vec![
+            create_test_record("1", -5.0, "A"),
+            create_test_record("2", 5.0, "A"),
+            create_test_record("3", 50.0, "A"),
+        ];
+        clamp_values(&mut records, 0.0, 10.0);
+        let values: Vec<f64> = records.iter().map(|r| r.value).collect();
+        assert_eq!(values, vec![0.0, 5.0, 10.0]);
+    }
+
+    #[test]
+    fn test_split_by_category_partitions_in_order() {
+        let records: Vec<DataRecord> = (0..5000)
+            .map(|i| create_test_record(&i.to_string(), i as f64, ["A", "B", "C"][i % 3]))
+            .collect();
+
+        let buckets = split_by_category(&records);
+        assert_eq!(buckets.len(), 3);
+
+        let mut ids: Vec<&str> = buckets.values().flatten().map(|r| r.id.as_str()).collect();
+        ids.sort_unstable();
+        let mut expected: Vec<&str> = records.iter().map(|r| r.id.as_str()).collect();
+        expected.sort_unstable();
+        assert_eq!(ids, expected);
+
+        for (category, bucket) in &buckets {
+            assert!(bucket.iter().all(|r| r.category.as_str() == category));
+            assert!(bucket.windows(2).all(|w| w[0].value < w[1].value));
+        }
+        assert!(split_by_category(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_record_index_lookup() {
+        let records: Vec<DataRecord> = (0..1000)
+            .map(|i| create_test_record(&format!("id-{}", i), i as f64, "A"))
+            .collect();
+
+        let index = RecordIndex::build(&records).unwrap();
+        assert_eq!(index.len(), 1000);
+        // Lookups go through the hash map, never a scan of `records`
+        assert_eq!(index.get("id-999").map(|r| r.value), Some(999.0));
+        assert!(index.get("missing").is_none());
+        assert!(index.contains("id-0"));
+        assert!(!index.contains("missing"));
+
+        let found = index.get_many(&["id-1", "missing", "id-2"]);
+        assert_eq!(
+            found.iter().map(|r| r.map(|r| r.value)).collect::<Vec<_>>(),
+            vec![Some(1.0), None, Some(2.0)]
+        );
+    }
+
+    #[test]
+    fn test_record_index_duplicate_ids() {
+        let records = vec![
+            create_test_record("1", 1.0, "A"),
+            create_test_record("2", 2.0, "A"),
+            create_test_record("1", 3.0, "B"),
+        ];
+
+        assert!(matches!(
+            RecordIndex::build(&records),
+            Err(ProcessingError::DuplicateId(id)) if id == "1"
+        ));
+
+        let index = RecordIndex::rebuild(&records);
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.get("1").map(|r| r.value), Some(3.0));
+
+        // An owning index behaves the same
+        assert!(RecordIndex::build(records.clone()).is_err());
+        let owned: RecordIndex<'static> = RecordIndex::rebuild(records);
+        assert_eq!(owned.get("1").map(|r| r.value), Some(3.0));
+    }
+
+    #[test]
+    fn test_compute_entropy() {
+        let records_for = |categories: &[&str]| -> Vec<DataRecord> {
+            categories
+                .iter()
+                .enumerate()
+                .map(|(i, c)| create_test_record(&i.to_string(), 1.0, c))
+                .collect()
+        };
+
+        assert_eq!(compute_entropy(&[]), 0.0);
+        assert_eq!(compute_entropy(&records_for(&["A", "A"])), 0.0);
+        assert_eq!(compute_entropy(&records_for(&["A", "B"])), 1.0);
+
+        // p = (1/2, 1/4, 1/4): 0.5 * 1 + 2 * 0.25 * 2 = 1.5 bits
+        let skewed = records_for(&["A", "A", "B", "C"]);
+        assert!((compute_entropy(&skewed) - 1.5).abs() < 1e-12);
+        assert!((compute_normalized_entropy(&skewed) - 1.5 / 3f64.log2()).abs() < 1e-12);
+
+        // Uniform over three categories: log2(3) bits
+        let uniform = records_for(&["A", "B", "C", "C", "B", "A"]);
+        assert!((compute_entropy(&uniform) - 3f64.log2()).abs() < 1e-12);
+        assert_eq!(compute_normalized_entropy(&uniform), 1.0);
+
+        assert_eq!(
+            compute_normalized_entropy(&records_for(&["A", "A", "A"])),
+            0.0
+        );
+        assert_eq!(compute_normalized_entropy(&[]), 0.0);
+    }
+
+    fn create_time_series(values: &[f64]) -> Vec<DataRecord> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let mut record = create_test_record(&i.to_string(), *v, "A");
+                record.timestamp = format!("2024-01-15T10:00:{:02}Z", i);
+                record
+            })
+            .rev()
+            .collect()
+    }
+
+    #[test]
+    fn test_compute_sma() {
+        let values = [0.1, 0.2, 0.7, 1.3, 5.0];
+        let records = create_time_series(&values);
+
+        let identity: Vec<f64> = compute_sma(&records, 1)
+            .into_iter()
+            .map(|(_, v)| v)
+            .collect();
+        assert_eq!(identity, values);
+
+        let smoothed = compute_sma(&records, 2);
+        assert_eq!(smoothed[0], ("2024-01-15T10:00:00Z".to_string(), 0.1));
+        assert!((smoothed[1].1 - 0.15).abs() < 1e-12);
+        assert!((smoothed[4].1 - 3.15).abs() < 1e-12);
+        assert_eq!(compute_sma(&records, 0), compute_sma(&records, 1));
+        assert!(compute_sma(&[], 3).is_empty());
+    }
+
+    #[test]
+    fn test_trailing_means_recover_after_large_and_non_finite_values() {
+        let means = trailing_means(&[1e17, 1.0, 1.0, 3.0], 2);
+        assert_eq!(means[2..], [1.0, 2.0]);
+
+        let means = trailing_means(&[1.0, f64::NAN, 2.0, 4.0, f64::INFINITY, 6.0, 8.0], 2);
+        assert!(means[1].is_nan() && means[2].is_nan());
+        assert_eq!(means[3], 3.0);
+        assert_eq!(means[4..6], [f64::INFINITY, f64::INFINITY]);
+        assert_eq!(means[6], 7.0);
+
+        let means = trailing_means(&[f64::INFINITY, f64::NEG_INFINITY, 1.0], 2);
+        assert!(means[1].is_nan());
+        assert_eq!(means[2], f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_compute_ema() {
+        let values = [0.1, 0.2, 0.7, 1.3, 5.0];
+        let records = create_time_series(&values);
+
+        let identity: Vec<f64> = compute_ema(&records, 1.0)
+            .unwrap()
+            .into_iter()
+            .map(|(_, v)| v)
+            .collect();
+        assert_eq!(identity, values);
+
+        let smoothed = compute_ema(&records, 0.5).unwrap();
+        assert_eq!(smoothed[0].1, 0.1);
+        assert!((smoothed[1].1 - 0.15).abs() < 1e-12);
+        assert!((smoothed[2].1 - 0.425).abs() < 1e-12);
+
+        for alpha in [0.0, -0.5, 1.5, f64::NAN] {
+            assert!(matches!(
+                compute_ema(&records, alpha),
+                Err(ProcessingError::InvalidArgument(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_sum_values_kahan_bounded_error() {
+        let values = vec![0.1; 10_000_000];
+        // 0.1 is stored as 0.1000000000000000055511..., so the exact sum of
+        // ten million copies rounds to 1_000_000.0
+        let exact = 1_000_000.0_f64;
+        let ulp = f64::EPSILON * exact;
+
+        let naive: f64 = values.iter().fold(0.0, |acc, v| acc + v);
+        let kahan = sum_values_kahan(&values);
+
+        assert!((naive - exact).abs() > 1_000.0 * ulp);
+        assert!(
+            (kahan - exact).abs() <= ulp,
+            "kahan error {}",
+            kahan - exact
+        );
+        // Pairwise is far better than naive but not as tight as Kahan
+        assert!((sum_values_pairwise(&values) - exact).abs() <= 64.0 * ulp);
+        assert_eq!(sum_values_kahan(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_process_records_with_config_paths_agree() {
+        let records: Vec<DataRecord> = (0..5000)
+            .map(|i| create_test_record(&i.to_string(), 0.1 * (i % 7) as f64, "A"))
+            .collect();
+
+        let kahan = ProcessingConfig {
+            use_kahan: true,
+            parallel_threshold: 10,
+            outlier_cap: None,
+        };
+        let pairwise = ProcessingConfig {
+            use_kahan: false,
+            ..ProcessingConfig::default()
+        };
+        let a = process_records_with_config(&records, &kahan).unwrap();
+        let b = process_records_with_config(&records, &pairwise).unwrap();
+
+        assert!((a.total_value - b.total_value).abs() < 1e-9);
+        assert_eq!(a.total_processed, b.total_processed);
+        assert!(matches!(
+            process_records_with_config(
+                &[
+                    create_test_record("1", f64::MAX, "A"),
+                    create_test_record("2", f64::MAX, "A")
+                ],
+                &kahan
+            ),
+            Err(ProcessingError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_transform_pipeline_order_matters() {
+        let records = vec![
+            create_test_record("1", 4.0, "A"),
+            create_test_record("2", 8.0, "A"),
+        ];
+
+        let scale_then_clamp = TransformPipeline::new().scale(2.0).clamp(0.0, 10.0);
+        let clamp_then_scale = TransformPipeline::new().clamp(0.0, 10.0).scale(2.0);
+
+        let values = |pipeline: &TransformPipeline| -> Vec<f64> {
+            apply_pipeline(&records, pipeline)
+                .iter()
+                .map(|r| r.value)
+                .collect()
+        };
+        assert_eq!(values(&scale_then_clamp), vec![8.0, 10.0]);
+        assert_eq!(values(&clamp_then_scale), vec![8.0, 16.0]);
+
+        let transform = scale_then_clamp.add_offset(1.0).build();
+        assert_eq!(transform(records[1].clone()).map(|r| r.value), Some(11.0));
+    }
+
+    #[test]
+    fn test_transform_pipeline_metadata_filter_and_json() {
+        let records = vec![
+            create_record_with_metadata("1", 1.0, &[("env", "prod")]),
+            create_record_with_metadata("2", 2.0, &[("env", "dev")]),
+            create_test_record("3", 3.0, "A"),
+        ];
+
+        let pipeline = TransformPipeline::from_json(
+            r#"[{"op":"apply_metadata_filter","params":{"key":"env","value":"prod"}}]"#,
+        );
+        assert!(pipeline.is_err());
+
+        let pipeline = TransformPipeline::from_json(
+            r#"[{"op":"metadata_filter","params":{"key":"env","value":"prod"}},
+                {"op":"add_offset","params":{"offset":0.5}}]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            pipeline,
+            TransformPipeline::new()
+                .apply_metadata_filter("env", "prod")
+                .add_offset(0.5)
+        );
+
+        let out = apply_pipeline(&records, &pipeline);
+        assert_eq!(out.len(), 1);
+        assert_eq!((out[0].id.as_str(), out[0].value), ("1", 1.5));
+    }
+
+    #[test]
+    fn test_data_record_builder() {
+        let literal = DataRecord {
+            id: "1".into(),
+            value: 10.0,
+            category: "A".into(),
+            timestamp: "2024-01-15T10:00:00Z".to_string(),
+            metadata: Some(HashMap::from([("env".to_string(), "prod".to_string())])),
+        };
+        let built = DataRecord::builder()
+            .id("1")
+            .value(10.0)
+            .category("A")
+            .timestamp("2024-01-15T10:00:00Z")
+            .metadata_entry("env", "prod")
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&built).unwrap(),
+            serde_json::to_value(&literal).unwrap()
+        );
+
+        match DataRecordBuilder::default().build() {
+            Err(ProcessingError::ValidationFailed { errors }) => {
+                assert_eq!(errors[0].message, "ID cannot be empty")
+            }
+            other => panic!("expected ValidationFailed, got {:?}", other),
+        }
+        assert!(DataRecord::builder()
+            .id("2")
+            .value(-1.0)
+            .category("A")
+            .timestamp("t")
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_record_diff_additions_and_removals() {
+        let before = vec![create_test_record("1", 1.0, "A")];
+        let after = vec![
+            create_test_record("1", 1.0, "A"),
+            create_test_record("2", 2.0, "A"),
+        ];
+
+        let added = record_diff(&before, &after);
+        assert_eq!(added.added.len(), 1);
+        assert_eq!(added.added[0].id, "2");
+        assert!(added.removed.is_empty() && added.changed.is_empty());
+        assert_eq!(added.unchanged_count, 1);
+
+        let removed = record_diff(&after, &before);
+        assert_eq!(removed.removed.len(), 1);
+        assert_eq!(removed.removed[0].id, "2");
+        assert!(removed.added.is_empty());
+    }
+
+    #[test]
+    fn test_record_diff_changes() {
+        let mut value_changed = create_test_record("1", 5.0, "A");
+        value_changed.timestamp = "2024-02-01T00:00:00Z".to_string();
+        let before = vec![
+            create_test_record("1", 1.0, "A"),
+            create_record_with_metadata("2", 2.0, &[("k", "v")]),
+            create_record_with_metadata("3", 3.0, &[]),
+        ];
+        let after = vec![
+            value_changed,
+            create_record_with_metadata("2", 2.0, &[("k", "other")]),
+            create_test_record("3", 3.0, "A"),
+        ];
+
+        let diff = record_diff(&before, &after);
+        let changed: Vec<(&str, f64, f64)> = diff
+            .changed
+            .iter()
+            .map(|(old, new)| (old.id.as_str(), old.value, new.value))
+            .collect();
+        assert_eq!(changed, vec![("1", 1.0, 5.0), ("2", 2.0, 2.0)]);
+        // Empty metadata equals no metadata
+        assert_eq!(diff.unchanged_count, 1);
+    }
+
+    #[test]
+    fn test_record_diff_empty_inputs() {
+        let records = vec![create_test_record("1", 1.0, "A")];
+
+        let diff = record_diff(&[], &[]);
+        assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.changed.is_empty());
+        assert_eq!(diff.unchanged_count, 0);
+
+        assert_eq!(record_diff(&[], &records).added.len(), 1);
+        assert_eq!(record_diff(&records, &[]).removed.len(), 1);
+    }
+
+    #[test]
+    fn test_paginate_records_visits_every_record_once() {
+        let records: Vec<DataRecord> = (0..23)
+            .map(|i| create_test_record(&i.to_string(), i as f64, "A"))
+            .collect();
+
+        let mut seen = Vec::new();
+        let mut offset = 0;
+        loop {
+            let page = paginate_records(&records, offset, 5);
+            assert_eq!(page.total, 23);
+            assert_eq!(page.has_prev, offset > 0);
+            seen.extend(page.items.iter().map(|r| r.id.clone()));
+            if !page.has_next {
+                break;
+            }
+            offset += page.limit;
+        }
+
+        let expected: Vec<String> = (0..23).map(|i| i.to_string()).collect();
+        assert_eq!(seen, expected);
+
+        let past_end = paginate_records(&records, 100, 5);
+        assert!(past_end.items.is_empty() && !past_end.has_next);
+    }
+
+    #[test]
+    fn test_cursor_paginate_records() {
+        let records: Vec<DataRecord> = (0..7)
+            .map(|i| create_test_record(&format!("r{}", i), i as f64, "A"))
+            .collect();
+
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = cursor_paginate_records(&records, cursor.as_deref(), 3).unwrap();
+            seen.extend(page.items.iter().map(|r| r.id.clone()));
+            if !page.has_more {
+                assert!(page.next_cursor.is_none());
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+        assert_eq!(seen.len(), 7);
+        assert_eq!(decode_cursor(&encode_cursor("r3")).as_deref(), Some("r3"));
+
+        let unknown = cursor_paginate_records(&records, Some("not a cursor!"), 3).unwrap();
+        assert!(unknown.items.is_empty() && !unknown.has_more);
+
+        assert!(matches!(
+            cursor_paginate_records(&records, None, 0),
+            Err(ProcessingError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_metadata_cooccurrence_is_ordered() {
+        let records = vec![
+            create_record_with_metadata("1", 1.0, &[("x", "A"), ("y", "B")]),
+            create_record_with_metadata("2", 1.0, &[("x", "A"), ("y", "B")]),
+            create_record_with_metadata("3", 1.0, &[("x", "B"), ("y", "A")]),
+            create_record_with_metadata("4", 1.0, &[("x", "A")]),
+        ];
+
+        let counts = compute_metadata_cooccurrence(&records, "x", "y");
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[&("A".to_string(), "B".to_string())], 2);
+        assert_eq!(counts[&("B".to_string(), "A".to_string())], 1);
+
+        let swapped = compute_metadata_cooccurrence(&records, "y", "x");
+        assert_eq!(swapped[&("B".to_string(), "A".to_string())], 2);
+    }
+
+    #[test]
+    fn test_category_transition_matrix() {
+        let mut records = vec![
+            create_test_record("3", 1.0, "A"),
+            create_test_record("1", 1.0, "A"),
+            create_test_record("2", 1.0, "B"),
+        ];
+        records[0].timestamp = "2024-01-15T10:02:00Z".to_string();
+        records[1].timestamp = "2024-01-15T10:00:00Z".to_string();
+        records[2].timestamp = "2024-01-15T10:01:00Z".to_string();
+
+        let matrix = compute_category_transition_matrix(&records);
+        assert_eq!(matrix.len(), 2);
+        assert_eq!(matrix[&("A".to_string(), "B".to_string())], 1);
+        assert_eq!(matrix[&("B".to_string(), "A".to_string())], 1);
+
+        assert!(compute_category_transition_matrix(&records[..1]).is_empty());
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn test_data_record_schema() {
+        let schema: serde_json::Value = serde_json::from_str(&data_record_schema()).unwrap();
+
+        assert!(schema.get("$schema").is_some());
+        assert_eq!(schema["type"], "object");
+        let required: Vec<&str> = schema["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|v| v.as_str())
+            .collect();
+        for field in ["id", "value", "category", "timestamp"] {
+            assert!(required.contains(&field), "missing {}", field);
+        }
+        assert!(!required.contains(&"metadata"));
+        assert!(schema["properties"]["id"]["description"].is_string());
+    }
+
+    #[test]
+    fn test_validate_timestamp_format() {
+        let parsed = validate_timestamp_format("2024-01-15T10:00:00+02:00").unwrap();
+        assert_eq!(parsed.to_rfc3339(), "2024-01-15T08:00:00+00:00");
+        assert!(validate_timestamp_format("2024-01-15T10:00:00Z").is_ok());
+
+        for bad in ["not a timestamp", "2024-13-01T00:00:00Z", "2024-01-15"] {
+            assert!(matches!(
+                validate_timestamp_format(bad),
+                Err(ProcessingError::InvalidArgument(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_validate_record_strict_mode() {
+        let mut record = create_test_record("1", 1.0, "A");
+        record.timestamp = "2024-13-01T00:00:00Z".to_string();
+
+        assert!(validate_record(&record, ValidationMode::Lenient).is_ok());
+        let error = validate_record(&record, ValidationMode::Strict).unwrap_err();
+        assert_eq!(error.kind, ValidationErrorKind::InvalidTimestamp);
+
+        record.timestamp = "2024-12-01T00:00:00Z".to_string();
+        assert!(validate_record(&record, ValidationMode::Strict).is_ok());
+    }
+
+    #[test]
+    fn test_process_records_partial() {
+        let mut records: Vec<DataRecord> = (0..90)
+            .map(|i| create_test_record(&i.to_string(), 1.0, "A"))
+            .collect();
+        records.extend((90..100).map(|i| create_test_record(&i.to_string(), -1.0, "B")));
+
+        let (result, errors) = process_records_partial(&records);
+        assert_eq!(result.total_processed, 90);
+        assert!((result.total_value - 90.0).abs() < 1e-9);
+        assert_eq!(result.categories.get("B"), None);
+        assert_eq!(errors.len(), 10);
+        assert_eq!(errors[0].record_id, "90");
+
+        let (empty, errors) = process_records_partial(&records[90..]);
+        assert_eq!(empty.total_processed, 0);
+        assert_eq!(empty.total_value, 0.0);
+        assert!(empty.average_value.is_nan());
+        assert_eq!(errors.len(), 10);
+
+        // NaN fails validation rather than reaching the min/max comparisons
+        records.push(create_test_record("nan", f64::NAN, "A"));
+        let (result, errors) = process_records_partial(&records);
+        assert_eq!(result.total_processed, 90);
+        assert_eq!(result.max_value, 1.0);
+        assert_eq!(errors.len(), 11);
+        assert_eq!(errors[10].field, "value");
+
+        let stats = get_category_stats(&records, "A").unwrap();
+        assert_eq!(stats.count, 91);
+        assert_eq!(stats.min_value, 1.0);
+    }
+
+    #[cfg(feature = "postcard")]
+    #[test]
+    fn test_postcard_round_trip() {
+        let records = vec![
+            create_test_record("1", 1.5, "A"),
+            create_record_with_metadata("2", 2.0, &[("sensor", "t1")]),
+        ];
+
+        let bytes = records_to_postcard(&records).unwrap();
+        let decoded = records_from_postcard(&bytes).unwrap();
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].id, "1");
+        assert_eq!(decoded[1].metadata, records[1].metadata);
+        assert!(bytes.len() < serde_json::to_vec(&records).unwrap().len());
+
+        assert!(records_from_postcard(&bytes[..bytes.len() - 1]).is_err());
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(records_from_postcard(&trailing).is_err());
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn test_bincode_round_trip() {
+        let mut records = generate_sample_data(50);
+        records[3].metadata = None;
+        records[7].value = -0.0;
+
+        let bytes = encode_records_binary(&records).unwrap();
+        let decoded = decode_records_binary(&bytes).unwrap();
+        assert_eq!(decoded.len(), records.len());
+        for (original, decoded) in records.iter().zip(&decoded) {
+            assert_eq!(decoded.id, original.id);
+            assert_eq!(decoded.value.to_bits(), original.value.to_bits());
+            assert_eq!(decoded.category, original.category);
+            assert_eq!(decoded.timestamp, original.timestamp);
+            assert_eq!(decoded.metadata, original.metadata);
+        }
+
+        let from_json: Vec<DataRecord> =
+            serde_json::from_slice(&serde_json::to_vec(&records).unwrap()).unwrap();
+        let binary = process_records(&decoded).unwrap();
+        let json = process_records(&from_json).unwrap();
+        assert_eq!(binary.total_value, json.total_value);
+        assert_eq!(binary.categories, json.categories);
+
+        assert!(decode_records_binary(&bytes[..bytes.len() - 1]).is_err());
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(decode_records_binary(&trailing).is_err());
+    }
+
+    #[test]
+    fn test_generate_sample_data() {
+        let records = generate_sample_data(8);
+        assert_eq!(records.len(), 8);
+        assert_eq!(records[5].id.as_str(), "record_5");
+        assert_eq!(records[5].category.as_str(), "B");
+        assert_eq!(records[5].metadata.as_ref().unwrap()["index"], "5");
+        assert!(records
+            .iter()
+            .all(|r| validate_record(r, ValidationMode::Strict).is_ok()));
+    }
+
+    #[test]
+    fn test_compute_gini() {
+        assert_eq!(compute_gini(&[5.0; 10]).unwrap(), 0.0);
+
+        let mut concentrated = vec![0.0; 999];
+        concentrated.push(100.0);
+        let gini = compute_gini(&concentrated).unwrap();
+        assert!((gini - 0.999).abs() < 1e-12);
+
+        // Order of the input does not matter
+        assert_eq!(
+            compute_gini(&[3.0, 1.0, 2.0]).unwrap(),
+            compute_gini(&[1.0, 2.0, 3.0]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_compute_gini_edge_cases() {
+        assert_eq!(compute_gini(&[]).unwrap(), 0.0);
+        assert_eq!(compute_gini(&[42.0]).unwrap(), 0.0);
+        assert_eq!(compute_gini(&[0.0, 0.0]).unwrap(), 0.0);
+        assert!(compute_gini(&[1.0, -1.0]).is_err());
+        assert!(compute_gini(&[1.0, f64::NAN]).is_err());
+    }
+
+    #[test]
+    fn test_sliding_window_evicts_as_time_advances() {
+        let mut window = SlidingWindow::new(60);
+        for (i, ts) in [
+            "2024-01-15T10:00:00Z",
+            "2024-01-15T10:00:30Z",
+            "2024-01-15T10:00:50Z",
+        ]
+        .iter()
+        .enumerate()
+        {
+            let mut record = create_test_record(&i.to_string(), (i + 1) as f64, "A");
+            record.timestamp = ts.to_string();
+            window.push(record).unwrap();
+        }
+        assert_eq!(window.current_count(), 3);
+        assert_eq!(window.current_stats().total_value, 6.0);
+
+        // 10:01:00 keeps 10:00:00 (inclusive bound); 10:01:01 drops it
+        window.advance_to("2024-01-15T10:01:00Z").unwrap();
+        assert_eq!(window.current_count(), 3);
+        window.advance_to("2024-01-15T10:01:01Z").unwrap();
+        assert_eq!(window.current_count(), 2);
+        assert_eq!(
+            window.oldest_timestamp().as_deref(),
+            Some("2024-01-15T10:00:30Z")
+        );
+        let stats = window.current_stats();
+        assert_eq!(stats.total_value, 5.0);
+        assert_eq!(stats.min_value, 2.0);
+
+        let mut late = create_test_record("late", 10.0, "B");
+        late.timestamp = "2024-01-15T10:02:00Z".to_string();
+        window.push(late).unwrap();
+        assert_eq!(window.current_count(), 1);
+        assert_eq!(
+            window.newest_timestamp().as_deref(),
+            Some("2024-01-15T10:02:00Z")
+        );
+        assert_eq!(window.current_stats().categories.get("B"), Some(&1));
+    }
+
+    #[test]
+    fn test_sliding_window_out_of_order_and_invalid() {
+        let mut window = SlidingWindow::new(60);
+        let mut newer = create_test_record("1", 1.0, "A");
+        newer.timestamp = "2024-01-15T10:00:30Z".to_string();
+        let mut older = create_test_record("2", 2.0, "A");
+        older.timestamp = "2024-01-15T10:00:10Z".to_string();
+        let mut expired = create_test_record("3", 3.0, "A");
+        expired.timestamp = "2024-01-15T09:00:00Z".to_string();
+        window.push(newer).unwrap();
+        window.push(older).unwrap();
+        window.push(expired).unwrap();
+
+        assert_eq!(window.current_count(), 2);
+        assert_eq!(
+            window.oldest_timestamp().as_deref(),
+            Some("2024-01-15T10:00:10Z")
+        );
+
+        let mut bad = create_test_record("4", 4.0, "A");
+        bad.timestamp = "yesterday".to_string();
+        assert!(window.push(bad).is_err());
+        assert_eq!(window.current_count(), 2);
+
+        let empty = SlidingWindow::new(60);
+        assert_eq!(empty.current_stats().total_processed, 0);
+        assert!(empty.oldest_timestamp().is_none());
+    }
+
+    #[test]
+    fn test_sliding_window_small_values_survive_large_eviction() {
+        let mut window = SlidingWindow::new(60);
+        for (id, value, timestamp) in [
+            ("1", 1e17, "2024-01-15T10:00:00Z"),
+            ("2", 1.0, "2024-01-15T10:00:30Z"),
+            ("3", 2.0, "2024-01-15T10:01:10Z"),
+        ] {
+            let mut record = create_test_record(id, value, "A");
+            record.timestamp = timestamp.to_string();
+            window.push(record).unwrap();
+        }
+
+        // 1e17 has been evicted; 1.0 and 2.0 remain
+        let stats = window.current_stats();
+        assert_eq!(stats.total_processed, 2);
+        assert_eq!(stats.total_value, 3.0);
+        assert_eq!(stats.average_value, 1.5);
+    }
+
+    #[test]
+    fn test_line_processor_matches_process_records() {
+        let records = generate_sample_data(50_000);
+        let mut processor = LineProcessor::new();
+
+        let mut snapshots = Vec::new();
+        for record in &records {
+            let line = serde_json::to_string(record).unwrap();
+            if let Some(stats) = processor.push_line(&line).unwrap() {
+                snapshots.push(stats.total_processed);
+            }
+        }
+        assert_eq!(snapshots, vec![10_000, 20_000, 30_000, 40_000, 50_000]);
+
+        let streamed = processor.flush().unwrap();
+        let expected = process_records(&records).unwrap();
+        assert_eq!(streamed.total_processed, expected.total_processed);
+        assert_eq!(streamed.total_value, expected.total_value);
+        assert_eq!(streamed.average_value, expected.average_value);
+        assert_eq!(streamed.min_value, expected.min_value);
+        assert_eq!(streamed.max_value, expected.max_value);
+        assert_eq!(streamed.categories, expected.categories);
+    }
+
+    #[test]
+    fn test_line_processor_rejects_bad_lines_and_resets() {
+        let mut processor = LineProcessor::new();
+        assert!(matches!(
+            processor.flush(),
+            Err(ProcessingError::EmptyInput)
+        ));
+
+        let good = serde_json::to_string(&create_test_record("1", 5.0, "A")).unwrap();
+        let negative = serde_json::to_string(&create_test_record("2", -1.0, "A")).unwrap();
+        assert!(processor.push_line(&good).unwrap().is_none());
+        assert!(processor.push_line("   ").unwrap().is_none());
+        assert!(matches!(
+            processor.push_line("{not json"),
+            Err(ProcessingError::ParseError { .. })
+        ));
+        assert!(matches!(
+            processor.push_line(&negative),
+            Err(ProcessingError::ValidationFailed { .. })
+        ));
+        assert_eq!(processor.count(), 1);
+        assert_eq!(processor.flush().unwrap().total_value, 5.0);
+
+        processor.reset();
+        assert_eq!(processor.count(), 0);
+        assert!(processor.flush().is_err());
+    }
+
+    #[test]
+    fn test_records_to_elasticsearch_bulk() {
+        let records = vec![
+            create_record_with_metadata("1", 10.0, &[("trace", "t-1")]),
+            create_record_with_metadata("2", 20.0, &[("trace", "t-2")]),
+        ];
+
+        let ndjson = records_to_elasticsearch_bulk(&records, "records", None).unwrap();
+        assert!(ndjson.ends_with('\n'));
+        let lines: Vec<&str> = ndjson.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], r#"{"index":{"_index":"records"}}"#);
+        let source: DataRecord = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(source.id.as_str(), "1");
+        assert_eq!(source.metadata, records[0].metadata);
+
+        let with_id = records_to_elasticsearch_bulk(&records, "records", Some("id")).unwrap();
+        assert!(with_id.starts_with(r#"{"index":{"_id":"1","_index":"records"}}"#));
+        let with_trace = records_to_elasticsearch_bulk(&records, "records", Some("trace")).unwrap();
+        assert!(with_trace.contains(r#""_id":"t-2""#));
+    }
+
+    #[test]
+    fn test_records_to_elasticsearch_bulk_errors() {
+        let records = vec![create_test_record("1", 10.0, "A")];
+
+        assert!(records_to_elasticsearch_bulk(&records, "", None).is_err());
+        assert!(records_to_elasticsearch_bulk(&records, "Logs", None).is_err());
+        assert!(records_to_elasticsearch_bulk(&records, "_logs", None).is_err());
+        assert!(records_to_elasticsearch_bulk(&records, "logs", Some("missing")).is_err());
+        assert_eq!(
+            records_to_elasticsearch_bulk(&[], "logs", None).unwrap(),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_filter_by_metadata_variants() {
+        let records = vec![
+            create_record_with_metadata("1", 1.0, &[("region", "us")]),
+            create_record_with_metadata("2", 2.0, &[("region", "us-east")]),
+            create_record_with_metadata("3", 3.0, &[("region", "eu-us")]),
+            create_record_with_metadata("4", 4.0, &[("zone", "us")]),
+            create_test_record("5", 5.0, "A"),
+        ];
+        let ids = |found: Vec<DataRecord>| -> Vec<String> {
+            found.iter().map(|r| r.id.to_string()).collect()
+        };
+
+        assert_eq!(ids(filter_by_metadata(&records, "region", "us")), ["1"]);
+        assert_eq!(
+            ids(filter_by_metadata_prefix(&records, "region", "us")),
+            ["1", "2"]
+        );
+        assert_eq!(
+            ids(filter_by_metadata_regex(&records, "region", "us").unwrap()),
+            ["1", "2", "3"]
+        );
+        assert_eq!(
+            ids(filter_by_metadata_regex(&records, "region", "^us$").unwrap()),
+            ["1"]
+        );
+        // The record without metadata never matches, even a match-all pattern
+        assert_eq!(
+            filter_by_metadata_regex(&records, "region", "")
+                .unwrap()
+                .len(),
+            3
+        );
+        assert!(filter_by_metadata_regex(&records, "region", "(").is_err());
+    }
+
+    #[test]
+    fn test_compute_frequency_distribution() {
+        let mut records = vec![
+            create_record_with_metadata("1", 1.0, &[("region", "us")]),
+            create_record_with_metadata("2", 2.0, &[("region", "eu")]),
+            create_record_with_metadata("3", 3.0, &[("region", "us")]),
+            create_test_record("4", 4.0, "B"),
+        ];
+        records[3].timestamp = "2024-01-16T23:59:59+00:00".to_string();
+
+        let by_category =
+            compute_frequency_distribution(&records, |r| FieldName::Category.extract(r));
+        assert_eq!(by_category.values().sum::<usize>(), records.len());
+        assert_eq!(by_category["B"], 1);
+
+        let by_day = compute_frequency_distribution(&records, |r| FieldName::Timestamp.extract(r));
+        assert_eq!(by_day.values().sum::<usize>(), records.len());
+        assert_eq!(by_day["2024-01-16"], 1);
+
+        let field: FieldName = "region".parse().unwrap();
+        let by_region = compute_frequency_distribution(&records, |r| field.extract(r));
+        assert_eq!(
+            by_region,
+            HashMap::from([("us".to_string(), 2), ("eu".to_string(), 1)])
+        );
+
+        assert!("".parse::<FieldName>().is_err());
+    }
+
+    #[test]
+    fn test_validate_records_with_rules() {
+        let mut records = vec![
+            create_test_record("abc123", 10.0, "A"),
+            create_test_record("AB123456", 10.0, "A"),
+            create_test_record("CD654321", 2_000_000.0, "Z"),
+        ];
+        records[1].metadata = Some(HashMap::from([("owner".to_string(), "ops".to_string())]));
+        let rules: Vec<ValidationRule> = serde_json::from_str(
+            r#"[{"IdPattern": "^[A-Z]{2}\\d{6}$"}, {"MaxValue": 1000000}, {"AllowedCategories": ["A"]}]"#,
+        )
+        .unwrap();
+
+        let failures = validate_records_with_rules(&records, &rules).unwrap();
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].0, 0);
+        assert_eq!(failures[0].1.kind, ValidationErrorKind::RuleViolation);
+        assert!(failures[0].1.message.contains("does not match"));
+        assert_eq!(failures[0].1.field, "id");
+        // Only the first failing rule is reported
+        assert_eq!(failures[1].0, 2);
+        assert!(failures[1].1.message.contains("at most"));
+        assert_eq!(failures[1].1.field, "value");
+
+        let required = [ValidationRule::RequiredMetadataKey("owner".to_string())];
+        let failures = validate_records_with_rules(&records, &required).unwrap();
+        assert_eq!(
+            failures.iter().map(|(i, _)| *i).collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+
+        let bad = [ValidationRule::IdPattern("(".to_string())];
+        assert!(matches!(
+            validate_records_with_rules(&records, &bad),
+            Err(ProcessingError::InvalidArgument(_))
+        ));
+        assert!(validate_records_with_rules(&records, &[])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_merge_datasets_strategies() {
+        let primary = vec![
+            create_record_with_metadata("1", 10.0, &[("source", "primary")]),
+            create_test_record("2", 5.0, "A"),
+        ];
+        let secondary = vec![
+            create_record_with_metadata("3", 1.0, &[("source", "secondary")]),
+            create_record_with_metadata("1", 20.0, &[("source", "secondary"), ("region", "eu")]),
+        ];
+        let ids =
+            |records: &[DataRecord]| records.iter().map(|r| r.id.to_string()).collect::<Vec<_>>();
+        let merge = |conflict| merge_datasets(primary.clone(), secondary.clone(), conflict);
+
+        let kept = merge(ConflictResolution::KeepPrimary);
+        assert_eq!(ids(&kept), vec!["1", "2", "3"]);
+        assert_eq!(kept[0].value, 10.0);
+        assert_eq!(kept[0].metadata.as_ref().unwrap().len(), 1);
+
+        let replaced = merge(ConflictResolution::KeepSecondary);
+        assert_eq!(ids(&replaced), vec!["1", "2", "3"]);
+        assert_eq!(replaced[0].value, 20.0);
+
+        let combined = merge(ConflictResolution::MergeMetadata);
+        let metadata = combined[0].metadata.as_ref().unwrap();
+        assert_eq!(combined[0].value, 10.0);
+        assert_eq!(metadata["source"], "primary");
+        assert_eq!(metadata["region"], "eu");
+
+        let highest = merge(ConflictResolution::UseHighestValue);
+        assert_eq!(highest[0].value, 20.0);
+        assert_eq!(highest[0].metadata.as_ref().unwrap()["source"], "secondary");
+
+        // Without overlap every strategy is a plain concatenation
+        let disjoint = vec![create_test_record("9", 1.0, "B")];
+        for conflict in [
+            ConflictResolution::KeepPrimary,
+            ConflictResolution::KeepSecondary,
+            ConflictResolution::MergeMetadata,
+            ConflictResolution::UseHighestValue,
+        ] {
+            assert_eq!(
+                ids(&merge_datasets(primary.clone(), disjoint.clone(), conflict)),
+                vec!["1", "2", "9"]
+            );
+            assert_eq!(
+                ids(&merge_datasets(Vec::new(), secondary.clone(), conflict)),
+                vec!["3", "1"]
+            );
+            assert_eq!(
+                ids(&merge_datasets(primary.clone(), Vec::new(), conflict)),
+                vec!["1", "2"]
+            );
+            assert!(merge_datasets(Vec::new(), Vec::new(), conflict).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_merge_datasets_repeated_secondary_ids() {
+        let primary = vec![create_test_record("1", 10.0, "A")];
+        let secondary = vec![
+            create_test_record("4", 1.0, "B"),
+            create_test_record("1", 30.0, "B"),
+            create_test_record("4", 2.0, "B"),
+            create_test_record("1", 20.0, "B"),
+            create_test_record("4", 3.0, "B"),
+        ];
+
+        let merged = merge_datasets(primary, secondary, ConflictResolution::KeepSecondary);
+        let pairs: Vec<(&str, f64)> = merged.iter().map(|r| (r.id.as_str(), r.value)).collect();
+        assert_eq!(pairs, vec![("1", 20.0), ("4", 3.0)]);
+    }
+
+    #[test]
+    fn test_compute_rate_of_change() {
+        // value = t, with uneven gaps between points
+        let records: Vec<DataRecord> = [0i64, 10, 15, 45, 46, 100]
+            .iter()
+            .rev()
+            .map(|&t| {
+                let mut record = create_test_record(&t.to_string(), t as f64, "A");
+                record.timestamp = Utc
+                    .timestamp_opt(1_700_000_000 + t, 0)
+                    .unwrap()
+                    .to_rfc3339();
+                record
+            })
+            .collect();
+
+        for window in [1, 3] {
+            let rates = compute_rate_of_change(&records, window).unwrap();
+            assert_eq!(rates.len(), records.len() - 1);
+            assert!(rates.iter().all(|(_, rate)| (rate - 1.0).abs() < 1e-9));
+        }
+
+        // Window 1 is the raw derivative; a wider window averages it
+        let mut stepped = records.clone();
+        stepped
+            .iter_mut()
+            .filter(|r| r.id == "100")
+            .for_each(|r| r.value = 154.0);
+        let raw = compute_rate_of_change(&stepped, 1).unwrap();
+        assert!((raw.last().unwrap().1 - 2.0).abs() < 1e-9);
+        let smoothed = compute_rate_of_change(&stepped, 2).unwrap();
+        assert!((smoothed.last().unwrap().1 - 1.5).abs() < 1e-9);
+
+        assert!(compute_rate_of_change(&records[..1], 1).is_err());
+        assert!(compute_rate_of_change(&records, 0).is_err());
+    }
+
+    #[test]
+    fn test_compute_percentile_by_group() {
+        let mut records = generate_sample_data(200);
+        records.truncate(199);
+
+        let maxima = compute_percentile_by_group(&records, 1.0, GroupKey::Category).unwrap();
+        assert_eq!(maxima.len(), 4);
+        for (category, max) in &maxima {
+            assert_eq!(
+                *max,
+                get_category_stats(&records, category).unwrap().max_value
+            );
+        }
+
+        let medians = compute_percentile_by_group(
+            &[
+                create_record_with_metadata("1", 1.0, &[("endpoint", "/a")]),
+                create_record_with_metadata("2", 3.0, &[("endpoint", "/a")]),
+                create_record_with_metadata("3", 9.0, &[("endpoint", "/b")]),
+                create_test_record("4", 100.0, "A"),
+            ],
+            0.5,
+            "endpoint".parse().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            medians,
+            HashMap::from([("/a".to_string(), 2.0), ("/b".to_string(), 9.0)])
+        );
+
+        assert!(compute_percentile_by_group(&records, 1.5, GroupKey::Category).is_err());
+        assert!(compute_percentile_by_group(&[], 0.5, GroupKey::Category)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_group_by_fields_composite_keys() {
+        let mut records = vec![
+            create_record_with_metadata("1", 10.0, &[("region", "us-east")]),
+            create_record_with_metadata("2", 20.0, &[("region", "eu-west")]),
+            create_record_with_metadata("3", 30.0, &[("region", "us-east")]),
+            create_test_record("4", 40.0, "A"),
+        ];
+        records[2].category = "B".into();
+        let fields = [FieldName::Category, "region".parse::<FieldName>().unwrap()];
+
+        let groups = group_by_fields(&records, &fields);
+        assert_eq!(groups.len(), 3);
+        let ids = |key: [&str; 2]| -> Vec<&str> {
+            let key: Vec<String> = key.iter().map(|s| s.to_string()).collect();
+            groups[&key].iter().map(|r| r.id.as_str()).collect()
+        };
+        assert_eq!(ids(["A", "us-east"]), vec!["1"]);
+        assert_eq!(ids(["A", "eu-west"]), vec!["2"]);
+        assert_eq!(ids(["B", "us-east"]), vec!["3"]);
+
+        let by_day = group_by_fields(&records, &[FieldName::Timestamp]);
+        assert_eq!(by_day[&vec!["2024-01-15".to_string()]].len(), 4);
+        assert_eq!(
+            group_by_fields(&records, &[])[&Vec::<String>::new()].len(),
+            4
+        );
+        assert!("".parse::<FieldName>().is_err());
+    }
+
+    #[test]
+    fn test_aggregate_by_single_field_matches_aggregate_by_category() {
+        let records = generate_sample_data(1_000);
+        let by_fields = aggregate_by_fields(&records, &[FieldName::Category]).unwrap();
+        let by_category = aggregate_by_category(&records, &[]).unwrap();
+
+        assert_eq!(by_fields.len(), by_category.len());
+        for (category, stats) in &by_category {
+            let result = &by_fields[&vec![category.clone()]];
+            assert_eq!(result.total_processed, stats.count);
+            assert_eq!(result.total_value, stats.total_value);
+            assert_eq!(result.average_value, stats.average_value);
+            assert_eq!(result.min_value, stats.min_value);
+            assert_eq!(result.max_value, stats.max_value);
+        }
+    }
+
+    #[test]
+    fn test_outlier_cap_excludes_data_entry_errors() {
+        let mut records: Vec<DataRecord> = (0..999)
+            .map(|i| create_test_record(&i.to_string(), 1.0 + (i % 3) as f64 * 0.01, "A"))
+            .collect();
+        records.push(create_test_record("bad", 1e15, "B"));
+
+        let config = ProcessingConfig {
+            outlier_cap: Some(3.0),
+            ..ProcessingConfig::default()
+        };
+        let capped = process_records_with_config(&records, &config).unwrap();
+        assert!((capped.average_value - 1.01).abs() < 1e-9);
+        assert_eq!(capped.capped_count, 1);
+        assert_eq!(capped.total_processed, 999);
+        assert_eq!(capped.max_value, 1.02);
+        assert!(!capped.categories.contains_key("B"));
+
+        // The default config keeps every record
+        let uncapped = process_records(&records).unwrap();
+        assert_eq!(uncapped.capped_count, 0);
+        assert!(uncapped.average_value > 1e11);
+
+        for cap in [0.5, -1.0, f64::NAN] {
+            let config = ProcessingConfig {
+                outlier_cap: Some(cap),
+                ..ProcessingConfig::default()
+            };
+            assert!(matches!(
+                process_records_with_config(&records, &config),
+                Err(ProcessingError::InvalidArgument(_))
+            ));
+        }
+
+        let config: ProcessingConfig = serde_json::from_str(r#"{"outlier_cap": 3.0}"#).unwrap();
+        assert_eq!(config.outlier_cap, Some(3.0));
+        assert!(config.use_kahan);
+    }
+
+    #[test]
+    fn test_merge_sums_capped_count() {
+        let mut merged = ProcessResult {
+            capped_count: 2,
+            ..StatsAccumulator::new().finalize()
+        };
+        let other = ProcessResult {
+            capped_count: 3,
+            ..process_records(&[create_test_record("1", 5.0, "A")]).unwrap()
+        };
+        merged.merge(&other);
+        assert_eq!(merged.capped_count, 5);
+        assert_eq!(merged.total_processed, 1);
+    }
 }
@@ -0,0 +1,100 @@
+//! Property tests for invariants of the core record operations
+
+use proptest::prelude::*;
+use rust_core::{
+    compute_percentiles, dedup_records, filter_by_category, get_unique_categories,
+    normalize_values, process_records, DataRecord, DedupStrategy,
+};
+use std::collections::HashSet;
+
+/// A `DataRecord` that always passes `validate_record`
+///
+/// `DataRecord` lives in another crate, so the `Arbitrary` impl goes on this
+/// wrapper. IDs are drawn from a small alphabet so duplicates are common.
+#[derive(Debug, Clone)]
+struct ValidRecord(DataRecord);
+
+impl Arbitrary for ValidRecord {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        ("[a-z0-9]{1,3}", 0.001f64..1e6, "[A-E]", 0u32..86_400)
+            .prop_map(|(id, value, category, seconds)| {
+                ValidRecord(
+                    DataRecord::builder()
+                        .id(&id)
+                        .value(value)
+                        .category(&category)
+                        .timestamp(&format!(
+                            "2024-01-15T{:02}:{:02}:{:02}Z",
+                            seconds / 3600,
+                            seconds / 60 % 60,
+                            seconds % 60
+                        ))
+                        .build()
+                        .expect("generated records are valid"),
+                )
+            })
+            .boxed()
+    }
+}
+
+fn records(max_len: usize) -> impl Strategy<Value = Vec<DataRecord>> {
+    prop::collection::vec(any::<ValidRecord>(), 1..max_len)
+        .prop_map(|records| records.into_iter().map(|r| r.0).collect())
+}
+
+proptest! {
+    #[test]
+    fn average_lies_between_min_and_max(records in records(200)) {
+        let result = process_records(&records).unwrap();
+        // The mean of equal values can round a hair outside them
+        let slack = result.max_value.abs() * 1e-12;
+
+        prop_assert!(result.min_value <= result.average_value + slack);
+        prop_assert!(result.average_value <= result.max_value + slack);
+    }
+
+    #[test]
+    fn category_filters_partition_records(records in records(200)) {
+        let filtered: usize = get_unique_categories(&records)
+            .iter()
+            .map(|category| filter_by_category(&records, category).len())
+            .sum();
+
+        // Each record has exactly one category, so the filters never overlap
+        prop_assert_eq!(filtered, records.len());
+    }
+
+    #[test]
+    fn dedup_keep_first_leaves_unique_ids(records in records(200)) {
+        let deduped = dedup_records(records, DedupStrategy::KeepFirst);
+
+        let ids: HashSet<&str> = deduped.iter().map(|r| r.id.as_str()).collect();
+        prop_assert_eq!(ids.len(), deduped.len());
+    }
+
+    #[test]
+    fn normalize_spans_unit_interval(mut records in records(200)) {
+        let first = records[0].value;
+        prop_assume!(records.iter().any(|r| r.value != first));
+
+        normalize_values(&mut records);
+
+        let min = records.iter().map(|r| r.value).fold(f64::INFINITY, f64::min);
+        let max = records.iter().map(|r| r.value).fold(f64::NEG_INFINITY, f64::max);
+        prop_assert_eq!(min, 0.0);
+        prop_assert_eq!(max, 1.0);
+    }
+
+    #[test]
+    fn zeroth_percentile_is_minimum(records in records(200)) {
+        let values: Vec<f64> = records.iter().map(|r| r.value).collect();
+
+        let percentiles = compute_percentiles(&values, &[0.0]).unwrap();
+
+        let min = *values.iter().min_by(|a, b| a.total_cmp(b)).unwrap();
+        prop_assert_eq!(percentiles[0], min);
+    }
+}
@@ -82,6 +82,30 @@ function benchmarkFiltering() {
     }
 }
 
+function benchmarkBinaryIpc() {
+    printHeader('Benchmark: JSON vs Binary IPC (process 100K records)');
+
+    const size = 100_000;
+    const data = generateSampleData(size);
+    const json = JSON.stringify(data);
+    const buf = rust.encodeRecordsBinary(data);
+
+    const jsonTime = benchmark('JSON IPC', () => {
+        rust.processRecords(JSON.parse(json));
+    });
+
+    const binaryTime = benchmark('Binary IPC', () => {
+        rust.processRecordsBinary(buf);
+    });
+
+    console.log(`Operation: Decode + Process Records`);
+    console.log(`  Dataset: ${size.toLocaleString()} records (JSON ${json.length.toLocaleString()} bytes, binary ${buf.length.toLocaleString()} bytes)`);
+    console.log(`  JSON.parse + processRecords: ${jsonTime.toFixed(2)}ms`);
+    console.log(`  processRecordsBinary:        ${binaryTime.toFixed(2)}ms`);
+    console.log(`  Speedup:    ${(jsonTime / binaryTime).toFixed(1)}x`);
+    console.log();
+}
+
 function main() {
     console.log('\n' + '='.repeat(80));
     console.log('  🚀 Rust vs JavaScript Performance Benchmark');
@@ -94,6 +118,7 @@ function main() {
 
     benchmarkProcessing();
     benchmarkFiltering();
+    benchmarkBinaryIpc();
 
     console.log('='.repeat(80));
     console.log('  ✅ Benchmark Complete!');
@@ -1,9 +1,15 @@
 /**
  * Node.js Example: Using Rust via napi-rs
  *
- * This demonstrates 5 scenarios using the Rust-powered Node.js binding
+ * This demonstrates 7 scenarios using the Rust-powered Node.js binding
  */
 
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { Transform, TransformCallback } from 'stream';
+import { pipeline } from 'stream/promises';
+import { StringDecoder } from 'string_decoder';
 import * as rust from '@rust-integration/node-binding';
 import { DataRecord } from './types';
 
@@ -57,13 +63,13 @@ function example2_validation() {
     ];
 
     console.log('Testing valid record:');
-    const validError = rust.validateRecord(validRecord);
-    console.log(validError ? `  ❌ ${validError}` : '  ✅ Valid');
+    const validResult = rust.validateRecord(validRecord);
+    console.log(validResult.valid ? '  ✅ Valid' : `  ❌ ${validResult.field}: ${validResult.message}`);
 
     console.log('\nTesting invalid records:');
     invalidRecords.forEach((record, i) => {
-        const error = rust.validateRecord(record as DataRecord);
-        console.log(`  Record ${i + 1}: ${error ? `❌ ${error}` : '✅ Valid'}`);
+        const result = rust.validateRecord(record as DataRecord);
+        console.log(`  Record ${i + 1}: ${result.valid ? '✅ Valid' : `❌ ${result.field}: ${result.message}`}`);
     });
 }
 
@@ -139,10 +145,114 @@ function example5_benchmark() {
     });
 }
 
+/**
+ * Example 6: Async Processing
+ */
+async function example6_asyncProcessing() {
+    printHeader('Example 6: Async Processing (libuv thread pool)');
+
+    const records = rust.generateSampleData(100_000);
+
+    console.log(`Processing ${records.length.toLocaleString()} records off the event loop...`);
+
+    const [asyncResult, filtered] = await Promise.all([
+        rust.processRecordsAsync(records),
+        rust.filterByCategoryAsync(records, 'A'),
+    ]);
+    const syncResult = rust.processRecords(records);
+
+    if (JSON.stringify(asyncResult) !== JSON.stringify(syncResult)) {
+        throw new Error('processRecordsAsync result differs from processRecords');
+    }
+
+    console.log(`✅ Async result matches sync result`);
+    console.log(`   Total processed: ${asyncResult.totalProcessed}`);
+    console.log(`   Category A records: ${filtered.length}`);
+}
+
+/**
+ * Transform stream that feeds NDJSON lines to a LogProcessor
+ *
+ * Chunks are split on newlines, holding back a partial last line until the
+ * next chunk. A StringDecoder likewise holds back a UTF-8 character split
+ * across chunks. Running statistics are emitted as `stats` events; the final
+ * ProcessResult is the stream's only output, written when the input ends.
+ */
+function logProcessorStream(): Transform {
+    const decoder = new StringDecoder('utf8');
+    let pending = '';
+    const stream: Transform = new Transform({
+        readableObjectMode: true,
+        transform(chunk: Buffer, _encoding: BufferEncoding, callback: TransformCallback) {
+            const lines = (pending + decoder.write(chunk)).split('\n');
+            pending = lines.pop() ?? '';
+            try {
+                lines.forEach(line => processor.push(line));
+                callback();
+            } catch (error) {
+                callback(error as Error);
+            }
+        },
+        flush(callback: TransformCallback) {
+            try {
+                processor.push(pending + decoder.end());
+                callback(null, processor.flush());
+            } catch (error) {
+                callback(error as Error);
+            }
+        },
+    });
+    const processor = new rust.LogProcessor(stats => stream.emit('stats', stats));
+    return stream;
+}
+
+/**
+ * Example 7: Streaming a File Through LogProcessor
+ */
+async function example7_streaming() {
+    printHeader('Example 7: Streaming NDJSON (LogProcessor)');
+
+    const records = rust.generateSampleData(50_000);
+    const file = path.join(os.tmpdir(), `records-${process.pid}.ndjson`);
+    fs.writeFileSync(file, records.map(r => JSON.stringify(r)).join('\n') + '\n');
+
+    console.log(`Streaming ${records.length.toLocaleString()} records from ${file}...`);
+
+    const processor = logProcessorStream();
+    processor.on('stats', (stats: rust.ProcessResult) => {
+        console.log(`   ...${stats.totalProcessed.toLocaleString()} records so far`);
+    });
+
+    const outputs: rust.ProcessResult[] = [];
+    try {
+        await pipeline(
+            fs.createReadStream(file),
+            processor,
+            async (output: AsyncIterable<rust.ProcessResult>) => {
+                for await (const stats of output) {
+                    outputs.push(stats);
+                }
+            },
+        );
+    } finally {
+        fs.unlinkSync(file);
+    }
+
+    const [result] = outputs;
+    const expected = rust.processRecords(records);
+    if (result.totalProcessed !== expected.totalProcessed ||
+        result.totalValue !== expected.totalValue) {
+        throw new Error('LogProcessor result differs from processRecords');
+    }
+
+    console.log(`✅ Streamed result matches processRecords`);
+    console.log(`   Total processed: ${result.totalProcessed}`);
+}
+
 /**
  * Main function
  */
-function main() {
+async function main() {
     console.log('\n' + '='.repeat(70));
     console.log('  Rust + Node.js Integration Examples');
     console.log('='.repeat(70));
@@ -155,6 +265,8 @@ function main() {
         example3_filtering();
         example4_categoryStats();
         example5_benchmark();
+        await example6_asyncProcessing();
+        await example7_streaming();
 
         console.log('='.repeat(70));
         console.log('  ✅ All examples completed successfully!');
@@ -28,6 +28,8 @@ export interface CategoryStats {
     average_value: number;
     min_value: number;
     max_value: number;
+    /** `[quantile, value]` pairs, when percentiles were requested */
+    percentiles?: [number, number][];
 }
 
 export interface BenchmarkResult {
@@ -36,6 +38,24 @@ export interface BenchmarkResult {
     records_per_second: number;
 }
 
+/** One page of items from offset pagination */
+export interface PaginatedResult<T = DataRecord> {
+    items: T[];
+    total: number;
+    offset: number;
+    limit: number;
+    hasNext: boolean;
+    hasPrev: boolean;
+}
+
+/** One page of items from cursor pagination */
+export interface CursorPaginatedResult<T = DataRecord> {
+    items: T[];
+    /** Pass back as the cursor for the next page; absent on the last page */
+    nextCursor?: string;
+    hasMore: boolean;
+}
+
 /**
  * Generate sample test data
  */
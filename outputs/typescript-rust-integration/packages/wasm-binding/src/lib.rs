@@ -7,6 +7,7 @@
 
 use wasm_bindgen::prelude::*;
 use rust_core::{self, DataRecord, ProcessResult as CoreProcessResult};
+use std::collections::{BTreeMap, HashMap};
 
 // Use web-sys for browser APIs
 use web_sys::console;
@@ -74,6 +75,12 @@ impl ProcessResult {
         self.result.max_value
     }
 
+    /// Get number of records left out by `outlier_cap`
+    #[wasm_bindgen(getter)]
+    pub fn capped_count(&self) -> usize {
+        self.result.capped_count
+    }
+
     /// Convert to JSON string
     pub fn to_json(&self) -> Result<String, JsValue> {
         serde_json::to_string(&self.result)
@@ -100,12 +107,32 @@ pub fn validate_record(record_json: &str) -> Option<String> {
         Err(e) => return Some(format!("JSON parse error: {}", e)),
     };
 
-    match rust_core::validate_record(&record) {
+    match rust_core::validate_record(&record, rust_core::ValidationMode::Lenient) {
         Ok(_) => None,
         Err(e) => Some(e.message),
     }
 }
 
+/// Validate many records in one call
+///
+/// Takes JSON array of records, returns JSON array of
+/// `{ id, valid, error? }` objects in input order.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const results = JSON.parse(validateRecordsBatch(JSON.stringify(records)));
+/// const invalidCount = results.filter(r => !r.valid).length;
+/// ```
+#[wasm_bindgen(js_name = validateRecordsBatch)]
+pub fn validate_records_batch(records_json: &str) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let batch = rust_core::validate_records_batch(&records);
+
+    serde_json::to_string(&batch).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
 /// Process records and compute statistics
 ///
 /// Takes JSON array of records, returns ProcessResult.
@@ -127,7 +154,35 @@ pub fn process_records(records_json: &str) -> Result<ProcessResult, JsValue> {
         .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
 
     let result = rust_core::process_records(&records)
-        .map_err(|e| JsValue::from_str(&e))?;
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    Ok(ProcessResult { result })
+}
+
+/// Process records with summation and outlier settings
+///
+/// `config_json` is a JSON object with any of `use_kahan`,
+/// `parallel_threshold` and `outlier_cap`; omitted fields keep their
+/// defaults. With `outlier_cap` set to `k`, records more than `k` standard
+/// deviations from the mean are left out and counted in `capped_count`.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const result = processRecordsWithConfig(JSON.stringify(records), JSON.stringify({ outlier_cap: 3 }));
+/// console.log(`Left out ${result.capped_count} outliers`);
+/// ```
+#[wasm_bindgen(js_name = processRecordsWithConfig)]
+pub fn process_records_with_config(
+    records_json: &str,
+    config_json: &str,
+) -> Result<ProcessResult, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+    let config: rust_core::ProcessingConfig = serde_json::from_str(config_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let result = rust_core::process_records_with_config(&records, &config)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
 
     Ok(ProcessResult { result })
 }
@@ -174,6 +229,143 @@ pub fn filter_by_value(records_json: &str, min_value: f64) -> Result<String, JsV
         .map_err(|e| JsValue::from_str(&e.to_string()))
 }
 
+/// Records handled between two yields in the `*Async` functions
+const ASYNC_CHUNK_SIZE: usize = 10_000;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(handler: &js_sys::Function, timeout: i32) -> JsValue;
+}
+
+/// Hand control back to the JavaScript event loop
+///
+/// Awaits a `setTimeout(0)` promise rather than `Promise.resolve()`: a
+/// resolved promise only runs the microtask queue, so the browser still
+/// could not paint or handle input until the whole computation finished.
+async fn yield_to_event_loop() {
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        set_timeout(&resolve, 0);
+    });
+    // The promise never rejects
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+/// Process records without blocking the main thread for the whole batch
+///
+/// Same result as `processRecords`, computed 10,000 records at a time with
+/// a yield to the event loop between chunks so the page can repaint and
+/// handle input.
+///
+/// Limitations:
+/// - The work still runs on the calling thread. WASM cannot hand it to a
+///   Web Worker from here; load the module in a Worker yourself for true
+///   background processing.
+/// - Parsing `records_json` happens in one step before the first yield.
+/// - If records fail validation, the error lists only the invalid records
+///   of the first chunk that contains any.
+/// - Browsers clamp nested `setTimeout` calls to about 4ms, so each chunk
+///   adds a small delay to the total time.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const result = await processRecordsAsync(JSON.stringify(records));
+/// console.log(`Processed ${result.total_processed} records`);
+/// ```
+#[wasm_bindgen(js_name = processRecordsAsync)]
+pub async fn process_records_async(records_json: String) -> Result<ProcessResult, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(&records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+    drop(records_json);
+
+    if records.is_empty() {
+        return Err(JsValue::from_str(
+            &rust_core::ProcessingError::EmptyInput.to_string(),
+        ));
+    }
+
+    let mut merged: Option<CoreProcessResult> = None;
+    for (i, chunk) in records.chunks(ASYNC_CHUNK_SIZE).enumerate() {
+        if i > 0 {
+            yield_to_event_loop().await;
+        }
+        let result =
+            rust_core::process_records(chunk).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        match merged.as_mut() {
+            Some(total) => total.merge(&result),
+            None => merged = Some(result),
+        }
+    }
+
+    let result = merged.expect("non-empty input yields at least one chunk");
+    // Each chunk's sum can be finite while the merged sum overflows
+    if !result.total_value.is_finite() && records.iter().all(|r| r.value.is_finite()) {
+        return Err(JsValue::from_str(
+            &rust_core::ProcessingError::Overflow.to_string(),
+        ));
+    }
+
+    Ok(ProcessResult { result })
+}
+
+/// Filter records by category without blocking the main thread
+///
+/// Same output as `filterByCategory`, filtered in chunks with a yield to the
+/// event loop between them. See `processRecordsAsync` for the limitations.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const filtered = JSON.parse(await filterByCategoryAsync(JSON.stringify(records), "A"));
+/// ```
+#[wasm_bindgen(js_name = filterByCategoryAsync)]
+pub async fn filter_by_category_async(
+    records_json: String,
+    category: String,
+) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(&records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+    drop(records_json);
+
+    let mut filtered = Vec::new();
+    for (i, chunk) in records.chunks(ASYNC_CHUNK_SIZE).enumerate() {
+        if i > 0 {
+            yield_to_event_loop().await;
+        }
+        filtered.extend(rust_core::filter_by_category(chunk, &category));
+    }
+
+    serde_json::to_string(&filtered).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Filter records by minimum value without blocking the main thread
+///
+/// Same output as `filterByValue`, filtered in chunks with a yield to the
+/// event loop between them. See `processRecordsAsync` for the limitations.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const filtered = JSON.parse(await filterByValueAsync(JSON.stringify(records), 1000.0));
+/// ```
+#[wasm_bindgen(js_name = filterByValueAsync)]
+pub async fn filter_by_value_async(
+    records_json: String,
+    min_value: f64,
+) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(&records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+    drop(records_json);
+
+    let mut filtered = Vec::new();
+    for (i, chunk) in records.chunks(ASYNC_CHUNK_SIZE).enumerate() {
+        if i > 0 {
+            yield_to_event_loop().await;
+        }
+        filtered.extend(rust_core::filter_by_value(chunk, min_value));
+    }
+
+    serde_json::to_string(&filtered).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
 /// Get statistics for a specific category
 ///
 /// Returns JSON string with stats, or null if category doesn't exist.
@@ -203,6 +395,59 @@ pub fn get_category_stats(records_json: &str, category: &str) -> Result<Option<S
     }
 }
 
+/// Look up a single record by ID
+///
+/// Returns the JSON-serialized record, or null if no record has that ID.
+/// With duplicate IDs the first match is returned.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const json = getRecordById(JSON.stringify(records), "42");
+/// const record = json ? JSON.parse(json) : null;
+/// ```
+#[wasm_bindgen(js_name = getRecordById)]
+pub fn get_record_by_id(records_json: &str, id: &str) -> Result<Option<String>, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    records
+        .iter()
+        .find(|r| r.id == id)
+        .map(|r| serde_json::to_string(r).map_err(|e| JsValue::from_str(&e.to_string())))
+        .transpose()
+}
+
+/// Look up many records by ID in one call
+///
+/// Takes a JSON array of records and a JSON array of ID strings, returns a
+/// JSON array with one entry per requested ID, in the order of `ids_json`:
+/// the matching record, or null if there is none. With duplicate record IDs
+/// the first match is returned. Runs in O(n + m) via a hash index.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const found = JSON.parse(getRecordsByIds(JSON.stringify(records), JSON.stringify(["3", "1"])));
+/// // [{ id: "3", ... }, { id: "1", ... }]
+/// ```
+#[wasm_bindgen(js_name = getRecordsByIds)]
+pub fn get_records_by_ids(records_json: &str, ids_json: &str) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+    let ids: Vec<String> = serde_json::from_str(ids_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let mut index: HashMap<&str, &DataRecord> = HashMap::with_capacity(records.len());
+    for record in &records {
+        index.entry(record.id.as_str()).or_insert(record);
+    }
+    let found: Vec<Option<&DataRecord>> = ids
+        .iter()
+        .map(|id| index.get(id.as_str()).copied())
+        .collect();
+
+    serde_json::to_string(&found).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
 /// Get all unique categories
 ///
 /// Returns JSON array of category names.
@@ -224,6 +469,643 @@ pub fn get_unique_categories(records_json: &str) -> Result<String, JsValue> {
         .map_err(|e| JsValue::from_str(&e.to_string()))
 }
 
+/// Compute a category × time-bucket heatmap of average values
+///
+/// Returns JSON array of `{ time_bucket, category_averages }` rows.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const rows = JSON.parse(computeHeatmap(JSON.stringify(records), 15));
+/// rows.forEach(row => console.log(row.time_bucket, row.category_averages));
+/// ```
+#[wasm_bindgen(js_name = computeHeatmap)]
+pub fn compute_heatmap(records_json: &str, bucket_minutes: u32) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let rows = rust_core::compute_heatmap(&records, bucket_minutes as u64)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    serde_json::to_string(&rows)
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Get the metadata keys present in at least one record
+///
+/// Returns JSON array of sorted key names.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const keys = JSON.parse(metadataKeysUnion(JSON.stringify(records)));
+/// console.log(`Seen metadata keys: ${keys.join(", ")}`);
+/// ```
+#[wasm_bindgen(js_name = metadataKeysUnion)]
+pub fn metadata_keys_union(records_json: &str) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let keys = rust_core::metadata_keys_union(&records);
+
+    serde_json::to_string(&keys)
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Get the metadata keys present in every record
+///
+/// Returns JSON array of sorted key names.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const keys = JSON.parse(metadataKeysIntersection(JSON.stringify(records)));
+/// console.log(`Common metadata keys: ${keys.join(", ")}`);
+/// ```
+#[wasm_bindgen(js_name = metadataKeysIntersection)]
+pub fn metadata_keys_intersection(records_json: &str) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let keys = rust_core::metadata_keys_intersection(&records);
+
+    serde_json::to_string(&keys)
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Compute the entropy of the value distribution within each category
+///
+/// Returns JSON object mapping category name to entropy in bits.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const entropy = JSON.parse(computeValueEntropyPerCategory(JSON.stringify(records), 10));
+/// console.log(`Category A entropy: ${entropy["A"]} bits`);
+/// ```
+#[wasm_bindgen(js_name = computeValueEntropyPerCategory)]
+pub fn compute_value_entropy_per_category(
+    records_json: &str,
+    bucket_count: usize,
+) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let entropy = rust_core::compute_value_entropy_per_category(&records, bucket_count);
+
+    serde_json::to_string(&entropy).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Pair each record with its percentile rank (0-100) in the value distribution
+///
+/// Returns JSON array of `{ record, percentile_rank }` objects in input order.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const ranked = JSON.parse(computePercentileRanks(JSON.stringify(records)));
+/// ranked.forEach(r => console.log(r.record.id, r.percentile_rank));
+/// ```
+#[wasm_bindgen(js_name = computePercentileRanks)]
+pub fn compute_percentile_ranks(records_json: &str) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let ranked: Vec<serde_json::Value> = rust_core::compute_percentile_ranks(&records)
+        .into_iter()
+        .map(|(record, percentile_rank)| {
+            serde_json::json!({
+                "record": record,
+                "percentile_rank": percentile_rank,
+            })
+        })
+        .collect();
+
+    serde_json::to_string(&ranked).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Render records as an HTML table for debugging
+///
+/// Takes JSON array of records and an optional row limit, returns HTML.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// document.body.innerHTML = recordsToHtmlTable(JSON.stringify(records), 50);
+/// ```
+#[wasm_bindgen(js_name = recordsToHtmlTable)]
+pub fn records_to_html_table(
+    records_json: &str,
+    max_rows: Option<usize>,
+) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    Ok(rust_core::records_to_html_table(&records, max_rows))
+}
+
+/// Sample records with probability proportional to their value
+///
+/// Takes JSON array of records, returns JSON array of `n` sampled records.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const sample = JSON.parse(weightedSample(JSON.stringify(records), 100, 42));
+/// ```
+#[wasm_bindgen(js_name = weightedSample)]
+pub fn weighted_sample(records_json: &str, n: usize, seed: u32) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let sample = rust_core::weighted_sample(&records, n, seed as u64)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    serde_json::to_string(&sample).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Bucket values into equal-width histogram buckets spanning [min, max]
+///
+/// Returns JSON array of `{ lower, upper, count }` objects. NaN and infinite
+/// values are skipped.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const values = new Float64Array(records.map(r => r.value));
+/// const buckets = JSON.parse(computeHistogram(values, 20));
+/// ```
+#[wasm_bindgen(js_name = computeHistogram)]
+pub fn compute_histogram(values: &[f64], bucket_count: usize) -> Result<String, JsValue> {
+    let buckets: Vec<serde_json::Value> = rust_core::compute_histogram(values, bucket_count)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?
+        .into_iter()
+        .map(|(lower, upper, count)| {
+            serde_json::json!({
+                "lower": lower,
+                "upper": upper,
+                "count": count,
+            })
+        })
+        .collect();
+
+    serde_json::to_string(&buckets).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Get statistics for every category
+///
+/// Returns JSON object mapping category name to its stats, with keys in sorted
+/// order. An empty input yields `{}`.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const all = JSON.parse(getCategoryStatsAll(JSON.stringify(records)));
+/// Object.entries(all).forEach(([category, stats]) => console.log(category, stats.count));
+/// ```
+#[wasm_bindgen(js_name = getCategoryStatsAll)]
+pub fn get_category_stats_all(records_json: &str) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    // BTreeMap gives deterministic key order in the serialized object
+    let stats: BTreeMap<String, rust_core::CategoryStats> =
+        rust_core::aggregate_by_category(&records, &[])
+            .map_err(|e| JsValue::from_str(&e.to_string()))?
+            .into_iter()
+            .collect();
+
+    serde_json::to_string(&stats).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Return the `n` highest-value records, sorted by value descending
+///
+/// Takes JSON array of records, returns JSON array of at most `n` records.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const top10 = JSON.parse(topNByValue(JSON.stringify(records), 10));
+/// ```
+#[wasm_bindgen(js_name = topNByValue)]
+pub fn top_n_by_value(records_json: &str, n: usize) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let top = rust_core::top_n_by_value(&records, n);
+
+    serde_json::to_string(&top).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Sort records by one or more keys
+///
+/// Takes JSON array of records and JSON array of sort key names (e.g.
+/// `["CategoryAsc", "ValueDesc"]`), returns JSON array of sorted records.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const sorted = JSON.parse(sortRecords(JSON.stringify(records), JSON.stringify(["TimestampDesc"])));
+/// ```
+#[wasm_bindgen(js_name = sortRecords)]
+pub fn sort_records(records_json: &str, keys_json: &str) -> Result<String, JsValue> {
+    let mut records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+    let keys: Vec<rust_core::SortKey> = serde_json::from_str(keys_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    rust_core::sort_records(&mut records, &keys);
+
+    serde_json::to_string(&records).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Uniformly sample `k` records using reservoir sampling
+///
+/// Takes JSON array of records, returns JSON array of at most `k` records.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const sample = JSON.parse(reservoirSample(JSON.stringify(records), 1000, 42));
+/// ```
+#[wasm_bindgen(js_name = reservoirSample)]
+pub fn reservoir_sample(records_json: &str, k: usize, seed: u32) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let sample = rust_core::reservoir_sample_seeded(&records, k, seed as u64);
+
+    serde_json::to_string(&sample).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Remove records with duplicate IDs
+///
+/// `strategy` is `"KeepFirst"`, `"KeepLast"` or `"KeepHighestValue"`.
+/// Returns JSON array of records ordered by first occurrence of each ID.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const unique = JSON.parse(dedupRecords(JSON.stringify(records), "KeepFirst"));
+/// ```
+#[wasm_bindgen(js_name = dedupRecords)]
+pub fn dedup_records(records_json: &str, strategy: &str) -> Result<String, JsValue> {
+    let strategy: rust_core::DedupStrategy = strategy
+        .parse()
+        .map_err(|e: rust_core::ProcessingError| JsValue::from_str(&e.to_string()))?;
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let deduped = rust_core::dedup_records(records, strategy);
+
+    serde_json::to_string(&deduped).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Split records into one array per category
+///
+/// Returns a JSON object mapping category to an array of its records, with
+/// keys in sorted order and each array in the records' original order.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const byCategory = JSON.parse(splitByCategory(JSON.stringify(records)));
+/// ```
+#[wasm_bindgen(js_name = splitByCategory)]
+pub fn split_by_category(records_json: &str) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let buckets: BTreeMap<String, Vec<DataRecord>> =
+        rust_core::split_by_category(&records).into_iter().collect();
+
+    serde_json::to_string(&buckets).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Shannon entropy (in bits) of the category distribution
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const bits = computeEntropy(JSON.stringify(records));
+/// ```
+#[wasm_bindgen(js_name = computeEntropy)]
+pub fn compute_entropy(records_json: &str) -> Result<f64, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    Ok(rust_core::compute_entropy(&records))
+}
+
+/// Category entropy scaled into `[0.0, 1.0]`; 1.0 means perfectly balanced
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const balance = computeNormalizedEntropy(JSON.stringify(records));
+/// ```
+#[wasm_bindgen(js_name = computeNormalizedEntropy)]
+pub fn compute_normalized_entropy(records_json: &str) -> Result<f64, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    Ok(rust_core::compute_normalized_entropy(&records))
+}
+
+/// Simple moving average of values in timestamp order
+///
+/// Returns JSON array of `[timestamp, value]` pairs. The first `window - 1`
+/// points average whatever predecessors exist. `window` must be > 0.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const smoothed = JSON.parse(computeSMA(JSON.stringify(records), 5));
+/// ```
+#[wasm_bindgen(js_name = computeSMA)]
+pub fn compute_sma(records_json: &str, window: usize) -> Result<String, JsValue> {
+    if window == 0 {
+        return Err(JsValue::from_str("window must be greater than 0"));
+    }
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let points = rust_core::compute_sma(&records, window);
+    serde_json::to_string(&points).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Exponential moving average of values in timestamp order
+///
+/// Returns JSON array of `[timestamp, value]` pairs. `alpha` must be in `(0, 1]`.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const smoothed = JSON.parse(computeEMA(JSON.stringify(records), 0.3));
+/// ```
+#[wasm_bindgen(js_name = computeEMA)]
+pub fn compute_ema(records_json: &str, alpha: f64) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let points =
+        rust_core::compute_ema(&records, alpha).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    serde_json::to_string(&points).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Decode a MessagePack-encoded array of records
+#[cfg(feature = "msgpack")]
+fn decode_records_msgpack(data: &[u8]) -> Result<Vec<DataRecord>, JsValue> {
+    rmp_serde::from_slice(data)
+        .map_err(|e| JsValue::from_str(&format!("MessagePack decode error: {}", e)))
+}
+
+/// Encode a value as MessagePack, with structs written as maps keyed by field name
+#[cfg(feature = "msgpack")]
+fn encode_msgpack<T: serde::Serialize>(value: &T) -> Result<Vec<u8>, JsValue> {
+    rmp_serde::to_vec_named(value)
+        .map_err(|e| JsValue::from_str(&format!("MessagePack encode error: {}", e)))
+}
+
+/// Process records passed as MessagePack bytes (requires the `msgpack` feature)
+///
+/// Takes a MessagePack array of records and returns the ProcessResult as
+/// MessagePack bytes, avoiding JSON text on both sides of the boundary.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// import { encode, decode } from '@msgpack/msgpack';
+/// const result = decode(processRecordsMsgPack(encode(records)));
+/// ```
+#[cfg(feature = "msgpack")]
+#[wasm_bindgen(js_name = processRecordsMsgPack)]
+pub fn process_records_msgpack(data: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let records = decode_records_msgpack(data)?;
+
+    let result =
+        rust_core::process_records(&records).map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    encode_msgpack(&result)
+}
+
+/// Validate records passed as MessagePack bytes (requires the `msgpack` feature)
+///
+/// Returns the same array of `{ id, valid, error? }` objects as
+/// `validateRecordsBatch`, in input order, encoded as MessagePack.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const results = decode(validateRecordsMsgPack(encode(records)));
+/// const invalidCount = results.filter(r => !r.valid).length;
+/// ```
+#[cfg(feature = "msgpack")]
+#[wasm_bindgen(js_name = validateRecordsMsgPack)]
+pub fn validate_records_msgpack(data: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let records = decode_records_msgpack(data)?;
+
+    encode_msgpack(&rust_core::validate_records_batch(&records))
+}
+
+/// Compare MessagePack and JSON round-trip cost (requires the `msgpack` feature)
+///
+/// Encodes and decodes `count` sample records both ways and returns JSON
+/// with `msgpack_ms`, `json_ms`, `speedup` (`json_ms / msgpack_ms`) and the
+/// encoded sizes in bytes.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const report = JSON.parse(benchmarkMsgPackVsJson(100000));
+/// console.log(`MessagePack is ${report.speedup.toFixed(1)}x faster`);
+/// ```
+#[cfg(feature = "msgpack")]
+#[wasm_bindgen(js_name = benchmarkMsgPackVsJson)]
+pub fn benchmark_msgpack_vs_json(count: usize) -> Result<String, JsValue> {
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("No window object"))?;
+    let performance = window
+        .performance()
+        .ok_or_else(|| JsValue::from_str("No performance object"))?;
+    let records = rust_core::generate_sample_data(count);
+
+    let start = performance.now();
+    let json = serde_json::to_string(&records).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let _: Vec<DataRecord> =
+        serde_json::from_str(&json).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let json_ms = performance.now() - start;
+
+    let start = performance.now();
+    let bytes = encode_msgpack(&records)?;
+    let _ = decode_records_msgpack(&bytes)?;
+    let msgpack_ms = performance.now() - start;
+
+    let report = serde_json::json!({
+        "count": count,
+        "msgpack_ms": msgpack_ms,
+        "json_ms": json_ms,
+        "speedup": json_ms / msgpack_ms,
+        "msgpack_bytes": bytes.len(),
+        "json_bytes": json.len(),
+    });
+
+    serde_json::to_string(&report).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Get the JSON Schema for `DataRecord` as a pretty-printed string
+#[wasm_bindgen(js_name = getDataRecordSchema)]
+pub fn get_data_record_schema() -> String {
+    rust_core::data_record_schema()
+}
+
+/// Gini coefficient of a value distribution
+///
+/// 0 means all values are equal; approaches 1 as the total concentrates in a
+/// single value. Throws if any value is negative, NaN or infinite.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const gini = computeGini(new Float64Array(records.map(r => r.value)));
+/// ```
+#[wasm_bindgen(js_name = computeGini)]
+pub fn compute_gini(values: &[f64]) -> Result<f64, JsValue> {
+    rust_core::compute_gini(values).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Filter records whose `metadata[key]` equals `value`
+///
+/// Takes a JSON array of records, returns a JSON array of matches. Records
+/// without metadata or without `key` are never returned.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const usEast = JSON.parse(filterByMetadata(JSON.stringify(records), "region", "us-east-1"));
+/// ```
+#[wasm_bindgen(js_name = filterByMetadata)]
+pub fn filter_by_metadata(records_json: &str, key: &str, value: &str) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let filtered = rust_core::filter_by_metadata(&records, key, value);
+
+    serde_json::to_string(&filtered).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Filter records whose `metadata[key]` starts with `prefix`
+///
+/// Takes a JSON array of records, returns a JSON array of matches.
+#[wasm_bindgen(js_name = filterByMetadataPrefix)]
+pub fn filter_by_metadata_prefix(
+    records_json: &str,
+    key: &str,
+    prefix: &str,
+) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let filtered = rust_core::filter_by_metadata_prefix(&records, key, prefix);
+
+    serde_json::to_string(&filtered).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Filter records whose `metadata[key]` matches a Rust regular expression
+///
+/// Takes a JSON array of records, returns a JSON array of matches. The
+/// pattern matches anywhere in the value; throws if it is invalid.
+#[wasm_bindgen(js_name = filterByMetadataRegex)]
+pub fn filter_by_metadata_regex(
+    records_json: &str,
+    key: &str,
+    pattern: &str,
+) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let filtered = rust_core::filter_by_metadata_regex(&records, key, pattern)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    serde_json::to_string(&filtered).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Count how often each value of a field occurs
+///
+/// Takes a JSON array of records and a field (`"category"`, `"timestamp"` for
+/// per-day counts, or a metadata key); returns a JSON object of counts.
+#[wasm_bindgen(js_name = computeFrequency)]
+pub fn compute_frequency(records_json: &str, field: &str) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+    let field: rust_core::FieldName = field
+        .parse()
+        .map_err(|e: rust_core::ProcessingError| JsValue::from_str(&e.to_string()))?;
+
+    let counts = rust_core::compute_frequency_distribution(&records, |r| field.extract(r));
+
+    serde_json::to_string(&counts).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Check records against custom validation rules
+///
+/// Takes a JSON array of records and a JSON array of rules such as
+/// `[{"MaxValue": 1000000}, {"IdPattern": "^[A-Z]{2}\\d{6}$"}]`. Returns a
+/// JSON array of `{index, record_id, message}` for the first failing rule of
+/// each invalid record. Throws if an `IdPattern` is not a valid regular
+/// expression.
+#[wasm_bindgen(js_name = validateRecordsWithRules)]
+pub fn validate_records_with_rules(
+    records_json: &str,
+    rules_json: &str,
+) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+    let rules: Vec<rust_core::ValidationRule> = serde_json::from_str(rules_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let violations: Vec<serde_json::Value> =
+        rust_core::validate_records_with_rules(&records, &rules)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?
+            .into_iter()
+            .map(|(index, error)| {
+                serde_json::json!({
+                    "index": index,
+                    "record_id": error.record_id,
+                    "message": error.message,
+                })
+            })
+            .collect();
+
+    serde_json::to_string(&violations).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Smoothed rate of change of values per second, in timestamp order
+///
+/// Returns JSON array of `[timestamp, rate]` pairs. A `smoothingWindow` of 1
+/// returns the raw derivative. Throws if fewer than 2 records have valid
+/// timestamps or the window is zero.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const rates = JSON.parse(computeRateOfChange(JSON.stringify(records), 5));
+/// ```
+#[wasm_bindgen(js_name = computeRateOfChange)]
+pub fn compute_rate_of_change(
+    records_json: &str,
+    smoothing_window: usize,
+) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let points = rust_core::compute_rate_of_change(&records, smoothing_window)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    serde_json::to_string(&points).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Compute one percentile of `value` per group
+///
+/// Takes a JSON array of records, a quantile in `[0, 1]` and a group key
+/// (`"category"` or a metadata key); returns a JSON object from group to
+/// percentile.
+///
+/// # Example (JavaScript)
+/// ```javascript
+/// const p99 = JSON.parse(computePercentileByGroup(JSON.stringify(records), 0.99, "category"));
+/// ```
+#[wasm_bindgen(js_name = computePercentileByGroup)]
+pub fn compute_percentile_by_group(
+    records_json: &str,
+    percentile: f64,
+    group_key: &str,
+) -> Result<String, JsValue> {
+    let records: Vec<DataRecord> = serde_json::from_str(records_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+    let group_key: rust_core::GroupKey = group_key
+        .parse()
+        .map_err(|e: rust_core::ProcessingError| JsValue::from_str(&e.to_string()))?;
+
+    let by_group = rust_core::compute_percentile_by_group(&records, percentile, group_key)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    serde_json::to_string(&by_group).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
 /// Benchmark: Process records and measure performance using browser APIs
 ///
 /// Returns JSON with result and timing information.
@@ -246,7 +1128,7 @@ pub fn benchmark_process(records_json: &str) -> Result<String, JsValue> {
 
     let start = performance.now();
     let result = rust_core::process_records(&records)
-        .map_err(|e| JsValue::from_str(&e))?;
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
     let end = performance.now();
 
     let duration_ms = end - start;
@@ -275,23 +1157,7 @@ pub fn benchmark_process(records_json: &str) -> Result<String, JsValue> {
 /// ```
 #[wasm_bindgen(js_name = generateSampleData)]
 pub fn generate_sample_data(count: usize) -> Result<String, JsValue> {
-    let categories = vec!["A", "B", "C", "D"];
-    let mut records = Vec::with_capacity(count);
-
-    for i in 0..count {
-        let mut metadata = std::collections::HashMap::new();
-        metadata.insert("index".to_string(), i.to_string());
-
-        records.push(DataRecord {
-            id: format!("record_{}", i),
-            value: ((i % 1000) as f64) * 10.5,
-            category: categories[i % 4].to_string(),
-            timestamp: format!("2024-01-15T10:{:02}:{:02}Z", (i / 60) % 60, i % 60),
-            metadata: Some(metadata),
-        });
-    }
-
-    serde_json::to_string(&records)
+    serde_json::to_string(&rust_core::generate_sample_data(count))
         .map_err(|e| JsValue::from_str(&e.to_string()))
 }
 
@@ -315,4 +1181,137 @@ mod tests {
         let data: Vec<DataRecord> = serde_json::from_str(&data_json).unwrap();
         assert_eq!(data.len(), 10);
     }
+
+    #[wasm_bindgen_test]
+    fn test_get_category_stats_all() {
+        let records = r#"[
+            {"id":"1","value":10,"category":"B","timestamp":"2024-01-15T10:00:00Z"},
+            {"id":"2","value":20,"category":"A","timestamp":"2024-01-15T10:00:00Z"}
+        ]"#;
+        let json = get_category_stats_all(records).unwrap();
+        assert!(json.find("\"A\"").unwrap() < json.find("\"B\"").unwrap());
+
+        let stats: BTreeMap<String, rust_core::CategoryStats> =
+            serde_json::from_str(&json).unwrap();
+        assert_eq!(stats["A"].count, 1);
+        assert_eq!(stats["B"].total_value, 10.0);
+
+        assert_eq!(get_category_stats_all("[]").unwrap(), "{}");
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[wasm_bindgen_test]
+    fn test_msgpack_round_trip_matches_json() {
+        let records = rust_core::generate_sample_data(25);
+
+        let via_json: Vec<DataRecord> =
+            serde_json::from_str(&serde_json::to_string(&records).unwrap()).unwrap();
+        let via_msgpack = decode_records_msgpack(&encode_msgpack(&records).unwrap()).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&via_msgpack).unwrap(),
+            serde_json::to_value(&via_json).unwrap()
+        );
+
+        let result: CoreProcessResult = rmp_serde::from_slice(
+            &process_records_msgpack(&encode_msgpack(&records).unwrap()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(result.total_processed, 25);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_process_records_async_matches_sync() {
+        // More than two chunks, so the async path yields at least twice
+        let json = generate_sample_data(25_000).unwrap();
+
+        let sync = process_records(&json).unwrap().result;
+        let result = process_records_async(json).await.unwrap().result;
+
+        assert_eq!(result.total_processed, sync.total_processed);
+        assert_eq!(result.min_value, sync.min_value);
+        assert_eq!(result.max_value, sync.max_value);
+        assert_eq!(result.categories, sync.categories);
+        // Chunked sums may round differently from the single pass
+        assert!((result.total_value - sync.total_value).abs() <= 1e-9 * sync.total_value.abs());
+
+        assert!(process_records_async("[]".to_string()).await.is_err());
+        assert!(process_records_async("not json".to_string()).await.is_err());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_filter_async_matches_sync() {
+        let json = generate_sample_data(25_000).unwrap();
+
+        assert_eq!(
+            filter_by_category_async(json.clone(), "A".to_string())
+                .await
+                .unwrap(),
+            filter_by_category(&json, "A").unwrap()
+        );
+        assert_eq!(
+            filter_by_value_async(json.clone(), 500.0).await.unwrap(),
+            filter_by_value(&json, 500.0).unwrap()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_get_record_by_id() {
+        let records = r#"[
+            {"id":"1","value":10,"category":"A","timestamp":"2024-01-15T10:00:00Z"},
+            {"id":"2","value":20,"category":"B","timestamp":"2024-01-15T10:00:00Z"}
+        ]"#;
+
+        let record: DataRecord =
+            serde_json::from_str(&get_record_by_id(records, "2").unwrap().unwrap()).unwrap();
+        assert_eq!(record.value, 20.0);
+        assert!(get_record_by_id(records, "missing").unwrap().is_none());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_get_records_by_ids_preserves_order_and_nulls_missing() {
+        let records = r#"[
+            {"id":"1","value":10,"category":"A","timestamp":"2024-01-15T10:00:00Z"},
+            {"id":"2","value":20,"category":"B","timestamp":"2024-01-15T10:00:00Z"},
+            {"id":"3","value":30,"category":"C","timestamp":"2024-01-15T10:00:00Z"}
+        ]"#;
+
+        let json = get_records_by_ids(records, r#"["3","missing","1","3"]"#).unwrap();
+        let found: Vec<Option<DataRecord>> = serde_json::from_str(&json).unwrap();
+        let ids: Vec<Option<&str>> = found
+            .iter()
+            .map(|r| r.as_ref().map(|r| r.id.as_str()))
+            .collect();
+        assert_eq!(ids, vec![Some("3"), None, Some("1"), Some("3")]);
+
+        assert_eq!(get_records_by_ids(records, "[]").unwrap(), "[]");
+        assert!(get_records_by_ids(records, r#"[1]"#).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_process_records_with_outlier_cap() {
+        let mut records: Vec<DataRecord> = rust_core::generate_sample_data(999)
+            .into_iter()
+            .map(|mut r| {
+                r.value = 1.0;
+                r
+            })
+            .collect();
+        records.push(DataRecord {
+            id: "typo".into(),
+            value: 1e15,
+            category: "A".into(),
+            timestamp: "2024-01-15T10:00:00Z".to_string(),
+            metadata: None,
+        });
+        let json = serde_json::to_string(&records).unwrap();
+
+        let capped = process_records_with_config(&json, r#"{"outlier_cap": 3.0}"#).unwrap();
+        assert_eq!(capped.capped_count(), 1);
+        assert!((capped.average_value() - 1.0).abs() < 1e-9);
+
+        let default = process_records_with_config(&json, "{}").unwrap();
+        assert_eq!(default.capped_count(), 0);
+        assert!(process_records_with_config(&json, r#"{"outlier_cap": 0.5}"#).is_err());
+    }
 }